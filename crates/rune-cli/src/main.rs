@@ -138,6 +138,20 @@ struct SharedArgs {
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
     /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
+    ///
+    /// dedup-closures[=<true/false>] - Share a single function between identical closures which capture nothing.
+    ///
+    /// bignum[=<true/false>] - Compile integer literals which don't fit in 64 bits as big integers.
+    ///
+    /// strict[=<true/false>] - Report non-exhaustive matches over enums as errors instead of warnings.
+    ///
+    /// closure-to-fn[=<true/false>] - Warn about named closures which capture nothing and could be functions.
+    ///
+    /// warnings-as-errors[=<true/false>] - Report every warning as an error.
+    ///
+    /// feature=<name> - Enable items marked with `#[cfg(feature = "<name>")]`.
+    ///
+    /// const-budget=<steps> - The number of steps constant evaluation may take before it is aborted.
     #[structopt(name = "option", short = "O", number_of_values = 1)]
     compiler_options: Vec<String>,
 
@@ -149,13 +163,21 @@ struct SharedArgs {
 
 impl SharedArgs {
     /// Construct a runestick context according to the specified argument.
-    fn context(&self) -> Result<runestick::Context, runestick::ContextError> {
+    fn context(
+        &self,
+        options: &rune::Options,
+    ) -> Result<runestick::Context, runestick::ContextError> {
         let mut context = rune_modules::default_context()?;
 
         if self.experimental {
             context.install(&rune_modules::experiments::module(true)?)?;
         }
 
+        // NB: bignum mode needs the `std::bigint` module to be installed.
+        if options.bignum {
+            context.install(&runestick::modules::bigint::module()?)?;
+        }
+
         Ok(context)
     }
 }
@@ -388,7 +410,7 @@ fn load_path(
     Vec<(runestick::Hash, runestick::CompileMeta)>,
 )> {
     let shared = args.shared();
-    let context = shared.context()?;
+    let context = shared.context(options)?;
 
     let bytecode_path = path.with_extension("rnc");
 
@@ -486,7 +508,7 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
         Command::Check(checkargs) => {
             writeln!(out, "Checking: {}", path.display())?;
 
-            let context = checkargs.shared.context()?;
+            let context = checkargs.shared.context(options)?;
 
             let source = runestick::Source::from_path(path)
                 .with_context(|| format!("reading file: {}", path.display()))?;
@@ -753,10 +775,7 @@ where
                 writeln!(out, "fn {} ({}):", signature, hash)?;
             }
 
-            let debug = vm
-                .unit()
-                .debug_info()
-                .and_then(|d| d.instruction_at(vm.ip()));
+            let debug = vm.unit().debug_info_at(vm.ip());
 
             if with_source {
                 let debug_info = debug.and_then(|d| sources.get(d.source_id).map(|s| (s, d.span)));
@@ -196,6 +196,16 @@ impl State {
                                         }
                                     }
                                 },
+                                rune::ErrorKind::Warning(warning) => {
+                                    report(
+                                        &sources,
+                                        &mut by_url,
+                                        warning.span(),
+                                        source_id,
+                                        warning,
+                                        display_to_error,
+                                    );
+                                }
                                 rune::ErrorKind::Internal(message) => {
                                     let diagnostics = by_url.entry(url.clone()).or_default();
 
@@ -54,11 +54,49 @@ fn from_string(string: &str) -> runestick::Result<Value> {
 
 /// Convert any value to a json string.
 fn to_string(value: Value) -> runestick::Result<String> {
+    check_finite(&value)?;
     Ok(serde_json::to_string(&value)?)
 }
 
 /// Convert any value to json bytes.
 fn to_bytes(value: Value) -> runestick::Result<Bytes> {
+    check_finite(&value)?;
     let bytes = serde_json::to_vec(&value)?;
     Ok(Bytes::from_vec(bytes))
 }
+
+/// Check that the value doesn't contain any floats which can't be represented
+/// in json, which would otherwise silently be serialized as `null`.
+fn check_finite(value: &Value) -> runestick::Result<()> {
+    match value {
+        Value::Float(float) if !float.is_finite() => {
+            return Err(runestick::Error::msg(format!(
+                "cannot serialize `{}` as json",
+                float
+            )));
+        }
+        Value::Vec(vec) => {
+            for value in &*vec.borrow_ref()? {
+                check_finite(value)?;
+            }
+        }
+        Value::Tuple(tuple) => {
+            for value in tuple.borrow_ref()?.iter() {
+                check_finite(value)?;
+            }
+        }
+        Value::Object(object) => {
+            for value in object.borrow_ref()?.values() {
+                check_finite(value)?;
+            }
+        }
+        Value::Option(option) => {
+            if let Some(value) = &*option.borrow_ref()? {
+                check_finite(value)?;
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
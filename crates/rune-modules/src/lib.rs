@@ -53,6 +53,7 @@
 //! * [io]
 //! * [json]
 //! * [macros]
+//! * [parse]
 //! * [process]
 //! * [rand]
 //! * [signal]
@@ -71,6 +72,7 @@
 //! * `io` for the [io module][io]
 //! * `json` for the [json module][json]
 //! * `macros` for the [macros module][macros]
+//! * `parse` for the [parse module][parse]
 //! * `process` for the [process module][process]
 //! * `rand` for the [rand module][rand]
 //! * `signal` for the [signal module][signal]
@@ -86,6 +88,7 @@
 //! [io]: https://docs.rs/rune-modules/0/rune_modules/io/
 //! [json]: https://docs.rs/rune-modules/0/rune_modules/json/
 //! [macros]: https://docs.rs/rune-modules/0/rune_modules/macros/
+//! [parse]: https://docs.rs/rune-modules/0/rune_modules/parse/
 //! [process]: https://docs.rs/rune-modules/0/rune_modules/process/
 //! [rand]: https://docs.rs/rune-modules/0/rune_modules/rand/
 //! [signal]: https://docs.rs/rune-modules/0/rune_modules/signal/
@@ -139,6 +142,7 @@ modules! {
     io, "io",
     json, "json",
     macros, "macros",
+    parse, "parse",
     process, "process",
     rand, "rand",
     signal, "signal",
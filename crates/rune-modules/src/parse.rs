@@ -0,0 +1,167 @@
+//! The native `parse` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.9.1", features = ["parse"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::parse::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use parse;
+//!
+//! fn main() {
+//!     let config = parse::key_values("name = \"rune\"\nthreads = 4")?;
+//!     dbg(config);
+//! }
+//! ```
+
+use runestick::{ContextError, Module, Object, Value};
+
+/// Construct the `parse` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("parse");
+    module.function(&["key_values"], key_values)?;
+    Ok(module)
+}
+
+/// Parse lines of `key = value` into an object, with entries in the order in
+/// which they appear.
+///
+/// Values can be double-quoted strings, integers, floats, or the booleans
+/// `true` and `false`. Empty lines are ignored, and `#` starts a comment which
+/// runs until the end of the line unless it's part of a string.
+///
+/// Errors with the 1-based line number if a line is malformed or if a key is
+/// defined more than once.
+fn key_values(string: &str) -> runestick::Result<Object> {
+    let mut object = Object::new();
+
+    for (n, line) in string.lines().enumerate() {
+        let line_number = n + 1;
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let (key, value) = match line.find('=') {
+            Some(index) => (line[..index].trim(), line[index + 1..].trim()),
+            None => return Err(error(line_number, "expected `key = value`")),
+        };
+
+        if key.is_empty() || !key.chars().all(is_key_char) {
+            return Err(error(line_number, &format!("invalid key `{}`", key)));
+        }
+
+        let value = match parse_value(value) {
+            Some(value) => value,
+            None => return Err(error(line_number, &format!("invalid value `{}`", value))),
+        };
+
+        if object.insert(key.to_owned(), value).is_some() {
+            return Err(error(line_number, &format!("duplicate key `{}`", key)));
+        }
+    }
+
+    Ok(object)
+}
+
+/// Strip a trailing comment from the line, ignoring `#` inside of strings.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => (),
+        }
+    }
+
+    line
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+/// Parse a single value, returning `None` if it's not supported.
+fn parse_value(value: &str) -> Option<Value> {
+    match value {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => (),
+    }
+
+    if let Some(string) = value.strip_prefix('"') {
+        return parse_string(string);
+    }
+
+    if let Ok(integer) = value.parse::<i64>() {
+        return Some(Value::Integer(integer));
+    }
+
+    // NB: don't accept things like `inf` or `NaN` which `f64` would parse.
+    if value
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+    {
+        if let Ok(float) = value.parse::<f64>() {
+            return Some(Value::Float(float));
+        }
+    }
+
+    None
+}
+
+/// Parse the remainder of a string after its opening quote, which must end
+/// with the closing quote.
+fn parse_string(string: &str) -> Option<Value> {
+    let mut out = String::new();
+    let mut it = string.chars();
+
+    while let Some(c) = it.next() {
+        match c {
+            '"' => {
+                return if it.as_str().is_empty() {
+                    Some(Value::from(out))
+                } else {
+                    None
+                };
+            }
+            '\\' => out.push(match it.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '"' => '"',
+                '\\' => '\\',
+                _ => return None,
+            }),
+            c => out.push(c),
+        }
+    }
+
+    None
+}
+
+fn error(line: usize, message: &str) -> runestick::Error {
+    runestick::Error::msg(format!("line {}: {}", line, message))
+}
@@ -1,4 +1,5 @@
-use runestick::{ContextError, Module, Panic, Stack, Value, VmError};
+use runestick::modules::io;
+use runestick::{ContextError, Module, Panic, Stack, VmError};
 use std::io::Write as _;
 
 /// Provide a bunch of `std` functions which does something appropriate to the
@@ -7,7 +8,7 @@ pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", &["io"]);
     module.function(&["print"], print_impl)?;
     module.function(&["println"], println_impl)?;
-    module.raw_fn(&["dbg"], dbg_impl)?;
+    module.raw_fn_with_call_site(&["dbg"], dbg_impl, dbg_at_impl)?;
     Ok(module)
 }
 
@@ -32,12 +33,16 @@ fn println_impl(m: &str) -> Result<(), Panic> {
 }
 
 fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
-    let mut o = OUT.lock();
-
-    for value in stack.drain_stack_top(args)? {
-        writeln!(o, "{:?}", value).map_err(VmError::panic)?;
-    }
+    // NB: formatting might print, so don't hold on to the output lock.
+    let mut buf = Vec::new();
+    io::dbg_to(&mut buf, stack, args)?;
+    OUT.lock().extend_from_slice(&buf);
+    Ok(())
+}
 
-    stack.push(Value::Unit);
+fn dbg_at_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    let mut buf = Vec::new();
+    io::dbg_at_to(&mut buf, stack, args)?;
+    OUT.lock().extend_from_slice(&buf);
     Ok(())
 }
@@ -96,6 +96,10 @@ pub enum Expr {
     Await(Box<ast::ExprAwait>),
     /// Try expression.
     Try(Box<ast::ExprTry>),
+    /// An optional link in an expression chain.
+    Optional(Box<ast::ExprOptional>),
+    /// An expression chain containing optional links.
+    OptionalChain(Box<ast::ExprOptionalChain>),
     /// A select expression.
     Select(Box<ast::ExprSelect>),
     /// A closure expression.
@@ -176,6 +180,8 @@ impl Expr {
             Self::Index(expr) => take(&mut expr.attributes),
             Self::Await(expr) => take(&mut expr.attributes),
             Self::Try(expr) => take(&mut expr.attributes),
+            Self::Optional(expr) => take(&mut expr.attributes),
+            Self::OptionalChain(expr) => expr.expr.take_attributes(),
             Self::ForceSemi(expr) => expr.expr.take_attributes(),
             Self::Object(expr) => take(&mut expr.attributes),
             Self::Range(expr) => take(&mut expr.attributes),
@@ -213,6 +219,8 @@ impl Expr {
             Self::Index(expr) => &expr.attributes,
             Self::Await(expr) => &expr.attributes,
             Self::Try(expr) => &expr.attributes,
+            Self::Optional(expr) => &expr.attributes,
+            Self::OptionalChain(expr) => expr.expr.attributes(),
             Self::ForceSemi(expr) => expr.expr.attributes(),
             Self::MacroCall(expr) => &expr.attributes,
             Self::Object(expr) => &expr.attributes,
@@ -513,6 +521,9 @@ impl Expr {
         mut expr: Self,
         callable: Callable,
     ) -> Result<Self, ParseError> {
+        let start = expr.span();
+        let mut optional = false;
+
         while !p.is_eof()? {
             let is_callable = expr.is_callable(*callable);
 
@@ -537,6 +548,21 @@ impl Expr {
                         args,
                     }));
                 }
+                // <expr>?.<field>
+                K![?] if p.nth(1)? == K![.] => {
+                    optional = true;
+
+                    expr = Self::Optional(Box::new(ast::ExprOptional {
+                        attributes: expr.take_attributes(),
+                        expr,
+                        question: p.parse()?,
+                    }));
+                }
+                // NB: `??` is lexed as a single token, but unless it's followed
+                // by an expression it's two try operators, like in `a??`.
+                K![??] if !Self::peek_at(p.peeker(), 1) => {
+                    p.split_question_marks()?;
+                }
                 K![?] => {
                     expr = Self::Try(Box::new(ast::ExprTry {
                         attributes: expr.take_attributes(),
@@ -544,7 +570,8 @@ impl Expr {
                         try_token: p.parse()?,
                     }));
                 }
-                K![=] => {
+                // NB: optional chains cannot be assigned to.
+                K![=] if !optional => {
                     let eq = p.parse()?;
                     let rhs =
                         Self::parse_with(p, EagerBrace(true), EagerBinary(true), Callable(true))?;
@@ -597,6 +624,13 @@ impl Expr {
             }
         }
 
+        if optional {
+            expr = Self::OptionalChain(Box::new(ast::ExprOptionalChain {
+                span: start.join(expr.span()),
+                expr,
+            }));
+        }
+
         Ok(expr)
     }
 
@@ -615,6 +649,7 @@ impl Expr {
         loop {
             let lh = match *lookahead_tok {
                 Some(lh) if lh.precedence() > op.precedence() => lh,
+                Some(lh) if lh == op && op.is_right_assoc() => lh,
                 Some(lh) if lh.precedence() == op.precedence() && !op.is_assoc() => {
                     return Err(ParseError::new(
                         lhs.span().join(rhs.span()),
@@ -774,7 +809,14 @@ impl Parse for Expr {
 
 impl Peek for Expr {
     fn peek(p: &mut Peeker<'_>) -> bool {
-        match p.nth(0) {
+        Self::peek_at(p, 0)
+    }
+}
+
+impl Expr {
+    /// Test if an expression starts at the `n`th token.
+    fn peek_at(p: &mut Peeker<'_>, n: usize) -> bool {
+        match p.nth(n) {
             K![async] => true,
             K![self] => true,
             K![select] => true,
@@ -803,7 +845,7 @@ impl Peek for Expr {
             K![byte] => true,
             K![str] => true,
             K![bytestr] => true,
-            K!['label] => matches!(p.nth(1), K![:]),
+            K!['label] => matches!(p.nth(n + 1), K![:]),
             K![..] => true,
             _ => false,
         }
@@ -855,4 +897,16 @@ mod tests {
     fn test_expr_while() {
         testing::roundtrip::<ast::ExprWhile>(r#"while true {}"#);
     }
+
+    #[test]
+    fn test_expr_coalesce_right_assoc() {
+        let expr = testing::roundtrip::<ast::ExprBinary>(r#"a ?? b ?? c"#);
+        assert_eq!(expr.op, ast::BinOp::Coalesce);
+        assert!(matches!(expr.lhs, ast::Expr::Path(..)));
+
+        match &expr.rhs {
+            ast::Expr::Binary(rhs) => assert_eq!(rhs.op, ast::BinOp::Coalesce),
+            rhs => panic!("expected binary expression but got {:?}", rhs),
+        }
+    }
 }
@@ -68,6 +68,8 @@ pub enum BinOp {
     And,
     /// Lazy or operator `||`.
     Or,
+    /// Lazy coalescing operator `a ?? b`.
+    Coalesce,
     /// Bitwise left shift operator `a << b`.
     Shl,
     /// Bitwise right shift operator `a >> b`.
@@ -127,6 +129,7 @@ impl BinOp {
         match self {
             Self::And => true,
             Self::Or => true,
+            Self::Coalesce => true,
             _ => false,
         }
     }
@@ -135,16 +138,17 @@ impl BinOp {
     pub(super) fn precedence(self) -> usize {
         // NB: Rules from: https://doc.rust-lang.org/reference/expressions.html#expression-precedence
         match self {
-            Self::Is | Self::IsNot => 12,
-            Self::Mul | Self::Div | Self::Rem => 11,
-            Self::Add | Self::Sub => 10,
-            Self::Shl | Self::Shr => 9,
-            Self::BitAnd => 8,
-            Self::BitXor => 7,
-            Self::BitOr => 6,
-            Self::Eq | Self::Neq | Self::Lt | Self::Gt | Self::Lte | Self::Gte => 5,
-            Self::And => 4,
-            Self::Or => 3,
+            Self::Is | Self::IsNot => 13,
+            Self::Mul | Self::Div | Self::Rem => 12,
+            Self::Add | Self::Sub => 11,
+            Self::Shl | Self::Shr => 10,
+            Self::BitAnd => 9,
+            Self::BitXor => 8,
+            Self::BitOr => 7,
+            Self::Eq | Self::Neq | Self::Lt | Self::Gt | Self::Lte | Self::Gte => 6,
+            Self::And => 5,
+            Self::Or => 4,
+            Self::Coalesce => 3,
             Self::DotDot | Self::DotDotEq => 2,
             // assign operators
             _ => 1,
@@ -170,6 +174,11 @@ impl BinOp {
         }
     }
 
+    /// Test if operator is right associative.
+    pub(super) fn is_right_assoc(self) -> bool {
+        matches!(self, Self::Coalesce)
+    }
+
     /// Convert from a token.
     pub(super) fn from_peeker(p: &mut Peeker<'_>) -> Option<BinOp> {
         Some(match p.nth(0) {
@@ -190,6 +199,7 @@ impl BinOp {
             },
             K![&&] => Self::And,
             K![||] => Self::Or,
+            K![??] => Self::Coalesce,
             K![<<] => Self::Shl,
             K![>>] => Self::Shr,
             K![&] => Self::BitAnd,
@@ -241,6 +251,7 @@ impl fmt::Display for BinOp {
             Self::IsNot => write!(f, "is not"),
             Self::And => write!(f, "&&"),
             Self::Or => write!(f, "||"),
+            Self::Coalesce => write!(f, "??"),
             Self::Shl => write!(f, "<<"),
             Self::Shr => write!(f, ">>"),
             Self::BitAnd => write!(f, "&"),
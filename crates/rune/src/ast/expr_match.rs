@@ -76,10 +76,12 @@ expr_parse!(Match, ExprMatch, "match expression");
 /// use rune::{testing, ast};
 ///
 /// testing::roundtrip::<ast::ExprMatchBranch>("1 => { foo }");
+/// testing::roundtrip::<ast::ExprMatchBranch>("1 | 2 => { foo }");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 pub struct ExprMatchBranch {
     /// The pattern to match.
+    #[rune(parse_with = "parse_pat")]
     pub pat: ast::Pat,
     /// The branch condition.
     pub condition: Option<(T![if], ast::Expr)>,
@@ -88,3 +90,23 @@ pub struct ExprMatchBranch {
     /// The body of the match.
     pub body: ast::Expr,
 }
+
+/// Parse the pattern of a match branch, which can be an or-pattern.
+fn parse_pat(p: &mut Parser<'_>) -> Result<ast::Pat, ParseError> {
+    let first = p.parse::<ast::Pat>()?;
+
+    if !p.peek::<T![|]>()? {
+        return Ok(first);
+    }
+
+    let mut rest = Vec::new();
+
+    while p.peek::<T![|]>()? {
+        rest.push((p.parse()?, p.parse()?));
+    }
+
+    Ok(ast::Pat::PatOr(ast::PatOr {
+        first: Box::new(first),
+        rest,
+    }))
+}
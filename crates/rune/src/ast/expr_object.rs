@@ -16,6 +16,7 @@ use std::borrow::Cow;
 /// testing::roundtrip::<ast::ExprObject>("Foo {\"foo\": 42}");
 /// testing::roundtrip::<ast::ExprObject>("#{\"foo\": 42}");
 /// testing::roundtrip::<ast::ExprObject>("#{\"foo\": 42,}");
+/// testing::roundtrip::<ast::ExprObject>("#{..base, \"foo\": 42}");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
 pub struct ExprObject {
@@ -26,7 +27,7 @@ pub struct ExprObject {
     #[rune(meta)]
     pub ident: ObjectIdent,
     /// Assignments in the object.
-    pub assignments: ast::Braced<FieldAssign, T![,]>,
+    pub assignments: ast::Braced<ObjectField, T![,]>,
 }
 
 impl Peek for ExprObject {
@@ -57,6 +58,44 @@ impl Parse for ObjectIdent {
     }
 }
 
+/// An entry in an object literal.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub enum ObjectField {
+    /// A field assignment, like `foo: 42`.
+    Assign(FieldAssign),
+    /// The entries of another object spread into this one, like `..base`.
+    Spread(ObjectSpread),
+}
+
+/// Parse an object entry.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{testing, ast};
+///
+/// testing::roundtrip::<ast::ObjectField>("\"foo\": 42");
+/// testing::roundtrip::<ast::ObjectField>("..base");
+/// ```
+impl Parse for ObjectField {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        Ok(if p.peek::<T![..]>()? {
+            Self::Spread(p.parse()?)
+        } else {
+            Self::Assign(p.parse()?)
+        })
+    }
+}
+
+/// An object spread, like `..base`.
+#[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
+pub struct ObjectSpread {
+    /// The `..` token.
+    pub dot_dot: T![..],
+    /// The object whose entries are spread.
+    pub expr: ast::Expr,
+}
+
 /// A literal object field.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 pub struct FieldAssign {
@@ -0,0 +1,37 @@
+use crate::ast;
+use crate::{Spanned, ToTokens};
+use runestick::Span;
+
+/// A link in an optional chain `<expr>?.<field>`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub struct ExprOptional {
+    /// Attributes associated with expression.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The expression which might be absent.
+    pub expr: ast::Expr,
+    /// The optional operator `?`.
+    pub question: T![?],
+}
+
+/// An expression chain containing at least one optional link, which
+/// short-circuits to the absent value if any link is absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprOptionalChain {
+    /// The span of the whole chain.
+    pub span: Span,
+    /// The chained expression.
+    pub expr: ast::Expr,
+}
+
+impl Spanned for ExprOptionalChain {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl ToTokens for ExprOptionalChain {
+    fn to_tokens(&self, context: &crate::MacroContext, stream: &mut crate::TokenStream) {
+        self.expr.to_tokens(context, stream)
+    }
+}
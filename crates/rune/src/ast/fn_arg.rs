@@ -11,6 +11,8 @@ use crate::{Parse, ParseError, Parser, Spanned, ToTokens};
 /// testing::roundtrip::<ast::FnArg>("self");
 /// testing::roundtrip::<ast::FnArg>("_");
 /// testing::roundtrip::<ast::FnArg>("abc");
+/// testing::roundtrip::<ast::FnArg>("greeting = \"hello\"");
+/// testing::roundtrip::<ast::FnArg>("..rest");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 pub enum FnArg {
@@ -18,13 +20,54 @@ pub enum FnArg {
     SelfValue(T![self]),
     /// Function argument is a pattern binding.
     Pat(Box<ast::Pat>),
+    /// Function argument is a pattern binding with a default value.
+    Default(Box<FnArgDefault>),
+    /// Function argument which collects all remaining arguments.
+    Rest(Box<FnArgRest>),
 }
 
 impl Parse for FnArg {
     fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
         Ok(match p.nth(0)? {
             K![self] => Self::SelfValue(p.parse()?),
-            _ => Self::Pat(p.parse()?),
+            K![..] => Self::Rest(Box::new(p.parse()?)),
+            _ => {
+                let pat = p.parse()?;
+
+                if p.peek::<T![=]>()? {
+                    Self::Default(Box::new(FnArgDefault {
+                        pat,
+                        eq: p.parse()?,
+                        default: p.parse()?,
+                    }))
+                } else {
+                    Self::Pat(Box::new(pat))
+                }
+            }
         })
     }
 }
+
+/// A function argument with a default value, like `greeting = "hello"`.
+///
+/// The default value is evaluated when the function is called without the
+/// argument, and may refer to the arguments that come before it.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub struct FnArgDefault {
+    /// The pattern the argument is bound to.
+    pub pat: ast::Pat,
+    /// The `=` token.
+    pub eq: T![=],
+    /// The default value of the argument.
+    pub default: ast::Expr,
+}
+
+/// A function argument which collects all remaining arguments into a vector,
+/// like `..rest`.
+#[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
+pub struct FnArgRest {
+    /// The `..` token.
+    pub dot_dot: T![..],
+    /// The name the remaining arguments are bound to.
+    pub name: ast::Ident,
+}
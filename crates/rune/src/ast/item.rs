@@ -34,6 +34,20 @@ impl Item {
         }
     }
 
+    /// Access the attributes associated with the item mutably.
+    pub fn attributes_mut(&mut self) -> &mut Vec<ast::Attribute> {
+        match self {
+            Self::Use(item) => &mut item.attributes,
+            Self::Fn(item) => &mut item.attributes,
+            Self::Enum(item) => &mut item.attributes,
+            Self::Struct(item) => &mut item.attributes,
+            Self::Impl(item) => &mut item.attributes,
+            Self::Mod(item) => &mut item.attributes,
+            Self::Const(item) => &mut item.attributes,
+            Self::MacroCall(item) => &mut item.attributes,
+        }
+    }
+
     /// Take the attributes associated with the item.
     pub fn take_attributes(&mut self) -> Vec<ast::Attribute> {
         use std::mem::take;
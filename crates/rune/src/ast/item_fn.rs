@@ -18,6 +18,14 @@ use runestick::Span;
 /// let item = testing::roundtrip::<ast::ItemFn>("fn hello(foo, bar) {}");
 /// assert_eq!(item.args.len(), 2);
 ///
+/// let item = testing::roundtrip::<ast::ItemFn>("fn hello(foo, bar = 42) {}");
+/// assert_eq!(item.args.len(), 2);
+/// assert_eq!(item.defaults(), 1);
+///
+/// let item = testing::roundtrip::<ast::ItemFn>("fn hello(foo, ..rest) {}");
+/// assert_eq!(item.args.len(), 2);
+/// assert!(item.has_rest());
+///
 /// testing::roundtrip::<ast::ItemFn>("pub fn hello(foo, bar) {}");
 /// testing::roundtrip::<ast::ItemFn>("pub async fn hello(foo, bar) {}");
 /// testing::roundtrip::<ast::ItemFn>("#[inline] fn hello(foo, bar) {}");
@@ -81,6 +89,25 @@ impl ItemFn {
     pub fn is_instance(&self) -> bool {
         matches!(self.args.first(), Some((ast::FnArg::SelfValue(..), _)))
     }
+
+    /// Get the number of arguments which have default values.
+    pub fn defaults(&self) -> usize {
+        self.args
+            .iter()
+            .filter(|(arg, _)| matches!(arg, ast::FnArg::Default(..)))
+            .count()
+    }
+
+    /// Test if the last argument collects all remaining arguments.
+    pub fn has_rest(&self) -> bool {
+        matches!(self.args.last(), Some((ast::FnArg::Rest(..), _)))
+    }
+
+    /// Get the number of arguments the function takes, not counting the one
+    /// which collects all remaining arguments.
+    pub fn arity(&self) -> usize {
+        self.args.len() - usize::from(self.has_rest())
+    }
 }
 
 item_parse!(Fn, ItemFn, "function item");
@@ -56,7 +56,7 @@ impl<'a> Resolve<'a> for LitByte {
             .source(span.trim_start(2).trim_end(1))
             .ok_or_else(|| ResolveError::new(span, ResolveErrorKind::BadSlice))?;
 
-        let start = span.start.into_usize();
+        let start = span.trim_start(2).start.into_usize();
 
         let mut it = string
             .char_indices()
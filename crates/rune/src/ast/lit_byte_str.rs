@@ -41,7 +41,11 @@ impl LitByteStr {
                         }
                     }
                 }
-                c => Some(c as u8),
+                c if c.is_ascii() => Some(c as u8),
+                c => {
+                    let span = Span::new(start, start + c.len_utf8());
+                    return Err(ResolveError::new(span, ResolveErrorKind::BadByteStrChar));
+                }
             });
         }
 
@@ -102,11 +106,17 @@ impl<'a> Resolve<'a> for LitByteStr {
             .source(span)
             .ok_or_else(|| ResolveError::new(span, ResolveErrorKind::BadSlice))?;
 
-        Ok(if text.escaped {
-            Cow::Owned(self.parse_escaped(span, string)?)
-        } else {
-            Cow::Borrowed(string.as_bytes())
-        })
+        if text.escaped {
+            return Ok(Cow::Owned(self.parse_escaped(span, string)?));
+        }
+
+        if let Some((n, c)) = string.char_indices().find(|(_, c)| !c.is_ascii()) {
+            let start = span.start.into_usize() + n;
+            let span = Span::new(start, start + c.len_utf8());
+            return Err(ResolveError::new(span, ResolveErrorKind::BadByteStrChar));
+        }
+
+        Ok(Cow::Borrowed(string.as_bytes()))
     }
 }
 
@@ -71,7 +71,7 @@ impl<'a> Resolve<'a> for LitChar {
             .source(span.narrow(1))
             .ok_or_else(|| ResolveError::new(span, ResolveErrorKind::BadSlice))?;
 
-        let start = span.start.into_usize();
+        let start = span.narrow(1).start.into_usize();
 
         let mut it = string
             .char_indices()
@@ -4,6 +4,7 @@ use crate::{
     Storage, ToTokens,
 };
 use runestick::{Source, Span};
+use std::borrow::Cow;
 
 /// A number literal.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
@@ -26,6 +27,9 @@ pub struct LitNumber {
 /// testing::roundtrip::<ast::LitNumber>("42.42");
 /// testing::roundtrip::<ast::LitNumber>("0.42");
 /// testing::roundtrip::<ast::LitNumber>("0.42e10");
+/// testing::roundtrip::<ast::LitNumber>("2E-4");
+/// testing::roundtrip::<ast::LitNumber>("1_000_000");
+/// testing::roundtrip::<ast::LitNumber>("0xff_ff");
 /// ```
 impl Parse for LitNumber {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
@@ -64,8 +68,15 @@ impl<'a> Resolve<'a> for LitNumber {
             .source(span)
             .ok_or_else(|| ResolveError::new(span, ResolveErrorKind::BadSlice))?;
 
+        // NB: underscores are only used to separate digits.
+        let string = if string.contains('_') {
+            Cow::Owned(string.replace('_', ""))
+        } else {
+            Cow::Borrowed(string)
+        };
+
         if text.is_fractional {
-            let number = f64::from_str(string).map_err(err_span(span))?;
+            let number = f64::from_str(&string).map_err(err_span(span))?;
             return Ok(ast::Number::Float(number));
         }
 
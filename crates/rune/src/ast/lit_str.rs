@@ -49,7 +49,7 @@ impl LitStr {
             }
         };
 
-        let span = if text.wrapped { span.narrow(1) } else { span };
+        let span = text.contents(span);
 
         let string = source
             .source(span)
@@ -109,6 +109,7 @@ impl LitStr {
 ///
 /// testing::roundtrip::<ast::LitStr>("\"hello world\"");
 /// testing::roundtrip::<ast::LitStr>("\"hello\\nworld\"");
+/// testing::roundtrip::<ast::LitStr>("r#\"hello \"world\"\"#");
 /// ```
 impl Parse for LitStr {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
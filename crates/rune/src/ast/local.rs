@@ -1,8 +1,14 @@
 use crate::ast;
-use crate::{Parse, ParseError, Parser, Spanned, ToTokens};
+use crate::parsing::Opaque;
+use crate::{Id, Parse, ParseError, Parser, Peek, Peeker, Spanned, ToTokens};
 
 /// A local variable declaration `let <pattern> = <expr>;`
 ///
+/// A local can also be a guard in the form of `guard let <pattern> = <expr>
+/// else { .. };`, where the else block is evaluated if the pattern doesn't
+/// match. Note that `guard` is a contextual keyword, so it can still be used
+/// as an identifier.
+///
 /// # Examples
 ///
 /// ```rust
@@ -11,25 +17,97 @@ use crate::{Parse, ParseError, Parser, Spanned, ToTokens};
 /// testing::roundtrip::<ast::Local>("let x = 1;");
 /// testing::roundtrip::<ast::Local>("#[attr] let a = f();");
 /// testing::roundtrip::<ast::Local>("let a = b{}().foo[0].await;");
+/// testing::roundtrip::<ast::Local>("guard let Some(a) = b else { return; };");
+/// testing::roundtrip::<ast::Local>("let [a, b]: [_; N] = c;");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 pub struct Local {
     /// The attributes for the let expression
     #[rune(iter, meta)]
     pub attributes: Vec<ast::Attribute>,
+    /// The contextual `guard` keyword, if this local is a guard.
+    #[rune(iter, parse_with = "parse_guard")]
+    pub guard: Option<ast::Ident>,
     /// The `let` keyword.
     pub let_token: T![let],
     /// The name of the binding.
     pub pat: ast::Pat,
+    /// The type annotation of the binding.
+    #[rune(iter)]
+    pub ty: Option<ast::LocalType>,
     /// The equality keyword.
     pub eq: T![=],
     /// The expression the binding is assigned to.
     #[rune(parse_with = "parse_expr")]
     pub expr: ast::Expr,
+    /// The else block of a guard.
+    #[rune(iter)]
+    pub expr_else: Option<ast::ExprElse>,
     /// Trailing semicolon of the local.
     pub semi: T![;],
 }
 
+/// A type annotation on a local variable declaration.
+///
+/// Only vector types with a constant length are supported, like `[_; N]`.
+/// The length is evaluated at compile time and checked against the pattern
+/// being bound.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{testing, ast};
+///
+/// testing::roundtrip::<ast::LocalType>(": [_; 3]");
+/// testing::roundtrip::<ast::LocalType>(": [_; N * 2]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
+pub struct LocalType {
+    /// Opaque identifier for the type annotation.
+    #[rune(id)]
+    pub id: Option<Id>,
+    /// The `:` token.
+    pub colon: T![:],
+    /// The opening bracket.
+    pub open: T!['['],
+    /// The `_` element type.
+    pub underscore: T![_],
+    /// The `;` separating the element type and the length.
+    pub semi: T![;],
+    /// The constant length expression.
+    pub len: ast::Expr,
+    /// The closing bracket.
+    pub close: T![']'],
+}
+
+impl Opaque for LocalType {
+    fn id(&self) -> Option<Id> {
+        self.id
+    }
+}
+
+impl Peek for LocalType {
+    fn peek(p: &mut Peeker<'_>) -> bool {
+        matches!(p.nth(0), K![:])
+    }
+}
+
+/// Test if the parser is at a guard, which is an identifier followed by `let`.
+///
+/// The identifier is checked to be `guard` during indexing, since it can't be
+/// resolved while parsing.
+pub(crate) fn peek_guard(p: &mut Peeker<'_>) -> bool {
+    matches!((p.nth(0), p.nth(1)), (ast::Kind::Ident(..), K![let]))
+}
+
+fn parse_guard(p: &mut Parser<'_>) -> Result<Option<ast::Ident>, ParseError> {
+    if peek_guard(p.peeker()) {
+        Ok(Some(p.parse()?))
+    } else {
+        Ok(None)
+    }
+}
+
 fn parse_expr(p: &mut Parser<'_>) -> Result<ast::Expr, ParseError> {
     ast::Expr::parse_with(
         p,
@@ -59,6 +59,7 @@ mod expr_lit;
 mod expr_loop;
 mod expr_match;
 mod expr_object;
+mod expr_optional;
 mod expr_range;
 mod expr_return;
 mod expr_select;
@@ -119,7 +120,10 @@ pub use self::expr_let::ExprLet;
 pub use self::expr_lit::ExprLit;
 pub use self::expr_loop::ExprLoop;
 pub use self::expr_match::{ExprMatch, ExprMatchBranch};
-pub use self::expr_object::{AnonExprObject, ExprObject, FieldAssign, ObjectIdent, ObjectKey};
+pub use self::expr_object::{
+    AnonExprObject, ExprObject, FieldAssign, ObjectField, ObjectIdent, ObjectKey, ObjectSpread,
+};
+pub use self::expr_optional::{ExprOptional, ExprOptionalChain};
 pub use self::expr_range::{ExprRange, ExprRangeLimits};
 pub use self::expr_return::ExprReturn;
 pub use self::expr_select::{ExprSelect, ExprSelectBranch};
@@ -130,7 +134,7 @@ pub use self::expr_vec::ExprVec;
 pub use self::expr_while::ExprWhile;
 pub use self::expr_yield::ExprYield;
 pub use self::file::File;
-pub use self::fn_arg::FnArg;
+pub use self::fn_arg::{FnArg, FnArgDefault, FnArgRest};
 pub use self::force_semi::ForceSemi;
 pub use self::generated::Kind;
 pub use self::grouped::{AngleBracketed, Braced, Bracketed, Parenthesized};
@@ -151,9 +155,9 @@ pub use self::lit_byte_str::LitByteStr;
 pub use self::lit_char::LitChar;
 pub use self::lit_number::LitNumber;
 pub use self::lit_str::LitStr;
-pub use self::local::Local;
+pub use self::local::{Local, LocalType};
 pub use self::macro_call::MacroCall;
-pub use self::pat::{Pat, PatBinding, PatLit, PatObject, PatPath, PatTuple, PatVec};
+pub use self::pat::{Pat, PatBinding, PatLit, PatObject, PatOr, PatPath, PatTuple, PatVec};
 pub use self::path::{Path, PathKind, PathSegment};
 pub use self::stmt::{ItemOrExpr, Stmt, StmtSortKey};
 pub use self::token::{
@@ -20,6 +20,8 @@ pub enum Pat {
     PatBinding(PatBinding),
     /// The rest pattern `..`.
     PatRest(PatRest),
+    /// An or-pattern `a | b`, which is only supported in match branches.
+    PatOr(PatOr),
 }
 
 /// Parsing a block expression.
@@ -95,6 +97,7 @@ impl Parse for Pat {
                 return Ok(Self::PatRest(PatRest {
                     attributes,
                     dot_dot: p.parse()?,
+                    name: p.parse()?,
                 }))
             }
             K!['('] => {
@@ -191,6 +194,8 @@ pub struct PatLit {
 }
 
 /// The rest pattern `..` and associated attributes.
+///
+/// In vector patterns the rest can be bound to a name, like `..rest`.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 pub struct PatRest {
     /// Attribute associated with the rest pattern.
@@ -198,6 +203,35 @@ pub struct PatRest {
     pub attributes: Vec<ast::Attribute>,
     /// The rest token `..`.
     pub dot_dot: T![..],
+    /// The name the rest is bound to.
+    #[rune(iter)]
+    pub name: Option<ast::Ident>,
+}
+
+/// An or-pattern, like `1 | 2 | 3`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::{testing, ast};
+///
+/// testing::roundtrip::<ast::ExprMatchBranch>("1 | 2 => 3");
+/// testing::roundtrip::<ast::ExprMatchBranch>("Foo(n) | Bar(n) if n > 1 => n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+pub struct PatOr {
+    /// The first alternative.
+    pub first: Box<ast::Pat>,
+    /// The remaining alternatives, each preceded by a `|`.
+    #[rune(iter)]
+    pub rest: Vec<(T![|], ast::Pat)>,
+}
+
+impl PatOr {
+    /// Iterate over the alternatives of the or-pattern.
+    pub fn alternatives(&self) -> impl Iterator<Item = &ast::Pat> {
+        std::iter::once(&*self.first).chain(self.rest.iter().map(|(_, pat)| pat))
+    }
 }
 
 /// An array pattern.
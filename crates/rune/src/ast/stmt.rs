@@ -45,7 +45,7 @@ impl Stmt {
 
 impl Peek for Stmt {
     fn peek(p: &mut Peeker<'_>) -> bool {
-        matches!(p.nth(0), K![let]) || ItemOrExpr::peek(p)
+        matches!(p.nth(0), K![let]) || ast::local::peek_guard(p) || ItemOrExpr::peek(p)
     }
 }
 
@@ -53,7 +53,13 @@ impl Parse for Stmt {
     fn parse(p: &mut Parser) -> Result<Self, ParseError> {
         let mut attributes = p.parse()?;
         let visibility = p.parse()?;
-        let path = p.parse::<Option<ast::Path>>()?;
+        let guard = ast::local::peek_guard(p.peeker());
+
+        let path = if guard {
+            None
+        } else {
+            p.parse::<Option<ast::Path>>()?
+        };
 
         if ast::Item::peek_as_item(p.peeker(), path.as_ref()) {
             let item: ast::Item = ast::Item::parse_with_meta_path(p, attributes, visibility, path)?;
@@ -71,12 +77,29 @@ impl Parse for Stmt {
             return Err(ParseError::unsupported(span, "visibility modifier"));
         }
 
-        let stmt = if let K![let] = p.nth(0)? {
+        let stmt = if guard || p.nth(0)? == K![let] {
             if let Some(path) = path {
                 return Err(ParseError::expected(&path.first, "expected let statement"));
             }
 
             let local = Box::new(ast::Local::parse_with_meta(p, take(&mut attributes))?);
+
+            match (&local.guard, &local.expr_else) {
+                (Some(..), None) => {
+                    return Err(ParseError::expected(
+                        &local.semi.token,
+                        "else block of guard",
+                    ));
+                }
+                (None, Some(expr_else)) => {
+                    return Err(ParseError::unsupported(
+                        expr_else,
+                        "else block without guard",
+                    ));
+                }
+                _ => (),
+            }
+
             Self::Local(local)
         } else {
             let expr =
@@ -123,7 +146,13 @@ impl Parse for ItemOrExpr {
     fn parse(p: &mut Parser) -> Result<Self, ParseError> {
         let mut attributes = p.parse()?;
         let visibility = p.parse()?;
-        let path = p.parse::<Option<ast::Path>>()?;
+        let guard = ast::local::peek_guard(p.peeker());
+
+        let path = if guard {
+            None
+        } else {
+            p.parse::<Option<ast::Path>>()?
+        };
 
         if ast::Item::peek_as_item(p.peeker(), path.as_ref()) {
             let item: ast::Item = ast::Item::parse_with_meta_path(p, attributes, visibility, path)?;
@@ -104,14 +104,20 @@ impl Token {
             },
             Kind::Str(s) => match s {
                 StrSource::Text(text) => {
-                    let span = if text.wrapped {
-                        self.span.narrow(1)
-                    } else {
-                        self.span
-                    };
-
-                    let s = ctx.source().source(span).ok_or(fmt::Error)?;
-                    write!(f, "\"{}\"", s)?;
+                    let s = ctx
+                        .source()
+                        .source(text.contents(self.span))
+                        .ok_or(fmt::Error)?;
+
+                    match text.raw {
+                        Some(hashes) => {
+                            let hashes = "#".repeat(hashes);
+                            write!(f, "r{}\"{}\"{}", hashes, s, hashes)?;
+                        }
+                        None => {
+                            write!(f, "\"{}\"", s)?;
+                        }
+                    }
                 }
                 StrSource::Synthetic(id) => {
                     match ctx.storage().with_string(*id, |s| write!(f, "{:?}", s)) {
@@ -367,6 +373,21 @@ pub struct StrText {
     pub escaped: bool,
     /// Indicated if the buffer is wrapped or not.
     pub wrapped: bool,
+    /// The number of `#` delimiting the string if it's a raw string, like
+    /// `r#"foo"#`.
+    pub raw: Option<usize>,
+}
+
+impl StrText {
+    /// Get the span of the contents of the string, given the span of its
+    /// token.
+    pub fn contents(&self, span: Span) -> Span {
+        match self.raw {
+            Some(hashes) => span.trim_start(hashes + 2).trim_end(hashes + 1),
+            None if self.wrapped => span.narrow(1),
+            None => span,
+        }
+    }
 }
 
 /// The source of a number.
@@ -153,7 +153,7 @@ pub(super) fn parse_unicode_escape(
         _ => return Err(ResolveErrorKind::BadUnicodeEscape),
     };
 
-    let mut first = true;
+    let mut digits = 0;
     let mut result = 0u32;
 
     loop {
@@ -161,7 +161,7 @@ pub(super) fn parse_unicode_escape(
 
         match c {
             '}' => {
-                if first {
+                if digits == 0 {
                     return Err(ResolveErrorKind::BadUnicodeEscape);
                 }
 
@@ -172,14 +172,15 @@ pub(super) fn parse_unicode_escape(
                 return Err(ResolveErrorKind::BadUnicodeEscape);
             }
             c => {
-                first = false;
+                digits += 1;
 
-                result = match result.checked_shl(4) {
-                    Some(result) => result,
-                    None => {
-                        return Err(ResolveErrorKind::BadUnicodeEscape);
-                    }
-                };
+                // NB: a unicode escape has at most 6 digits, which also
+                // prevents the value from overflowing.
+                if digits > 6 {
+                    return Err(ResolveErrorKind::BadUnicodeEscape);
+                }
+
+                result <<= 4;
 
                 result += match c {
                     '0'..='9' => c as u32 - '0' as u32,
@@ -1,6 +1,6 @@
 mod attributes;
 use crate::ast;
-use crate::{Parse, ParseError, Resolve as _, Storage};
+use crate::{Parse, ParseError, Resolve as _, Storage, WarningKind};
 use runestick::Source;
 
 pub(crate) use self::attributes::Attributes;
@@ -59,3 +59,65 @@ impl Attribute for Test {
     /// Must match the specified name.
     const PATH: &'static str = "test";
 }
+
+/// An attribute suppressing the warnings of the given lints, like
+/// `#[allow(unused)]`.
+///
+/// Warnings are suppressed if any surrounding function or block allows their
+/// lint. An allow which doesn't suppress anything is not reported.
+#[derive(Parse)]
+pub(crate) struct Allow {
+    /// The lints being allowed.
+    pub lints: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Allow {
+    /// Resolve the names of the allowed lints, making sure that they exist.
+    pub(crate) fn lints(
+        &self,
+        storage: &Storage,
+        source: &Source,
+    ) -> Result<Vec<Box<str>>, ParseError> {
+        let mut out = Vec::new();
+
+        for (ident, _) in &self.lints {
+            let lint = ident.resolve(storage, source)?;
+
+            if !WarningKind::LINTS.contains(&lint.as_ref()) {
+                return Err(ParseError::msg(ident, "unsupported lint"));
+            }
+
+            out.push(lint.as_ref().into());
+        }
+
+        Ok(out)
+    }
+}
+
+impl Attribute for Allow {
+    /// Must match the specified name.
+    const PATH: &'static str = "allow";
+}
+
+/// A conditional compilation attribute, like `#[cfg(feature = "debug")]`.
+///
+/// The delimiting tokens are only kept for parsing.
+#[derive(Parse)]
+#[allow(dead_code)]
+pub(crate) struct Cfg {
+    /// The opening parenthesis.
+    pub open: T!['('],
+    /// The option being checked, like `feature`.
+    pub key: ast::Ident,
+    /// The `=` token.
+    pub eq: T![=],
+    /// The value of the option being checked.
+    pub value: ast::LitStr,
+    /// The closing parenthesis.
+    pub close: T![')'],
+}
+
+impl Attribute for Cfg {
+    /// Must match the specified name.
+    const PATH: &'static str = "cfg";
+}
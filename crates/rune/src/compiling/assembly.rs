@@ -11,6 +11,7 @@ pub enum AssemblyInst {
     JumpIfOrPop { label: Label },
     JumpIfNotOrPop { label: Label },
     JumpIfBranch { branch: i64, label: Label },
+    JumpIfFrameLen { len: usize, label: Label },
     PopAndJumpIfNot { count: usize, label: Label },
     IterNext { offset: usize, label: Label },
     Raw { raw: Inst },
@@ -102,6 +103,13 @@ impl Assembly {
             .push((AssemblyInst::JumpIfBranch { branch, label }, span));
     }
 
+    /// Add a jump to the given label if the current call frame holds at least
+    /// `len` values.
+    pub(crate) fn jump_if_frame_len(&mut self, len: usize, label: Label, span: Span) {
+        self.instructions
+            .push((AssemblyInst::JumpIfFrameLen { len, label }, span));
+    }
+
     /// Add a pop-and-jump-if-not instruction to a label.
     pub(crate) fn pop_and_jump_if_not(&mut self, count: usize, label: Label, span: Span) {
         self.instructions
@@ -140,4 +148,56 @@ impl Assembly {
 
         self.push(raw, span);
     }
+
+    /// Convert calls in tail position into tail calls, which reuse the call
+    /// frame of the current function.
+    ///
+    /// A call is in tail position if the only instructions executed after it,
+    /// following any unconditional jumps, clean up the stack and return the
+    /// value produced by the call.
+    pub(crate) fn optimize_tail_calls(&mut self) {
+        for pos in 0..self.instructions.len() {
+            let tail = match &self.instructions[pos].0 {
+                AssemblyInst::Raw {
+                    raw: Inst::Call { hash, args },
+                } => Inst::TailCall {
+                    hash: *hash,
+                    args: *args,
+                },
+                AssemblyInst::Raw {
+                    raw: Inst::CallFn { args },
+                } => Inst::TailCallFn { args: *args },
+                _ => continue,
+            };
+
+            if self.returns_from(pos + 1) {
+                self.instructions[pos].0 = AssemblyInst::Raw { raw: tail };
+            }
+        }
+    }
+
+    /// Test if execution starting at the given offset unconditionally returns
+    /// the value on top of the stack.
+    fn returns_from(&self, mut pos: usize) -> bool {
+        // NB: the number of jumps followed is bounded in case they form a
+        // cycle.
+        for _ in 0..=self.instructions.len() {
+            match self.instructions.get(pos) {
+                Some((AssemblyInst::Jump { label }, _)) => match self.labels.get(label) {
+                    Some(offset) => pos = *offset,
+                    None => return false,
+                },
+                Some((
+                    AssemblyInst::Raw {
+                        raw: Inst::Clean { .. },
+                    },
+                    _,
+                )) => pos += 1,
+                Some((AssemblyInst::Raw { raw: Inst::Return }, _)) => return true,
+                _ => return false,
+            }
+        }
+
+        false
+    }
 }
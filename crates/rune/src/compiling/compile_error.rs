@@ -7,6 +7,7 @@ use crate::{
     ResolveErrorKind, Spanned,
 };
 use runestick::debug::DebugSignature;
+use runestick::format::FormatStrError;
 use runestick::{CompileMeta, Hash, Item, Label, Location, SourceId, Span, SpannedError};
 use std::io;
 use std::path::PathBuf;
@@ -136,10 +137,19 @@ pub enum CompileErrorKind {
     MissingMacro { item: Item },
     #[error("{error}")]
     CallMacroError { item: Item, error: runestick::Error },
+    #[error("{error}")]
+    FormatStrError {
+        #[source]
+        #[from]
+        error: FormatStrError,
+    },
     #[error("no local variable `{name}`")]
     MissingLocal { name: String },
     #[error("missing item `{item}`")]
-    MissingItem { item: Item },
+    MissingItem {
+        item: Item,
+        suggestion: Option<Item>,
+    },
     #[error("unsupported crate prefix `::`")]
     UnsupportedGlobal,
     #[error("cannot load modules using a source without an associated URL")]
@@ -152,12 +162,24 @@ pub enum CompileErrorKind {
     UnsupportedWildcard,
     #[error("`self` not supported here")]
     UnsupportedSelf,
+    #[error("default argument values are not supported here")]
+    UnsupportedFnArgDefault,
+    #[error("argument without a default value can't follow an argument with one")]
+    FnArgMissingDefault { default_span: Span },
+    #[error("rest arguments are not supported here")]
+    UnsupportedFnArgRest,
+    #[error("rest argument must be the last argument")]
+    FnArgRestNotLast,
     #[error("unsupported unary operator `{op}`")]
     UnsupportedUnaryOp { op: ast::UnOp },
     #[error("unsupported binary operator `{op}`")]
     UnsupportedBinaryOp { op: ast::BinOp },
     #[error("{meta} is not an object")]
     UnsupportedLitObject { meta: CompileMeta },
+    #[error("object spread is only supported at the start of an object")]
+    ObjectSpreadNotFirst,
+    #[error("object spread is only supported in anonymous objects")]
+    UnsupportedObjectSpread,
     #[error("missing field `{field}` in declaration of `{item}`")]
     LitObjectMissingField { field: Box<str>, item: Item },
     #[error("`{field}` is not a field in `{item}`")]
@@ -172,6 +194,8 @@ pub enum CompileErrorKind {
     UnsupportedSelectPattern,
     #[error("unsupported field access")]
     BadFieldAccess,
+    #[error("`{item}` has no field `{field}`")]
+    UnknownStructField { field: Box<str>, item: Item },
     #[error("wrong number of arguments, expected `{expected}` but got `{actual}`")]
     UnsupportedArgumentCount {
         meta: CompileMeta,
@@ -182,6 +206,12 @@ pub enum CompileErrorKind {
     UnsupportedPattern { meta: CompileMeta },
     #[error("`..` is not supported in this location")]
     UnsupportedPatternRest,
+    #[error("the rest of a pattern can only be bound in vector patterns")]
+    UnsupportedPatternRestBinding,
+    #[error("only one `..` is allowed in a pattern")]
+    MultiplePatternRest,
+    #[error("variable `{name}` is not bound in all alternatives of the pattern")]
+    OrPatternBindingMismatch { name: String },
     #[error("this kind of expression is not supported as a pattern")]
     UnsupportedPatternExpr,
     #[error("not a valid binding")]
@@ -300,6 +330,34 @@ pub enum CompileErrorKind {
     UnsupportedGenerics,
     #[error("#[test] attributes are not supported on nested items")]
     NestedTest { nested_span: Span },
+    #[error("else block of guard must diverge through `return`, `break` or `continue`")]
+    GuardElseNotDiverging,
+    #[error("array length must be a constant non-negative integer")]
+    BadArrayLength,
+    #[error("array type annotations are only supported on vector patterns")]
+    UnsupportedArrayPattern,
+    #[error("pattern has `{actual}` elements, but the array length is `{expected}`")]
+    ArrayPatternLengthMismatch { expected: usize, actual: usize },
+    #[error("non-exhaustive match, missing {}", format_variants(.missing))]
+    NonExhaustiveMatchPatterns { missing: Vec<Item> },
+}
+
+/// Format a list of variants, like `` `Foo::A` and `Foo::B` ``.
+pub(crate) fn format_variants(variants: &[Item]) -> String {
+    let mut out = String::new();
+    let mut it = variants.iter().peekable();
+    let mut first = true;
+
+    while let Some(variant) = it.next() {
+        if !first {
+            out.push_str(if it.peek().is_some() { ", " } else { " and " });
+        }
+
+        out.push_str(&format!("`{}`", variant));
+        first = false;
+    }
+
+    out
 }
 
 /// A single stap as an import entry.
@@ -1,4 +1,5 @@
-use runestick::{CompileMeta, SourceId, Span};
+use crate::ItemKind;
+use runestick::{CompileMeta, Item, SourceId, Span};
 
 /// A visitor that will be called for every language item compiled.
 pub trait CompileVisitor {
@@ -8,11 +9,35 @@ pub trait CompileVisitor {
     /// Mark that we've encountered a specific compile meta at the given span.
     fn visit_meta(&self, _source_id: SourceId, _meta: &CompileMeta, _span: Span) {}
 
+    /// Visit the definition of a variable with the given name, like a `let`
+    /// binding, a pattern binding, or a function argument.
+    ///
+    /// The `span` corresponds to the `var_span` passed to
+    /// [visit_variable_use][CompileVisitor::visit_variable_use] for every use
+    /// of the variable.
+    fn visit_variable_def(&self, _source_id: SourceId, _name: &str, _span: Span) {}
+
     /// Visit a variable use.
     fn visit_variable_use(&self, _source_id: SourceId, _var_span: Span, _span: Span) {}
 
+    /// Visit a named item, like a function, a type or a constant, once all
+    /// sources have been indexed and before anything is built.
+    ///
+    /// This is called for every item which is declared in the compiled
+    /// sources, regardless of whether it's used or not, so it can be used to
+    /// list the symbols of a project.
+    fn visit_item(&self, _source_id: SourceId, _item: &Item, _span: Span, _kind: ItemKind) {}
+
     /// Visit something that is a module.
     fn visit_mod(&self, _source_id: SourceId, _span: Span) {}
+
+    /// Visit a call from the item `from` to the function `to`, which has been
+    /// statically resolved.
+    ///
+    /// This is not called for dynamic calls where the target isn't known at
+    /// compile time, like calls to closures, function pointers stored in
+    /// variables, or instance functions.
+    fn visit_call(&self, _source_id: SourceId, _from: &Item, _to: &Item, _span: Span) {}
 }
 
 /// A compile visitor that does nothing.
@@ -0,0 +1,229 @@
+use crate::collections::{HashMap, HashSet};
+use crate::query::{Query, SourceIndex};
+use crate::shared::Gen;
+use crate::{Diagnostic, Options, Sources, Storage};
+use runestick::{Item, Source, SourceId};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// A cache of indexed sources which can be reused across compilations.
+///
+/// When passed to [load_sources_with_cache](crate::load_sources_with_cache),
+/// only sources that have changed since the last compilation are parsed and
+/// indexed again. Everything else is restored from the cache and built as
+/// usual, so that items which refer to something that was removed by an edit
+/// are still reported.
+///
+/// Sources that depend on a changed source through imports are indexed again,
+/// as are sources with wildcard imports whenever anything changed. Sources
+/// which load modules from other files, or which produced diagnostics while
+/// being indexed, are never cached. Changing any of the [Options] which affect
+/// indexing, like the enabled features, causes every source to be indexed
+/// again.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::rc::Rc;
+///
+/// # fn main() -> runestick::Result<()> {
+/// let context = runestick::Context::with_default_modules()?;
+/// let options = rune::Options::default();
+/// let mut cache = rune::IndexCache::new();
+///
+/// for _ in 0..2 {
+///     let mut sources = rune::Sources::new();
+///     sources.insert(runestick::Source::new("entry", "pub fn main() { 42 }"));
+///
+///     let mut diagnostics = rune::Diagnostics::new();
+///
+///     rune::load_sources_with_cache(
+///         &context,
+///         &options,
+///         &mut sources,
+///         &mut diagnostics,
+///         Rc::new(rune::NoopCompileVisitor::new()),
+///         Rc::new(rune::FileSourceLoader::new()),
+///         &mut cache,
+///     )?;
+/// }
+///
+/// // The source didn't change, so it was not indexed again.
+/// assert!(cache.indexed().is_empty());
+/// # Ok(()) }
+/// ```
+#[derive(Default)]
+pub struct IndexCache {
+    /// Macro storage shared by all cached sources.
+    pub(crate) storage: Storage,
+    /// Id generator, shared so that ids in cached sources are never reused.
+    pub(crate) gen: Gen,
+    /// Cached sources by source id.
+    entries: HashMap<SourceId, CachedSource>,
+    /// Sources which were indexed during the last compilation.
+    indexed: Vec<SourceId>,
+    /// The options the cached sources were indexed with.
+    options: Option<IndexOptions>,
+}
+
+struct CachedSource {
+    /// The source the index was built from.
+    source: Arc<Source>,
+    /// The captured index.
+    index: SourceIndex,
+}
+
+impl IndexCache {
+    /// Construct a new empty cache.
+    pub fn new() -> Self {
+        Self {
+            storage: Storage::new(),
+            gen: Gen::new(),
+            entries: HashMap::new(),
+            indexed: Vec::new(),
+            options: None,
+        }
+    }
+
+    /// Get the ids of the sources that were indexed during the last
+    /// compilation, rather than restored from the cache.
+    pub fn indexed(&self) -> &[SourceId] {
+        &self.indexed
+    }
+
+    /// Clear the cache, causing every source to be indexed again.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Take the indexes which can be reused for the given sources and options.
+    ///
+    /// Anything not returned is removed from the cache, and has to be indexed
+    /// again.
+    pub(crate) fn take_reusable(
+        &mut self,
+        sources: &Sources,
+        options: &Options,
+    ) -> HashMap<SourceId, SourceIndex> {
+        self.indexed.clear();
+
+        let options = IndexOptions::new(options);
+
+        if self.options.as_ref() != Some(&options) {
+            self.entries.clear();
+            self.options = Some(options);
+        }
+
+        let mut changed = Vec::new();
+        let mut any_changed = sources
+            .source_ids()
+            .any(|source_id| !self.entries.contains_key(&source_id));
+
+        let mut reusable = HashMap::new();
+
+        for (source_id, cached) in self.entries.drain() {
+            let unchanged = match sources.get(source_id) {
+                Some(source) => is_same_source(source, &cached.source),
+                None => false,
+            };
+
+            if unchanged {
+                reusable.insert(source_id, cached.index);
+            } else {
+                changed.extend(cached.index.declared_items().cloned());
+                any_changed = true;
+            }
+        }
+
+        // NB: an edit might remove or add items that other sources depend on
+        // through their imports, so those have to be indexed again as well.
+        reusable.retain(|_, index| {
+            if any_changed && index.has_wildcard_imports() {
+                return false;
+            }
+
+            !index
+                .import_targets()
+                .any(|target| changed.iter().any(|item| is_related(target, item)))
+        });
+
+        // NB: nothing refers to the macro storage or the ids generated for
+        // sources that are indexed again, so start over once nothing is reused
+        // to avoid growing them with every edit.
+        if reusable.is_empty() {
+            self.storage = Storage::new();
+            self.gen = Gen::new();
+        }
+
+        reusable
+    }
+
+    /// Mark that the given source was indexed.
+    pub(crate) fn mark_indexed(&mut self, source_id: SourceId) {
+        self.indexed.push(source_id);
+    }
+
+    /// Capture the index of every root source which can be cached.
+    ///
+    /// `diagnostics` are the diagnostics produced while indexing, and `loaded`
+    /// the sources which loaded modules from other files.
+    pub(crate) fn capture(
+        &mut self,
+        query: &Query,
+        sources: &Sources,
+        roots: usize,
+        diagnostics: &[Diagnostic],
+        loaded: &HashSet<SourceId>,
+    ) {
+        let dirty = diagnostics
+            .iter()
+            .map(|diagnostic| match diagnostic {
+                Diagnostic::Error(error) => error.source_id(),
+                Diagnostic::Warning(warning) => warning.source_id(),
+            })
+            .collect::<HashSet<_>>();
+
+        for source_id in sources.source_ids().take(roots) {
+            if dirty.contains(&source_id) || loaded.contains(&source_id) {
+                continue;
+            }
+
+            let source = match sources.get(source_id) {
+                Some(source) => source.clone(),
+                None => continue,
+            };
+
+            let index = query.source_index(source_id);
+            self.entries
+                .insert(source_id, CachedSource { source, index });
+        }
+    }
+}
+
+/// The options which affect indexing.
+#[derive(PartialEq)]
+struct IndexOptions {
+    macros: bool,
+    features: BTreeSet<String>,
+    const_budget: usize,
+}
+
+impl IndexOptions {
+    fn new(options: &Options) -> Self {
+        Self {
+            macros: options.macros,
+            features: options.features.clone(),
+            const_budget: options.const_budget,
+        }
+    }
+}
+
+/// Test if two sources are the same.
+fn is_same_source(a: &Arc<Source>, b: &Arc<Source>) -> bool {
+    Arc::ptr_eq(a, b) || (a.name() == b.name() && a.path() == b.path() && a.as_str() == b.as_str())
+}
+
+/// Test if one item is the ancestor of another, or if they are the same.
+fn is_related(a: &Item, b: &Item) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
@@ -1,4 +1,5 @@
 use crate::ast;
+use crate::collections::{HashMap, HashSet};
 use crate::load::{FileSourceLoader, SourceLoader, Sources};
 use crate::query::{Build, BuildEntry, Query};
 #[cfg(compiler_v2)]
@@ -6,20 +7,23 @@ use crate::shared::ResultExt as _;
 use crate::shared::{Consts, Gen};
 use crate::worker::{LoadFileKind, Task, Worker};
 use crate::{Diagnostics, Options, Spanned as _, Storage};
-use runestick::{Context, Location, Source, Span};
+use runestick::{CompileItem, Context, Location, Source, Span};
 use std::rc::Rc;
 use std::sync::Arc;
 
 mod assembly;
 mod compile_error;
 mod compile_visitor;
+mod index_cache;
 mod unit_builder;
 mod v1;
 #[cfg(compiler_v2)]
 mod v2;
 
+pub(crate) use self::compile_error::format_variants;
 pub use self::compile_error::{CompileError, CompileErrorKind, CompileResult, ImportEntryStep};
 pub use self::compile_visitor::{CompileVisitor, NoopCompileVisitor};
+pub use self::index_cache::IndexCache;
 pub use self::unit_builder::{BuildError, InsertMetaError, LinkerError, UnitBuilder};
 use crate::parsing::Resolve as _;
 
@@ -58,13 +62,77 @@ pub fn compile_with_options<'a>(
     visitor: Rc<dyn CompileVisitor>,
     source_loader: Rc<dyn SourceLoader + 'a>,
 ) -> Result<(), ()> {
-    // Global storage.
-    let storage = Storage::new();
-    // Shared id generator.
-    let gen = Gen::new();
-    // Constants storage.
+    compile_with_cache(
+        context,
+        sources,
+        unit,
+        diagnostics,
+        options,
+        visitor,
+        source_loader,
+        None,
+    )
+}
+
+/// Encode the given object into a collection of asm, reusing and updating the
+/// indexes of unchanged sources in the given cache.
+pub(crate) fn compile_with_cache<'a>(
+    context: &Context,
+    sources: &mut Sources,
+    unit: &UnitBuilder,
+    diagnostics: &mut Diagnostics,
+    options: &Options,
+    visitor: Rc<dyn CompileVisitor>,
+    source_loader: Rc<dyn SourceLoader + 'a>,
+    cache: Option<&mut IndexCache>,
+) -> Result<(), ()> {
+    // NB: warnings have to be collected to be promoted into errors, even if
+    // the diagnostics are configured to ignore them.
+    let old = diagnostics.set_warnings_as_errors(options.warnings_as_errors);
+
+    let result = compile_with_cache_inner(
+        context,
+        sources,
+        unit,
+        diagnostics,
+        options,
+        visitor,
+        source_loader,
+        cache,
+    );
+
+    diagnostics.set_warnings_as_errors(old);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn compile_with_cache_inner<'a>(
+    context: &Context,
+    sources: &mut Sources,
+    unit: &UnitBuilder,
+    diagnostics: &mut Diagnostics,
+    options: &Options,
+    visitor: Rc<dyn CompileVisitor>,
+    source_loader: Rc<dyn SourceLoader + 'a>,
+    mut cache: Option<&mut IndexCache>,
+) -> Result<(), ()> {
+    let (storage, gen, mut reusable) = match &mut cache {
+        Some(cache) => {
+            let reusable = cache.take_reusable(sources, options);
+            (cache.storage.clone(), cache.gen.clone(), reusable)
+        }
+        // Global storage and shared id generator.
+        None => (Storage::new(), Gen::new(), HashMap::new()),
+    };
+
+    // Constants storage, seeded with the constants registered in the context
+    // so that they can be used in constant expressions.
     let consts = Consts::default();
 
+    for (item, const_value) in context.iter_constants() {
+        consts.insert(item.clone(), const_value.clone());
+    }
+
     // The worker queue.
     let mut worker = Worker::new(
         context,
@@ -79,6 +147,9 @@ pub fn compile_with_options<'a>(
         gen,
     );
 
+    let roots = worker.sources.source_ids().count();
+    let diagnostics_before = worker.diagnostics.diagnostics().len();
+
     // Queue up the initial sources to be loaded.
     for source_id in worker.sources.source_ids() {
         let mod_item = match worker.query.insert_root_mod(source_id, Span::empty()) {
@@ -89,6 +160,19 @@ pub fn compile_with_options<'a>(
             }
         };
 
+        if let Some(index) = reusable.remove(&source_id) {
+            if let Err(error) = worker.query.restore_source_index(&index) {
+                worker.diagnostics.error(source_id, error);
+                return Err(());
+            }
+
+            continue;
+        }
+
+        if let Some(cache) = &mut cache {
+            cache.mark_indexed(source_id);
+        }
+
         worker.queue.push_back(Task::LoadFile {
             kind: LoadFileKind::Root,
             source_id,
@@ -98,10 +182,39 @@ pub fn compile_with_options<'a>(
 
     worker.run();
 
+    let query = &worker.query;
+    worker
+        .diagnostics
+        .suppress_warnings(diagnostics_before, |warning| query.is_allowed(warning));
+
     if worker.diagnostics.has_error() {
+        if options.warnings_as_errors {
+            worker.diagnostics.promote_warnings();
+        }
+
         return Err(());
     }
 
+    for (item, location, kind) in worker.query.items() {
+        visitor.visit_item(location.source_id, &item, location.span, kind);
+    }
+
+    if let Some(cache) = &mut cache {
+        let loaded = worker
+            .loaded
+            .values()
+            .map(|(source_id, _)| *source_id)
+            .collect::<HashSet<_>>();
+
+        cache.capture(
+            &worker.query,
+            worker.sources,
+            roots,
+            &worker.diagnostics.diagnostics()[diagnostics_before..],
+            &loaded,
+        );
+    }
+
     loop {
         while let Some(entry) = worker.query.next_build_entry() {
             let source_id = entry.location.source_id;
@@ -131,6 +244,15 @@ pub fn compile_with_options<'a>(
         }
     }
 
+    let query = &worker.query;
+    worker
+        .diagnostics
+        .suppress_warnings(diagnostics_before, |warning| query.is_allowed(warning));
+
+    if options.warnings_as_errors {
+        worker.diagnostics.promote_warnings();
+    }
+
     if worker.diagnostics.has_error() {
         return Err(());
     }
@@ -152,12 +274,14 @@ struct CompileBuildEntry<'a> {
 impl CompileBuildEntry<'_> {
     fn compiler1<'a>(
         &'a mut self,
+        item: &Arc<CompileItem>,
         location: Location,
         source: &Arc<Source>,
         span: Span,
         asm: &'a mut Assembly,
     ) -> self::v1::Compiler<'a> {
         self::v1::Compiler {
+            item: item.clone(),
             visitor: self.visitor.clone(),
             storage: self.storage,
             source_id: location.source_id,
@@ -170,6 +294,7 @@ impl CompileBuildEntry<'_> {
             scopes: self::v1::Scopes::new(self.visitor.clone()),
             contexts: vec![span],
             loops: self::v1::Loops::new(),
+            optional_chains: Vec::new(),
             options: self.options,
             diagnostics: self.diagnostics,
         }
@@ -219,10 +344,12 @@ impl CompileBuildEntry<'_> {
                 let args = format_fn_args(&*source, f.ast.args.iter().map(|(a, _)| a))?;
 
                 let span = f.ast.span();
-                let count = f.ast.args.len();
+                let count = f.ast.arity();
 
-                let mut c = self.compiler1(location, &source, span, &mut asm);
+                let mut c = self.compiler1(&item, location, &source, span, &mut asm);
                 f.ast.assemble_fn(&mut c, false)?;
+                c.warn_unused_vars();
+                c.check_field_accesses()?;
 
                 // NB: experimental compiler that is work-in-progress
                 #[cfg(compiler_v2)]
@@ -241,6 +368,8 @@ impl CompileBuildEntry<'_> {
                         location,
                         item.item.clone(),
                         count,
+                        f.ast.defaults(),
+                        f.ast.has_rest(),
                         asm,
                         f.call,
                         args,
@@ -253,10 +382,10 @@ impl CompileBuildEntry<'_> {
                 let args = format_fn_args(&*source, f.ast.args.iter().map(|(a, _)| a))?;
 
                 let span = f.ast.span();
-                let count = f.ast.args.len();
+                let count = f.ast.arity();
                 let name = f.ast.name.resolve(self.storage, &*source)?;
 
-                let mut c = self.compiler1(location, &source, span, &mut asm);
+                let mut c = self.compiler1(&item, location, &source, span, &mut asm);
                 let meta = c.lookup_meta(f.instance_span, &f.impl_item)?;
 
                 let type_hash = meta
@@ -264,6 +393,8 @@ impl CompileBuildEntry<'_> {
                     .ok_or_else(|| CompileError::expected_meta(span, meta, "instance function"))?;
 
                 f.ast.assemble_fn(&mut c, true)?;
+                c.warn_unused_vars();
+                c.check_field_accesses()?;
 
                 if used.is_unused() {
                     c.diagnostics.not_used(location.source_id, span, None);
@@ -274,6 +405,8 @@ impl CompileBuildEntry<'_> {
                         type_hash,
                         name.as_ref(),
                         count,
+                        f.ast.defaults(),
+                        f.ast.has_rest(),
                         asm,
                         f.call,
                         args,
@@ -287,21 +420,38 @@ impl CompileBuildEntry<'_> {
                 let args =
                     format_fn_args(&*source, closure.ast.args.as_slice().iter().map(|(a, _)| a))?;
 
-                let mut c = self.compiler1(location, &source, span, &mut asm);
+                let mut c = self.compiler1(&item, location, &source, span, &mut asm);
                 closure.ast.assemble_closure(&mut c, &closure.captures)?;
+                c.warn_unused_vars();
+                c.check_field_accesses()?;
 
                 if used.is_unused() {
                     c.diagnostics
                         .not_used(location.source_id, location.span, None);
+                } else if self.options.dedup_closures && closure.captures.is_empty() {
+                    self.unit.new_shared_function(
+                        location,
+                        item.item.clone(),
+                        closure.ast.args.len(),
+                        asm,
+                        closure.call,
+                        args,
+                    )?;
                 } else {
                     self.unit.new_function(
                         location,
                         item.item.clone(),
                         closure.ast.args.len(),
+                        0,
+                        false,
                         asm,
                         closure.call,
                         args,
                     )?;
+
+                    if !closure.captures.is_empty() {
+                        self.unit.insert_captures(&item.item, &closure.captures);
+                    }
                 }
             }
             Build::AsyncBlock(b) => {
@@ -310,8 +460,10 @@ impl CompileBuildEntry<'_> {
                 let args = b.captures.len();
                 let span = b.ast.span();
 
-                let mut c = self.compiler1(location, &source, span, &mut asm);
+                let mut c = self.compiler1(&item, location, &source, span, &mut asm);
                 b.ast.assemble_closure(&mut c, &b.captures)?;
+                c.warn_unused_vars();
+                c.check_field_accesses()?;
 
                 if used.is_unused() {
                     self.diagnostics
@@ -321,6 +473,8 @@ impl CompileBuildEntry<'_> {
                         location,
                         item.item.clone(),
                         args,
+                        0,
+                        false,
                         asm,
                         b.call,
                         Vec::new(),
@@ -356,7 +510,10 @@ impl CompileBuildEntry<'_> {
                 if let Some(item) = missing {
                     return Err(CompileError::new(
                         location.span,
-                        CompileErrorKind::MissingItem { item: item.clone() },
+                        CompileErrorKind::MissingItem {
+                            item: item.clone(),
+                            suggestion: self.query.suggest_item(self.context, item),
+                        },
                     ));
                 }
             }
@@ -372,6 +529,7 @@ impl CompileBuildEntry<'_> {
                                 location.span,
                                 CompileErrorKind::MissingItem {
                                     item: item.item.clone(),
+                                    suggestion: None,
                                 },
                             ))
                         }
@@ -397,8 +555,8 @@ where
             ast::FnArg::SelfValue(..) => {
                 args.push(String::from("self"));
             }
-            ast::FnArg::Pat(pat) => {
-                let span = pat.span();
+            ast::FnArg::Pat(..) | ast::FnArg::Default(..) | ast::FnArg::Rest(..) => {
+                let span = arg.span();
 
                 if let Some(s) = source.source(span) {
                     args.push(s.to_owned());
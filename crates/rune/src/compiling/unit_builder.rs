@@ -8,9 +8,9 @@ use crate::compiling::{Assembly, AssemblyInst};
 use crate::{CompileError, CompileErrorKind, Diagnostics};
 use runestick::debug::{DebugArgs, DebugSignature};
 use runestick::{
-    Call, CompileMeta, CompileMetaKind, ConstValue, Context, DebugInfo, DebugInst, Hash, Inst,
-    IntoComponent, Item, Label, Location, Protocol, Rtti, Span, StaticString, Unit, UnitFn,
-    VariantRtti,
+    Call, CompileMeta, CompileMetaCapture, CompileMetaKind, ConstValue, Context, DebugInfo,
+    DebugInst, Hash, Inst, IntoComponent, Item, Label, Location, Protocol, Rtti, Span,
+    StaticString, Unit, UnitFn, VariantRtti,
 };
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -43,10 +43,13 @@ impl UnitBuilder {
         this.prelude("assert", &["test", "assert"]);
         this.prelude("bool", &["bool"]);
         this.prelude("byte", &["byte"]);
+        this.prelude("call_depth", &["call_depth"]);
         this.prelude("char", &["char"]);
         this.prelude("dbg", &["io", "dbg"]);
         this.prelude("drop", &["mem", "drop"]);
+        this.prelude("ensure", &["ensure"]);
         this.prelude("Err", &["result", "Result", "Err"]);
+        this.prelude("fields", &["fields"]);
         this.prelude("file", &["macros", "builtin", "file"]);
         this.prelude("float", &["float"]);
         this.prelude("format", &["fmt", "format"]);
@@ -66,6 +69,7 @@ impl UnitBuilder {
         this.prelude("String", &["string", "String"]);
         this.prelude("stringify", &["stringify"]);
         this.prelude("unit", &["unit"]);
+        this.prelude("variant_name", &["variant_name"]);
         this.prelude("Vec", &["vec", "Vec"]);
 
         Self {
@@ -104,16 +108,20 @@ impl UnitBuilder {
             }
         }
 
+        inner.tests.sort_by(|(a, _), (b, _)| a.iter().cmp(b.iter()));
+
         Ok(Unit::new(
             inner.instructions,
             inner.functions,
             inner.static_strings,
             inner.static_bytes,
+            inner.static_bigints,
             inner.static_object_keys,
             inner.rtti,
             inner.variant_rtti,
             inner.debug,
             inner.constants,
+            inner.tests,
         ))
     }
 
@@ -206,6 +214,15 @@ impl UnitBuilder {
         Ok(new_slot)
     }
 
+    /// Insert a static big integer and return its associated slot that can
+    /// later be looked up through [lookup_bigint][Unit::lookup_bigint].
+    pub(crate) fn new_static_bigint(&self, current: &num::BigInt) -> usize {
+        let mut inner = self.inner.borrow_mut();
+        let new_slot = inner.static_bigints.len();
+        inner.static_bigints.push(current.clone());
+        new_slot
+    }
+
     /// Insert a new collection of static object keys, or return one already
     /// existing.
     pub(crate) fn new_static_object_keys_iter<I>(
@@ -441,13 +458,19 @@ impl UnitBuilder {
                     return Err(InsertMetaError::VariantRttiConflict { hash });
                 }
             }
-            CompileMetaKind::Enum { type_hash } => {
+            CompileMetaKind::Enum { type_hash, .. } => {
                 inner.constants.insert(
                     Hash::instance_function(*type_hash, Protocol::INTO_TYPE_NAME),
                     ConstValue::String(meta.item.item.to_string()),
                 );
             }
-            CompileMetaKind::Function { .. } => (),
+            CompileMetaKind::Function {
+                type_hash, is_test, ..
+            } => {
+                if *is_test {
+                    inner.tests.push((meta.item.item.clone(), *type_hash));
+                }
+            }
             CompileMetaKind::Closure { .. } => (),
             CompileMetaKind::AsyncBlock { .. } => (),
             CompileMetaKind::Const { .. } => (),
@@ -470,6 +493,8 @@ impl UnitBuilder {
         location: Location,
         path: Item,
         args: usize,
+        defaults: usize,
+        rest: bool,
         assembly: Assembly,
         call: Call,
         debug_args: Vec<String>,
@@ -480,7 +505,13 @@ impl UnitBuilder {
         let hash = Hash::type_hash(&path);
 
         inner.functions_rev.insert(offset, hash);
-        let info = UnitFn::Offset { offset, call, args };
+        let info = UnitFn::Offset {
+            offset,
+            call,
+            args,
+            defaults,
+            rest,
+        };
         let signature = DebugSignature::new(path, debug_args);
 
         if inner.functions.insert(hash, info).is_some() {
@@ -503,6 +534,104 @@ impl UnitBuilder {
         Ok(())
     }
 
+    /// Record the names of the variables captured by the closure with the
+    /// given path.
+    pub(crate) fn insert_captures(&self, path: &Item, captures: &[CompileMetaCapture]) {
+        let names = captures.iter().map(|c| c.ident.to_string()).collect();
+
+        self.inner
+            .borrow_mut()
+            .debug_info_mut()
+            .captures
+            .insert(Hash::type_hash(path), names);
+    }
+
+    /// Declare a new function for a closure which doesn't capture its
+    /// environment.
+    ///
+    /// If an identical function has already been declared through this
+    /// method, the new function shares its instructions instead of adding
+    /// them to the unit again.
+    pub(crate) fn new_shared_function(
+        &self,
+        location: Location,
+        path: Item,
+        args: usize,
+        assembly: Assembly,
+        call: Call,
+        debug_args: Vec<String>,
+    ) -> Result<(), CompileError> {
+        let mut inner = self.inner.borrow_mut();
+        let inner = &mut *inner;
+
+        let offset = inner.instructions.len();
+        let hash = Hash::type_hash(&path);
+        let signature = DebugSignature::new(path, debug_args);
+
+        if inner.functions.contains_key(&hash) {
+            return Err(CompileError::new(
+                location.span,
+                CompileErrorKind::FunctionConflict {
+                    existing: signature,
+                },
+            ));
+        }
+
+        inner.add_assembly(location, assembly)?;
+
+        let instructions = &inner.instructions[offset..];
+
+        let existing = inner.shared_functions.iter().copied().find(|f| {
+            f.call == call
+                && f.args == args
+                && inner.instructions.get(f.offset..f.offset + f.len) == Some(instructions)
+        });
+
+        let offset = match existing {
+            Some(existing) => {
+                log::trace!("shared fn: {} => {}", signature.path, existing.offset);
+
+                inner.instructions.truncate(offset);
+
+                if let Some(debug) = &mut inner.debug {
+                    debug.instructions.truncate(offset);
+                }
+
+                existing.offset
+            }
+            None => {
+                inner.shared_functions.push(SharedFunction {
+                    offset,
+                    len: instructions.len(),
+                    call,
+                    args,
+                });
+
+                inner.functions_rev.insert(offset, hash);
+                offset
+            }
+        };
+
+        inner.functions.insert(
+            hash,
+            UnitFn::Offset {
+                offset,
+                call,
+                args,
+                defaults: 0,
+                rest: false,
+            },
+        );
+
+        inner.constants.insert(
+            Hash::instance_function(hash, Protocol::INTO_TYPE_NAME),
+            ConstValue::String(signature.path.to_string()),
+        );
+
+        inner.debug_info_mut().functions.insert(hash, signature);
+        Ok(())
+    }
+
     /// Register a new function re-export.
     pub(crate) fn new_function_reexport(
         &self,
@@ -532,6 +661,8 @@ impl UnitBuilder {
         type_hash: Hash,
         name: &str,
         args: usize,
+        defaults: usize,
+        rest: bool,
         assembly: Assembly,
         call: Call,
         debug_args: Vec<String>,
@@ -544,7 +675,13 @@ impl UnitBuilder {
         let instance_fn = Hash::instance_function(type_hash, name);
         let hash = Hash::type_hash(&path);
 
-        let info = UnitFn::Offset { offset, call, args };
+        let info = UnitFn::Offset {
+            offset,
+            call,
+            args,
+            defaults,
+            rest,
+        };
         let signature = DebugSignature::new(path, debug_args);
 
         if inner.functions.insert(instance_fn, info).is_some() {
@@ -621,6 +758,8 @@ struct Inner {
     instructions: Vec<Inst>,
     /// Registered re-exports.
     reexports: HashMap<Hash, Hash>,
+    /// Functions which can be shared by identical closures.
+    shared_functions: Vec<SharedFunction>,
     /// Where functions are located in the collection of instructions.
     functions: HashMap<Hash, UnitFn>,
     /// Function by address.
@@ -633,6 +772,8 @@ struct Inner {
     static_bytes: Vec<Vec<u8>>,
     /// Reverse lookup for static byte strings.
     static_bytes_rev: HashMap<Hash, usize>,
+    /// A static big integer.
+    static_bigints: Vec<num::BigInt>,
     /// Slots used for object keys.
     ///
     /// This is used when an object is used in a pattern match, to avoid having
@@ -655,6 +796,21 @@ struct Inner {
 
     /// Constant values
     constants: HashMap<Hash, ConstValue>,
+    /// Functions annotated with `#[test]`.
+    tests: Vec<(Item, Hash)>,
+}
+
+/// A function whose instructions can be shared.
+#[derive(Debug, Clone, Copy)]
+struct SharedFunction {
+    /// Offset of the first instruction of the function.
+    offset: usize,
+    /// The number of instructions in the function.
+    len: usize,
+    /// The way the function is called.
+    call: Call,
+    /// The number of arguments the function takes.
+    args: usize,
 }
 
 impl Inner {
@@ -674,8 +830,13 @@ impl Inner {
     }
 
     /// Translate the given assembly into instructions.
-    fn add_assembly(&mut self, location: Location, assembly: Assembly) -> Result<(), CompileError> {
+    fn add_assembly(
+        &mut self,
+        location: Location,
+        mut assembly: Assembly,
+    ) -> Result<(), CompileError> {
         self.label_count = assembly.label_count;
+        assembly.optimize_tail_calls();
 
         self.required_functions.extend(assembly.required_functions);
 
@@ -710,6 +871,11 @@ impl Inner {
                     self.instructions
                         .push(Inst::JumpIfBranch { branch, offset });
                 }
+                AssemblyInst::JumpIfFrameLen { len, label } => {
+                    comment = Some(format!("label:{}", label));
+                    let offset = translate_offset(span, pos, label, &assembly.labels)?;
+                    self.instructions.push(Inst::JumpIfFrameLen { len, offset });
+                }
                 AssemblyInst::PopAndJumpIfNot { count, label } => {
                     comment = Some(format!("label:{}", label));
                     let offset = translate_offset(span, pos, label, &assembly.labels)?;
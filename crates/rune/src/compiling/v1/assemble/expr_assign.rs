@@ -17,6 +17,7 @@ impl Assemble for ast::ExprAssign {
                     .ok_or_else(|| CompileError::msg(path, "unsupported path"))?;
                 let ident = segment.resolve(c.storage, &*c.source)?;
                 let var = c.scopes.get_var(&*ident, c.source_id, span)?;
+                c.scopes.mark_assigned(var);
                 c.asm.push(Inst::Replace { offset: var.offset }, span);
                 true
             }
@@ -26,6 +26,11 @@ impl Assemble for ast::ExprBinary {
             return Ok(Asm::top(span));
         }
 
+        if let Some(operands) = string_concat_operands(self) {
+            compile_string_concat(c, span, &operands, needs)?;
+            return Ok(Asm::top(span));
+        }
+
         let guard = c.scopes.push_child(span)?;
 
         // NB: need to declare these as anonymous local variables so that they
@@ -88,6 +93,84 @@ fn rhs_needs_of(op: ast::BinOp) -> Needs {
     }
 }
 
+/// Collect the operands of a chain of additions which starts with a literal
+/// string, like `"a" + b + c`, in the order they should be evaluated.
+fn string_concat_operands(expr: &ast::ExprBinary) -> Option<Vec<&ast::Expr>> {
+    if expr.op != ast::BinOp::Add {
+        return None;
+    }
+
+    let mut operands = vec![&expr.rhs];
+    let mut current = &expr.lhs;
+
+    while let ast::Expr::Binary(binary) = current {
+        if binary.op != ast::BinOp::Add {
+            break;
+        }
+
+        operands.push(&binary.rhs);
+        current = &binary.lhs;
+    }
+
+    match current {
+        ast::Expr::Lit(expr_lit) if matches!(expr_lit.lit, ast::Lit::Str(..)) => {
+            operands.push(current);
+        }
+        _ => return None,
+    }
+
+    operands.reverse();
+    Some(operands)
+}
+
+/// Compile a chain of additions starting with a literal string into a single
+/// string concatenation, which formats each operand using the display
+/// protocol.
+///
+/// This is only an optimization, adding a value to any string at runtime
+/// formats it in the same way.
+fn compile_string_concat(
+    c: &mut Compiler<'_>,
+    span: Span,
+    operands: &[&ast::Expr],
+    needs: Needs,
+) -> CompileResult<()> {
+    let guard = c.scopes.push_child(span)?;
+    let mut size_hint = 0;
+
+    for operand in operands {
+        if let ast::Expr::Lit(expr_lit) = operand {
+            if let ast::Lit::Str(s) = &expr_lit.lit {
+                let s = s.resolve(c.storage, &*c.source)?;
+                size_hint += s.len();
+
+                let slot = c.unit.new_static_string(operand.span(), &*s)?;
+                c.asm.push(Inst::String { slot }, operand.span());
+                c.scopes.decl_anon(span)?;
+                continue;
+            }
+        }
+
+        operand.assemble(c, Needs::Value)?.apply(c)?;
+        c.scopes.decl_anon(span)?;
+    }
+
+    c.asm.push(
+        Inst::StringConcat {
+            len: operands.len(),
+            size_hint,
+        },
+        span,
+    );
+
+    if !needs.value() {
+        c.asm.push(Inst::Pop, span);
+    }
+
+    c.scopes.pop(guard, span)?;
+    Ok(())
+}
+
 fn compile_conditional_binop(
     c: &mut Compiler<'_>,
     lhs: &ast::Expr,
@@ -108,6 +191,12 @@ fn compile_conditional_binop(
         ast::BinOp::Or => {
             c.asm.jump_if_or_pop(end_label, lhs.span());
         }
+        ast::BinOp::Coalesce => {
+            c.asm.push(Inst::Dup, lhs.span());
+            c.asm.push(Inst::IsPresent, lhs.span());
+            c.asm.jump_if(end_label, lhs.span());
+            c.asm.push(Inst::Pop, lhs.span());
+        }
         op => {
             return Err(CompileError::new(
                 span,
@@ -148,6 +237,7 @@ fn compile_assign_binop(
 
             let ident = segment.resolve(c.storage, &*c.source)?;
             let var = c.scopes.get_var(&*ident, c.source_id, span)?;
+            c.scopes.mark_assigned(var);
 
             Some(InstTarget::Offset(var.offset))
         }
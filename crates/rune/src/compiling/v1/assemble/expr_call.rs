@@ -1,4 +1,5 @@
 use crate::compiling::v1::assemble::prelude::*;
+use runestick::format::FormatStr;
 
 /// Compile a call expression.
 impl Assemble for ast::ExprCall {
@@ -99,6 +100,7 @@ impl Assemble for ast::ExprCall {
         }
 
         let meta = c.lookup_meta(path.span(), &named.item)?;
+        let mut call_site = None;
 
         match &meta.kind {
             CompileMetaKind::UnitStruct { .. } | CompileMetaKind::UnitVariant { .. } => {
@@ -132,7 +134,39 @@ impl Assemble for ast::ExprCall {
                         .remove_tuple_call_parens(c.source_id, span, tuple, c.context());
                 }
             }
-            CompileMetaKind::Function { .. } => (),
+            CompileMetaKind::Function {
+                args,
+                defaults,
+                rest,
+                call_site: at,
+                ..
+            } => {
+                if let Some(args) = *args {
+                    let actual = self.args.len();
+
+                    if (actual > args && !*rest) || actual + *defaults < args {
+                        return Err(CompileError::new(
+                            span,
+                            CompileErrorKind::UnsupportedArgumentCount {
+                                meta: meta.clone(),
+                                expected: args,
+                                actual,
+                            },
+                        ));
+                    }
+                }
+
+                if meta.item.item == Item::with_crate_item("std", &["fmt", "format"]) {
+                    validate_format(c, self)?;
+                }
+
+                if !self.args.is_empty() {
+                    call_site = *at;
+                }
+
+                c.visitor
+                    .visit_call(c.source_id, &c.item.item, &meta.item.item, span);
+            }
             CompileMetaKind::ConstFn { id, .. } => {
                 let from = c.query.item_for(self)?;
                 let const_fn = c.query.const_fn_for((self.span(), *id))?;
@@ -153,14 +187,35 @@ impl Assemble for ast::ExprCall {
             }
         };
 
-        for (expr, _) in &self.args {
-            expr.assemble(c, Needs::Value)?.apply(c)?;
-            c.scopes.decl_anon(span)?;
-        }
+        // NB: direct calls to functions like `dbg` pass along the call site of
+        // each argument.
+        if let Some(hash) = call_site {
+            let (line, _) = c.source.position(span.start.into_usize());
 
-        let hash = Hash::type_hash(&meta.item.item);
-        c.asm
-            .push_with_comment(Inst::Call { hash, args }, span, meta.to_string());
+            for (expr, _) in &self.args {
+                let text = c.source.source(expr.span()).unwrap_or_default();
+                let location = format!("[{}:{}] {}", c.source.name(), line, text);
+                let slot = c.unit.new_static_string(span, &location)?;
+                c.asm.push(Inst::String { slot }, span);
+                c.scopes.decl_anon(span)?;
+
+                expr.assemble(c, Needs::Value)?.apply(c)?;
+                c.scopes.decl_anon(span)?;
+            }
+
+            let args = args * 2;
+            c.asm
+                .push_with_comment(Inst::Call { hash, args }, span, meta.to_string());
+        } else {
+            for (expr, _) in &self.args {
+                expr.assemble(c, Needs::Value)?.apply(c)?;
+                c.scopes.decl_anon(span)?;
+            }
+
+            let hash = Hash::type_hash(&meta.item.item);
+            c.asm
+                .push_with_comment(Inst::Call { hash, args }, span, meta.to_string());
+        }
 
         // NB: we put it here to preserve the call in case it has side effects.
         // But if we don't need the value, then pop it from the stack.
@@ -172,3 +227,26 @@ impl Assemble for ast::ExprCall {
         Ok(Asm::top(span))
     }
 }
+
+/// Validate a literal format string passed to `std::fmt::format`.
+///
+/// Format strings which are not literals are validated at runtime.
+fn validate_format(c: &mut Compiler<'_>, call: &ast::ExprCall) -> CompileResult<()> {
+    let expr_lit = match call.args.first() {
+        Some((ast::Expr::Lit(expr_lit), _)) => expr_lit,
+        _ => return Ok(()),
+    };
+
+    let lit = match &expr_lit.lit {
+        ast::Lit::Str(lit) => lit,
+        _ => return Ok(()),
+    };
+
+    let format = lit.resolve(c.storage, &*c.source)?;
+
+    if let Err(error) = FormatStr::parse(&format, call.args.len() - 1) {
+        return Err(CompileError::new(lit, error));
+    }
+
+    Ok(())
+}
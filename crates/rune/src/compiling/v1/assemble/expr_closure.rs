@@ -22,6 +22,18 @@ impl AssembleClosure for ast::ExprClosure {
                         let offset = c.scopes.decl_anon(pat.span())?;
                         patterns.push((pat, offset));
                     }
+                    ast::FnArg::Default(arg) => {
+                        return Err(CompileError::new(
+                            arg,
+                            CompileErrorKind::UnsupportedFnArgDefault,
+                        ))
+                    }
+                    ast::FnArg::Rest(arg) => {
+                        return Err(CompileError::new(
+                            arg,
+                            CompileErrorKind::UnsupportedFnArgRest,
+                        ))
+                    }
                 }
             }
 
@@ -74,6 +86,7 @@ impl Assemble for ast::ExprClosure {
                     span,
                     CompileErrorKind::MissingItem {
                         item: item.item.clone(),
+                        suggestion: None,
                     },
                 ))
             }
@@ -100,6 +113,10 @@ impl Assemble for ast::ExprClosure {
             );
         } else {
             // Construct a closure environment.
+            //
+            // NB: copying a captured variable only copies the value in its
+            // slot, so reference types like vectors and objects are shared
+            // with the closure while scalars are not.
             for capture in captures {
                 if do_move {
                     let var = c.scopes.take_var(&capture.ident, c.source_id, span)?;
@@ -38,6 +38,14 @@ impl Assemble for ast::ExprFieldAccess {
             ast::ExprField::Path(path) => {
                 if let Some(ident) = path.try_as_ident() {
                     let field = ident.resolve(c.storage, &*c.source)?;
+
+                    if let ast::Expr::Path(path) = &self.expr {
+                        if let Some(name) = path.try_as_ident() {
+                            let name = name.resolve(c.storage, &*c.source)?;
+                            c.scopes.access_field(&*name, &*field, ident.span());
+                        }
+                    }
+
                     let slot = c.unit.new_static_string(span, field.as_ref())?;
 
                     c.asm.push(Inst::ObjectIndexGet { slot }, span);
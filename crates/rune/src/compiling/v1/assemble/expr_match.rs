@@ -58,6 +58,8 @@ impl Assemble for ast::ExprMatch {
             branches.push((branch_label, scope));
         }
 
+        check_exhaustive(c, self)?;
+
         // what to do in case nothing matches and the pattern doesn't have any
         // default match branch.
         if needs.value() {
@@ -89,3 +91,160 @@ impl Assemble for ast::ExprMatch {
         Ok(Asm::top(span))
     }
 }
+
+/// How the pattern of a match branch covers the variants of an enum.
+enum Coverage {
+    /// The pattern matches any value.
+    Any,
+    /// The pattern matches a variant of the given enum. If `complete` is set,
+    /// it matches every value of that variant.
+    Variant {
+        enum_item: Item,
+        variant: Item,
+        complete: bool,
+    },
+    /// The pattern matches something else.
+    Other,
+}
+
+/// Check that a match over a user-defined enum covers all of its variants,
+/// unless it has a branch which matches anything.
+///
+/// Branches with conditions are not considered to cover anything.
+fn check_exhaustive(c: &mut Compiler<'_>, expr_match: &ast::ExprMatch) -> CompileResult<()> {
+    let mut enum_item = None;
+    let mut covered = Vec::new();
+
+    for (branch, _) in &expr_match.branches {
+        let unconditional = branch.condition.is_none();
+
+        let pats = match &branch.pat {
+            ast::Pat::PatOr(pat_or) => pat_or.alternatives().collect(),
+            pat => vec![pat],
+        };
+
+        for pat in pats {
+            match pat_coverage(c, pat)? {
+                Coverage::Any => {
+                    if unconditional {
+                        return Ok(());
+                    }
+                }
+                Coverage::Variant {
+                    enum_item: item,
+                    variant,
+                    complete,
+                } => {
+                    match &enum_item {
+                        Some(existing) if *existing != item => return Ok(()),
+                        Some(..) => (),
+                        None => enum_item = Some(item),
+                    }
+
+                    if unconditional && complete {
+                        covered.push(variant);
+                    }
+                }
+                Coverage::Other => return Ok(()),
+            }
+        }
+    }
+
+    let enum_item = match enum_item {
+        Some(enum_item) => enum_item,
+        None => return Ok(()),
+    };
+
+    let span = expr_match.expr.span();
+    let meta = c.lookup_meta(span, &enum_item)?;
+
+    // NB: only enums declared in scripts are checked.
+    let variants = match &meta.kind {
+        CompileMetaKind::Enum { variants, .. } if meta.source.is_some() => variants,
+        _ => return Ok(()),
+    };
+
+    let missing = variants
+        .iter()
+        .filter(|variant| !covered.contains(variant))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    if c.options.strict {
+        return Err(CompileError::new(
+            span,
+            CompileErrorKind::NonExhaustiveMatchPatterns { missing },
+        ));
+    }
+
+    c.diagnostics
+        .non_exhaustive_match(c.source_id, span, missing, c.context());
+    Ok(())
+}
+
+/// Calculate the coverage of a single branch pattern.
+fn pat_coverage(c: &mut Compiler<'_>, pat: &ast::Pat) -> CompileResult<Coverage> {
+    let (path, items) = match pat {
+        ast::Pat::PatIgnore(..) => return Ok(Coverage::Any),
+        ast::Pat::PatPath(pat_path) => (&pat_path.path, None),
+        ast::Pat::PatTuple(ast::PatTuple {
+            path: Some(path),
+            items,
+            ..
+        }) => (path, Some(items.iter())),
+        ast::Pat::PatObject(ast::PatObject {
+            ident: ast::ObjectIdent::Named(path),
+            items,
+            ..
+        }) => (path, Some(items.iter())),
+        _ => return Ok(Coverage::Other),
+    };
+
+    let named = c.convert_path_to_named(path)?;
+
+    let meta = match c.try_lookup_meta(path.span(), &named.item)? {
+        Some(meta) => meta,
+        None if items.is_none() && named.as_local().is_some() => return Ok(Coverage::Any),
+        None => return Ok(Coverage::Other),
+    };
+
+    let enum_item = match &meta.kind {
+        CompileMetaKind::UnitVariant { enum_item, .. }
+        | CompileMetaKind::TupleVariant { enum_item, .. }
+        | CompileMetaKind::StructVariant { enum_item, .. } => enum_item.clone(),
+        _ => return Ok(Coverage::Other),
+    };
+
+    let mut complete = true;
+
+    for (pat, _) in items.into_iter().flatten() {
+        if !is_irrefutable(c, pat)? {
+            complete = false;
+            break;
+        }
+    }
+
+    Ok(Coverage::Variant {
+        enum_item,
+        variant: meta.item.item.clone(),
+        complete,
+    })
+}
+
+/// Test if the given nested pattern matches any value.
+fn is_irrefutable(c: &mut Compiler<'_>, pat: &ast::Pat) -> CompileResult<bool> {
+    Ok(match pat {
+        ast::Pat::PatIgnore(..) | ast::Pat::PatRest(..) => true,
+        ast::Pat::PatBinding(binding) => is_irrefutable(c, &binding.pat)?,
+        ast::Pat::PatPath(pat_path) => {
+            let named = c.convert_path_to_named(&pat_path.path)?;
+
+            named.as_local().is_some() && c.try_lookup_meta(pat_path.span(), &named.item)?.is_none()
+        }
+        _ => false,
+    })
+}
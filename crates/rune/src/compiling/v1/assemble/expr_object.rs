@@ -12,12 +12,29 @@ impl Assemble for ast::ExprObject {
         let mut keys = Vec::<Box<str>>::new();
         let mut check_keys = Vec::new();
         let mut keys_dup = HashMap::new();
+        let mut assignments = Vec::new();
+        let mut spread = None;
+
+        for (index, (field, _)) in self.assignments.iter().enumerate() {
+            let assign = match field {
+                ast::ObjectField::Assign(assign) => assign,
+                ast::ObjectField::Spread(object_spread) if index == 0 => {
+                    spread = Some(object_spread);
+                    continue;
+                }
+                ast::ObjectField::Spread(object_spread) => {
+                    return Err(CompileError::new(
+                        object_spread,
+                        CompileErrorKind::ObjectSpreadNotFirst,
+                    ));
+                }
+            };
 
-        for (assign, _) in &self.assignments {
             let span = assign.span();
             let key = assign.key.resolve(c.storage, &*c.source)?;
             keys.push(key.as_ref().into());
             check_keys.push((key.as_ref().into(), assign.key.span()));
+            assignments.push(assign);
 
             if let Some(existing) = keys_dup.insert(key.into_owned(), span) {
                 return Err(CompileError::new(
@@ -30,7 +47,19 @@ impl Assemble for ast::ExprObject {
             }
         }
 
-        for (assign, _) in &self.assignments {
+        if let Some(spread) = spread {
+            if let ast::ObjectIdent::Named(..) = &self.ident {
+                return Err(CompileError::new(
+                    spread,
+                    CompileErrorKind::UnsupportedObjectSpread,
+                ));
+            }
+
+            spread.expr.assemble(c, Needs::Value)?.apply(c)?;
+            c.scopes.decl_anon(spread.span())?;
+        }
+
+        for assign in assignments {
             let span = assign.span();
 
             if let Some((_, expr)) = &assign.assign {
@@ -78,7 +107,11 @@ impl Assemble for ast::ExprObject {
                 };
             }
             ast::ObjectIdent::Anonymous(..) => {
-                c.asm.push(Inst::Object { slot }, span);
+                if spread.is_some() {
+                    c.asm.push(Inst::ObjectSpread { slot }, span);
+                } else {
+                    c.asm.push(Inst::Object { slot }, span);
+                }
             }
         }
 
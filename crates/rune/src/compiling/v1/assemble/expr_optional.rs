@@ -0,0 +1,81 @@
+use crate::compiling::v1::assemble::prelude::*;
+
+/// Compile an expression chain with optional links.
+impl Assemble for ast::ExprOptionalChain {
+    fn assemble(&self, c: &mut Compiler<'_>, needs: Needs) -> CompileResult<Asm> {
+        let span = self.span();
+        log::trace!("ExprOptionalChain => {:?}", c.source.source(span));
+
+        let end = c.asm.new_label("optional_chain_end");
+        let var_count = c.scopes.total_var_count(span)?;
+
+        c.optional_chains.push((end, var_count));
+        let result = self
+            .expr
+            .assemble(c, Needs::Value)
+            .and_then(|asm| asm.apply(c));
+        c.optional_chains.pop();
+        result?;
+
+        // NB: absent links jump here with the absent value on the stack.
+        c.asm.label(end)?;
+
+        if !needs.value() {
+            c.asm.push(Inst::Pop, span);
+        }
+
+        Ok(Asm::top(span))
+    }
+}
+
+/// Compile an optional link `<expr>?` in an expression chain.
+impl Assemble for ast::ExprOptional {
+    fn assemble(&self, c: &mut Compiler<'_>, needs: Needs) -> CompileResult<Asm> {
+        let span = self.span();
+        log::trace!("ExprOptional => {:?}", c.source.source(span));
+
+        let (end, var_count) = match c.optional_chains.last() {
+            Some(chain) => *chain,
+            None => {
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::Custom {
+                        message: "optional link outside of an optional chain",
+                    },
+                ))
+            }
+        };
+
+        let absent = c.asm.new_label("optional_absent");
+        let error = c.asm.new_label("optional_error");
+        let present = c.asm.new_label("optional_present");
+
+        self.expr.assemble(c, Needs::Value)?.apply(c)?;
+        c.asm.push(Inst::OptionalUnwrap, span);
+        c.asm.jump_if_branch(1, absent, span);
+        c.asm.jump_if_branch(2, error, span);
+        c.asm.push(Inst::Pop, span);
+        c.asm.jump(present, span);
+
+        // Clean up any temporaries pushed since the start of the chain, and
+        // short-circuit it with the absent value.
+        c.asm.label(absent)?;
+        let total_var_count = c.scopes.total_var_count(span)?;
+        c.locals_clean(total_var_count.saturating_sub(var_count), span);
+        c.asm.jump(end, span);
+
+        // Clean up all locals so far and return the error from the current
+        // function, like the try operator does.
+        c.asm.label(error)?;
+        c.locals_clean(total_var_count, span);
+        c.asm.push(Inst::Return, span);
+
+        c.asm.label(present)?;
+
+        if !needs.value() {
+            c.asm.push(Inst::Pop, span);
+        }
+
+        Ok(Asm::top(span))
+    }
+}
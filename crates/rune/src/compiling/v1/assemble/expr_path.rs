@@ -48,6 +48,7 @@ impl Assemble for ast::Path {
             span,
             CompileErrorKind::MissingItem {
                 item: named.item.clone(),
+                suggestion: c.query.suggest_item(c.context, &named.item),
             },
         ))
     }
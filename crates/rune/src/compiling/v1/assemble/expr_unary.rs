@@ -18,20 +18,8 @@ impl Assemble for ast::ExprUnary {
                         c.asm.push(Inst::float(-n), span);
                     }
                     ast::Number::Integer(int) => {
-                        use num::ToPrimitive as _;
                         use std::ops::Neg as _;
-
-                        let n = match int.neg().to_i64() {
-                            Some(n) => n,
-                            None => {
-                                return Err(CompileError::new(
-                                    span,
-                                    ParseErrorKind::BadNumberOutOfBounds,
-                                ));
-                            }
-                        };
-
-                        c.asm.push(Inst::integer(n), span);
+                        c.assemble_integer(&int.neg(), span)?;
                     }
                 }
 
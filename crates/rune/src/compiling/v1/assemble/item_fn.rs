@@ -7,8 +7,9 @@ impl AssembleFn for ast::ItemFn {
 
         let mut patterns = Vec::new();
         let mut first = true;
+        let has_defaults = self.defaults() > 0;
 
-        for (arg, _) in &self.args {
+        for (index, (arg, _)) in self.args.iter().enumerate() {
             let span = arg.span();
 
             match arg {
@@ -21,8 +22,30 @@ impl AssembleFn for ast::ItemFn {
                     c.scopes.new_var("self", span)?;
                 }
                 ast::FnArg::Pat(pat) => {
-                    let offset = c.scopes.decl_anon(pat.span())?;
-                    patterns.push((pat, offset));
+                    if !has_defaults || !bind_arg(c, pat)? {
+                        let offset = c.scopes.decl_anon(pat.span())?;
+                        patterns.push((pat.as_ref(), offset));
+                    }
+                }
+                ast::FnArg::Default(arg) => {
+                    // NB: the default value is only evaluated if the caller
+                    // didn't provide the argument, in which case it ends up in
+                    // the same stack slot as a provided argument would.
+                    let provided_label = c.asm.new_label("fn_arg_provided");
+                    c.asm.jump_if_frame_len(index + 1, provided_label, span);
+                    arg.default.assemble(c, Needs::Value)?.apply(c)?;
+                    c.asm.label(provided_label)?;
+
+                    if !bind_arg(c, &arg.pat)? {
+                        let offset = c.scopes.decl_anon(arg.pat.span())?;
+                        patterns.push((&arg.pat, offset));
+                    }
+                }
+                ast::FnArg::Rest(arg) => {
+                    c.asm.push(Inst::CollectRest { args: index }, span);
+                    let name = arg.name.resolve(c.storage, &*c.source)?;
+                    c.scopes
+                        .decl_var(name.as_ref(), c.source_id, arg.name.span())?;
                 }
             }
 
@@ -58,3 +81,29 @@ impl AssembleFn for ast::ItemFn {
         Ok(())
     }
 }
+
+/// Bind an argument which is a plain identifier directly to its stack slot,
+/// making it available to the default values of the arguments following it.
+///
+/// Returns `false` if the argument has to be bound through a pattern instead.
+fn bind_arg(c: &mut Compiler<'_>, pat: &ast::Pat) -> CompileResult<bool> {
+    let path = match pat {
+        ast::Pat::PatPath(path) => path,
+        _ => return Ok(false),
+    };
+
+    let span = path.span();
+    let named = c.convert_path_to_named(&path.path)?;
+
+    let ident = match named.as_local() {
+        Some(ident) => ident,
+        None => return Ok(false),
+    };
+
+    if c.try_lookup_meta(span, &named.item)?.is_some() {
+        return Ok(false);
+    }
+
+    c.scopes.decl_var(ident, c.source_id, span)?;
+    Ok(true)
+}
@@ -3,8 +3,6 @@ use crate::compiling::v1::assemble::prelude::*;
 /// Compile a literal number.
 impl Assemble for ast::LitNumber {
     fn assemble(&self, c: &mut Compiler<'_>, needs: Needs) -> CompileResult<Asm> {
-        use num::ToPrimitive as _;
-
         let span = self.span();
         log::trace!("LitNumber => {:?}", c.source.source(span));
 
@@ -21,17 +19,7 @@ impl Assemble for ast::LitNumber {
                 c.asm.push(Inst::float(number), span);
             }
             ast::Number::Integer(number) => {
-                let n = match number.to_i64() {
-                    Some(n) => n,
-                    None => {
-                        return Err(CompileError::new(
-                            span,
-                            ParseErrorKind::BadNumberOutOfBounds,
-                        ));
-                    }
-                };
-
-                c.asm.push(Inst::integer(n), span);
+                c.assemble_integer(&number, span)?;
             }
         }
 
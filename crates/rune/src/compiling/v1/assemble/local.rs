@@ -6,31 +6,69 @@ impl Assemble for ast::Local {
         let span = self.span();
         log::trace!("Local => {:?}", c.source.source(span));
 
+        if let Some(ty) = &self.ty {
+            c.check_local_type(ty, &self.pat)?;
+        }
+
+        if c.options.closure_to_fn {
+            check_closure_to_fn(c, &self.pat, &self.expr)?;
+        }
+
         let load = |c: &mut Compiler, needs: Needs| {
             // NB: assignments "move" the value being assigned.
             self.expr.assemble(c, needs)?.apply(c)?;
             Ok(())
         };
 
-        let false_label = c.asm.new_label("let_panic");
+        if let Some(expr_else) = &self.expr_else {
+            if !diverges(&expr_else.block) {
+                return Err(CompileError::new(
+                    expr_else,
+                    CompileErrorKind::GuardElseNotDiverging,
+                ));
+            }
+
+            // NB: the pattern is compiled in a child scope, so that a failed
+            // match only pops the variables declared by the pattern. The else
+            // block is then compiled in the parent scope, since that is what
+            // the stack looks like once we jump to it.
+            let guard = c.scopes.push_child(span)?;
+            let false_label = c.asm.new_label("guard_else");
+            let refutable = c.compile_pat(&self.pat, false_label, &load)?;
+            let scope = c.scopes.pop(guard, span)?;
+
+            if refutable {
+                let ok_label = c.asm.new_label("guard_ok");
+                c.asm.jump(ok_label, span);
+                c.asm.label(false_label)?;
+                expr_else.block.assemble(c, Needs::None)?.apply(c)?;
+                c.asm.label(ok_label)?;
+            }
+
+            c.scopes.merge(scope, span)?;
+        } else {
+            let false_label = c.asm.new_label("let_panic");
 
-        if c.compile_pat(&self.pat, false_label, &load)? {
-            c.diagnostics
-                .let_pattern_might_panic(c.source_id, span, c.context());
+            if c.compile_pat(&self.pat, false_label, &load)? {
+                c.diagnostics
+                    .let_pattern_might_panic(c.source_id, span, c.context());
 
-            let ok_label = c.asm.new_label("let_ok");
-            c.asm.jump(ok_label, span);
-            c.asm.label(false_label)?;
-            c.asm.push(
-                Inst::Panic {
-                    reason: runestick::PanicReason::UnmatchedPattern,
-                },
-                span,
-            );
+                let ok_label = c.asm.new_label("let_ok");
+                c.asm.jump(ok_label, span);
+                c.asm.label(false_label)?;
+                c.asm.push(
+                    Inst::Panic {
+                        reason: runestick::PanicReason::UnmatchedPattern,
+                    },
+                    span,
+                );
 
-            c.asm.label(ok_label)?;
+                c.asm.label(ok_label)?;
+            }
         }
 
+        decl_local_struct(c, &self.pat, &self.expr)?;
+
         // If a value is needed for a let expression, it is evaluated as a unit.
         if needs.value() {
             c.asm.push(Inst::unit(), span);
@@ -39,3 +77,97 @@ impl Assemble for ast::Local {
         Ok(Asm::top(span))
     }
 }
+
+/// Record the struct a variable is declared with, like `let foo = Foo { a: 1 };`,
+/// so that field accesses on it can be checked.
+fn decl_local_struct(c: &mut Compiler<'_>, pat: &ast::Pat, expr: &ast::Expr) -> CompileResult<()> {
+    let pat = match pat {
+        ast::Pat::PatPath(pat) => pat,
+        _ => return Ok(()),
+    };
+
+    let path = match expr {
+        ast::Expr::Object(object) => match &object.ident {
+            ast::ObjectIdent::Named(path) => path,
+            ast::ObjectIdent::Anonymous(..) => return Ok(()),
+        },
+        _ => return Ok(()),
+    };
+
+    let ident = match pat.path.try_as_ident() {
+        Some(ident) => ident.resolve(c.storage, &*c.source)?.into_owned(),
+        None => return Ok(()),
+    };
+
+    let named = c.convert_path_to_named(path)?;
+    let meta = c.lookup_meta(path.span(), &named.item)?;
+    c.scopes.decl_var_struct(&*ident, pat.span(), meta);
+    Ok(())
+}
+
+/// Warn if a closure which doesn't capture anything is bound to a name, like
+/// `let add = |a, b| a + b;`, since it could be a function instead.
+fn check_closure_to_fn(
+    c: &mut Compiler<'_>,
+    pat: &ast::Pat,
+    expr: &ast::Expr,
+) -> CompileResult<()> {
+    let closure = match (pat, expr) {
+        (ast::Pat::PatPath(path), ast::Expr::Closure(closure))
+            if path.path.try_as_ident().is_some() =>
+        {
+            closure
+        }
+        _ => return Ok(()),
+    };
+
+    let span = closure.span();
+    let item = c.query.item_for(&**closure)?;
+
+    if let Some(meta) = c.query.query_meta(span, &item.item, Default::default())? {
+        if let CompileMetaKind::Closure { captures, .. } = &meta.kind {
+            if captures.is_empty() {
+                c.diagnostics
+                    .closure_without_captures(c.source_id, span, c.context());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Test if the given block unconditionally diverges, which is the case if any
+/// of its statements is a `return`, `break`, or `continue`, or an expression
+/// where all branches diverge.
+fn diverges(block: &ast::Block) -> bool {
+    block.statements.iter().any(|stmt| match stmt {
+        ast::Stmt::Expr(expr, _) => expr_diverges(expr),
+        _ => false,
+    })
+}
+
+/// Test if the given expression unconditionally diverges.
+fn expr_diverges(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::Return(..) | ast::Expr::Break(..) | ast::Expr::Continue(..) => true,
+        ast::Expr::Block(expr) => {
+            expr.async_token.is_none() && expr.const_token.is_none() && diverges(&expr.block)
+        }
+        ast::Expr::If(expr) => match &expr.expr_else {
+            Some(expr_else) => {
+                diverges(&expr.block)
+                    && expr.expr_else_ifs.iter().all(|e| diverges(&e.block))
+                    && diverges(&expr_else.block)
+            }
+            None => false,
+        },
+        ast::Expr::Match(expr) => {
+            !expr.branches.is_empty()
+                && expr
+                    .branches
+                    .iter()
+                    .all(|(branch, _)| expr_diverges(&branch.body))
+        }
+        _ => false,
+    }
+}
@@ -19,6 +19,7 @@ mod expr_let;
 mod expr_loop;
 mod expr_match;
 mod expr_object;
+mod expr_optional;
 mod expr_path;
 mod expr_range;
 mod expr_return;
@@ -6,8 +6,8 @@ use crate::query::{Named, Query, QueryConstFn, Used};
 use crate::shared::Consts;
 use crate::CompileResult;
 use crate::{
-    CompileError, CompileErrorKind, Diagnostics, Options, Resolve as _, Spanned, Storage,
-    UnitBuilder,
+    CompileError, CompileErrorKind, Diagnostics, Options, ParseErrorKind, Resolve as _, Spanned,
+    Storage, UnitBuilder,
 };
 use runestick::{
     CompileItem, CompileMeta, CompileMetaKind, ConstValue, Context, Inst, InstValue, Item, Label,
@@ -42,6 +42,8 @@ impl Needs {
 }
 
 pub(crate) struct Compiler<'a> {
+    /// The item being compiled.
+    pub(crate) item: Arc<CompileItem>,
     /// Compiler visitor.
     pub(crate) visitor: Rc<dyn CompileVisitor>,
     /// The source id of the source.
@@ -66,6 +68,9 @@ pub(crate) struct Compiler<'a> {
     pub(crate) contexts: Vec<Span>,
     /// The nesting of loop we are currently in.
     pub(crate) loops: Loops,
+    /// The end labels of the optional chains we are currently in, and the
+    /// number of local variables before each chain.
+    pub(crate) optional_chains: Vec<(Label, usize)>,
     /// Enabled optimizations.
     pub(crate) options: &'a Options,
     /// Compilation warnings.
@@ -104,7 +109,10 @@ impl<'a> Compiler<'a> {
 
         Err(CompileError::new(
             spanned,
-            CompileErrorKind::MissingItem { item: item.clone() },
+            CompileErrorKind::MissingItem {
+                item: item.clone(),
+                suggestion: self.query.suggest_item(self.context, item),
+            },
         ))
     }
 
@@ -281,6 +289,12 @@ impl<'a> Compiler<'a> {
                     self.asm.jump(then_label, span);
                     self.asm.label(false_label)?;
                 } else {
+                    self.diagnostics.irrefutable_let_condition(
+                        self.source_id,
+                        expr_let.pat.span(),
+                        self.context(),
+                    );
+
                     self.asm.jump(then_label, span);
                 };
 
@@ -309,13 +323,13 @@ impl<'a> Compiler<'a> {
         // that it is indeed a vector.
         self.asm.push(Inst::Copy { offset }, span);
 
-        let (is_open, count) = pat_items_count(&pat_vec.items)?;
+        let (rest, count) = pat_sequence_items(&pat_vec.items)?;
 
         self.asm.push(
             Inst::MatchSequence {
                 type_check: TypeCheck::Vec,
                 len: count,
-                exact: !is_open,
+                exact: rest.is_none(),
             },
             span,
         );
@@ -323,12 +337,55 @@ impl<'a> Compiler<'a> {
         self.asm
             .pop_and_jump_if_not(self.scopes.local_var_count(span)?, false_label, span);
 
-        for (index, (pat, _)) in pat_vec.items.iter().take(count).enumerate() {
+        self.compile_pat_sequence_items(pat_vec.items.as_slice(), rest, offset, false_label)
+    }
+
+    /// Encode the items of a vector or tuple pattern, where the value being
+    /// matched is stored at `offset`.
+    ///
+    /// Items following the rest pattern at index `rest` are loaded counting
+    /// from the back, and a named rest pattern is bound to a vector of the
+    /// items it covers.
+    fn compile_pat_sequence_items<U>(
+        &mut self,
+        items: &[(ast::Pat, U)],
+        rest: Option<usize>,
+        offset: usize,
+        false_label: Label,
+    ) -> CompileResult<()> {
+        for (index, (pat, _)) in items.iter().enumerate() {
             let span = pat.span();
 
+            let inst = match rest {
+                Some(rest) if index == rest => {
+                    if let Some(name) = pat_rest_binding(&items[rest..=rest]) {
+                        let name = name.resolve(self.storage, &*self.source)?;
+                        let end = items.len() - rest - 1;
+
+                        self.asm.push(
+                            Inst::VecRestAt {
+                                offset,
+                                start: rest,
+                                end,
+                            },
+                            span,
+                        );
+
+                        self.scopes.decl_var(name.as_ref(), self.source_id, span)?;
+                    }
+
+                    continue;
+                }
+                Some(rest) if index > rest => Inst::TupleIndexGetBackAt {
+                    offset,
+                    index: items.len() - index - 1,
+                },
+                _ => Inst::TupleIndexGetAt { offset, index },
+            };
+
             let load = move |c: &mut Self, needs: Needs| {
                 if needs.value() {
-                    c.asm.push(Inst::TupleIndexGetAt { offset, index }, span);
+                    c.asm.push(inst, span);
                 }
 
                 Ok(())
@@ -364,7 +421,7 @@ impl<'a> Compiler<'a> {
         // interact with it multiple times.
         let offset = self.scopes.decl_anon(span)?;
 
-        let type_check = if let Some(path) = &pat_tuple.path {
+        let (type_check, rest, count) = if let Some(path) = &pat_tuple.path {
             let named = self.convert_path_to_named(path)?;
             let meta = self.lookup_meta(path.span(), &named.item)?;
 
@@ -411,40 +468,144 @@ impl<'a> Compiler<'a> {
                 ));
             }
 
-            match self.context.type_check_for(&meta.item.item) {
+            let type_check = match self.context.type_check_for(&meta.item.item) {
                 Some(type_check) => type_check,
                 None => type_check,
-            }
+            };
+
+            let rest = if has_rest { Some(count) } else { None };
+            (type_check, rest, count)
         } else {
-            TypeCheck::Tuple
+            let (rest, count) = pat_sequence_items(&pat_tuple.items)?;
+            (TypeCheck::Tuple, rest, count)
         };
 
-        let (is_open, count) = pat_items_count(&pat_tuple.items)?;
+        if let Some(name) = pat_rest_binding(&pat_tuple.items) {
+            return Err(CompileError::new(
+                name,
+                CompileErrorKind::UnsupportedPatternRestBinding,
+            ));
+        }
 
         self.asm.push(Inst::Copy { offset }, span);
         self.asm.push(
             Inst::MatchSequence {
                 type_check,
                 len: count,
-                exact: !is_open,
+                exact: rest.is_none(),
             },
             span,
         );
         self.asm
             .pop_and_jump_if_not(self.scopes.local_var_count(span)?, false_label, span);
 
-        for (index, (pat, _)) in pat_tuple.items.iter().take(count).enumerate() {
+        self.compile_pat_sequence_items(pat_tuple.items.as_slice(), rest, offset, false_label)
+    }
+
+    /// Encode an or-pattern match.
+    ///
+    /// Every alternative must bind the same variables. Once an alternative
+    /// matches, the variables it bound are moved into the same slots
+    /// regardless of which alternative it was.
+    pub(crate) fn compile_pat_or(
+        &mut self,
+        pat_or: &ast::PatOr,
+        false_label: Label,
+        load: &dyn Fn(&mut Self, Needs) -> CompileResult<()>,
+    ) -> CompileResult<()> {
+        let span = pat_or.span();
+        log::trace!("PatOr => {:?}", self.source.source(span));
+
+        load(self, Needs::Value)?;
+        let offset = self.scopes.decl_anon(span)?;
+        let base = self.scopes.total_var_count(span)?;
+
+        let ok_label = self.asm.new_label("pat_or_ok");
+        let mut bindings = None::<Vec<(String, Span)>>;
+
+        for pat in pat_or.alternatives() {
             let span = pat.span();
+            let alternative_false = self.asm.new_label("pat_or_false");
+
+            let guard = self.scopes.push_child(span)?;
 
             let load = move |c: &mut Self, needs: Needs| {
                 if needs.value() {
-                    c.asm.push(Inst::TupleIndexGetAt { offset, index }, span);
+                    c.asm.push(Inst::Copy { offset }, span);
                 }
 
                 Ok(())
             };
 
-            self.compile_pat(&*pat, false_label, &load)?;
+            self.compile_pat(pat, alternative_false, &load)?;
+            let scope = self.scopes.pop(guard, span)?;
+            let vars = scope.vars();
+
+            let bindings = bindings.get_or_insert_with(|| {
+                vars.iter()
+                    .map(|(name, var)| (name.to_string(), var.span))
+                    .collect()
+            });
+
+            if let Some((name, var)) = vars
+                .iter()
+                .find(|(name, _)| !bindings.iter().any(|(n, _)| n == name))
+            {
+                return Err(CompileError::new(
+                    var.span,
+                    CompileErrorKind::OrPatternBindingMismatch {
+                        name: name.to_string(),
+                    },
+                ));
+            }
+
+            // Copy the bound variables to the top of the stack in the order
+            // they were declared by the first alternative, and move them into
+            // place.
+            for (name, _) in bindings.iter() {
+                let var = match vars.iter().find(|(n, _)| n == name) {
+                    Some((_, var)) => *var,
+                    None => {
+                        return Err(CompileError::new(
+                            span,
+                            CompileErrorKind::OrPatternBindingMismatch { name: name.clone() },
+                        ));
+                    }
+                };
+
+                self.scopes.mark_var_used(var);
+                self.asm.push(Inst::Copy { offset: var.offset }, span);
+            }
+
+            for index in (0..bindings.len()).rev() {
+                self.asm.push(
+                    Inst::Replace {
+                        offset: base + index,
+                    },
+                    span,
+                );
+            }
+
+            let count = scope.local_var_count - bindings.len();
+
+            if count > 0 {
+                self.asm.push(Inst::PopN { count }, span);
+            }
+
+            self.asm.jump(ok_label, span);
+            self.asm.label(alternative_false)?;
+        }
+
+        // None of the alternatives matched, so clean up like any other
+        // pattern which doesn't match.
+        let count = self.scopes.local_var_count(span)?;
+        self.asm.push(Inst::PopN { count }, span);
+        self.asm.jump(false_label, span);
+
+        self.asm.label(ok_label)?;
+
+        for (name, span) in bindings.into_iter().flatten() {
+            self.scopes.decl_var(&name, self.source_id, span)?;
         }
 
         Ok(())
@@ -474,6 +635,13 @@ impl<'a> Compiler<'a> {
         let mut bindings = Vec::new();
         let (has_rest, count) = pat_items_count(&pat_object.items)?;
 
+        if let Some(name) = pat_rest_binding(&pat_object.items) {
+            return Err(CompileError::new(
+                name,
+                CompileErrorKind::UnsupportedPatternRestBinding,
+            ));
+        }
+
         for (pat, _) in pat_object.items.iter().take(count) {
             let span = pat.span();
 
@@ -609,7 +777,7 @@ impl<'a> Compiler<'a> {
                 }
                 Binding::Ident(_, key) => {
                     self.asm.push(Inst::ObjectIndexGetAt { offset, slot }, span);
-                    self.scopes.decl_var(key, span)?;
+                    self.scopes.decl_var(key, self.source_id, span)?;
                 }
             }
         }
@@ -746,7 +914,7 @@ impl<'a> Compiler<'a> {
 
                 if let Some(ident) = named.as_local() {
                     load(self, Needs::Value)?;
-                    self.scopes.decl_var(ident, span)?;
+                    self.scopes.decl_var(ident, self.source_id, span)?;
                     return Ok(false);
                 }
 
@@ -774,6 +942,10 @@ impl<'a> Compiler<'a> {
                 self.compile_pat_object(object, false_label, &load)?;
                 Ok(true)
             }
+            ast::Pat::PatOr(pat_or) => {
+                self.compile_pat_or(pat_or, false_label, &load)?;
+                Ok(true)
+            }
             pat => Err(CompileError::new(
                 pat,
                 CompileErrorKind::UnsupportedPatternExpr,
@@ -879,6 +1051,32 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 
+    /// Assemble an integer literal. Literals which don't fit in an `i64` are
+    /// only permitted in bignum mode, where they are loaded as big integers.
+    pub(crate) fn assemble_integer(
+        &mut self,
+        number: &num::BigInt,
+        span: Span,
+    ) -> CompileResult<()> {
+        use num::ToPrimitive as _;
+
+        if let Some(n) = number.to_i64() {
+            self.asm.push(Inst::integer(n), span);
+            return Ok(());
+        }
+
+        if !self.options.bignum {
+            return Err(CompileError::new(
+                span,
+                ParseErrorKind::BadNumberOutOfBounds,
+            ));
+        }
+
+        let slot = self.unit.new_static_bigint(number);
+        self.asm.push(Inst::BigInt { slot }, span);
+        Ok(())
+    }
+
     /// Get the latest relevant warning context.
     pub(crate) fn context(&self) -> Option<Span> {
         self.contexts.last().copied()
@@ -923,7 +1121,7 @@ impl<'a> Compiler<'a> {
         }
 
         let mut interpreter = IrInterpreter {
-            budget: IrBudget::new(1_000_000),
+            budget: IrBudget::new(self.options.const_budget),
             scopes: Default::default(),
             module: from.module.clone(),
             item: from.item.clone(),
@@ -941,6 +1139,101 @@ impl<'a> Compiler<'a> {
         let value = interpreter.eval_value(&query_const_fn.ir_fn.ir, Used::Used)?;
         Ok(value.into_const(spanned)?)
     }
+
+    /// Emit warnings for all variables declared by patterns which were never
+    /// used.
+    pub(crate) fn warn_unused_vars(&mut self) {
+        for (name, span) in self.scopes.unused() {
+            self.diagnostics
+                .unused_variable(self.source_id, span, name.into());
+        }
+    }
+
+    /// Check field accesses on variables which are known to hold a struct,
+    /// erroring if the struct doesn't have the accessed field.
+    pub(crate) fn check_field_accesses(&self) -> CompileResult<()> {
+        if let Some((span, field, meta)) = self.scopes.missing_fields().next() {
+            return Err(CompileError::new(
+                span,
+                CompileErrorKind::UnknownStructField {
+                    field: field.into(),
+                    item: meta.item.item.clone(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the pattern of a local matches the length of its array
+    /// type annotation, like `let [a, b]: [_; N] = value;`.
+    pub(crate) fn check_local_type(
+        &mut self,
+        ty: &ast::LocalType,
+        pat: &ast::Pat,
+    ) -> CompileResult<()> {
+        let from = self.query.item_for(ty)?;
+
+        let expected = match self.eval_const_expr(ty, &from, &ty.len)? {
+            ConstValue::Integer(n) if n >= 0 => n as usize,
+            _ => return Err(CompileError::new(&ty.len, CompileErrorKind::BadArrayLength)),
+        };
+
+        let pat_vec = match pat {
+            ast::Pat::PatVec(pat_vec) => pat_vec,
+            _ => {
+                return Err(CompileError::new(
+                    ty,
+                    CompileErrorKind::UnsupportedArrayPattern,
+                ))
+            }
+        };
+
+        let (rest, actual) = pat_sequence_items(&pat_vec.items)?;
+
+        if actual > expected || (rest.is_none() && actual != expected) {
+            return Err(CompileError::new(
+                pat,
+                CompileErrorKind::ArrayPatternLengthMismatch { expected, actual },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate the given expression as a constant in the context of the
+    /// `from` item.
+    pub(crate) fn eval_const_expr<S>(
+        &mut self,
+        spanned: S,
+        from: &CompileItem,
+        expr: &ast::Expr,
+    ) -> Result<ConstValue, CompileError>
+    where
+        S: Copy + Spanned,
+    {
+        let mut ir_query = self.query.as_ir_query();
+
+        let mut compiler = IrCompiler {
+            storage: self.storage.clone(),
+            source: self.source.clone(),
+            query: &mut *ir_query,
+        };
+
+        let ir = compiler.compile(expr)?;
+
+        let mut interpreter = IrInterpreter {
+            budget: IrBudget::new(self.options.const_budget),
+            scopes: Default::default(),
+            module: from.module.clone(),
+            item: from.item.clone(),
+            consts: self.consts.clone(),
+            query: &mut *ir_query,
+        };
+
+        let value = interpreter.eval_value(&ir, Used::Used)?;
+        Ok(value.into_const(spanned)?)
+    }
 }
 
 /// Test if the given pattern is open or not.
@@ -975,3 +1268,44 @@ where
 
     Ok((is_open, count))
 }
+
+/// Get the position of the rest pattern among the items of a vector or tuple
+/// pattern, and the number of items which are not the rest pattern.
+///
+/// Unlike [pat_items_count], the rest pattern may appear anywhere, but only
+/// once.
+fn pat_sequence_items<'a, I: 'a, U: 'a>(items: I) -> Result<(Option<usize>, usize), CompileError>
+where
+    I: IntoIterator<Item = &'a (ast::Pat, U)>,
+{
+    let mut rest = None;
+    let mut count = 0;
+
+    for (index, (pat, _)) in items.into_iter().enumerate() {
+        if let ast::Pat::PatRest(pat_rest) = pat {
+            if rest.is_some() {
+                return Err(CompileError::new(
+                    pat_rest,
+                    CompileErrorKind::MultiplePatternRest,
+                ));
+            }
+
+            rest = Some(index);
+        } else {
+            count += 1;
+        }
+    }
+
+    Ok((rest, count))
+}
+
+/// Get the name that the rest pattern in `items` is bound to, if any.
+fn pat_rest_binding<'a, I: 'a, U: 'a>(items: I) -> Option<&'a ast::Ident>
+where
+    I: IntoIterator<Item = &'a (ast::Pat, U)>,
+{
+    items.into_iter().find_map(|(pat, _)| match pat {
+        ast::Pat::PatRest(pat_rest) => pat_rest.name.as_ref(),
+        _ => None,
+    })
+}
@@ -1,7 +1,8 @@
 use crate::collections::HashMap;
 use crate::compiling::Assembly;
 use crate::{CompileError, CompileErrorKind, CompileResult, CompileVisitor};
-use runestick::{Inst, SourceId, Span};
+use runestick::{CompileMeta, CompileMetaKind, Inst, SourceId, Span};
+use std::cell::Cell;
 use std::rc::Rc;
 
 /// A locally declared variable, its calculated stack offset and where it was
@@ -11,9 +12,11 @@ pub struct Var {
     /// Slot offset from the current stack frame.
     pub(crate) offset: usize,
     /// Token assocaited with the variable.
-    span: Span,
+    pub(crate) span: Span,
     /// Variable has been taken at the given position.
     moved_at: Option<Span>,
+    /// Index of the declaration used to track if the variable is used.
+    decl: Option<usize>,
 }
 
 impl Var {
@@ -88,6 +91,27 @@ impl Scope {
         }
     }
 
+    /// Merge the variables of a child scope into this scope.
+    fn merge(&mut self, child: Scope) {
+        self.locals.extend(child.locals);
+        self.anon.extend(child.anon);
+        self.total_var_count = child.total_var_count;
+        self.local_var_count += child.local_var_count;
+    }
+
+    /// Get the named variables declared in this scope, ordered by their slot
+    /// offset.
+    pub(crate) fn vars(&self) -> Vec<(&str, &Var)> {
+        let mut vars = self
+            .locals
+            .iter()
+            .map(|(name, var)| (name.as_str(), var))
+            .collect::<Vec<_>>();
+
+        vars.sort_by_key(|(_, var)| var.offset);
+        vars
+    }
+
     /// Insert a new local, and return the old one if there's a conflict.
     fn new_var(&mut self, name: &str, span: Span) -> CompileResult<usize> {
         let offset = self.total_var_count;
@@ -96,6 +120,7 @@ impl Scope {
             offset,
             span,
             moved_at: None,
+            decl: None,
         };
 
         self.total_var_count += 1;
@@ -115,7 +140,7 @@ impl Scope {
     }
 
     /// Insert a new local, and return the old one if there's a conflict.
-    fn decl_var(&mut self, name: &str, span: Span) -> usize {
+    fn decl_var(&mut self, name: &str, span: Span, decl: usize) -> usize {
         let offset = self.total_var_count;
 
         log::trace!("decl {} => {}", name, offset);
@@ -126,6 +151,7 @@ impl Scope {
                 offset,
                 span,
                 moved_at: None,
+                decl: Some(decl),
             },
         );
 
@@ -207,9 +233,35 @@ impl Scope {
 #[must_use]
 pub(crate) struct ScopeGuard(usize);
 
+/// A variable declared through a pattern, used to track if it's ever used.
+struct Decl {
+    /// The name of the variable.
+    name: Box<str>,
+    /// Where the variable was declared.
+    span: Span,
+    /// If the variable has been used.
+    used: Cell<bool>,
+    /// If the variable has been assigned to after it was declared.
+    assigned: Cell<bool>,
+    /// The struct the variable was declared with, if known.
+    meta: Option<CompileMeta>,
+}
+
+/// A field access on a variable which holds a known struct.
+struct FieldAccess {
+    /// The declaration of the variable being accessed.
+    decl: usize,
+    /// The name of the field.
+    field: Box<str>,
+    /// The span of the field access.
+    span: Span,
+}
+
 pub(crate) struct Scopes {
     visitor: Rc<dyn CompileVisitor>,
     scopes: Vec<Scope>,
+    decls: Vec<Decl>,
+    field_accesses: Vec<FieldAccess>,
 }
 
 impl Scopes {
@@ -218,6 +270,8 @@ impl Scopes {
         Self {
             visitor,
             scopes: vec![Scope::new()],
+            decls: Vec::new(),
+            field_accesses: Vec::new(),
         }
     }
 
@@ -235,6 +289,7 @@ impl Scopes {
             if let Some(var) = scope.get(name, span)? {
                 log::trace!("found var: {} => {:?}", name, var);
                 self.visitor.visit_variable_use(source_id, var.span, span);
+                Self::mark_used(&self.decls, var);
                 return Ok(Some(var));
             }
         }
@@ -256,6 +311,7 @@ impl Scopes {
             if let Some(var) = scope.take(name, span)? {
                 log::trace!("found var: {} => {:?}", name, var);
                 self.visitor.visit_variable_use(source_id, var.span, span);
+                Self::mark_used(&self.decls, var);
                 return Ok(Some(var));
             }
         }
@@ -305,8 +361,124 @@ impl Scopes {
     }
 
     /// Declare the given variable.
-    pub(crate) fn decl_var(&mut self, name: &str, span: Span) -> CompileResult<usize> {
-        Ok(self.last_mut(span)?.decl_var(name, span))
+    pub(crate) fn decl_var(
+        &mut self,
+        name: &str,
+        source_id: SourceId,
+        span: Span,
+    ) -> CompileResult<usize> {
+        let decl = self.decls.len();
+        let offset = self.last_mut(span)?.decl_var(name, span, decl);
+        self.visitor.visit_variable_def(source_id, name, span);
+
+        self.decls.push(Decl {
+            name: name.into(),
+            span,
+            used: Cell::new(false),
+            assigned: Cell::new(false),
+            meta: None,
+        });
+
+        Ok(offset)
+    }
+
+    /// Iterate over the declared variables which have never been used, in the
+    /// order they were declared.
+    ///
+    /// Variables whose name starts with an underscore are never reported.
+    pub(crate) fn unused(&self) -> impl Iterator<Item = (&str, Span)> + '_ {
+        self.decls
+            .iter()
+            .filter(|decl| !decl.used.get() && !decl.name.starts_with('_'))
+            .map(|decl| (&*decl.name, decl.span))
+    }
+
+    /// Record that the variable declared at the given span holds an instance
+    /// of the struct described by `meta`.
+    pub(crate) fn decl_var_struct(&mut self, name: &str, span: Span, meta: CompileMeta) {
+        let decl = match self.find_var(name) {
+            Some(var) if var.span == span => var.decl,
+            _ => return,
+        };
+
+        if let Some(decl) = decl.and_then(|decl| self.decls.get_mut(decl)) {
+            decl.meta = Some(meta);
+        }
+    }
+
+    /// Mark the declaration of the given variable as assigned to, which means
+    /// that the type of its value is no longer known.
+    pub(crate) fn mark_assigned(&self, var: &Var) {
+        if let Some(decl) = var.decl.and_then(|decl| self.decls.get(decl)) {
+            decl.assigned.set(true);
+        }
+    }
+
+    /// Mark the declaration of the given variable as used, for variables which
+    /// are accessed without being looked up by name.
+    pub(crate) fn mark_var_used(&self, var: &Var) {
+        Self::mark_used(&self.decls, var);
+    }
+
+    /// Record a field access on the variable with the given name, so that it
+    /// can be checked if the variable is known to hold a struct.
+    pub(crate) fn access_field(&mut self, name: &str, field: &str, span: Span) {
+        let decl = match self.find_var(name).and_then(|var| var.decl) {
+            Some(decl) => decl,
+            None => return,
+        };
+
+        if matches!(self.decls.get(decl), Some(Decl { meta: Some(..), .. })) {
+            self.field_accesses.push(FieldAccess {
+                decl,
+                field: field.into(),
+                span,
+            });
+        }
+    }
+
+    /// Iterate over field accesses on variables which hold a known struct that
+    /// doesn't have the accessed field.
+    ///
+    /// Variables which are assigned to anywhere are never reported, since the
+    /// type of their value might change.
+    pub(crate) fn missing_fields(&self) -> impl Iterator<Item = (Span, &str, &CompileMeta)> + '_ {
+        self.field_accesses.iter().filter_map(move |access| {
+            let decl = self.decls.get(access.decl)?;
+
+            if decl.assigned.get() {
+                return None;
+            }
+
+            let meta = decl.meta.as_ref()?;
+
+            let fields = match &meta.kind {
+                CompileMetaKind::Struct { object, .. } => &object.fields,
+                CompileMetaKind::StructVariant { object, .. } => &object.fields,
+                _ => return None,
+            };
+
+            if fields.contains(&access.field) {
+                return None;
+            }
+
+            Some((access.span, &*access.field, meta))
+        })
+    }
+
+    /// Find the variable with the given name without marking it as used.
+    fn find_var(&self, name: &str) -> Option<&Var> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.locals.get(name))
+    }
+
+    /// Mark the declaration of the given variable as used.
+    fn mark_used(decls: &[Decl], var: &Var) {
+        if let Some(decl) = var.decl.and_then(|decl| decls.get(decl)) {
+            decl.used.set(true);
+        }
     }
 
     /// Declare an anonymous variable.
@@ -339,6 +511,13 @@ impl Scopes {
         self.pop_unchecked(span)
     }
 
+    /// Merge the variables of a previously popped child scope into the last
+    /// scope, making them available to the rest of it.
+    pub(crate) fn merge(&mut self, scope: Scope, span: Span) -> CompileResult<()> {
+        self.last_mut(span)?.merge(scope);
+        Ok(())
+    }
+
     /// Pop the last of the scope.
     pub(crate) fn pop_last(&mut self, span: Span) -> CompileResult<Scope> {
         self.pop(ScopeGuard(1), span)
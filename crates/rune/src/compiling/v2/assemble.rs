@@ -112,6 +112,14 @@ impl Assemble for ast::Local {
         let span = self.span();
         log::trace!("Local => {:?}", c.source.source(span));
 
+        if self.expr_else.is_some() {
+            return Err(CompileError::msg(span, "unsupported guard"));
+        }
+
+        if self.ty.is_some() {
+            return Err(CompileError::msg(span, "unsupported type annotation"));
+        }
+
         match &self.pat {
             ast::Pat::PatPath(path) => {
                 if let Some(name) = path.path.try_as_ident() {
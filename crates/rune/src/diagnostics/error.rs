@@ -1,5 +1,5 @@
 use crate::compiling::LinkerError;
-use crate::{BuildError, CompileError, ParseError, QueryError};
+use crate::{BuildError, CompileError, ParseError, QueryError, WarningKind};
 use runestick::SourceId;
 use std::error;
 use std::fmt;
@@ -78,6 +78,11 @@ pub enum ErrorKind {
         #[source]
         BuildError,
     ),
+    /// A warning which was promoted to an error because
+    /// [Options::warnings_as_errors][crate::Options::warnings_as_errors] is
+    /// enabled.
+    #[error("warning treated as error")]
+    Warning(#[source] WarningKind),
     /// An internal error.
     #[error("internal error: {0}")]
     Internal(&'static str),
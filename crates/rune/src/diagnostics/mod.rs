@@ -1,4 +1,4 @@
-use runestick::{SourceId, Span};
+use runestick::{Item, SourceId, Span};
 
 mod error;
 mod warning;
@@ -63,6 +63,9 @@ pub struct Diagnostics {
     last_error: Option<usize>,
     /// First warning in chain.
     last_warning: Option<usize>,
+    /// If warnings are collected to be promoted into errors, regardless of
+    /// the mode.
+    warnings_as_errors: bool,
 }
 
 impl Diagnostics {
@@ -72,6 +75,7 @@ impl Diagnostics {
             mode,
             last_error: None,
             last_warning: None,
+            warnings_as_errors: false,
         }
     }
 
@@ -169,6 +173,21 @@ impl Diagnostics {
         );
     }
 
+    /// Indicate that the pattern in a let condition always matches.
+    ///
+    /// Like `if let x = value`.
+    pub fn irrefutable_let_condition(
+        &mut self,
+        source_id: usize,
+        span: Span,
+        context: Option<Span>,
+    ) {
+        self.warning(
+            source_id,
+            WarningKind::IrrefutableLetCondition { span, context },
+        );
+    }
+
     /// Indicate that we encountered a template string without any expansion
     /// groups.
     ///
@@ -206,17 +225,116 @@ impl Diagnostics {
         );
     }
 
+    /// Add a warning about a match over an enum which doesn't cover all of its
+    /// variants.
+    pub fn non_exhaustive_match(
+        &mut self,
+        source_id: usize,
+        span: Span,
+        missing: Vec<Item>,
+        context: Option<Span>,
+    ) {
+        self.warning(
+            source_id,
+            WarningKind::NonExhaustiveMatch {
+                span,
+                missing,
+                context,
+            },
+        );
+    }
+
+    /// Add a warning about a variable which is declared but never used.
+    ///
+    /// Like `let x = 42;` where `x` is never read.
+    pub fn unused_variable(&mut self, source_id: usize, span: Span, name: Box<str>) {
+        self.warning(source_id, WarningKind::UnusedVariable { span, name });
+    }
+
+    /// Add a warning about a named closure which doesn't capture anything.
+    ///
+    /// Like `let add = |a, b| a + b;`.
+    pub fn closure_without_captures(
+        &mut self,
+        source_id: usize,
+        span: Span,
+        context: Option<Span>,
+    ) {
+        self.warning(
+            source_id,
+            WarningKind::ClosureWithoutCaptures { span, context },
+        );
+    }
+
     /// Add a warning about an unecessary semi-colon.
     pub fn uneccessary_semi_colon(&mut self, source_id: usize, span: Span) {
         self.warning(source_id, WarningKind::UnecessarySemiColon { span });
     }
 
+    /// Add a warning about a doc comment which isn't followed by an item.
+    ///
+    /// Like `/// Hello` right before a `let` statement.
+    pub fn unused_doc_comment(&mut self, source_id: usize, span: Span) {
+        self.warning(source_id, WarningKind::UnusedDocComment { span });
+    }
+
+    /// Set if warnings should be collected so that they can be promoted into
+    /// errors, even if warnings are disabled. Returns the previous value.
+    pub(crate) fn set_warnings_as_errors(&mut self, enabled: bool) -> bool {
+        std::mem::replace(&mut self.warnings_as_errors, enabled)
+    }
+
+    /// Promote every warning collected so far into an error.
+    pub(crate) fn promote_warnings(&mut self) {
+        if self.last_warning.is_none() {
+            return;
+        }
+
+        for diagnostic in self.take() {
+            match diagnostic {
+                Diagnostic::Error(error) => self.error(error.source_id, *error.kind),
+                Diagnostic::Warning(warning) => {
+                    self.error(warning.source_id, ErrorKind::Warning(warning.kind))
+                }
+            }
+        }
+    }
+
+    /// Remove the warnings collected since the diagnostic at `start` for which
+    /// `suppressed` returns `true`.
+    pub(crate) fn suppress_warnings<F>(&mut self, start: usize, mut suppressed: F)
+    where
+        F: FnMut(&Warning) -> bool,
+    {
+        if self.last_warning.is_none() {
+            return;
+        }
+
+        for (index, diagnostic) in self.take().into_iter().enumerate() {
+            match diagnostic {
+                Diagnostic::Error(error) => self.error(error.source_id, *error.kind),
+                Diagnostic::Warning(warning) => {
+                    if index < start || !suppressed(&warning) {
+                        self.warning(warning.source_id, warning.kind);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Take all collected diagnostics, so that they can be pushed again.
+    fn take(&mut self) -> Vec<Diagnostic> {
+        self.last_error = None;
+        self.last_warning = None;
+        std::mem::take(&mut self.diagnostics)
+    }
+
     /// Push a warning to the collection of diagnostics.
     pub fn warning<T>(&mut self, source_id: SourceId, kind: T)
     where
         WarningKind: From<T>,
     {
-        if !self.mode.warnings() {
+        if !self.mode.warnings() && !self.warnings_as_errors {
             return;
         }
 
@@ -1,10 +1,10 @@
-use runestick::{SourceId, Span};
+use runestick::{Item, SourceId, Span};
 use std::error;
 use std::fmt;
 use thiserror::Error;
 
 /// Compilation warning.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Warning {
     /// The last warning reported in the chain.
     pub(super) last: Option<usize>,
@@ -32,13 +32,7 @@ impl Warning {
 
     /// Get the span of the warning.
     pub fn span(&self) -> Span {
-        match &self.kind {
-            WarningKind::NotUsed { span, .. } => *span,
-            WarningKind::LetPatternMightPanic { span, .. } => *span,
-            WarningKind::TemplateWithoutExpansions { span, .. } => *span,
-            WarningKind::RemoveTupleCallParams { span, .. } => *span,
-            WarningKind::UnecessarySemiColon { span, .. } => *span,
-        }
+        self.kind.span()
     }
 }
 
@@ -55,7 +49,7 @@ impl error::Error for Warning {
 }
 
 /// Compilation warning kind.
-#[derive(Debug, Clone, Copy, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum WarningKind {
     /// Item identified by the span is not used.
     #[error("not used")]
@@ -74,6 +68,15 @@ pub enum WarningKind {
         /// The context in which it is used.
         context: Option<Span>,
     },
+    /// Warning that the pattern of an `if let` or `while let` condition always
+    /// matches.
+    #[error("pattern always matches")]
+    IrrefutableLetCondition {
+        /// The span of the pattern.
+        span: Span,
+        /// The context in which it is used.
+        context: Option<Span>,
+    },
     /// Encountered a template string without an expansion.
     #[error("using a template string without expansions, like `Hello World`")]
     TemplateWithoutExpansions {
@@ -98,4 +101,84 @@ pub enum WarningKind {
         /// Span where the semi-colon is.
         span: Span,
     },
+    /// A match over an enum doesn't cover all of its variants.
+    #[error("non-exhaustive match, missing {}", crate::compiling::format_variants(.missing))]
+    NonExhaustiveMatch {
+        /// Span of the matched expression.
+        span: Span,
+        /// The variants which are not covered.
+        missing: Vec<Item>,
+        /// The context in which it is used.
+        context: Option<Span>,
+    },
+    /// A variable is declared but never read.
+    #[error("variable `{name}` is never used")]
+    UnusedVariable {
+        /// Span where the variable is declared.
+        span: Span,
+        /// The name of the variable.
+        name: Box<str>,
+    },
+    /// A closure which is bound to a name doesn't capture anything, and could
+    /// be a function instead.
+    #[error("closure doesn't capture its environment")]
+    ClosureWithoutCaptures {
+        /// Span of the closure.
+        span: Span,
+        /// The context in which it is used.
+        context: Option<Span>,
+    },
+    /// A doc comment which isn't followed by an item it could document.
+    #[error("unused doc comment")]
+    UnusedDocComment {
+        /// Span of the doc comment.
+        span: Span,
+    },
+}
+
+impl WarningKind {
+    /// The names of the lints which warnings belong to.
+    pub(crate) const LINTS: &'static [&'static str] = &[
+        "unused",
+        "let_pattern_might_panic",
+        "irrefutable_let_condition",
+        "template_without_expansions",
+        "unused_parens",
+        "unnecessary_semicolon",
+        "non_exhaustive_match",
+        "closure_without_captures",
+    ];
+
+    /// The name of the lint the warning belongs to, which can be used to
+    /// suppress it with an `#[allow(..)]` attribute.
+    pub fn lint(&self) -> &'static str {
+        match self {
+            Self::NotUsed { .. } => "unused",
+            Self::LetPatternMightPanic { .. } => "let_pattern_might_panic",
+            Self::IrrefutableLetCondition { .. } => "irrefutable_let_condition",
+            Self::TemplateWithoutExpansions { .. } => "template_without_expansions",
+            Self::RemoveTupleCallParams { .. } => "unused_parens",
+            Self::UnecessarySemiColon { .. } => "unnecessary_semicolon",
+            Self::NonExhaustiveMatch { .. } => "non_exhaustive_match",
+            Self::UnusedVariable { .. } => "unused",
+            Self::ClosureWithoutCaptures { .. } => "closure_without_captures",
+            Self::UnusedDocComment { .. } => "unused",
+        }
+    }
+
+    /// Get the span of the warning.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::NotUsed { span, .. } => *span,
+            Self::LetPatternMightPanic { span, .. } => *span,
+            Self::IrrefutableLetCondition { span, .. } => *span,
+            Self::TemplateWithoutExpansions { span, .. } => *span,
+            Self::RemoveTupleCallParams { span, .. } => *span,
+            Self::UnecessarySemiColon { span, .. } => *span,
+            Self::NonExhaustiveMatch { span, .. } => *span,
+            Self::UnusedVariable { span, .. } => *span,
+            Self::ClosureWithoutCaptures { span, .. } => *span,
+            Self::UnusedDocComment { span } => *span,
+        }
+    }
 }
@@ -1,5 +1,6 @@
 //! Runtime helpers for loading code and emitting diagnostics.
 
+use crate::compiling::format_variants;
 use crate::{
     CompileErrorKind, Diagnostics, Error, ErrorKind, IrErrorKind, LinkerError, QueryErrorKind,
     ResolveErrorKind, Sources, Spanned as _, Warning, WarningKind,
@@ -261,6 +262,18 @@ where
 
             *context
         }
+        WarningKind::IrrefutableLetCondition { span, context } => {
+            labels.push(
+                Label::primary(this.source_id(), span.range())
+                    .with_message("pattern always matches"),
+            );
+
+            let mut note = String::new();
+            writeln!(note, "Hint: Use a `let` statement instead")?;
+            notes.push(note);
+
+            *context
+        }
         WarningKind::TemplateWithoutExpansions { span, context } => {
             labels.push(
                 Label::primary(this.source_id(), span.range())
@@ -297,6 +310,63 @@ where
                     .with_message("unnecessary semicolon"),
             );
 
+            None
+        }
+        WarningKind::NonExhaustiveMatch {
+            span,
+            missing,
+            context,
+        } => {
+            labels.push(
+                Label::primary(this.source_id(), span.range())
+                    .with_message("not all variants are covered"),
+            );
+
+            let mut note = String::new();
+            writeln!(note, "Hint: Cover the missing variants or add a `_` branch")?;
+            writeln!(note, "Missing: {}", format_variants(missing))?;
+            notes.push(note);
+
+            *context
+        }
+        WarningKind::UnusedVariable { span, name } => {
+            labels.push(Label::primary(this.source_id(), span.range()).with_message("never used"));
+
+            let mut note = String::new();
+            writeln!(
+                note,
+                "Hint: If this is intentional, prefix it with an underscore: `_{}`",
+                name
+            )?;
+            notes.push(note);
+
+            None
+        }
+        WarningKind::ClosureWithoutCaptures { span, context } => {
+            labels.push(
+                Label::primary(this.source_id(), span.range())
+                    .with_message("closure doesn't capture anything"),
+            );
+
+            let mut note = String::new();
+            writeln!(note, "Hint: Declare it as a function with `fn` instead")?;
+            notes.push(note);
+
+            *context
+        }
+        WarningKind::UnusedDocComment { span } => {
+            labels.push(
+                Label::primary(this.source_id(), span.range())
+                    .with_message("not followed by an item"),
+            );
+
+            let mut note = String::new();
+            writeln!(
+                note,
+                "Hint: Use `//` for comments which aren't documentation"
+            )?;
+            notes.push(note);
+
             None
         }
     };
@@ -364,6 +434,7 @@ where
             return Ok(());
         }
         ErrorKind::ParseError(error) => error.span(),
+        ErrorKind::Warning(warning) => warning.span(),
         ErrorKind::CompileError(error) => {
             format_compile_error(
                 this,
@@ -463,6 +534,18 @@ where
                         .with_message("nested in here"),
                 );
             }
+            CompileErrorKind::MissingItem {
+                suggestion: Some(suggestion),
+                ..
+            } => {
+                notes.push(format!("Hint: did you mean `{}`?", suggestion));
+            }
+            CompileErrorKind::FnArgMissingDefault { default_span } => {
+                labels.push(
+                    Label::secondary(this.source_id(), default_span.range())
+                        .with_message("default value given here"),
+                );
+            }
             _ => (),
         }
 
@@ -511,6 +594,12 @@ where
                         .with_message("previously defined here"),
                 );
             }
+            QueryErrorKind::FieldConflict { existing_span, .. } => {
+                labels.push(
+                    Label::secondary(this.source_id(), existing_span.range())
+                        .with_message("previously declared here"),
+                );
+            }
             QueryErrorKind::NotVisible {
                 chain,
                 location: Location { source_id, span },
@@ -639,6 +728,41 @@ pub trait DumpInstructions {
     ) -> io::Result<()>
     where
         O: WriteColor;
+
+    /// Disassemble the current unit into a string, listing the instructions of
+    /// every function together with the source they were compiled from.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rune::DumpInstructions as _;
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let context = runestick::Context::with_default_modules()?;
+    /// let mut sources = rune::Sources::new();
+    /// sources.insert(runestick::Source::new("entry", "pub fn main() { 42 }"));
+    ///
+    /// let mut diagnostics = rune::Diagnostics::new();
+    /// let unit = rune::load_sources(
+    ///     &context,
+    ///     &rune::Options::default(),
+    ///     &mut sources,
+    ///     &mut diagnostics,
+    /// )?;
+    ///
+    /// let output = unit.disassemble(&sources);
+    /// assert!(output.contains("fn main()"));
+    /// assert!(output.contains("return"));
+    /// # Ok(()) }
+    /// ```
+    fn disassemble(&self, sources: &Sources) -> String {
+        let mut out = termcolor::Buffer::no_color();
+
+        self.dump_instructions(&mut out, sources, true)
+            .expect("dumping to a buffer shouldn't fail");
+
+        String::from_utf8_lossy(out.as_slice()).into_owned()
+    }
 }
 
 impl DumpInstructions for Unit {
@@ -654,7 +778,7 @@ impl DumpInstructions for Unit {
         let mut first_function = true;
 
         for (n, inst) in self.iter_instructions().enumerate() {
-            let debug = self.debug_info().and_then(|d| d.instruction_at(n));
+            let debug = self.debug_info_at(n);
 
             if let Some((hash, signature)) = self.debug_info().and_then(|d| d.function_at(n)) {
                 if !std::mem::take(&mut first_function) {
@@ -0,0 +1,461 @@
+//! Formatting of sources into a canonical style.
+
+use crate::ast;
+use crate::{Lexer, ParseError};
+use runestick::{Source, Span};
+
+/// The indentation used for every nested level.
+const INDENT: &str = "    ";
+
+/// Format the given source in a canonical style.
+///
+/// The source is parsed to make sure that it is valid, after which it is
+/// rendered back with consistent indentation and spacing between tokens.
+/// Operators are spaced by the role they play, so `a-1` and `-x*-y` become
+/// `a - 1` and `-x * -y`, while generic arguments like `foo::<i64>` are kept
+/// intact.
+/// Comments and line breaks are preserved, with consecutive blank lines being
+/// collapsed into one. Template strings are preserved as they are.
+///
+/// Formatting a source which has already been formatted returns it unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::Source;
+///
+/// let source = Source::new("main", "pub fn main( ) {\nlet a = [1 ,2];   // two\na\n}");
+/// let formatted = rune::format_source(&source)?;
+///
+/// assert_eq!(formatted, "pub fn main() {\n    let a = [1, 2]; // two\n    a\n}\n");
+/// # Ok::<_, rune::ParseError>(())
+/// ```
+pub fn format_source(source: &Source) -> Result<String, ParseError> {
+    let source = source.as_str();
+    crate::parse_all::<ast::File>(source)?;
+
+    let mut lexer = Lexer::new(source);
+    let mut formatter = Formatter::new(source);
+    let mut template = None::<(usize, usize)>;
+
+    while let Some(token) = lexer.next()? {
+        let text = &source[token.span.range()];
+
+        // NB: templates are expanded into a sequence of synthetic tokens by the
+        // lexer, where the delimiters point to the backticks, so we emit them
+        // verbatim once we've seen the closing backtick.
+        if let Some((start, depth)) = &mut template {
+            if text == "`" {
+                match token.kind {
+                    K!['('] => *depth += 1,
+                    K![')'] => {
+                        *depth -= 1;
+
+                        // NB: the closing parenthesis of the leading
+                        // `#[builtin(literal)]` attribute also points to
+                        // the opening backtick.
+                        if *depth == 0 && token.span.start.into_usize() > *start {
+                            let span = Span::new(*start, token.span.end);
+                            formatter.emit(ast::Kind::Eof, span);
+                            template = None;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            continue;
+        }
+
+        if token.kind == K![#] && text == "`" {
+            template = Some((token.span.start.into_usize(), 0));
+            continue;
+        }
+
+        formatter.emit(token.kind, token.span);
+    }
+
+    Ok(formatter.finish())
+}
+
+/// The role a token plays, as determined by the tokens preceding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    /// A token without any special role.
+    Plain,
+    /// A binary operator, like the `-` in `a - b`.
+    Binary,
+    /// A prefix operator, like the `-` in `-a`.
+    Prefix,
+    /// The opening `|` of closure arguments.
+    ClosureOpen,
+    /// The closing `|` of closure arguments, or an empty `||`.
+    ClosureClose,
+    /// The opening `<` of generic arguments, like in `foo::<i64>`.
+    GenericOpen,
+    /// The closing `>` of generic arguments.
+    GenericClose,
+}
+
+/// An open delimiter which hasn't been closed yet.
+struct Open {
+    /// If the delimiter increased the indentation level.
+    indents: bool,
+    /// The line the delimiter was opened on.
+    line: usize,
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    out: String,
+    /// The content of the current line, without indentation.
+    line: String,
+    /// The indentation level of the current line.
+    indent: usize,
+    /// The number of the current line.
+    line_number: usize,
+    /// If a delimiter opened on the current line increases the indentation.
+    line_indents: bool,
+    /// If the current line only consists of closing delimiters so far, which
+    /// decrease its indentation.
+    leading_close: bool,
+    /// Delimiters which are currently open.
+    open: Vec<Open>,
+    /// The kind of the previously emitted token.
+    prev: Option<ast::Kind>,
+    /// The role of the previously emitted token.
+    prev_role: Role,
+    /// The number of open delimiters when closure arguments were opened.
+    closure: Option<usize>,
+    /// The nesting depth of generic arguments.
+    generics: usize,
+    /// The end of the previously emitted token.
+    end: usize,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            out: String::new(),
+            line: String::new(),
+            indent: 0,
+            line_number: 0,
+            line_indents: false,
+            leading_close: false,
+            open: Vec::new(),
+            prev: None,
+            prev_role: Role::Plain,
+            closure: None,
+            generics: 0,
+            end: 0,
+        }
+    }
+
+    /// Emit the token with the given kind and span. Verbatim chunks of the
+    /// source like templates use the `Eof` kind.
+    fn emit(&mut self, kind: ast::Kind, span: Span) {
+        let range = span.range();
+        let had_space = self.gap(range.start, Some(kind));
+        let role = self.role(kind);
+
+        if let ast::Kind::Close(..) = kind {
+            if let Some(open) = self.open.pop() {
+                if open.indents && open.line == self.line_number {
+                    self.line_indents = false;
+                }
+            }
+        }
+
+        let is_close = matches!(kind, ast::Kind::Close(..));
+
+        if self.line.is_empty() {
+            self.indent = self.indent_level();
+            self.leading_close = is_close;
+        } else {
+            if self.leading_close && is_close {
+                self.indent = self.indent_level();
+            } else {
+                self.leading_close = false;
+            }
+
+            if needs_space(self.prev, self.prev_role, kind, role, had_space) {
+                self.line.push(' ');
+            }
+        }
+
+        self.line.push_str(&self.source[range.clone()]);
+
+        if let ast::Kind::Open(..) = kind {
+            let indents = !self.line_indents;
+            self.line_indents = true;
+
+            self.open.push(Open {
+                indents,
+                line: self.line_number,
+            });
+        }
+
+        self.prev = Some(kind);
+        self.prev_role = role;
+        self.end = range.end;
+    }
+
+    /// Determine the role of the next token with the given kind, updating the
+    /// closure and generic arguments being tracked.
+    fn role(&mut self, kind: ast::Kind) -> Role {
+        let operand =
+            self.prev_role == Role::GenericClose || self.prev.map(ends_operand).unwrap_or_default();
+
+        if self.generics > 0 {
+            match kind {
+                K![<] => {
+                    self.generics += 1;
+                    return Role::GenericOpen;
+                }
+                K![>] => {
+                    self.generics -= 1;
+                    return Role::GenericClose;
+                }
+                K![>>] => {
+                    self.generics = self.generics.saturating_sub(2);
+                    return Role::GenericClose;
+                }
+                _ => return Role::Plain,
+            }
+        }
+
+        if kind == K![<] && self.prev == Some(K![::]) {
+            self.generics += 1;
+            return Role::GenericOpen;
+        }
+
+        if kind == K![|] && self.closure == Some(self.open.len()) {
+            self.closure = None;
+            return Role::ClosureClose;
+        }
+
+        if operand {
+            if is_binary_operator(kind) {
+                return Role::Binary;
+            }
+
+            return Role::Plain;
+        }
+
+        match kind {
+            K![|] => {
+                self.closure = Some(self.open.len());
+                Role::ClosureOpen
+            }
+            K![||] => Role::ClosureClose,
+            K![-] | K![*] | K![&] => Role::Prefix,
+            _ => Role::Plain,
+        }
+    }
+
+    /// Process the comments and whitespace between the previous token and the
+    /// given position, returning `true` if there was any whitespace.
+    fn gap(&mut self, to: usize, next: Option<ast::Kind>) -> bool {
+        let gap = &self.source[self.end..to];
+        let mut newlines = 0;
+        let mut rest = gap;
+
+        while let Some(c) = rest.chars().next() {
+            if rest.starts_with("//") {
+                let len = rest.find('\n').unwrap_or(rest.len());
+                let comment = rest[..len].trim_end();
+
+                if newlines == 0 && !self.line.is_empty() {
+                    self.line.push(' ');
+                } else {
+                    self.newline(newlines, next);
+                    self.indent = self.indent_level();
+                }
+
+                self.line.push_str(comment);
+                newlines = 0;
+                rest = &rest[len..];
+                continue;
+            }
+
+            if c == '\n' {
+                newlines += 1;
+            }
+
+            rest = &rest[c.len_utf8()..];
+        }
+
+        if newlines > 0 {
+            self.newline(newlines, next);
+        }
+
+        !gap.is_empty()
+    }
+
+    /// Finish the current line, inserting a blank line if the source had
+    /// multiple line breaks, unless it's next to a delimiter.
+    fn newline(&mut self, newlines: usize, next: Option<ast::Kind>) {
+        if !self.line.is_empty() {
+            for _ in 0..self.indent {
+                self.out.push_str(INDENT);
+            }
+
+            self.out.push_str(&self.line);
+            self.out.push('\n');
+            self.line.clear();
+            self.line_number += 1;
+            self.line_indents = false;
+        }
+
+        let blank = newlines > 1
+            && !self.out.is_empty()
+            && !self.out.ends_with("\n\n")
+            && !matches!(self.prev, Some(ast::Kind::Open(..)))
+            && !matches!(next, Some(ast::Kind::Close(..)) | None);
+
+        if blank {
+            self.out.push('\n');
+        }
+    }
+
+    /// The current indentation level.
+    fn indent_level(&self) -> usize {
+        self.open.iter().filter(|o| o.indents).count()
+    }
+
+    fn finish(mut self) -> String {
+        self.gap(self.source.len(), None);
+        self.newline(0, None);
+        self.out
+    }
+}
+
+/// Test if a space is needed between the two given tokens on the same line.
+fn needs_space(
+    prev: Option<ast::Kind>,
+    prev_role: Role,
+    kind: ast::Kind,
+    role: Role,
+    had_space: bool,
+) -> bool {
+    let prev = match prev {
+        Some(prev) => prev,
+        None => return false,
+    };
+
+    if matches!(
+        prev_role,
+        Role::Prefix | Role::ClosureOpen | Role::GenericOpen
+    ) || matches!(role, Role::GenericOpen | Role::GenericClose)
+    {
+        return false;
+    }
+
+    if role == Role::ClosureClose && kind == K![|] {
+        return false;
+    }
+
+    if prev_role == Role::GenericClose && matches!(kind, K!['('] | K![::]) {
+        return false;
+    }
+
+    if prev_role == Role::Binary || role == Role::Binary || prev_role == Role::ClosureClose {
+        return true;
+    }
+
+    if matches!(
+        kind,
+        K![,] | K![;] | K![:] | K![.] | K![::] | K![?] | K![')'] | K![']']
+    ) {
+        return false;
+    }
+
+    if matches!(prev, K!['('] | K!['['] | K![.] | K![::] | K![#] | K![!]) {
+        return false;
+    }
+
+    if matches!(prev, K![,] | K![;] | K![:]) {
+        return true;
+    }
+
+    if prev == K!['{'] {
+        return kind != K!['}'];
+    }
+
+    if matches!(kind, K!['{'] | K!['}']) {
+        return true;
+    }
+
+    // Calls, indexing, and macro calls.
+    if matches!(prev, ast::Kind::Ident(..) | K![')'] | K![']'])
+        && matches!(kind, K!['('] | K!['['] | K![!])
+    {
+        return false;
+    }
+
+    if is_spaced_operator(prev) || is_spaced_operator(kind) {
+        return true;
+    }
+
+    had_space
+}
+
+/// Test if the given token ends an operand, so that a following operator is
+/// a binary one.
+fn ends_operand(kind: ast::Kind) -> bool {
+    matches!(
+        kind,
+        ast::Kind::Ident(..)
+            | ast::Kind::Number(..)
+            | ast::Kind::Str(..)
+            | ast::Kind::Char(..)
+            | ast::Kind::Byte(..)
+            | ast::Kind::ByteStr(..)
+            | ast::Kind::Eof
+            | K![')']
+            | K![']']
+            | K![?]
+            | K![self]
+            | K![Self]
+            | K![true]
+            | K![false]
+    )
+}
+
+/// Operators which are surrounded by spaces when used in a binary position.
+fn is_binary_operator(kind: ast::Kind) -> bool {
+    matches!(
+        kind,
+        K![+] | K![-] | K![*] | K![/] | K![%] | K![&] | K![|] | K![^] | K![<<] | K![>>]
+    ) || is_spaced_operator(kind)
+}
+
+/// Binary operators which are always surrounded by spaces.
+fn is_spaced_operator(kind: ast::Kind) -> bool {
+    matches!(
+        kind,
+        K![=]
+            | K![==]
+            | K![!=]
+            | K![<]
+            | K![<=]
+            | K![>]
+            | K![>=]
+            | K![&&]
+            | K![||]
+            | K![??]
+            | K![=>]
+            | K![->]
+            | K![+=]
+            | K![-=]
+            | K![*=]
+            | K![/=]
+            | K![%=]
+            | K![&=]
+            | K![|=]
+            | K![^=]
+            | K![<<=]
+            | K![>>=]
+    )
+}
@@ -0,0 +1,69 @@
+use runestick::{Source, Span};
+
+/// The doc comments of a single source, which are attached to the item that
+/// immediately follows them.
+#[derive(Debug, Default)]
+pub(crate) struct DocComments {
+    /// Spans of doc comments in source order, and whether they have been
+    /// attached to an item.
+    comments: Vec<(Span, bool)>,
+}
+
+impl DocComments {
+    /// Construct doc comments from the spans collected by the lexer.
+    pub(crate) fn new(spans: Vec<Span>) -> Self {
+        Self {
+            comments: spans.into_iter().map(|span| (span, false)).collect(),
+        }
+    }
+
+    /// Take the doc comments which immediately precede the item with the
+    /// given span, separated from it and from each other only by whitespace.
+    ///
+    /// Consecutive doc comments are concatenated with newlines, and a single
+    /// space following the `///` is stripped from each line.
+    pub(crate) fn take(&mut self, source: &Source, span: Span) -> Option<Box<str>> {
+        let source = source.as_str();
+        let end = self.comments.partition_point(|(c, _)| c.end <= span.start);
+        let mut start = end;
+        let mut next = span.start.into_usize();
+
+        while start > 0 {
+            let (comment, used) = self.comments[start - 1];
+
+            if used || !is_whitespace(source.get(comment.end.into_usize()..next)?) {
+                break;
+            }
+
+            next = comment.start.into_usize();
+            start -= 1;
+        }
+
+        if start == end {
+            return None;
+        }
+
+        let mut lines = Vec::with_capacity(end - start);
+
+        for (comment, used) in &mut self.comments[start..end] {
+            *used = true;
+            let line = &source[comment.trim_start(3).range()];
+            lines.push(line.strip_prefix(' ').unwrap_or(line));
+        }
+
+        Some(lines.join("\n").into())
+    }
+
+    /// Iterate over the spans of doc comments which were never attached to an
+    /// item.
+    pub(crate) fn unused(&self) -> impl Iterator<Item = Span> + '_ {
+        self.comments
+            .iter()
+            .filter(|(_, used)| !used)
+            .map(|(span, _)| *span)
+    }
+}
+
+fn is_whitespace(s: &str) -> bool {
+    s.chars().all(char::is_whitespace)
+}
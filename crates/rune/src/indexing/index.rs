@@ -1,7 +1,7 @@
 use crate::ast;
 use crate::attrs;
 use crate::collections::HashMap;
-use crate::indexing::{IndexFnKind, IndexLocal as _, IndexScopes};
+use crate::indexing::{DocComments, IndexFnKind, IndexLocal as _, IndexScopes};
 use crate::load::{SourceLoader, Sources};
 use crate::macros::MacroCompiler;
 use crate::parsing::{Parse, Parser};
@@ -70,9 +70,53 @@ pub(crate) struct Indexer<'a> {
     ///
     /// Then, `nested_item` would point to the span of `pub fn public`.
     pub(crate) nested_item: Option<Span>,
+    /// Doc comments of the source being indexed.
+    pub(crate) doc_comments: DocComments,
 }
 
 impl<'a> Indexer<'a> {
+    /// Evaluate and strip the `#[cfg]` attributes from the given collection
+    /// of attributes, returning `false` if the item they belong to is
+    /// disabled.
+    fn is_cfg_enabled(&self, attributes: &mut Vec<ast::Attribute>) -> CompileResult<bool> {
+        let mut enabled = true;
+        let mut retained = Vec::with_capacity(attributes.len());
+
+        for a in attributes.drain(..) {
+            let is_cfg = match a.path.try_as_ident() {
+                Some(ident) => {
+                    ident.resolve(&self.storage, &self.source)?
+                        == <attrs::Cfg as attrs::Attribute>::PATH
+                }
+                None => false,
+            };
+
+            if !is_cfg {
+                retained.push(a);
+                continue;
+            }
+
+            let mut parser = Parser::from_token_stream(&a.input);
+            let cfg = parser.parse::<attrs::Cfg>()?;
+            parser.eof()?;
+
+            let key = cfg.key.resolve(&self.storage, &self.source)?;
+
+            if key != "feature" {
+                return Err(CompileError::msg(
+                    cfg.key,
+                    "unsupported cfg option, expected `feature`",
+                ));
+            }
+
+            let value = cfg.value.resolve(&self.storage, &self.source)?;
+            enabled &= self.options.features.contains(value.as_ref());
+        }
+
+        *attributes = retained;
+        Ok(enabled)
+    }
+
     /// Try to expand an internal macro.
     fn try_expand_internal_macro(
         &mut self,
@@ -129,7 +173,9 @@ impl<'a> Indexer<'a> {
             BuiltInMacro::Line(_) | BuiltInMacro::File(_) => { /* Nothing to index */ }
         }
 
-        let id = self.query.insert_new_builtin_macro(internal_macro)?;
+        let id = self
+            .query
+            .insert_new_builtin_macro(self.source_id, internal_macro)?;
         ast.id = Some(id);
         Ok(true)
     }
@@ -383,9 +429,14 @@ impl<'a> Indexer<'a> {
     ) -> Result<(), CompileError> {
         let mut queue = items.drain(..).collect::<VecDeque<_>>();
 
-        while let Some((item, semi)) = queue.pop_front() {
+        while let Some((mut item, semi)) = queue.pop_front() {
+            if !self.is_cfg_enabled(item.attributes_mut())? {
+                continue;
+            }
+
             match item {
                 ast::Item::Use(item_use) => {
+                    self.doc_comments.take(&self.source, item_use.span());
                     let visibility = ast_to_visibility(&item_use.visibility)?;
 
                     let import = Import {
@@ -440,9 +491,16 @@ impl<'a> Indexer<'a> {
 
         let mut queue = stmts.drain(..).collect::<VecDeque<_>>();
 
-        while let Some(stmt) = queue.pop_front() {
+        while let Some(mut stmt) = queue.pop_front() {
+            if let ast::Stmt::Item(item, _) = &mut stmt {
+                if !self.is_cfg_enabled(item.attributes_mut())? {
+                    continue;
+                }
+            }
+
             match stmt {
                 ast::Stmt::Item(ast::Item::Use(item_use), _) => {
+                    self.doc_comments.take(&self.source, item_use.span());
                     let visibility = ast_to_visibility(&item_use.visibility)?;
 
                     let import = Import {
@@ -609,6 +667,20 @@ impl Index for ast::ItemFn {
         let span = self.span();
         log::trace!("ItemFn => {:?}", idx.source.source(span));
 
+        let docs = idx.doc_comments.take(&idx.source, span);
+
+        let mut attributes = attrs::Attributes::new(
+            self.attributes.clone(),
+            idx.storage.clone(),
+            idx.source.clone(),
+        );
+
+        if let Some((_, allow)) = attributes.try_parse::<attrs::Allow>()? {
+            let lints = allow.lints(&idx.storage, &*idx.source)?;
+            idx.query
+                .insert_allow(Location::new(idx.source_id, span), lints);
+        }
+
         let name = self.name.resolve(&idx.storage, &*idx.source)?;
         let _guard = idx.items.push_name(name.as_ref());
 
@@ -641,16 +713,37 @@ impl Index for ast::ItemFn {
         }
 
         let guard = idx.scopes.push_function(kind);
+        let mut default_span = None;
+        let last = self.args.len().saturating_sub(1);
 
-        for (arg, _) in &mut self.args {
+        for (index, (arg, _)) in self.args.iter_mut().enumerate() {
             match arg {
                 ast::FnArg::SelfValue(s) => {
                     let span = s.span();
                     idx.scopes.declare("self", span)?;
                 }
                 ast::FnArg::Pat(pat) => {
+                    if let Some(default_span) = default_span {
+                        return Err(CompileError::new(
+                            pat.span(),
+                            CompileErrorKind::FnArgMissingDefault { default_span },
+                        ));
+                    }
+
                     pat.index_local(idx)?;
                 }
+                ast::FnArg::Default(arg) => {
+                    default_span = Some(arg.span());
+                    arg.default.index(idx)?;
+                    arg.pat.index_local(idx)?;
+                }
+                ast::FnArg::Rest(arg) => {
+                    if index != last {
+                        return Err(CompileError::new(arg, CompileErrorKind::FnArgRestNotLast));
+                    }
+
+                    arg.name.index_local(idx)?;
+                }
             }
         }
 
@@ -674,12 +767,16 @@ impl Index for ast::ItemFn {
                 }
 
                 idx.query
-                    .index_const_fn(&item, &idx.source, Box::new(self.clone()))?;
+                    .index_const_fn(&item, &idx.source, Box::new(self.clone()), docs)?;
 
                 return Ok(());
             }
         };
 
+        let args = self.arity();
+        let defaults = self.defaults();
+        let rest = self.has_rest();
+
         let fun = Function {
             ast: Box::new(self.clone()),
             call,
@@ -689,12 +786,6 @@ impl Index for ast::ItemFn {
         // inside of a nested item.
         let is_public = item.is_public() && idx.nested_item.is_none();
 
-        let mut attributes = attrs::Attributes::new(
-            self.attributes.clone(),
-            idx.storage.clone(),
-            idx.source.clone(),
-        );
-
         let is_test = match attributes.try_parse::<attrs::Test>()? {
             Some((span, _)) => {
                 if let Some(nested_span) = idx.nested_item {
@@ -748,6 +839,10 @@ impl Index for ast::ItemFn {
             let kind = CompileMetaKind::Function {
                 type_hash: Hash::type_hash(&item.item),
                 is_test: false,
+                args: Some(args),
+                defaults,
+                rest,
+                call_site: None,
             };
 
             let meta = CompileMeta {
@@ -758,6 +853,7 @@ impl Index for ast::ItemFn {
                     path: idx.source.path().map(ToOwned::to_owned),
                     source_id: idx.source_id,
                 }),
+                docs,
             };
 
             idx.query.insert_meta(span, meta)?;
@@ -774,6 +870,10 @@ impl Index for ast::ItemFn {
             let kind = CompileMetaKind::Function {
                 type_hash: Hash::type_hash(&item.item),
                 is_test,
+                args: Some(args),
+                defaults,
+                rest,
+                call_site: None,
             };
 
             let meta = CompileMeta {
@@ -784,6 +884,7 @@ impl Index for ast::ItemFn {
                     path: idx.source.path().map(ToOwned::to_owned),
                     source_id: idx.source_id,
                 }),
+                docs,
             };
 
             idx.query.insert_meta(span, meta)?;
@@ -791,6 +892,7 @@ impl Index for ast::ItemFn {
             idx.query.index(IndexedEntry {
                 item,
                 source: idx.source.clone(),
+                docs,
                 indexed: Indexed::Function(fun),
             });
         }
@@ -804,7 +906,19 @@ impl Index for ast::ExprBlock {
         let span = self.span();
         log::trace!("ExprBlock => {:?}", idx.source.source(span));
 
-        if let Some(span) = self.attributes.option_span() {
+        let mut attributes = attrs::Attributes::new(
+            self.attributes.clone(),
+            idx.storage.clone(),
+            idx.source.clone(),
+        );
+
+        if let Some((_, allow)) = attributes.try_parse::<attrs::Allow>()? {
+            let lints = allow.lints(&idx.storage, &*idx.source)?;
+            idx.query
+                .insert_allow(Location::new(idx.source_id, span), lints);
+        }
+
+        if let Some(span) = attributes.remaining() {
             return Err(CompileError::msg(
                 span,
                 "block attributes are not supported yet",
@@ -843,7 +957,7 @@ impl Index for ast::ExprBlock {
             }
 
             self.block.index(idx)?;
-            idx.query.index_const(&item, &idx.source, self)?;
+            idx.query.index_const(&item, &idx.source, self, None)?;
             return Ok(());
         }
 
@@ -949,8 +1063,28 @@ impl Index for ast::Local {
             return Err(CompileError::msg(span, "attributes are not supported"));
         }
 
+        if let Some(guard) = &self.guard {
+            if guard.resolve(&idx.storage, &*idx.source)? != "guard" {
+                return Err(CompileError::from(ParseError::expected(
+                    &guard.token,
+                    "`guard` or `let`",
+                )));
+            }
+        }
+
         self.pat.index(idx)?;
+
+        if let Some(ty) = &mut self.ty {
+            ty.id = Some(idx.items.id());
+            ty.len.index(idx)?;
+        }
+
         self.expr.index(idx)?;
+
+        if let Some(expr_else) = &mut self.expr_else {
+            expr_else.block.index(idx)?;
+        }
+
         Ok(())
     }
 }
@@ -1004,7 +1138,14 @@ impl Index for ast::Pat {
             }
             ast::Pat::PatIgnore(..) => (),
             ast::Pat::PatLit(..) => (),
-            ast::Pat::PatRest(..) => (),
+            ast::Pat::PatRest(pat_rest) => {
+                if let Some(name) = &mut pat_rest.name {
+                    name.index(idx)?;
+                }
+            }
+            ast::Pat::PatOr(pat_or) => {
+                pat_or.index(idx)?;
+            }
         }
 
         Ok(())
@@ -1028,6 +1169,21 @@ impl Index for ast::PatTuple {
     }
 }
 
+impl Index for ast::PatOr {
+    fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
+        let span = self.span();
+        log::trace!("PatOr => {:?}", idx.source.source(span));
+
+        self.first.index(idx)?;
+
+        for (_, pat) in &mut self.rest {
+            pat.index(idx)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Index for ast::PatBinding {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
         let span = self.span();
@@ -1090,6 +1246,8 @@ impl Index for ast::Expr {
             }
             ast::Expr::Block(block) => {
                 block.index(idx)?;
+                // NB: block attributes are validated when indexing the block.
+                attributes.drain();
             }
             ast::Expr::Group(expr) => {
                 expr.expr.index(idx)?;
@@ -1148,6 +1306,12 @@ impl Index for ast::Expr {
             ast::Expr::Try(expr_try) => {
                 expr_try.index(idx)?;
             }
+            ast::Expr::Optional(expr_optional) => {
+                expr_optional.expr.index(idx)?;
+            }
+            ast::Expr::OptionalChain(expr_optional_chain) => {
+                expr_optional_chain.expr.index(idx)?;
+            }
             ast::Expr::Select(expr_select) => {
                 expr_select.index(idx)?;
             }
@@ -1289,6 +1453,7 @@ impl Index for ast::Condition {
 impl Index for ast::ItemEnum {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
         let span = self.span();
+        let docs = idx.doc_comments.take(&idx.source, span);
 
         if let Some(first) = self.attributes.first() {
             return Err(CompileError::msg(
@@ -1309,7 +1474,7 @@ impl Index for ast::ItemEnum {
             visibility,
         )?;
 
-        idx.query.index_enum(&enum_item, &idx.source)?;
+        let mut variants = Vec::new();
 
         for (variant, _) in &mut self.variants {
             if let Some(first) = variant.attributes.first() {
@@ -1340,11 +1505,14 @@ impl Index for ast::ItemEnum {
                 Visibility::Public,
             )?;
             variant.id = Some(item.id);
+            variants.push(item.item.clone());
 
             idx.query
                 .index_variant(&item, &idx.source, enum_item.id, variant.clone())?;
         }
 
+        idx.query
+            .index_enum(&enum_item, &idx.source, variants, docs)?;
         Ok(())
     }
 }
@@ -1352,6 +1520,7 @@ impl Index for ast::ItemEnum {
 impl Index for Box<ast::ItemStruct> {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
         let span = self.span();
+        let docs = idx.doc_comments.take(&idx.source, span);
 
         if let Some(first) = self.attributes.first() {
             return Err(CompileError::msg(
@@ -1387,13 +1556,17 @@ impl Index for Box<ast::ItemStruct> {
         )?;
         self.id = Some(item.id);
 
-        idx.query.index_struct(&item, &idx.source, self.clone())?;
+        idx.query
+            .index_struct(&item, &idx.source, self.clone(), docs)?;
         Ok(())
     }
 }
 
 impl Index for ast::ItemImpl {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
+        // NB: doc comments are accepted on impl blocks, but not recorded.
+        idx.doc_comments.take(&idx.source, self.span());
+
         if let Some(first) = self.attributes.first() {
             return Err(CompileError::msg(
                 first,
@@ -1421,6 +1594,16 @@ impl Index for ast::ItemImpl {
         let new = Arc::new(idx.items.item().clone());
         let old = std::mem::replace(&mut idx.impl_item, Some(new));
 
+        let mut functions = Vec::with_capacity(self.functions.len());
+
+        for mut item_fn in self.functions.drain(..) {
+            if idx.is_cfg_enabled(&mut item_fn.attributes)? {
+                functions.push(item_fn);
+            }
+        }
+
+        self.functions = functions;
+
         for item_fn in &mut self.functions {
             item_fn.index(idx)?;
         }
@@ -1432,6 +1615,9 @@ impl Index for ast::ItemImpl {
 
 impl Index for ast::ItemMod {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
+        // NB: doc comments are accepted on modules, but not recorded.
+        idx.doc_comments.take(&idx.source, self.span());
+
         if let Some(first) = self.attributes.first() {
             return Err(CompileError::msg(
                 first,
@@ -1480,6 +1666,7 @@ impl Index for Box<ast::ItemConst> {
         }
 
         let span = self.span();
+        let docs = idx.doc_comments.take(&idx.source, span);
         let name = self.name.resolve(&idx.storage, &*idx.source)?;
         let _guard = idx.items.push_name(name.as_ref());
 
@@ -1497,7 +1684,8 @@ impl Index for Box<ast::ItemConst> {
         self.expr.index(idx)?;
         idx.nested_item = last;
 
-        idx.query.index_const(&item, &idx.source, &self.expr)?;
+        idx.query
+            .index_const(&item, &idx.source, &self.expr, docs)?;
         Ok(())
     }
 }
@@ -1654,6 +1842,18 @@ impl Index for Box<ast::ExprClosure> {
                 ast::FnArg::Pat(pat) => {
                     pat.index_local(idx)?;
                 }
+                ast::FnArg::Default(arg) => {
+                    return Err(CompileError::new(
+                        arg,
+                        CompileErrorKind::UnsupportedFnArgDefault,
+                    ));
+                }
+                ast::FnArg::Rest(arg) => {
+                    return Err(CompileError::new(
+                        arg,
+                        CompileErrorKind::UnsupportedFnArgRest,
+                    ));
+                }
             }
         }
 
@@ -1897,9 +2097,16 @@ impl Index for ast::ExprObject {
             ast::ObjectIdent::Anonymous(..) => (),
         }
 
-        for (assign, _) in &mut self.assignments {
-            if let Some((_, expr)) = &mut assign.assign {
-                expr.index(idx)?;
+        for (field, _) in &mut self.assignments {
+            match field {
+                ast::ObjectField::Assign(assign) => {
+                    if let Some((_, expr)) = &mut assign.assign {
+                        expr.index(idx)?;
+                    }
+                }
+                ast::ObjectField::Spread(spread) => {
+                    spread.expr.index(idx)?;
+                }
             }
         }
 
@@ -33,7 +33,14 @@ impl IndexLocal for ast::Pat {
             }
             ast::Pat::PatIgnore(..) => (),
             ast::Pat::PatLit(..) => (),
-            ast::Pat::PatRest(..) => (),
+            ast::Pat::PatRest(pat_rest) => {
+                if let Some(name) = &mut pat_rest.name {
+                    name.index_local(idx)?;
+                }
+            }
+            ast::Pat::PatOr(pat_or) => {
+                pat_or.index_local(idx)?;
+            }
         }
 
         Ok(())
@@ -129,6 +136,21 @@ impl IndexLocal for ast::PatTuple {
     }
 }
 
+impl IndexLocal for ast::PatOr {
+    fn index_local(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
+        let span = self.span();
+        log::trace!("PatOr => {:?}", idx.source.source(span));
+
+        self.first.index_local(idx)?;
+
+        for (_, pat) in &mut self.rest {
+            pat.index_local(idx)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl IndexLocal for ast::PatBinding {
     fn index_local(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
         let span = self.span();
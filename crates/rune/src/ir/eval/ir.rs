@@ -19,9 +19,11 @@ impl IrEval for ir::Ir {
             ir::IrKind::Assign(ir_assign) => ir_assign.eval(interp, used),
             ir::IrKind::Template(ir_template) => ir_template.eval(interp, used),
             ir::IrKind::Name(name) => Ok(interp.resolve_var(self.span(), name.as_ref(), used)?),
+            ir::IrKind::Path(item) => Ok(interp.resolve_path(self.span(), item, used)?),
             ir::IrKind::Target(ir_target) => Ok(interp.scopes.get_target(ir_target)?),
             ir::IrKind::Value(value) => Ok(IrValue::from_const(value.clone())),
             ir::IrKind::Branches(branches) => branches.eval(interp, used),
+            ir::IrKind::Match(ir_match) => ir_match.eval(interp, used),
             ir::IrKind::Loop(ir_loop) => ir_loop.eval(interp, used),
             ir::IrKind::Break(ir_break) => {
                 ir_break.eval(interp, used)?;
@@ -1,4 +1,5 @@
 use crate::ir::eval::prelude::*;
+use crate::IrErrorKind;
 
 impl IrEval for ir::IrBranches {
     type Output = IrValue;
@@ -11,7 +12,11 @@ impl IrEval for ir::IrBranches {
         for (ir_condition, branch) in &self.branches {
             let guard = interp.scopes.push();
 
-            let output = if ir_condition.eval(interp, used)? {
+            let condition = ir_condition
+                .eval(interp, used)
+                .map_err(|outcome| not_const_condition(ir_condition, outcome))?;
+
+            let output = if condition {
                 Some(branch.eval(interp, used)?)
             } else {
                 None
@@ -31,3 +36,17 @@ impl IrEval for ir::IrBranches {
         Ok(IrValue::Unit)
     }
 }
+
+/// Point errors raised because a condition isn't a constant expression at the
+/// condition as a whole.
+fn not_const_condition(condition: &ir::IrCondition, outcome: IrEvalOutcome) -> IrEvalOutcome {
+    match outcome {
+        IrEvalOutcome::NotConst(span) => IrEvalOutcome::Error(
+            IrError::new(span, IrErrorKind::NotConst).into_not_const_condition(condition),
+        ),
+        IrEvalOutcome::Error(error) => {
+            IrEvalOutcome::Error(error.into_not_const_condition(condition))
+        }
+        outcome => outcome,
+    }
+}
@@ -0,0 +1,41 @@
+use crate::ir::eval::prelude::*;
+use crate::IrErrorKind;
+
+impl IrEval for ir::IrMatch {
+    type Output = IrValue;
+
+    fn eval(
+        &self,
+        interp: &mut IrInterpreter<'_>,
+        used: Used,
+    ) -> Result<Self::Output, IrEvalOutcome> {
+        let value = self.value.eval(interp, used)?;
+
+        for branch in &self.branches {
+            let guard = interp.scopes.push();
+
+            let mut matches = branch.pat.matches(interp, value.clone(), used, branch)?;
+
+            if let (true, Some(condition)) = (matches, &branch.condition) {
+                matches = condition.as_bool(interp, used)?;
+            }
+
+            let output = if matches {
+                Some(branch.body.eval(interp, used)?)
+            } else {
+                None
+            };
+
+            interp.scopes.pop(branch, guard)?;
+
+            if let Some(output) = output {
+                return Ok(output);
+            }
+        }
+
+        Err(IrEvalOutcome::from(IrError::new(
+            self,
+            IrErrorKind::NoMatchingBranch,
+        )))
+    }
+}
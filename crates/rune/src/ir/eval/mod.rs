@@ -1,7 +1,7 @@
 use crate::ir::{IrInterpreter, IrPat, IrValue};
 use crate::query::Used;
 use crate::{IrError, Spanned};
-use runestick::Span;
+use runestick::{ConstValue, Span};
 
 mod ir;
 mod ir_assign;
@@ -12,6 +12,7 @@ mod ir_call;
 mod ir_condition;
 mod ir_decl;
 mod ir_loop;
+mod ir_match;
 mod ir_object;
 mod ir_scope;
 mod ir_set;
@@ -85,10 +86,35 @@ impl Matches for IrPat {
                 interp.scopes.decl(name, value, spanned)?;
                 Ok(true)
             }
+            IrPat::Value(expected) => Ok(value_matches(&value, expected, spanned.span())?),
         }
     }
 }
 
+/// Test if the given value is equal to the expected literal value.
+fn value_matches(value: &IrValue, expected: &ConstValue, span: Span) -> Result<bool, IrError> {
+    Ok(match (value, expected) {
+        (IrValue::Unit, ConstValue::Unit) => true,
+        (IrValue::Byte(a), ConstValue::Byte(b)) => a == b,
+        (IrValue::Char(a), ConstValue::Char(b)) => a == b,
+        (IrValue::Bool(a), ConstValue::Bool(b)) => a == b,
+        (IrValue::Integer(a), ConstValue::Integer(b)) => *a == num::BigInt::from(*b),
+        (IrValue::String(a), ConstValue::String(b)) => {
+            *a.borrow_ref().map_err(|e| IrError::new(span, e))? == *b
+        }
+        (IrValue::String(a), ConstValue::StaticString(b)) => {
+            a.borrow_ref().map_err(|e| IrError::new(span, e))?.as_str() == b.as_str()
+        }
+        (IrValue::Bytes(a), ConstValue::Bytes(b)) => {
+            a.borrow_ref()
+                .map_err(|e| IrError::new(span, e))?
+                .as_slice()
+                == &**b
+        }
+        _ => false,
+    })
+}
+
 /// The outcome of a constant evaluation.
 pub enum IrEvalOutcome {
     /// Encountered expression that is not a valid constant expression.
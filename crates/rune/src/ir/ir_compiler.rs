@@ -2,7 +2,7 @@ use crate::ir;
 use crate::query::BuiltInMacro;
 use crate::query::BuiltInTemplate;
 use crate::{IrErrorKind, Resolve, Spanned, Storage};
-use runestick::{Bytes, ConstValue, Source};
+use runestick::{Bytes, ConstValue, Item, Source};
 use std::sync::Arc;
 
 use crate::ast;
@@ -98,6 +98,7 @@ impl IrCompile for ast::Expr {
             ast::Expr::Assign(expr_assign) => expr_assign.compile(c)?,
             ast::Expr::Call(expr_call) => ir::Ir::new(self.span(), expr_call.compile(c)?),
             ast::Expr::If(expr_if) => ir::Ir::new(self.span(), expr_if.compile(c)?),
+            ast::Expr::Match(expr_match) => ir::Ir::new(self.span(), expr_match.compile(c)?),
             ast::Expr::Loop(expr_loop) => ir::Ir::new(self.span(), expr_loop.compile(c)?),
             ast::Expr::While(expr_while) => ir::Ir::new(self.span(), expr_while.compile(c)?),
             ast::Expr::Lit(expr_lit) => expr_lit.compile(c)?,
@@ -379,7 +380,14 @@ impl IrCompile for ast::ExprObject {
     fn compile(&self, c: &mut IrCompiler<'_>) -> Result<Self::Output, IrError> {
         let mut assignments = Vec::new();
 
-        for (assign, _) in &self.assignments {
+        for (field, _) in &self.assignments {
+            let assign = match field {
+                ast::ObjectField::Assign(assign) => assign,
+                ast::ObjectField::Spread(spread) => {
+                    return Err(IrError::msg(spread, "object spread not supported yet"));
+                }
+            };
+
             let key = c.resolve(&assign.key)?.into_owned();
 
             let ir = if let Some((_, expr)) = &assign.assign {
@@ -529,6 +537,19 @@ impl IrCompile for ast::Path {
             return Ok(ir::Ir::new(span, <Box<str>>::from(name)));
         }
 
+        if self.global.is_none() {
+            let mut item = Item::new();
+
+            for segment in self.as_components() {
+                match segment {
+                    ast::PathSegment::Ident(ident) => item.push(c.resolve(ident)?),
+                    _ => return Err(IrError::msg(segment, "not supported yet")),
+                }
+            }
+
+            return Ok(ir::Ir::new(span, item));
+        }
+
         Err(IrError::msg(span, "not supported yet"))
     }
 }
@@ -567,6 +588,10 @@ impl IrCompile for ast::Local {
     fn compile(&self, c: &mut IrCompiler<'_>) -> Result<Self::Output, IrError> {
         let span = self.span();
 
+        if self.expr_else.is_some() {
+            return Err(IrError::msg(span, "not supported yet"));
+        }
+
         let name = loop {
             match &self.pat {
                 ast::Pat::PatIgnore(_) => {
@@ -620,6 +645,11 @@ impl IrCompile for ast::Pat {
     fn compile(&self, c: &mut IrCompiler<'_>) -> Result<Self::Output, IrError> {
         match self {
             ast::Pat::PatIgnore(..) => return Ok(ir::IrPat::Ignore),
+            ast::Pat::PatLit(pat_lit) => {
+                if let ir::IrKind::Value(value) = pat_lit.expr.compile(c)?.kind {
+                    return Ok(ir::IrPat::Value(value));
+                }
+            }
             ast::Pat::PatPath(path) => {
                 if let Some(ident) = path.path.try_as_ident() {
                     let name = c.resolve(ident)?;
@@ -640,12 +670,12 @@ impl IrCompile for ast::ExprIf {
         let mut branches = Vec::new();
         let mut default_branch = None;
 
-        let condition = self.condition.compile(c)?;
+        let condition = compile_condition(c, &self.condition)?;
         let ir = self.block.compile(c)?;
         branches.push((condition, ir));
 
         for expr_else_if in &self.expr_else_ifs {
-            let condition = expr_else_if.condition.compile(c)?;
+            let condition = compile_condition(c, &expr_else_if.condition)?;
             let ir = expr_else_if.block.compile(c)?;
             branches.push((condition, ir));
         }
@@ -662,6 +692,48 @@ impl IrCompile for ast::ExprIf {
     }
 }
 
+/// Compile the condition of an `if` expression, pointing any error at the
+/// condition as a whole.
+fn compile_condition(
+    c: &mut IrCompiler<'_>,
+    condition: &ast::Condition,
+) -> Result<ir::IrCondition, IrError> {
+    condition
+        .compile(c)
+        .map_err(|error| error.into_not_const_condition(condition))
+}
+
+impl IrCompile for ast::ExprMatch {
+    type Output = ir::IrMatch;
+
+    fn compile(&self, c: &mut IrCompiler<'_>) -> Result<Self::Output, IrError> {
+        let value = self.expr.compile(c)?;
+        let mut branches = Vec::new();
+
+        for (branch, _) in &self.branches {
+            let pat = branch.pat.compile(c)?;
+
+            let condition = match &branch.condition {
+                Some((_, condition)) => Some(condition.compile(c)?),
+                None => None,
+            };
+
+            branches.push(ir::IrMatchBranch {
+                span: branch.span(),
+                pat,
+                condition,
+                body: branch.body.compile(c)?,
+            });
+        }
+
+        Ok(ir::IrMatch {
+            span: self.span(),
+            value: Box::new(value),
+            branches,
+        })
+    }
+}
+
 impl IrCompile for ast::ExprWhile {
     type Output = ir::IrLoop;
 
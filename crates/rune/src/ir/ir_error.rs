@@ -32,6 +32,25 @@ impl IrError {
         )
     }
 
+    /// Convert an error raised while processing the condition of an `if`
+    /// expression into one pointing at the condition as a whole, if it was
+    /// raised because the condition isn't a constant expression.
+    pub(crate) fn into_not_const_condition<S>(self, condition: S) -> Self
+    where
+        S: Spanned,
+    {
+        match &*self.kind {
+            IrErrorKind::NotConst
+            | IrErrorKind::Custom { .. }
+            | IrErrorKind::UnsupportedMeta { .. }
+            | IrErrorKind::FnNotFound => IrError::new(
+                condition,
+                IrErrorKind::NotConstCondition { error: self.kind },
+            ),
+            _ => self,
+        }
+    }
+
     /// Construct a callback to build an access error with the given spanned.
     pub fn access<S>(spanned: S) -> impl FnOnce(AccessError) -> Self
     where
@@ -87,6 +106,13 @@ pub enum IrErrorKind {
     /// expression.
     #[error("expected a constant expression")]
     NotConst,
+    /// The condition of a constant `if` expression could not be evaluated.
+    #[error("condition is not a constant expression: {error}")]
+    NotConstCondition {
+        /// The reason the condition couldn't be evaluated.
+        #[source]
+        error: Box<IrErrorKind>,
+    },
     /// Trying to process a cycle of constants.
     #[error("constant cycle detected")]
     ConstCycle,
@@ -105,8 +131,11 @@ pub enum IrErrorKind {
         actual: TypeInfo,
     },
     /// Exceeded evaluation budget.
-    #[error("evaluation budget exceeded")]
-    BudgetExceeded,
+    #[error("evaluation budget of {budget} steps exceeded")]
+    BudgetExceeded {
+        /// The number of steps that were allowed.
+        budget: usize,
+    },
     /// Integer underflow.
     #[error("integer underflow")]
     IntegerUnderflow,
@@ -134,6 +163,9 @@ pub enum IrErrorKind {
         /// Name of the missing thing.
         name: Box<str>,
     },
+    /// None of the branches in a constant match expression matched the value.
+    #[error("no branch matched the value being matched over")]
+    NoMatchingBranch,
     /// Error raised when trying to use a break outside of a loop.
     #[error("break outside of supported loop")]
     BreakOutsideOfLoop,
@@ -104,24 +104,64 @@ impl IrInterpreter<'_> {
             return Ok(ir_value.clone());
         }
 
-        let mut base = self.item.clone();
+        if let Some(ir_value) = self.resolve_item(spanned, &Item::with_item(&[name]), used)? {
+            return Ok(ir_value);
+        }
 
-        loop {
-            let item = base.extended(name);
+        if name.starts_with(char::is_lowercase) {
+            Err(IrError::new(
+                spanned,
+                IrErrorKind::MissingLocal { name: name.into() },
+            ))
+        } else {
+            Err(IrError::new(
+                spanned,
+                IrErrorKind::MissingConst { name: name.into() },
+            ))
+        }
+    }
+
+    /// Resolve the constant at the given path, like `math::PI`.
+    pub(crate) fn resolve_path(
+        &mut self,
+        spanned: Span,
+        path: &Item,
+        used: Used,
+    ) -> Result<IrValue, IrError> {
+        if let Some(ir_value) = self.resolve_item(spanned, path, used)? {
+            return Ok(ir_value);
+        }
+
+        // NB: constants registered in the context live in their own crate.
+        let mut it = path.iter();
 
-            if let Some(const_value) = self.consts.get(&item) {
+        if let Some(name) = it.next_str() {
+            if let Some(const_value) = self.consts.get(&Item::with_crate_item(name, it)) {
                 return Ok(IrValue::from_const(const_value));
             }
+        }
 
-            if let Some(meta) = self.query.query_meta(spanned, &item, used)? {
-                match &meta.kind {
-                    CompileMetaKind::Const { const_value, .. } => {
-                        return Ok(IrValue::from_const(const_value.clone()));
-                    }
-                    _ => {
-                        return Err(IrError::new(spanned, IrErrorKind::UnsupportedMeta { meta }));
-                    }
-                }
+        Err(IrError::new(
+            spanned,
+            IrErrorKind::MissingConst {
+                name: path.to_string().into(),
+            },
+        ))
+    }
+
+    /// Look up the constant at the given path relative to the current item,
+    /// and each of its parents.
+    fn resolve_item(
+        &mut self,
+        spanned: Span,
+        path: &Item,
+        used: Used,
+    ) -> Result<Option<IrValue>, IrError> {
+        let mut base = self.item.clone();
+
+        loop {
+            if let Some(ir_value) = self.lookup_const(spanned, &base.join(path), used)? {
+                return Ok(Some(ir_value));
             }
 
             if base.is_empty() {
@@ -131,16 +171,37 @@ impl IrInterpreter<'_> {
             base.pop();
         }
 
-        if name.starts_with(char::is_lowercase) {
-            Err(IrError::new(
-                spanned,
-                IrErrorKind::MissingLocal { name: name.into() },
-            ))
-        } else {
-            Err(IrError::new(
-                spanned,
-                IrErrorKind::MissingConst { name: name.into() },
-            ))
+        Ok(None)
+    }
+
+    /// Look up the constant at the given item, following imports.
+    fn lookup_const(
+        &mut self,
+        spanned: Span,
+        item: &Item,
+        used: Used,
+    ) -> Result<Option<IrValue>, IrError> {
+        if let Some(const_value) = self.consts.get(item) {
+            return Ok(Some(IrValue::from_const(const_value)));
+        }
+
+        let meta = match self.query.query_meta(spanned, item, used)? {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+
+        match &meta.kind {
+            CompileMetaKind::Const { const_value, .. } => {
+                Ok(Some(IrValue::from_const(const_value.clone())))
+            }
+            CompileMetaKind::Import { target, .. } => {
+                if let Some(ir_value) = self.lookup_const(spanned, target, used)? {
+                    return Ok(Some(ir_value));
+                }
+
+                Err(IrError::new(spanned, IrErrorKind::UnsupportedMeta { meta }))
+            }
+            _ => Err(IrError::new(spanned, IrErrorKind::UnsupportedMeta { meta })),
         }
     }
 
@@ -384,13 +445,19 @@ impl IrScopes {
 
 /// A budget dictating the number of evaluations the compiler is allowed to do.
 pub(crate) struct IrBudget {
+    /// The number of steps allowed in total.
+    limit: usize,
+    /// The number of steps remaining.
     budget: usize,
 }
 
 impl IrBudget {
     /// Construct a new constant evaluation budget with the given constraint.
     pub(crate) fn new(budget: usize) -> Self {
-        Self { budget }
+        Self {
+            limit: budget,
+            budget,
+        }
     }
 
     /// Take an item from the budget. Errors if the budget is exceeded.
@@ -399,7 +466,10 @@ impl IrBudget {
         S: Spanned,
     {
         if self.budget == 0 {
-            return Err(IrError::new(spanned, IrErrorKind::BudgetExceeded));
+            return Err(IrError::new(
+                spanned,
+                IrErrorKind::BudgetExceeded { budget: self.limit },
+            ));
         }
 
         self.budget -= 1;
@@ -20,7 +20,7 @@ pub(crate) use self::ir_interpreter::IrBudget;
 pub(crate) use self::ir_query::IrQuery;
 
 use crate::Spanned;
-use runestick::{ConstValue, Span};
+use runestick::{ConstValue, Item, Span};
 
 macro_rules! decl_kind {
     (
@@ -105,6 +105,8 @@ decl_kind! {
         Template(IrTemplate),
         /// A named value.
         Name(Box<str>),
+        /// A path to a constant, like `math::PI`.
+        Path(Item),
         /// A local name. Could either be a local variable or a reference to
         /// something else, like another const declaration.
         Target(IrTarget),
@@ -112,6 +114,8 @@ decl_kind! {
         Value(ConstValue),
         /// A sequence of conditional branches.
         Branches(IrBranches),
+        /// A match expression.
+        Match(IrMatch),
         /// A loop.
         Loop(IrLoop),
         /// A break to the given target.
@@ -259,6 +263,34 @@ pub enum IrPat {
     Ignore,
     /// A named binding.
     Binding(Box<str>),
+    /// A literal value, like `1` or `"foo"`.
+    Value(ConstValue),
+}
+
+/// A match expression.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrMatch {
+    /// The span of the match.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The value being matched over.
+    pub(crate) value: Box<Ir>,
+    /// The branches of the match, tested in order.
+    pub(crate) branches: Vec<IrMatchBranch>,
+}
+
+/// A branch in a match expression.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrMatchBranch {
+    /// The span of the branch.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The pattern of the branch.
+    pub(crate) pat: IrPat,
+    /// The optional condition of the branch.
+    pub(crate) condition: Option<Ir>,
+    /// The body of the branch.
+    pub(crate) body: Ir,
 }
 
 /// A loop with an optional condition.
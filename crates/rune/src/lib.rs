@@ -181,6 +181,7 @@ mod compiling;
 mod diagnostics;
 #[cfg(feature = "diagnostics")]
 mod emit_diagnostics;
+mod formatting;
 mod indexing;
 mod ir;
 mod load;
@@ -203,15 +204,18 @@ mod collections {
 
 pub use self::compiling::{
     BuildError, CompileError, CompileErrorKind, CompileResult, CompileVisitor, ImportEntryStep,
-    LinkerError, NoopCompileVisitor, UnitBuilder,
+    IndexCache, LinkerError, NoopCompileVisitor, UnitBuilder,
 };
 pub use self::diagnostics::{Diagnostic, Diagnostics, Error, ErrorKind, Warning, WarningKind};
 #[cfg(feature = "diagnostics")]
 pub use self::emit_diagnostics::{
     termcolor, DiagnosticsError, DumpInstructions, EmitDiagnostics, EmitSource,
 };
+pub use self::formatting::format_source;
 pub use self::ir::{IrError, IrErrorKind, IrValue};
-pub use self::load::{load_sources, load_sources_with_visitor, LoadSourcesError};
+pub use self::load::{
+    load_sources, load_sources_with_cache, load_sources_with_visitor, LoadSourcesError,
+};
 pub use self::load::{FileSourceLoader, SourceLoader, Sources};
 pub use self::macros::{
     with_context, MacroContext, Quote, Storage, ToTokens, TokenStream, TokenStreamIter,
@@ -221,7 +225,7 @@ pub use self::parsing::{
     Id, Lexer, Parse, ParseError, ParseErrorKind, Parser, Peek, Peeker, Resolve, ResolveError,
     ResolveErrorKind, ResolveOwned,
 };
-pub use self::query::{QueryError, QueryErrorKind, Used};
+pub use self::query::{ItemKind, QueryError, QueryErrorKind, Used};
 pub use self::shared::{ScopeError, ScopeErrorKind};
 pub use self::spanned::{OptionSpanned, Spanned};
 pub use compiling::compile;
@@ -1,5 +1,5 @@
 use crate::compiling;
-use crate::{Diagnostics, Options};
+use crate::{Diagnostics, IndexCache, Options};
 use runestick::{Context, Unit};
 use std::rc::Rc;
 use thiserror::Error;
@@ -88,6 +88,50 @@ pub fn load_sources_with_visitor<'a>(
     diagnostics: &mut Diagnostics,
     visitor: Rc<dyn compiling::CompileVisitor>,
     source_loader: Rc<dyn SourceLoader + 'a>,
+) -> Result<Unit, LoadSourcesError> {
+    load_sources_inner(
+        context,
+        options,
+        sources,
+        diagnostics,
+        visitor,
+        source_loader,
+        None,
+    )
+}
+
+/// Load the specified sources with a visitor, only indexing the sources which
+/// have changed since the last time the given [IndexCache] was used.
+///
+/// See [IndexCache] for details.
+pub fn load_sources_with_cache<'a>(
+    context: &Context,
+    options: &Options,
+    sources: &mut Sources,
+    diagnostics: &mut Diagnostics,
+    visitor: Rc<dyn compiling::CompileVisitor>,
+    source_loader: Rc<dyn SourceLoader + 'a>,
+    cache: &mut IndexCache,
+) -> Result<Unit, LoadSourcesError> {
+    load_sources_inner(
+        context,
+        options,
+        sources,
+        diagnostics,
+        visitor,
+        source_loader,
+        Some(cache),
+    )
+}
+
+fn load_sources_inner<'a>(
+    context: &Context,
+    options: &Options,
+    sources: &mut Sources,
+    diagnostics: &mut Diagnostics,
+    visitor: Rc<dyn compiling::CompileVisitor>,
+    source_loader: Rc<dyn SourceLoader + 'a>,
+    cache: Option<&mut IndexCache>,
 ) -> Result<Unit, LoadSourcesError> {
     let unit = if context.has_default_modules() {
         compiling::UnitBuilder::with_default_prelude()
@@ -95,7 +139,7 @@ pub fn load_sources_with_visitor<'a>(
         compiling::UnitBuilder::default()
     };
 
-    let result = compiling::compile_with_options(
+    let result = compiling::compile_with_cache(
         &*context,
         sources,
         &unit,
@@ -103,6 +147,7 @@ pub fn load_sources_with_visitor<'a>(
         options,
         visitor,
         source_loader,
+        cache,
     );
 
     if let Err(()) = result {
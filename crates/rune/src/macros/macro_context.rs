@@ -140,6 +140,7 @@ impl MacroContext {
         T: Spanned + IrCompile,
         T::Output: IrEval,
     {
+        let budget = self.query.const_budget();
         let mut ir_query = self.query.as_ir_query();
 
         let mut ir_compiler = IrCompiler {
@@ -151,7 +152,7 @@ impl MacroContext {
         let output = ir_compiler.compile(target)?;
 
         let mut ir_interpreter = IrInterpreter {
-            budget: IrBudget::new(1_000_000),
+            budget: IrBudget::new(budget),
             scopes: Default::default(),
             module: self.item.module.clone(),
             item: self.item.item.clone(),
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use thiserror::Error;
 
 /// Error when parsing configuration.
@@ -9,10 +10,25 @@ pub enum ConfigurationError {
         /// The unsupported option.
         option: String,
     },
+    /// Tried to enable a feature without specifying its name.
+    #[error("missing name of feature to enable")]
+    MissingFeatureName,
+    /// Tried to set a constant evaluation budget which is not a number.
+    #[error("bad constant evaluation budget `{budget}`")]
+    BadConstBudget {
+        /// The bad budget.
+        budget: String,
+    },
 }
 
+/// The default number of steps constant evaluation is allowed to take.
+pub(crate) const DEFAULT_CONST_BUDGET: usize = 1_000_000;
+
 /// Compiler options.
-#[derive(Debug, Clone, Copy)]
+///
+/// Note that these options are `Clone` but not `Copy`, since they hold the
+/// set of features enabled for conditional compilation.
+#[derive(Debug, Clone)]
 pub struct Options {
     /// Perform link-time checks.
     pub(crate) link_checks: bool,
@@ -24,6 +40,21 @@ pub struct Options {
     pub(crate) macros: bool,
     /// Support (experimental) bytecode caching.
     pub bytecode: bool,
+    /// Share a single function between identical closures which do not
+    /// capture their environment.
+    pub(crate) dedup_closures: bool,
+    /// Compile integer literals which don't fit in an `i64` as big integers.
+    pub bignum: bool,
+    /// Treat lints which likely indicate bugs as errors.
+    pub(crate) strict: bool,
+    /// Suggest turning named closures which capture nothing into functions.
+    pub(crate) closure_to_fn: bool,
+    /// Report every warning as an error.
+    pub(crate) warnings_as_errors: bool,
+    /// Features enabled for conditional compilation through `#[cfg]`.
+    pub(crate) features: BTreeSet<String>,
+    /// The number of steps constant evaluation is allowed to take.
+    pub(crate) const_budget: usize,
 
     /// Compile for and enable test features
     pub cfg_test: bool,
@@ -57,6 +88,37 @@ impl Options {
             Some("bytecode") => {
                 self.bytecode = it.next() != Some("false");
             }
+            Some("dedup-closures") => {
+                self.dedup_closures = it.next() != Some("false");
+            }
+            Some("bignum") => {
+                self.bignum = it.next() != Some("false");
+            }
+            Some("strict") => {
+                self.strict = it.next() != Some("false");
+            }
+            Some("closure-to-fn") => {
+                self.closure_to_fn = it.next() != Some("false");
+            }
+            Some("warnings-as-errors") => {
+                self.warnings_as_errors = it.next() != Some("false");
+            }
+            Some("feature") => match it.next() {
+                Some(feature) if !feature.is_empty() => {
+                    self.features.insert(feature.to_owned());
+                }
+                _ => return Err(ConfigurationError::MissingFeatureName),
+            },
+            Some("const-budget") => {
+                let budget = it.next().unwrap_or_default();
+
+                self.const_budget =
+                    budget
+                        .parse()
+                        .map_err(|_| ConfigurationError::BadConstBudget {
+                            budget: budget.to_owned(),
+                        })?;
+            }
             Some("test") => {
                 self.cfg_test = it.next() != Some("false");
             }
@@ -100,6 +162,69 @@ impl Options {
         self.bytecode = enabled;
     }
 
+    /// Set if closures which do not capture their environment should be
+    /// deduplicated. Defaults to `false`.
+    ///
+    /// When enabled, capture-free closures which compile to identical
+    /// instructions share a single function in the unit.
+    pub fn dedup_closures(&mut self, enabled: bool) {
+        self.dedup_closures = enabled;
+    }
+
+    /// Set if integer literals which don't fit in an `i64` should be compiled
+    /// as big integers from the `std::bigint` module instead of being
+    /// rejected. Defaults to `false`.
+    ///
+    /// The `std::bigint` module is not part of the default context, so it has
+    /// to be installed with [runestick::modules::bigint::module].
+    pub fn bignum(&mut self, enabled: bool) {
+        self.bignum = enabled;
+    }
+
+    /// Set if lints which likely indicate bugs should be reported as errors
+    /// instead of warnings. Defaults to `false`.
+    ///
+    /// This currently covers matches over enums which don't cover every
+    /// variant.
+    pub fn strict(&mut self, enabled: bool) {
+        self.strict = enabled;
+    }
+
+    /// Set if a warning should be emitted for closures which are bound to a
+    /// name but don't capture anything, suggesting that they are declared as
+    /// functions instead. Defaults to `false`.
+    pub fn closure_to_fn(&mut self, enabled: bool) {
+        self.closure_to_fn = enabled;
+    }
+
+    /// Set if every warning should be reported as an error, causing
+    /// compilation to fail. Defaults to `false`.
+    pub fn warnings_as_errors(&mut self, enabled: bool) {
+        self.warnings_as_errors = enabled;
+    }
+
+    /// Set if the given feature is enabled or not. No features are enabled by
+    /// default.
+    ///
+    /// Items marked with `#[cfg(feature = "<name>")]` are only compiled if
+    /// the named feature is enabled, otherwise they are treated as if they
+    /// don't exist.
+    pub fn feature(&mut self, feature: &str, enabled: bool) {
+        if enabled {
+            self.features.insert(feature.to_owned());
+        } else {
+            self.features.remove(feature);
+        }
+    }
+
+    /// Set the number of steps constant evaluation is allowed to take before
+    /// it's aborted with an error. Defaults to `1_000_000`.
+    ///
+    /// Lowering this is useful when compiling untrusted code.
+    pub fn const_budget(&mut self, budget: usize) {
+        self.const_budget = budget;
+    }
+
     /// Memoize the instance function in a loop. Defaults to `false`.
     pub fn memoize_instance_fn(&mut self, enabled: bool) {
         self.memoize_instance_fn = enabled;
@@ -114,6 +239,13 @@ impl Default for Options {
             debug_info: true,
             macros: true,
             bytecode: false,
+            dedup_closures: false,
+            bignum: false,
+            strict: false,
+            closure_to_fn: false,
+            warnings_as_errors: false,
+            features: BTreeSet::new(),
+            const_budget: DEFAULT_CONST_BUDGET,
             cfg_test: false,
             v2: false,
         }
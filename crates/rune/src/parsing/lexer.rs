@@ -13,6 +13,8 @@ pub struct Lexer<'a> {
     modes: LexerModes,
     /// Buffered tokens.
     buffer: VecDeque<ast::Token>,
+    /// Spans of the doc comments encountered so far, like `/// Hello`.
+    doc_comments: Vec<Span>,
 }
 
 impl<'a> Lexer<'a> {
@@ -46,6 +48,7 @@ impl<'a> Lexer<'a> {
             iter: SourceIter::new(source),
             modes: LexerModes::default(),
             buffer: VecDeque::new(),
+            doc_comments: Vec::new(),
         }
     }
 
@@ -54,6 +57,26 @@ impl<'a> Lexer<'a> {
         self.iter.end_span(0)
     }
 
+    /// Take the spans of the doc comments which have been encountered so far,
+    /// in the order in which they appear in the source.
+    ///
+    /// Doc comments are line comments starting with exactly three slashes,
+    /// like `/// Hello`. They are otherwise ignored by the lexer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rune::Lexer;
+    /// use runestick::span;
+    ///
+    /// let mut lexer = Lexer::new("/// Hello\n//// Not docs\nfn");
+    /// while lexer.next().unwrap().is_some() {}
+    /// assert_eq!(lexer.take_doc_comments(), vec![span!(0, 9)]);
+    /// ```
+    pub fn take_doc_comments(&mut self) -> Vec<Span> {
+        std::mem::take(&mut self.doc_comments)
+    }
+
     fn emit_builtin_attribute(&mut self, span: Span) {
         self.buffer.push_back(ast::Token { kind: K![#], span });
 
@@ -127,17 +150,23 @@ impl<'a> Lexer<'a> {
             ast::NumberBase::Decimal
         };
 
+        let is_decimal = matches!(base, ast::NumberBase::Decimal);
         let mut is_fractional = false;
         let mut has_exponent = false;
 
         while let Some(c) = self.iter.peek() {
             match c {
-                'e' if !has_exponent => {
+                'e' | 'E' if is_decimal && !has_exponent => {
                     self.iter.next();
                     has_exponent = true;
                     is_fractional = true;
+
+                    // NB: the exponent might be signed, like `2e-4`.
+                    if matches!(self.iter.peek(), Some('-') | Some('+')) {
+                        self.iter.next();
+                    }
                 }
-                '.' if !is_fractional => {
+                '.' if is_decimal && !is_fractional => {
                     if let Some(p2) = self.iter.peek2() {
                         // NB: only skip if the next peek matches:
                         // * the beginning of an ident.
@@ -156,7 +185,8 @@ impl<'a> Lexer<'a> {
                     self.iter.next();
                     is_fractional = true;
                 }
-                c if c.is_alphanumeric() => {
+                // NB: underscores are digit separators, like `1_000_000`.
+                c if c.is_alphanumeric() || c == '_' => {
                     self.iter.next();
                 }
                 _ => break,
@@ -316,6 +346,51 @@ impl<'a> Lexer<'a> {
             kind: kind(ast::StrSource::Text(ast::StrText {
                 escaped,
                 wrapped: true,
+                raw: None,
+            })),
+            span: self.iter.span_from(start),
+        }))
+    }
+
+    /// Consume a raw string literal, like `r#"foo"#`, where the leading `r` has
+    /// already been consumed.
+    fn next_raw_str(&mut self, start: usize) -> Result<Option<ast::Token>, ParseError> {
+        let mut hashes = 0;
+
+        while let Some('#') = self.iter.peek() {
+            self.iter.next();
+            hashes += 1;
+        }
+
+        // NB: the opening quote.
+        self.iter.next();
+        let open = self.iter.span_from(start);
+
+        loop {
+            match self.iter.next() {
+                Some('"') => {
+                    let rest = self.iter.rest();
+
+                    if rest.len() >= hashes && rest[..hashes].chars().all(|c| c == '#') {
+                        for _ in 0..hashes {
+                            self.iter.next();
+                        }
+
+                        break;
+                    }
+                }
+                Some(..) => (),
+                None => {
+                    return Err(ParseError::new(open, ParseErrorKind::UnterminatedStrLit));
+                }
+            }
+        }
+
+        Ok(Some(ast::Token {
+            kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
+                escaped: false,
+                wrapped: true,
+                raw: Some(hashes),
             })),
             span: self.iter.span_from(start),
         }))
@@ -370,6 +445,7 @@ impl<'a> Lexer<'a> {
                             kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
                                 escaped: take(&mut escaped),
                                 wrapped: false,
+                                raw: None,
                             })),
                             span,
                         });
@@ -419,6 +495,7 @@ impl<'a> Lexer<'a> {
                             kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
                                 escaped: take(&mut escaped),
                                 wrapped: false,
+                                raw: None,
                             })),
                             span,
                         });
@@ -517,7 +594,16 @@ impl<'a> Lexer<'a> {
                             break ast::Kind::PipeEq;
                         }
                         ('/', '/') => {
+                            let rest = self.iter.rest();
+                            let is_doc = rest.starts_with("//") && !rest.starts_with("///");
                             self.consume_line();
+
+                            if is_doc {
+                                let (comment, _) = self.iter.source_from(start);
+                                let span = Span::new(start, start + comment.trim_end().len());
+                                self.doc_comments.push(span);
+                            }
+
                             continue 'outer;
                         }
                         (':', ':') => {
@@ -548,6 +634,10 @@ impl<'a> Lexer<'a> {
                             self.iter.next();
                             break ast::Kind::PipePipe;
                         }
+                        ('?', '?') => {
+                            self.iter.next();
+                            break ast::Kind::QuestionMarkQuestionMark;
+                        }
                         ('<', '<') => {
                             self.iter.next();
 
@@ -586,6 +676,9 @@ impl<'a> Lexer<'a> {
                             self.iter.next();
                             break ast::Kind::Arrow;
                         }
+                        ('r', '"') | ('r', '#') if is_raw_str(self.iter.rest()) => {
+                            return self.next_raw_str(start);
+                        }
                         ('b', '\'') => {
                             self.iter.next();
                             self.iter.next();
@@ -629,7 +722,18 @@ impl<'a> Lexer<'a> {
                     }
                     '[' => ast::Kind::Open(ast::Delimiter::Bracket),
                     ']' => ast::Kind::Close(ast::Delimiter::Bracket),
-                    '_' => ast::Kind::Underscore,
+                    '_' => {
+                        // NB: an underscore followed by identifier characters
+                        // is an identifier, like `_unused`.
+                        if matches!(
+                            self.iter.peek(),
+                            Some('a'..='z' | 'A'..='Z' | '_' | '0'..='9')
+                        ) {
+                            return self.next_ident(start);
+                        }
+
+                        ast::Kind::Underscore
+                    }
                     ',' => ast::Kind::Comma,
                     ':' => ast::Kind::Colon,
                     '#' => ast::Kind::Pound,
@@ -704,6 +808,11 @@ impl<'a> Lexer<'a> {
     }
 }
 
+/// Test if the given source, which follows an `r`, starts a raw string.
+fn is_raw_str(source: &str) -> bool {
+    source.trim_start_matches('#').starts_with('"')
+}
+
 #[derive(Debug, Clone)]
 struct SourceIter<'a> {
     source: &'a str,
@@ -745,6 +854,11 @@ impl<'a> SourceIter<'a> {
         Span::new(start, self.source.len())
     }
 
+    /// Get the source which hasn't been consumed yet.
+    fn rest(&self) -> &'a str {
+        self.chars.as_str()
+    }
+
     /// Peek the next index.
     fn peek(&self) -> Option<char> {
         self.chars.clone().next()
@@ -938,6 +1052,24 @@ mod tests {
             },
             _,
         };
+
+        test_lexer! {
+            "1_000_000 0xe_e1",
+            ast::Token {
+                span: span!(0, 9),
+                kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
+                    is_fractional: false,
+                    base: ast::NumberBase::Decimal,
+                })),
+            },
+            ast::Token {
+                span: span!(10, 16),
+                kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
+                    is_fractional: false,
+                    base: ast::NumberBase::Hex,
+                })),
+            },
+        };
     }
 
     #[test]
@@ -973,7 +1105,7 @@ mod tests {
             },
             ast::Token {
                 span: span!(10, 19),
-                kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText { escaped: false, wrapped: true })),
+                kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText { escaped: false, wrapped: true, raw: None })),
             }
         };
     }
@@ -1051,6 +1183,21 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_underscore_idents() {
+        test_lexer! {
+            "_unused _",
+            ast::Token {
+                span: span!(0, 7),
+                kind: ast::Kind::Ident(ast::StringSource::Text),
+            },
+            ast::Token {
+                span: span!(8, 9),
+                kind: ast::Kind::Underscore,
+            },
+        };
+    }
+
     #[test]
     fn test_template_literals() {
         test_lexer! {
@@ -1099,6 +1246,7 @@ mod tests {
                 kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
                     escaped: false,
                     wrapped: false,
+                    raw: None,
                 })),
                 span: span!(1, 5),
             },
@@ -1118,6 +1266,7 @@ mod tests {
                 kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
                     escaped: true,
                     wrapped: false,
+                    raw: None,
                 })),
                 span: span!(11, 18),
             },
@@ -1176,6 +1325,7 @@ mod tests {
                 kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
                     escaped: false,
                     wrapped: false,
+                    raw: None,
                 })),
                 span: span!(1, 5),
             },
@@ -1195,6 +1345,7 @@ mod tests {
                 kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
                     escaped: false,
                     wrapped: false,
+                    raw: None,
                 })),
                 span: span!(11, 12),
             },
@@ -1222,6 +1373,7 @@ mod tests {
                 kind: ast::Kind::ByteStr(ast::StrSource::Text(ast::StrText {
                     escaped: false,
                     wrapped: true,
+                    raw: None,
                 })),
             },
         };
@@ -1233,6 +1385,7 @@ mod tests {
                 kind: ast::Kind::ByteStr(ast::StrSource::Text(ast::StrText {
                     escaped: false,
                     wrapped: true,
+                    raw: None,
                 })),
             },
         };
@@ -130,6 +130,29 @@ impl<'a> Parser<'a> {
         &mut self.peeker
     }
 
+    /// Split a leading `??` token into two `?` tokens, so that they can be
+    /// parsed as two separate try operators.
+    pub(crate) fn split_question_marks(&mut self) -> Result<(), ParseError> {
+        let token = match self.peeker.at(0)? {
+            Some(token) if token.kind == Kind::QuestionMarkQuestionMark => token,
+            _ => return Ok(()),
+        };
+
+        let second = token.span.trim_start(1usize);
+
+        self.peeker.buf[0] = Token {
+            kind: Kind::QuestionMark,
+            span: second,
+        };
+
+        self.peeker.buf.push_front(Token {
+            kind: Kind::QuestionMark,
+            span: Span::new(token.span.start, second.start),
+        });
+
+        Ok(())
+    }
+
     /// Consume the next token from the parser.
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Result<Token, ParseError> {
@@ -150,6 +173,14 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Take the spans of the doc comments which have been encountered so far,
+    /// like `/// Hello`.
+    ///
+    /// Parsing from a token stream never produces any doc comments.
+    pub fn take_doc_comments(&mut self) -> Vec<Span> {
+        self.peeker.source.take_doc_comments()
+    }
+
     /// Test if the parser is at end-of-file, after which there is no more input
     /// to parse.
     pub fn is_eof(&mut self) -> Result<bool, ParseError> {
@@ -289,6 +320,14 @@ impl Source<'_> {
             SourceInner::TokenStream(token_stream) => Ok(token_stream.next()),
         }
     }
+
+    /// Take the doc comments encountered so far.
+    pub(crate) fn take_doc_comments(&mut self) -> Vec<Span> {
+        match &mut self.inner {
+            SourceInner::Lexer(lexer) => lexer.take_doc_comments(),
+            SourceInner::TokenStream(..) => Vec::new(),
+        }
+    }
 }
 
 impl fmt::Debug for Source<'_> {
@@ -70,6 +70,8 @@ pub enum ResolveErrorKind {
     BadByteLiteral,
     #[error("unicode escapes are not supported as a byte or byte string")]
     BadUnicodeEscapeInByteString,
+    #[error("byte strings may only contain ASCII characters, use an escape like `\\xff` instead")]
+    BadByteStrChar,
     #[error("number literal not valid")]
     BadNumberLiteral,
 }
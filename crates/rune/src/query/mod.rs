@@ -4,11 +4,12 @@ use crate::ast;
 use crate::collections::{HashMap, HashSet};
 use crate::ir;
 use crate::ir::{IrBudget, IrCompile, IrCompiler, IrInterpreter, IrQuery};
+use crate::options::DEFAULT_CONST_BUDGET;
 use crate::parsing::Opaque;
 use crate::shared::{Consts, Gen, Items};
 use crate::{
     CompileError, CompileErrorKind, CompileVisitor, Id, ImportEntryStep, NoopCompileVisitor,
-    Resolve as _, Spanned, Storage, UnitBuilder,
+    Resolve as _, Spanned, Storage, UnitBuilder, Warning,
 };
 use runestick::format;
 use runestick::{
@@ -116,6 +117,7 @@ impl Query {
         unit: UnitBuilder,
         consts: Consts,
         gen: Gen,
+        const_budget: usize,
     ) -> Self {
         Self {
             inner: Rc::new(RefCell::new(QueryInner {
@@ -131,9 +133,11 @@ impl Query {
                 const_fns: HashMap::new(),
                 query_paths: HashMap::new(),
                 internal_macros: HashMap::new(),
+                allows: Vec::new(),
                 items: HashMap::new(),
                 names: Names::default(),
                 modules: HashMap::new(),
+                const_budget,
             })),
         }
     }
@@ -169,6 +173,11 @@ impl Query {
         self.inner.borrow_mut().queue.push_back(entry)
     }
 
+    /// The number of steps constant evaluation is allowed to take.
+    pub(crate) fn const_budget(&self) -> usize {
+        self.inner.borrow().const_budget
+    }
+
     /// Access a clone of the storage associated with query.
     pub(crate) fn storage(&self) -> Storage {
         self.inner.borrow().storage.clone()
@@ -276,11 +285,34 @@ impl Query {
     /// Insert a new expanded internal macro.
     pub(crate) fn insert_new_builtin_macro(
         &mut self,
+        source_id: SourceId,
         internal_macro: BuiltInMacro,
     ) -> Result<Id, QueryError> {
         self.inner
             .borrow_mut()
-            .insert_new_builtin_macro(internal_macro)
+            .insert_new_builtin_macro(source_id, internal_macro)
+    }
+
+    /// Allow the given lints for everything within the given location.
+    pub(crate) fn insert_allow(&self, location: Location, lints: Vec<Box<str>>) {
+        self.inner
+            .borrow_mut()
+            .allows
+            .push(Arc::new(QueryAllow { location, lints }));
+    }
+
+    /// Test if the given warning is suppressed by an `#[allow(..)]` attribute
+    /// surrounding it.
+    pub(crate) fn is_allowed(&self, warning: &Warning) -> bool {
+        let span = warning.span();
+        let lint = warning.kind().lint();
+
+        self.inner.borrow().allows.iter().any(|allow| {
+            allow.location.source_id == warning.source_id()
+                && allow.location.span.start <= span.start
+                && span.end <= allow.location.span.end
+                && allow.lints.iter().any(|l| l.as_ref() == lint)
+        })
     }
 
     /// Get the item for the given identifier.
@@ -318,6 +350,7 @@ impl Query {
         item: &Arc<CompileItem>,
         source: &Arc<Source>,
         expr: &T,
+        docs: Option<Box<str>>,
     ) -> Result<(), QueryError>
     where
         T: IrCompile<Output = ir::Ir>,
@@ -337,6 +370,7 @@ impl Query {
         inner.index(IndexedEntry {
             item: item.clone(),
             source: source.clone(),
+            docs,
             indexed: Indexed::Const(Const {
                 module: item.module.clone(),
                 ir,
@@ -352,12 +386,14 @@ impl Query {
         item: &Arc<CompileItem>,
         source: &Arc<Source>,
         item_fn: Box<ast::ItemFn>,
+        docs: Option<Box<str>>,
     ) -> Result<(), QueryError> {
         log::trace!("new const fn: {:?}", item.item);
 
         self.inner.borrow_mut().index(IndexedEntry {
             item: item.clone(),
             source: source.clone(),
+            docs,
             indexed: Indexed::ConstFn(ConstFn { item_fn }),
         });
 
@@ -369,13 +405,16 @@ impl Query {
         &self,
         item: &Arc<CompileItem>,
         source: &Arc<Source>,
+        variants: Vec<Item>,
+        docs: Option<Box<str>>,
     ) -> Result<(), QueryError> {
         log::trace!("new enum: {:?}", item.item);
 
         self.inner.borrow_mut().index(IndexedEntry {
             item: item.clone(),
             source: source.clone(),
-            indexed: Indexed::Enum,
+            docs,
+            indexed: Indexed::Enum(Enum { variants }),
         });
 
         Ok(())
@@ -387,12 +426,14 @@ impl Query {
         item: &Arc<CompileItem>,
         source: &Arc<Source>,
         ast: Box<ast::ItemStruct>,
+        docs: Option<Box<str>>,
     ) -> Result<(), QueryError> {
         log::trace!("new struct: {:?}", item.item);
 
         self.inner.borrow_mut().index(IndexedEntry {
             item: item.clone(),
             source: source.clone(),
+            docs,
             indexed: Indexed::Struct(Struct::new(ast)),
         });
 
@@ -412,6 +453,7 @@ impl Query {
         self.inner.borrow_mut().index(IndexedEntry {
             item: item.clone(),
             source: source.clone(),
+            docs: None,
             indexed: Indexed::Variant(Variant::new(enum_id, ast)),
         });
 
@@ -433,6 +475,7 @@ impl Query {
         self.inner.borrow_mut().index(IndexedEntry {
             item: item.clone(),
             source: source.clone(),
+            docs: None,
             indexed: Indexed::Closure(Closure {
                 ast,
                 captures,
@@ -459,6 +502,7 @@ impl Query {
         self.inner.borrow_mut().index(IndexedEntry {
             item: item.clone(),
             source: source.clone(),
+            docs: None,
             indexed: Indexed::AsyncBlock(AsyncBlock {
                 ast,
                 captures,
@@ -472,6 +516,10 @@ impl Query {
 
     /// Remove and queue up unused entries for building.
     ///
+    /// Names which are only provided by multiple conflicting wildcard imports
+    /// are left in the index, so that they only raise an ambiguity error if
+    /// they are actually used.
+    ///
     /// Returns boolean indicating if any unused entries were queued up.
     pub(crate) fn queue_unused_entries(&self) -> Result<bool, (SourceId, QueryError)> {
         let mut inner = self.inner.borrow_mut();
@@ -479,6 +527,7 @@ impl Query {
         let unused = inner
             .indexed
             .values()
+            .filter(|entries| !is_ambiguous_wildcard(entries))
             .flat_map(|entries| entries.iter())
             .map(|e| e.item.clone())
             .collect::<Vec<_>>();
@@ -577,6 +626,7 @@ impl Query {
         inner.index(IndexedEntry {
             item,
             source: source.clone(),
+            docs: None,
             indexed: Indexed::Import(Import { wildcard, entry }),
         });
 
@@ -603,6 +653,56 @@ impl Query {
             .collect::<Vec<_>>()
     }
 
+    /// Find the known sibling of a missing item whose name is the closest to
+    /// it, so that it can be suggested in place of a likely typo.
+    ///
+    /// Returns `None` if no sibling is close enough to the missing item.
+    pub(crate) fn suggest_item(&self, context: &Context, item: &Item) -> Option<Item> {
+        let mut parent = item.clone();
+
+        let name = match parent.pop()? {
+            Component::Str(name) => name,
+            _ => return None,
+        };
+
+        let max_distance = usize::max(1, name.chars().count() / 3);
+
+        let mut candidates = self.iter_components(parent.iter());
+        candidates.extend(
+            context
+                .iter_components(parent.iter())
+                .map(ComponentRef::into_component),
+        );
+
+        let mut best = None::<(usize, Box<str>)>;
+
+        for candidate in candidates {
+            let candidate = match candidate {
+                Component::Str(candidate) => candidate,
+                _ => continue,
+            };
+
+            let distance = edit_distance(&name, &candidate);
+
+            if distance == 0 || distance > max_distance {
+                continue;
+            }
+
+            // NB: break ties by name, since components are not ordered.
+            let better = match &best {
+                Some((d, b)) => (distance, &candidate) < (*d, b),
+                None => true,
+            };
+
+            if better {
+                best = Some((distance, candidate));
+            }
+        }
+
+        let (_, name) = best?;
+        Some(parent.extended(&*name))
+    }
+
     pub(crate) fn import(
         &self,
         span: Span,
@@ -613,6 +713,156 @@ impl Query {
         let mut inner = self.inner.borrow_mut();
         inner.import(span, module, item, used)
     }
+
+    /// Iterate over all named items known to the query system, like functions,
+    /// types and constants, together with their locations and kinds.
+    ///
+    /// Unlike [Query::queue_unused_entries] this does not consume the index or
+    /// disturb the build queue, so it can be used to list symbols after
+    /// indexing. Items are ordered by their location.
+    pub(crate) fn items(&self) -> impl Iterator<Item = (Item, Location, ItemKind)> {
+        let inner = self.inner.borrow();
+
+        let indexed = inner
+            .indexed
+            .values()
+            .flat_map(|entries| entries.iter())
+            .filter_map(|e| Some((&e.item, ItemKind::from_indexed(&e.indexed)?)));
+
+        let meta = inner
+            .meta
+            .values()
+            .filter_map(|m| Some((&m.item, ItemKind::from_meta_kind(&m.kind)?)));
+
+        let mut items = indexed
+            .chain(meta)
+            .map(|(item, kind)| (item.location, item.item.clone(), kind))
+            .collect::<Vec<_>>();
+
+        items.sort_by_key(|(location, ..)| (location.source_id, location.span));
+        items
+            .into_iter()
+            .map(|(location, item, kind)| (item, location, kind))
+    }
+
+    /// Capture everything that was indexed from the given source, so that it
+    /// can be restored with [Query::restore_source_index] in a later
+    /// compilation without indexing the source again.
+    ///
+    /// This must be called after the worker has run, but before anything has
+    /// been built since building consumes indexed entries.
+    pub(crate) fn source_index(&self, source_id: SourceId) -> SourceIndex {
+        let inner = self.inner.borrow();
+
+        let items = inner
+            .items
+            .iter()
+            .filter(|(_, item)| item.location.source_id == source_id)
+            .map(|(id, item)| (*id, item.clone()))
+            .collect();
+
+        let modules = inner
+            .modules
+            .values()
+            .filter(|m| m.location.source_id == source_id && m.parent.is_some())
+            .cloned()
+            .collect();
+
+        let indexed = inner
+            .indexed
+            .values()
+            .flat_map(|entries| entries.iter())
+            .filter(|e| e.item.location.source_id == source_id)
+            .cloned()
+            .collect();
+
+        let query_paths = inner
+            .query_paths
+            .iter()
+            .filter(|(_, p)| p.module.location.source_id == source_id)
+            .map(|(id, p)| (*id, p.clone()))
+            .collect();
+
+        let internal_macros = inner
+            .internal_macros
+            .iter()
+            .filter(|(_, (s, _))| *s == source_id)
+            .map(|(id, (_, m))| (*id, m.clone()))
+            .collect();
+
+        let allows = inner
+            .allows
+            .iter()
+            .filter(|a| a.location.source_id == source_id)
+            .cloned()
+            .collect();
+
+        let queue = inner
+            .queue
+            .iter()
+            .filter(|e| e.location.source_id == source_id)
+            .cloned()
+            .collect();
+
+        // NB: some items, like public functions, have their meta inserted
+        // directly while indexing.
+        let meta = inner
+            .meta
+            .values()
+            .filter(|m| matches!(&m.source, Some(s) if s.source_id == source_id))
+            .cloned()
+            .collect();
+
+        SourceIndex {
+            source_id,
+            meta,
+            items,
+            modules,
+            indexed,
+            query_paths,
+            internal_macros,
+            allows,
+            queue,
+        }
+    }
+
+    /// Restore a source index previously captured with
+    /// [Query::source_index].
+    pub(crate) fn restore_source_index(&self, index: &SourceIndex) -> Result<(), QueryError> {
+        for meta in &index.meta {
+            let span = meta.source.as_ref().map(|s| s.span).unwrap_or_default();
+            self.insert_meta(span, meta.clone())?;
+        }
+
+        let mut inner = self.inner.borrow_mut();
+
+        for (id, item) in &index.items {
+            inner.items.insert(*id, item.clone());
+        }
+
+        for module in &index.modules {
+            inner.insert_name(&module.item);
+            inner.modules.insert(module.item.clone(), module.clone());
+        }
+
+        for entry in &index.indexed {
+            inner.index(entry.clone());
+        }
+
+        for (id, query_path) in &index.query_paths {
+            inner.query_paths.insert(*id, query_path.clone());
+        }
+
+        for (id, internal_macro) in &index.internal_macros {
+            inner
+                .internal_macros
+                .insert(*id, (index.source_id, internal_macro.clone()));
+        }
+
+        inner.allows.extend(index.allows.iter().cloned());
+        inner.queue.extend(index.queue.iter().cloned());
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -640,8 +890,11 @@ struct QueryInner {
     const_fns: HashMap<Id, Arc<QueryConstFn>>,
     /// Query paths.
     query_paths: HashMap<Id, Arc<QueryPath>>,
-    /// The result of internally resolved macros.
-    internal_macros: HashMap<Id, Arc<BuiltInMacro>>,
+    /// The result of internally resolved macros, and the source they were
+    /// expanded in.
+    internal_macros: HashMap<Id, (SourceId, Arc<BuiltInMacro>)>,
+    /// Lints allowed through `#[allow(..)]` attributes.
+    allows: Vec<Arc<QueryAllow>>,
     /// Associated between `id` and `Item`. Use to look up items through
     /// `item_for` with an opaque id.
     ///
@@ -652,6 +905,8 @@ struct QueryInner {
     names: Names,
     /// Modules and associated metadata.
     modules: HashMap<Item, Arc<CompileMod>>,
+    /// The number of steps constant evaluation is allowed to take.
+    const_budget: usize,
 }
 
 impl Default for QueryInner {
@@ -669,9 +924,11 @@ impl Default for QueryInner {
             const_fns: Default::default(),
             query_paths: Default::default(),
             internal_macros: Default::default(),
+            allows: Default::default(),
             items: Default::default(),
             names: Default::default(),
             modules: Default::default(),
+            const_budget: DEFAULT_CONST_BUDGET,
         }
     }
 }
@@ -694,6 +951,7 @@ impl QueryInner {
     ) -> Result<Arc<BuiltInMacro>, QueryError> {
         let internal_macro = id
             .and_then(|n| self.internal_macros.get(&n))
+            .map(|(_, internal_macro)| internal_macro)
             .ok_or_else(|| {
                 QueryError::new(
                     span,
@@ -769,10 +1027,12 @@ impl QueryInner {
     /// Insert a new expanded internal macro.
     pub(crate) fn insert_new_builtin_macro(
         &mut self,
+        source_id: SourceId,
         internal_macro: BuiltInMacro,
     ) -> Result<Id, QueryError> {
         let id = self.gen.next();
-        self.internal_macros.insert(id, Arc::new(internal_macro));
+        self.internal_macros
+            .insert(id, (source_id, Arc::new(internal_macro)));
         Ok(id)
     }
 
@@ -793,6 +1053,7 @@ impl QueryInner {
         span: Span,
         item: Arc<CompileItem>,
         source: Arc<Source>,
+        docs: Option<Box<str>>,
         indexed: Indexed,
         used: Used,
     ) -> Result<(), QueryError> {
@@ -802,6 +1063,7 @@ impl QueryInner {
         let entry = IndexedEntry {
             item,
             source,
+            docs,
             indexed,
         };
 
@@ -912,7 +1174,7 @@ impl QueryInner {
         let import = match entry.indexed {
             Indexed::Import(import) => import.entry,
             indexed => {
-                self.import_indexed(span, entry.item, entry.source, indexed, used)?;
+                self.import_indexed(span, entry.item, entry.source, entry.docs, indexed, used)?;
                 return Ok(None);
             }
         };
@@ -925,6 +1187,7 @@ impl QueryInner {
                 target: import.target.clone(),
             },
             source: None,
+            docs: None,
         };
 
         self.insert_meta(span, meta)?;
@@ -1203,13 +1466,15 @@ impl QueryInner {
             item: query_item,
             indexed,
             source,
+            docs,
         } = entry;
 
         let path = source.path().map(ToOwned::to_owned);
 
         let kind = match indexed {
-            Indexed::Enum => CompileMetaKind::Enum {
+            Indexed::Enum(e) => CompileMetaKind::Enum {
                 type_hash: Hash::type_hash(&query_item.item),
+                variants: e.variants.into(),
             },
             Indexed::Variant(variant) => {
                 let enum_item = self.item_for(query_item.location.span, Some(variant.enum_id))?;
@@ -1229,6 +1494,10 @@ impl QueryInner {
                 struct_into_item_decl(&query_item.item, st.ast.body, None, &self.storage, &*source)?
             }
             Indexed::Function(f) => {
+                let args = f.ast.arity();
+                let defaults = f.ast.defaults();
+                let rest = f.ast.has_rest();
+
                 self.queue.push_back(BuildEntry {
                     location: query_item.location,
                     item: query_item.clone(),
@@ -1240,6 +1509,10 @@ impl QueryInner {
                 CompileMetaKind::Function {
                     type_hash: Hash::type_hash(&query_item.item),
                     is_test: false,
+                    args: Some(args),
+                    defaults,
+                    rest,
+                    call_site: None,
                 }
             }
             Indexed::Closure(c) => {
@@ -1280,7 +1553,7 @@ impl QueryInner {
             }
             Indexed::Const(c) => {
                 let mut const_compiler = IrInterpreter {
-                    budget: IrBudget::new(1_000_000),
+                    budget: IrBudget::new(self.const_budget),
                     scopes: Default::default(),
                     module: c.module.clone(),
                     item: query_item.item.clone(),
@@ -1358,6 +1631,7 @@ impl QueryInner {
             item: query_item,
             kind,
             source: Some(source),
+            docs,
         })
     }
 
@@ -1456,9 +1730,58 @@ impl Default for Used {
     }
 }
 
+/// The kind of a named item, as passed to [CompileVisitor::visit_item].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    /// A function, including instance functions.
+    Function,
+    /// A constant function.
+    ConstFn,
+    /// A constant.
+    Const,
+    /// A struct.
+    Struct,
+    /// An enum.
+    Enum,
+    /// A variant of an enum.
+    Variant,
+}
+
+impl ItemKind {
+    /// Get the kind of the given indexed entry, if it's a named item.
+    fn from_indexed(indexed: &Indexed) -> Option<Self> {
+        Some(match indexed {
+            Indexed::Function(..) => Self::Function,
+            Indexed::ConstFn(..) => Self::ConstFn,
+            Indexed::Const(..) => Self::Const,
+            Indexed::Struct(..) => Self::Struct,
+            Indexed::Enum(..) => Self::Enum,
+            Indexed::Variant(..) => Self::Variant,
+            Indexed::Closure(..) | Indexed::AsyncBlock(..) | Indexed::Import(..) => return None,
+        })
+    }
+
+    /// Get the kind of the given compile meta, if it's a named item.
+    fn from_meta_kind(kind: &CompileMetaKind) -> Option<Self> {
+        Some(match kind {
+            CompileMetaKind::Function { .. } => Self::Function,
+            CompileMetaKind::Const { .. } => Self::Const,
+            CompileMetaKind::ConstFn { .. } => Self::ConstFn,
+            CompileMetaKind::UnitStruct { .. }
+            | CompileMetaKind::TupleStruct { .. }
+            | CompileMetaKind::Struct { .. } => Self::Struct,
+            CompileMetaKind::Enum { .. } => Self::Enum,
+            CompileMetaKind::UnitVariant { .. }
+            | CompileMetaKind::TupleVariant { .. }
+            | CompileMetaKind::StructVariant { .. } => Self::Variant,
+            _ => return None,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum Indexed {
-    Enum,
+    Enum(Enum),
     Struct(Struct),
     Variant(Variant),
     Function(Function),
@@ -1469,6 +1792,12 @@ pub(crate) enum Indexed {
     Import(Import),
 }
 
+#[derive(Debug, Clone)]
+pub struct Enum {
+    /// The items of all variants of the enum.
+    pub(crate) variants: Vec<Item>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Import {
     /// The import entry.
@@ -1598,6 +1927,8 @@ pub(crate) struct IndexedEntry {
     pub(crate) item: Arc<CompileItem>,
     /// The source of the indexed entry.
     pub(crate) source: Arc<Source>,
+    /// The doc comments of the entry.
+    pub(crate) docs: Option<Box<str>>,
     /// The entry data.
     pub(crate) indexed: Indexed,
 }
@@ -1612,6 +1943,15 @@ impl IndexedEntry {
     }
 }
 
+/// Lints allowed through an `#[allow(..)]` attribute.
+#[derive(Debug)]
+pub(crate) struct QueryAllow {
+    /// The location of the item or block the attribute belongs to.
+    pub(crate) location: Location,
+    /// The names of the allowed lints.
+    pub(crate) lints: Vec<Box<str>>,
+}
+
 /// Query information for a path.
 #[derive(Debug)]
 pub(crate) struct QueryPath {
@@ -1620,6 +1960,59 @@ pub(crate) struct QueryPath {
     pub(crate) item: Item,
 }
 
+/// Everything that was indexed from a single source.
+///
+/// Captured with [Query::source_index] and restored with
+/// [Query::restore_source_index].
+#[derive(Clone)]
+pub(crate) struct SourceIndex {
+    /// The source the index was captured from.
+    source_id: SourceId,
+    /// Meta inserted while indexing the source.
+    meta: Vec<CompileMeta>,
+    /// Items declared in the source.
+    items: Vec<(Id, Arc<CompileItem>)>,
+    /// Modules declared in the source, excluding the root module.
+    modules: Vec<Arc<CompileMod>>,
+    /// Indexed entries, which have not been built yet.
+    indexed: Vec<IndexedEntry>,
+    /// Query paths for AST elements in the source.
+    query_paths: Vec<(Id, Arc<QueryPath>)>,
+    /// Built-in macros expanded in the source.
+    internal_macros: Vec<(Id, Arc<BuiltInMacro>)>,
+    /// Lints allowed in the source.
+    allows: Vec<Arc<QueryAllow>>,
+    /// Build entries queued up while indexing.
+    queue: Vec<BuildEntry>,
+}
+
+impl SourceIndex {
+    /// Iterate over every item this source provides to other sources.
+    pub(crate) fn declared_items(&self) -> impl Iterator<Item = &Item> {
+        self.modules
+            .iter()
+            .map(|m| &m.item)
+            .chain(self.meta.iter().map(|m| &m.item.item))
+            .chain(self.indexed.iter().map(|e| &e.item.item))
+    }
+
+    /// Iterate over the targets of all imports in this source.
+    pub(crate) fn import_targets(&self) -> impl Iterator<Item = &Item> {
+        self.indexed.iter().filter_map(|e| match &e.indexed {
+            Indexed::Import(import) => Some(&import.entry.target),
+            _ => None,
+        })
+    }
+
+    /// Test if this source contains wildcard imports, which are expanded based
+    /// on the contents of other sources.
+    pub(crate) fn has_wildcard_imports(&self) -> bool {
+        self.indexed
+            .iter()
+            .any(|e| matches!(&e.indexed, Indexed::Import(import) if import.wildcard))
+    }
+}
+
 /// An indexed constant function.
 #[derive(Debug)]
 pub(crate) struct QueryConstFn {
@@ -1702,14 +2095,28 @@ fn struct_body_meta(
 ) -> Result<CompileMetaKind, QueryError> {
     let type_hash = Hash::type_hash(item);
 
-    let mut fields = HashSet::new();
+    let mut fields = HashMap::<Box<str>, Span>::new();
 
     for (ast::Field { name, .. }, _) in st {
-        let name = name.resolve(storage, &*source)?;
-        fields.insert(name.into());
+        // NB: pin resolve errors to the name of the offending field.
+        let field = name
+            .resolve(storage, &*source)
+            .map_err(|error| QueryError::new(name.span(), error.into_kind()))?;
+
+        if let Some(existing_span) = fields.insert(field.as_ref().into(), name.span()) {
+            return Err(QueryError::new(
+                name.span(),
+                QueryErrorKind::FieldConflict {
+                    field: field.as_ref().into(),
+                    existing_span,
+                },
+            ));
+        }
     }
 
-    let object = CompileMetaStruct { fields };
+    let object = CompileMetaStruct {
+        fields: fields.into_iter().map(|(field, _)| field).collect(),
+    };
 
     Ok(match enum_item {
         Some(enum_item) => CompileMetaKind::StructVariant {
@@ -1777,3 +2184,165 @@ struct ImportStep {
 fn into_chain(chain: Vec<ImportEntryStep>) -> Vec<Location> {
     chain.into_iter().map(|c| c.location).collect()
 }
+
+/// Calculate the number of single character insertions, deletions or
+/// substitutions needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = usize::min(substitution, usize::min(row[j], row[j + 1]) + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Test if the given entries are multiple wildcard imports of the same name,
+/// which is only an error if the name is used.
+fn is_ambiguous_wildcard(entries: &[IndexedEntry]) -> bool {
+    entries.len() > 1
+        && entries
+            .iter()
+            .all(|e| matches!(&e.indexed, Indexed::Import(import) if import.wildcard))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, ItemKind, QueryErrorKind};
+    use crate::shared::{Consts, Gen};
+    use crate::worker::{LoadFileKind, Task, Worker};
+    use crate::{
+        ast, Diagnostics, FileSourceLoader, NoopCompileVisitor, Options, ResolveErrorKind, Sources,
+        Spanned as _, Storage, UnitBuilder,
+    };
+    use runestick::{Context, Item, Source, Span};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_items() {
+        let source = r#"
+        struct Point { x, y }
+        const LIMIT = 10;
+        fn add(a, b) { a + b }
+        pub fn main() { add(1, 2) }
+        "#;
+
+        let context = Context::with_default_modules().unwrap();
+        let options = Options::default();
+        let mut diagnostics = Diagnostics::new();
+        let mut sources = Sources::new();
+        let source_id = sources.insert(Source::new("main", source));
+
+        let mut worker = Worker::new(
+            &context,
+            &mut sources,
+            &options,
+            UnitBuilder::default(),
+            Consts::default(),
+            &mut diagnostics,
+            Rc::new(NoopCompileVisitor::new()),
+            Rc::new(FileSourceLoader::new()),
+            Storage::new(),
+            Gen::new(),
+        );
+
+        let mod_item = worker
+            .query
+            .insert_root_mod(source_id, Span::empty())
+            .unwrap();
+
+        worker.queue.push_back(Task::LoadFile {
+            kind: LoadFileKind::Root,
+            source_id,
+            mod_item,
+        });
+
+        worker.run();
+        assert!(!worker.diagnostics.has_error());
+
+        let expected = vec![
+            (
+                Item::with_item(&["Point"]),
+                Span::new(9, 30),
+                ItemKind::Struct,
+            ),
+            (
+                Item::with_item(&["LIMIT"]),
+                Span::new(39, 55),
+                ItemKind::Const,
+            ),
+            (
+                Item::with_item(&["add"]),
+                Span::new(65, 87),
+                ItemKind::Function,
+            ),
+            (
+                Item::with_item(&["main"]),
+                Span::new(96, 123),
+                ItemKind::Function,
+            ),
+        ];
+
+        let items = || {
+            worker
+                .query
+                .items()
+                .map(|(item, location, kind)| (item, location.span, kind))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(items(), expected);
+        // Listing items doesn't consume anything.
+        assert_eq!(items(), expected);
+
+        let entry = worker.query.next_build_entry().unwrap();
+        assert_eq!(entry.item.item, Item::with_item(&["main"]));
+        assert!(worker.query.next_build_entry().is_none());
+    }
+
+    #[test]
+    fn test_struct_field_resolve_span() {
+        let source = Source::new("test", "struct Foo { a, b }");
+        let mut st = crate::parse_all::<ast::ItemStruct>(source.as_str()).unwrap();
+
+        let body = match st.body {
+            ast::ItemStructBody::StructBody(ref mut body) => body,
+            _ => panic!("expected struct body"),
+        };
+
+        // NB: a synthetic identifier which is missing from storage.
+        let (field, _) = body.iter_mut().nth(1).unwrap();
+        field.name.source = ast::StringSource::Synthetic(42);
+        let span = field.name.span();
+
+        let item = Item::with_item(&["Foo"]);
+        let error = super::struct_body_meta(&item, None, &Storage::new(), &source, body.clone())
+            .unwrap_err();
+
+        assert_eq!(span, Span::new(16, 17));
+        assert_eq!(error.span(), span);
+        assert!(matches!(
+            error.kind(),
+            QueryErrorKind::ResolveError {
+                error: ResolveErrorKind::BadSyntheticId { id: 42, .. }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("foo", "foo"), 0);
+        assert_eq!(edit_distance("fooo", "foo"), 1);
+        assert_eq!(edit_distance("bar", "baz"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
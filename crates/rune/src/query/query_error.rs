@@ -2,7 +2,7 @@ use crate::compiling::{ImportEntryStep, InsertMetaError};
 use crate::{
     Id, IrError, IrErrorKind, ParseError, ParseErrorKind, ResolveError, ResolveErrorKind, Spanned,
 };
-use runestick::{CompileMeta, Item, Location, Visibility};
+use runestick::{CompileMeta, Item, Location, Span, Visibility};
 use thiserror::Error;
 
 error! {
@@ -51,6 +51,11 @@ pub enum QueryErrorKind {
     MissingId { what: &'static str, id: Option<Id> },
     #[error("cannot define conflicting item `{item}`")]
     ItemConflict { item: Item, other: Location },
+    #[error("field `{field}` is already declared")]
+    FieldConflict {
+        field: Box<str>,
+        existing_span: Span,
+    },
     #[error("`{item}` can refer to multiple things")]
     AmbiguousItem {
         item: Item,
@@ -2,10 +2,12 @@
 
 use crate::ast;
 use crate::collections::HashMap;
-use crate::indexing::{Index as _, IndexScopes, Indexer};
+use crate::indexing::{DocComments, Index as _, IndexScopes, Indexer};
 use crate::query::Query;
 use crate::shared::{Consts, Gen, Items};
-use crate::{CompileVisitor, Diagnostics, Options, SourceLoader, Sources, Storage, UnitBuilder};
+use crate::{
+    CompileVisitor, Diagnostics, Options, Parser, SourceLoader, Sources, Storage, UnitBuilder,
+};
 use runestick::{Context, Item, SourceId, Span};
 use std::collections::VecDeque;
 use std::rc::Rc;
@@ -62,7 +64,14 @@ impl<'a> Worker<'a> {
             source_loader,
             consts: consts.clone(),
             queue: VecDeque::new(),
-            query: Query::new(visitor, storage.clone(), unit, consts, gen.clone()),
+            query: Query::new(
+                visitor,
+                storage.clone(),
+                unit,
+                consts,
+                gen.clone(),
+                options.const_budget,
+            ),
             storage,
             gen,
             loaded: HashMap::new(),
@@ -93,7 +102,9 @@ impl<'a> Worker<'a> {
                         }
                     };
 
-                    let mut file = match crate::parse_all::<ast::File>(source.as_str()) {
+                    let mut parser = Parser::new(source.as_str());
+
+                    let mut file = match parser.parse_all::<ast::File>() {
                         Ok(file) => file,
                         Err(error) => {
                             self.diagnostics.error(source_id, error);
@@ -101,6 +112,8 @@ impl<'a> Worker<'a> {
                         }
                     };
 
+                    let doc_comments = DocComments::new(parser.take_doc_comments());
+
                     let root = match kind {
                         LoadFileKind::Root => source.path().map(ToOwned::to_owned),
                         LoadFileKind::Module { root } => root,
@@ -129,10 +142,18 @@ impl<'a> Worker<'a> {
                         visitor: self.visitor.clone(),
                         source_loader: self.source_loader.clone(),
                         nested_item: None,
+                        doc_comments,
                     };
 
-                    if let Err(error) = file.index(&mut indexer) {
-                        indexer.diagnostics.error(source_id, error);
+                    match file.index(&mut indexer) {
+                        Ok(()) => {
+                            for span in indexer.doc_comments.unused() {
+                                indexer.diagnostics.unused_doc_comment(source_id, span);
+                            }
+                        }
+                        Err(error) => {
+                            indexer.diagnostics.error(source_id, error);
+                        }
                     }
                 }
                 Task::ExpandImport(import) => {
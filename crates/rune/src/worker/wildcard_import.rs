@@ -64,7 +64,10 @@ impl WildcardImport {
         if !self.found {
             return Err(CompileError::new(
                 self.span,
-                CompileErrorKind::MissingItem { item: self.name },
+                CompileErrorKind::MissingItem {
+                    item: self.name,
+                    suggestion: None,
+                },
             ));
         }
 
@@ -76,7 +76,37 @@ impl Derive {
 
         let tokens = ctx.tokens_with_module(&attrs);
 
-        let generics = &self.input.generics;
+        let mut generics = self.input.generics.clone();
+
+        // NB: lifetimes can't be supported, since types need to be `'static`
+        // to have a stable type hash.
+        if let Some(lifetime) = generics.lifetimes().next() {
+            return Err(vec![syn::Error::new_spanned(
+                lifetime,
+                "`Any` can't be derived for types with lifetime parameters",
+            )]);
+        }
+
+        let type_params = generics
+            .type_params()
+            .map(|param| param.ident.clone())
+            .collect::<Vec<_>>();
+
+        if !type_params.is_empty() {
+            let named = &tokens.named;
+            let where_clause = generics.make_where_clause();
+
+            // Every parameter participates in the type hash and the type
+            // name, so each monomorphization is registered as a distinct
+            // type.
+            for param in type_params {
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#param: 'static + #named));
+            }
+        }
+
+        let generics = &generics;
         let install_with = match ctx.expand_install_with(&self.input, &tokens, &attrs, generics) {
             Some(install_with) => install_with,
             None => return Err(ctx.errors),
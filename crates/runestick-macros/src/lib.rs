@@ -100,6 +100,29 @@ pub fn to_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 ///     Ok(module)
 /// }
 /// ```
+///
+/// ## Generic types
+///
+/// Generic types are supported, and each type parameter is given a
+/// `T: 'static + Named` bound. Every instantiation of the type is a distinct
+/// type with its own hash which has to be registered separately. Types with
+/// lifetime parameters are not supported.
+///
+/// ```rust
+/// use runestick::Any;
+///
+/// #[derive(Any)]
+/// struct Wrapper<T> {
+///     inner: T,
+/// }
+///
+/// fn install() -> Result<runestick::Module, runestick::ContextError> {
+///     let mut module = runestick::Module::new();
+///     module.ty::<Wrapper<i64>>()?;
+///     module.ty::<Wrapper<String>>()?;
+///     Ok(module)
+/// }
+/// ```
 #[proc_macro_derive(Any, attributes(rune))]
 pub fn any(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let derive = syn::parse_macro_input!(input as any::Derive);
@@ -0,0 +1,124 @@
+//! A borrowed view into bytes owned by another value.
+
+use crate::{Bytes, Mut, Ref, VmError, VmErrorKind};
+use std::fmt;
+
+/// A view into a sequence of bytes which is owned by another value.
+///
+/// This allows native functions to return a slice borrowed from one of their
+/// arguments without copying it. The view holds onto the access guard of the
+/// value it was created from, which keeps that value alive and prevents it
+/// from being moved or borrowed in an incompatible way for as long as the
+/// view is live.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{Any, BytesView, Module, Ref};
+///
+/// #[derive(Any)]
+/// struct Buffer {
+///     data: Vec<u8>,
+/// }
+///
+/// fn view(this: Ref<Buffer>) -> BytesView {
+///     BytesView::from(Ref::map(this, |buffer| &buffer.data[..]))
+/// }
+///
+/// # fn main() -> runestick::Result<()> {
+/// let mut module = Module::new();
+/// module.ty::<Buffer>()?;
+/// module.inst_fn("view", view)?;
+/// # Ok(()) }
+/// ```
+pub struct BytesView {
+    inner: Inner,
+}
+
+enum Inner {
+    Ref(Ref<[u8]>),
+    Mut(Mut<[u8]>),
+}
+
+impl BytesView {
+    /// Access the viewed bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.inner {
+            Inner::Ref(bytes) => bytes,
+            Inner::Mut(bytes) => bytes,
+        }
+    }
+
+    /// Get the number of bytes in the view.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Test if the view is empty.
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Test if the view permits modifying the bytes it refers to.
+    pub fn is_mutable(&self) -> bool {
+        matches!(self.inner, Inner::Mut(..))
+    }
+
+    /// Get the byte at the given index.
+    pub fn get(&self, index: usize) -> Option<u8> {
+        self.as_slice().get(index).copied()
+    }
+
+    /// Set the byte at the given index.
+    ///
+    /// This errors if the view was not created from a mutable borrow, or if
+    /// the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: u8) -> Result<(), VmError> {
+        let bytes = match &mut self.inner {
+            Inner::Mut(bytes) => bytes,
+            Inner::Ref(..) => return Err(VmError::panic("bytes view is not mutable")),
+        };
+
+        let len = bytes.len();
+
+        match bytes.get_mut(index) {
+            Some(byte) => {
+                *byte = value;
+                Ok(())
+            }
+            None => Err(VmError::from(VmErrorKind::OutOfRange {
+                index: index.into(),
+                len: len.into(),
+            })),
+        }
+    }
+
+    /// Copy the viewed bytes into an owned bytes container.
+    pub fn to_bytes(&self) -> Bytes {
+        Bytes::from_vec(self.as_slice().to_vec())
+    }
+}
+
+impl From<Ref<[u8]>> for BytesView {
+    fn from(bytes: Ref<[u8]>) -> Self {
+        Self {
+            inner: Inner::Ref(bytes),
+        }
+    }
+}
+
+impl From<Mut<[u8]>> for BytesView {
+    fn from(bytes: Mut<[u8]>) -> Self {
+        Self {
+            inner: Inner::Mut(bytes),
+        }
+    }
+}
+
+impl fmt::Debug for BytesView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+crate::__internal_impl_any!(BytesView);
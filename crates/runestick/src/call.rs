@@ -5,7 +5,7 @@ use std::fmt;
 /// How the function is called.
 ///
 /// Async functions create a sub-context and immediately return futures.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Call {
     /// Function is `async` and returns a future that must be await:ed to make
     /// progress.
@@ -0,0 +1,254 @@
+//! Time sources which can be used by scripts.
+//!
+//! See [Context::set_clock][crate::Context::set_clock].
+
+use std::cmp;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A boxed future returned by [Clock::sleep].
+pub type ClockFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A source of time used by the `std::time` module.
+pub trait Clock: Send + Sync {
+    /// Get the current time, as a duration since the epoch of the clock.
+    fn now(&self) -> Duration;
+
+    /// Construct a future which completes once the given duration has passed
+    /// according to this clock.
+    fn sleep(&self, duration: Duration) -> ClockFuture;
+}
+
+/// The default clock, backed by the system time.
+///
+/// Sleeping is implemented using a single background timer thread which is
+/// shared by all sleeps, so it doesn't depend on any particular executor.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+
+    fn sleep(&self, duration: Duration) -> ClockFuture {
+        let state = Arc::new(Mutex::new(SleepState::default()));
+
+        // NB: a deadline which can't be represented never expires.
+        if let Some(deadline) = Instant::now().checked_add(duration) {
+            TIMER.push(deadline, state.clone());
+        }
+
+        Box::pin(SystemSleep { state })
+    }
+}
+
+lazy_static::lazy_static! {
+    /// The timer shared by all sleeps of the [SystemClock].
+    static ref TIMER: Timer = Timer::spawn();
+}
+
+/// A timer which wakes up sleeps from a background thread once their
+/// deadlines have passed.
+struct Timer {
+    shared: Arc<(Mutex<BinaryHeap<TimerEntry>>, Condvar)>,
+}
+
+impl Timer {
+    /// Spawn the background thread of the timer.
+    fn spawn() -> Self {
+        let shared = Arc::new((Mutex::new(BinaryHeap::new()), Condvar::new()));
+        let thread_shared = shared.clone();
+
+        thread::Builder::new()
+            .name(String::from("runestick-timer"))
+            .spawn(move || Self::run(&thread_shared))
+            .expect("failed to spawn timer thread");
+
+        Self { shared }
+    }
+
+    /// Register a sleep which is woken up at the given deadline.
+    fn push(&self, deadline: Instant, state: Arc<Mutex<SleepState>>) {
+        let (queue, condvar) = &*self.shared;
+        queue.lock().unwrap().push(TimerEntry { deadline, state });
+        condvar.notify_one();
+    }
+
+    fn run(shared: &(Mutex<BinaryHeap<TimerEntry>>, Condvar)) {
+        let (queue, condvar) = shared;
+        let mut queue = queue.lock().unwrap();
+
+        loop {
+            let now = Instant::now();
+
+            let deadline = match queue.peek() {
+                Some(entry) => entry.deadline,
+                None => {
+                    queue = condvar.wait(queue).unwrap();
+                    continue;
+                }
+            };
+
+            if deadline > now {
+                queue = condvar.wait_timeout(queue, deadline - now).unwrap().0;
+                continue;
+            }
+
+            if let Some(entry) = queue.pop() {
+                let mut state = entry.state.lock().unwrap();
+                state.done = true;
+
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+/// A sleep waiting in the timer, ordered so that the earliest deadline is at
+/// the top of the heap.
+struct TimerEntry {
+    deadline: Instant,
+    state: Arc<Mutex<SleepState>>,
+}
+
+impl PartialEq for TimerEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for TimerEntry {}
+
+impl PartialOrd for TimerEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimerEntry {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        other.deadline.cmp(&self.deadline)
+    }
+}
+
+#[derive(Default)]
+struct SleepState {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+struct SystemSleep {
+    state: Arc<Mutex<SleepState>>,
+}
+
+impl Future for SystemSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.done {
+            return Poll::Ready(());
+        }
+
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// A clock which is controlled by the host.
+///
+/// Time only moves forward when [MockClock::advance] is called, at which point
+/// any sleeps which have expired are woken up. Clones of the clock share the
+/// same time, so the host can keep a handle to it after installing it.
+///
+/// ```rust
+/// use runestick::{Clock, MockClock};
+/// use std::time::Duration;
+///
+/// let clock = MockClock::new();
+/// clock.advance(Duration::from_secs(10));
+/// assert_eq!(clock.now(), Duration::from_secs(10));
+/// ```
+#[derive(Default, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    now: Duration,
+    wakers: Vec<Waker>,
+}
+
+impl MockClock {
+    /// Construct a new mock clock, starting at a time of zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance the clock by the given duration, waking up any sleeps which
+    /// are waiting for it.
+    pub fn advance(&self, duration: Duration) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap();
+            state.now += duration;
+            std::mem::take(&mut state.wakers)
+        };
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        self.state.lock().unwrap().now
+    }
+
+    fn sleep(&self, duration: Duration) -> ClockFuture {
+        let state = self.state.clone();
+        let deadline = state.lock().unwrap().now + duration;
+        Box::pin(MockSleep { state, deadline })
+    }
+}
+
+impl fmt::Debug for MockClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockClock")
+            .field("now", &self.now())
+            .finish()
+    }
+}
+
+struct MockSleep {
+    state: Arc<Mutex<MockState>>,
+    deadline: Duration,
+}
+
+impl Future for MockSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.now >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        state.wakers.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
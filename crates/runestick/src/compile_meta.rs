@@ -20,6 +20,9 @@ pub struct CompileMeta {
     pub kind: CompileMetaKind,
     /// The source of the meta.
     pub source: Option<CompileSource>,
+    /// The doc comments of the item, with consecutive `///` lines joined by
+    /// newlines.
+    pub docs: Option<Box<str>>,
 }
 
 /// Information on a compile sourc.
@@ -161,6 +164,8 @@ pub enum CompileMetaKind {
     Enum {
         /// The type hash associated with this meta kind.
         type_hash: Hash,
+        /// The items of all variants of the enum.
+        variants: Arc<[Item]>,
     },
     /// A function declaration.
     Function {
@@ -169,6 +174,22 @@ pub enum CompileMetaKind {
 
         /// Whether this function has a test annotation
         is_test: bool,
+
+        /// The number of arguments the function takes, if known.
+        args: Option<usize>,
+
+        /// The number of trailing arguments which have default values, and
+        /// can be omitted when calling the function.
+        defaults: usize,
+
+        /// Whether the function collects any arguments past `args` into a
+        /// vector.
+        rest: bool,
+
+        /// The hash of the variant of the function used for direct calls,
+        /// where each argument is preceded by a string describing its call
+        /// site.
+        call_site: Option<Hash>,
     },
     /// A closure.
     Closure {
@@ -1,16 +1,22 @@
 use crate::{
     collections::{HashMap, HashSet},
     module::{
-        ModuleAssociatedFn, ModuleFn, ModuleInternalEnum, ModuleMacro, ModuleType, ModuleUnitType,
+        ModuleAssociatedFn, ModuleAssociatedKind, ModuleFn, ModuleInternalEnum, ModuleMacro,
+        ModuleType, ModuleUnitType,
     },
-    CompileMeta, CompileMetaKind, CompileMetaStruct, CompileMetaTuple, ComponentRef, ConstValue,
-    Hash, IntoComponent, Item, Module, Names, Protocol, RuntimeContext, Stack, StaticType,
-    TypeCheck, TypeInfo, TypeOf, VmError,
+    Clock, CompileMeta, CompileMetaKind, CompileMetaStruct, CompileMetaTuple, ComponentRef,
+    ConstValue, Hash, IntoComponent, Item, Module, Names, Protocol, RuntimeContext, Stack,
+    StaticType, TypeCheck, TypeInfo, TypeOf, VmError,
 };
+use std::collections::BTreeSet;
 use std::{any, fmt, sync::Arc};
 
 use thiserror::Error;
 
+/// The name under which the call site variant of a function is registered, as
+/// an instance function of the function itself.
+const CALL_SITE: &str = "call_site";
+
 /// An error raised when building the context.
 #[derive(Debug, Error)]
 pub enum ContextError {
@@ -83,6 +89,16 @@ pub enum ContextError {
         /// The hash that already existed.
         existing: Hash,
     },
+    /// Raised when two distinct items hash to the same value.
+    #[error("item `{item}` has the same hash `{hash}` as `{existing}`")]
+    ConflictingItemHash {
+        /// The item we tried to install.
+        item: Item,
+        /// The item which was already installed with the same hash.
+        existing: Item,
+        /// The conflicting hash.
+        hash: Hash,
+    },
     /// Error raised when attempting to register a conflicting function.
     #[error("variant with `{item}` already exists")]
     ConflictingVariant {
@@ -240,6 +256,14 @@ pub struct Context {
     crates: HashSet<Box<str>>,
     /// Constants visible in this context
     constants: HashMap<Hash, ConstValue>,
+    /// The clock used by the `std::time` module, if overridden.
+    clock: Option<Arc<dyn Clock>>,
+    /// Names of fields which have getters registered, by type hash.
+    fields: HashMap<Hash, BTreeSet<String>>,
+    /// Type hashes of installed items, computed once during installation.
+    item_hashes: HashMap<Item, Hash>,
+    /// The set of installed item hashes, used to detect hash collisions.
+    hashes: HashSet<Hash>,
 }
 
 impl Context {
@@ -254,12 +278,15 @@ impl Context {
     ///   stdout and stderr by default, like `dbg`, `print`, and `println`.
     pub fn with_config(stdio: bool) -> Result<Self, ContextError> {
         let mut this = Self::new();
+        // NB: the core module registers the primitive types, which other
+        // modules add instance functions to.
+        this.install(&crate::modules::core::module()?)?;
         this.install(&crate::modules::any::module()?)?;
         this.install(&crate::modules::bytes::module()?)?;
         this.install(&crate::modules::char::module()?)?;
+        this.install(&crate::modules::clone::module()?)?;
         this.install(&crate::modules::cmp::module()?)?;
         this.install(&crate::modules::collections::module()?)?;
-        this.install(&crate::modules::core::module()?)?;
         this.install(&crate::modules::float::module()?)?;
         this.install(&crate::modules::fmt::module()?)?;
         this.install(&crate::modules::future::module()?)?;
@@ -274,6 +301,7 @@ impl Context {
         this.install(&crate::modules::result::module()?)?;
         this.install(&crate::modules::stream::module()?)?;
         this.install(&crate::modules::string::module()?)?;
+        this.install(&crate::modules::time::module()?)?;
         this.install(&crate::modules::vec::module()?)?;
         this.has_default_modules = true;
         Ok(this)
@@ -299,15 +327,36 @@ impl Context {
             functions: self.functions.clone(),
             types: self.types.iter().map(|(k, t)| (*k, t.type_check)).collect(),
             constants: self.constants.clone(),
+            clock: self.clock.clone(),
+            fields: self.fields.clone(),
         }
     }
 
+    /// Set the clock used by the `std::time` module.
+    ///
+    /// By default this is the [SystemClock][crate::SystemClock]. Installing a
+    /// [MockClock][crate::MockClock] allows the host to control the passage of
+    /// time, which is useful for testing time-dependent scripts.
+    ///
+    /// This only affects runtime contexts constructed after it's been called.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = Some(Arc::from(clock));
+    }
+
     /// Use the specified type check.
     pub fn type_check_for(&self, item: &Item) -> Option<TypeCheck> {
         let ty = self.types.get(&Hash::type_hash(item))?;
         Some(ty.type_check)
     }
 
+    /// Get the cached type hash of an installed item.
+    ///
+    /// This is the same as [Hash::type_hash] for the item, but avoids
+    /// recomputing it. Returns `None` if no such item has been installed.
+    pub fn lookup_item_hash(&self, item: &Item) -> Option<Hash> {
+        self.item_hashes.get(item).copied()
+    }
+
     /// Construct a new collection of functions with default packages installed.
     pub fn with_default_modules() -> Result<Self, ContextError> {
         Self::with_config(true)
@@ -368,6 +417,16 @@ impl Context {
         self.meta.get(name).cloned()
     }
 
+    /// Iterate over all constants registered in the context.
+    pub fn iter_constants(&self) -> impl Iterator<Item = (&Item, &ConstValue)> {
+        self.meta
+            .iter()
+            .filter_map(|(item, meta)| match &meta.kind {
+                CompileMetaKind::Const { const_value } => Some((item, const_value)),
+                _ => None,
+            })
+    }
+
     /// Iterate over all available functions
     pub fn iter_functions(&self) -> impl Iterator<Item = (Hash, &ContextSignature)> {
         let mut it = self.functions_info.iter();
@@ -425,11 +484,49 @@ impl Context {
                 inst,
                 |instance_type, field| key.kind.hash(instance_type, field),
             )?;
+
+            if key.kind == ModuleAssociatedKind::FieldFn(Protocol::GET) {
+                self.fields
+                    .entry(key.type_hash)
+                    .or_default()
+                    .insert(inst.name.clone());
+            }
         }
 
         Ok(())
     }
 
+    /// Get the type hash of the given item.
+    ///
+    /// Errors if a distinct item already installed has the same hash.
+    fn item_hash(&mut self, item: &Item) -> Result<Hash, ContextError> {
+        if let Some(hash) = self.item_hashes.get(item) {
+            return Ok(*hash);
+        }
+
+        let hash = Hash::type_hash(item);
+
+        if !self.hashes.insert(hash) {
+            // NB: collisions are rare, so the existing item is only looked up
+            // when one happens.
+            let existing = self
+                .item_hashes
+                .iter()
+                .find(|(_, h)| **h == hash)
+                .map(|(existing, _)| existing.clone())
+                .unwrap_or_default();
+
+            return Err(ContextError::ConflictingItemHash {
+                item: item.clone(),
+                existing,
+                hash,
+            });
+        }
+
+        self.item_hashes.insert(item.clone(), hash);
+        Ok(hash)
+    }
+
     /// Install the given meta.
     fn install_meta(&mut self, meta: CompileMeta) -> Result<(), ContextError> {
         if let Some(existing) = self.meta.insert(meta.item.item.clone(), meta.clone()) {
@@ -450,7 +547,7 @@ impl Context {
         ty: &ModuleType,
     ) -> Result<(), ContextError> {
         let item = module.item.extended(&*ty.name);
-        let hash = Hash::type_hash(&item);
+        let hash = self.item_hash(&item)?;
 
         self.install_type_info(
             hash,
@@ -471,6 +568,7 @@ impl Context {
                 },
             },
             source: None,
+            docs: None,
         })?;
 
         Ok(())
@@ -509,7 +607,7 @@ impl Context {
         let item = module.item.join(item);
         self.names.insert(&item);
 
-        let hash = Hash::type_hash(&item);
+        let hash = self.item_hash(&item)?;
 
         let signature = ContextSignature::Function {
             type_hash: hash,
@@ -530,6 +628,16 @@ impl Context {
         );
 
         self.functions.insert(hash, f.handler.clone());
+
+        let call_site = match &f.call_site {
+            Some(handler) => {
+                let call_site = Hash::instance_function(hash, CALL_SITE);
+                self.functions.insert(call_site, handler.clone());
+                Some(call_site)
+            }
+            None => None,
+        };
+
         self.meta.insert(
             item.clone(),
             CompileMeta {
@@ -537,8 +645,13 @@ impl Context {
                 kind: CompileMetaKind::Function {
                     type_hash: hash,
                     is_test: false,
+                    args: f.args,
+                    defaults: 0,
+                    rest: false,
+                    call_site,
                 },
                 source: None,
+                docs: None,
             },
         );
 
@@ -556,7 +669,7 @@ impl Context {
 
         self.names.insert(&item);
 
-        let hash = Hash::type_hash(&item);
+        let hash = self.item_hash(&item)?;
 
         self.macros.insert(hash, m.handler.clone());
         Ok(())
@@ -573,7 +686,7 @@ impl Context {
 
         self.names.insert(&item);
 
-        let hash = Hash::type_hash(&item);
+        let hash = self.item_hash(&item)?;
 
         self.constants.insert(hash, v.clone());
 
@@ -585,6 +698,7 @@ impl Context {
                     const_value: v.clone(),
                 },
                 source: None,
+                docs: None,
             },
         );
         Ok(())
@@ -639,8 +753,13 @@ impl Context {
                 kind: CompileMetaKind::Function {
                     type_hash: hash,
                     is_test: false,
+                    args: assoc.args,
+                    defaults: 0,
+                    rest: false,
+                    call_site: None,
                 },
                 source: None,
+                docs: None,
             },
         );
 
@@ -659,8 +778,8 @@ impl Context {
         }
 
         let item = module.item.extended(&*unit_type.name);
-        let hash = Hash::type_hash(&item);
-        self.unit_type = Some(Hash::type_hash(&item));
+        let hash = self.item_hash(&item)?;
+        self.unit_type = Some(hash);
         self.add_internal_tuple(None, item.clone(), 0, || ())?;
 
         self.install_type_info(
@@ -689,14 +808,20 @@ impl Context {
         }
 
         let enum_item = module.item.join(&internal_enum.base_type);
-        let enum_hash = Hash::type_hash(&enum_item);
+        let enum_hash = self.item_hash(&enum_item)?;
 
         self.install_meta(CompileMeta {
             item: Arc::new(enum_item.clone().into()),
             kind: CompileMetaKind::Enum {
                 type_hash: internal_enum.static_type.hash,
+                variants: internal_enum
+                    .variants
+                    .iter()
+                    .map(|variant| enum_item.extended(variant.name))
+                    .collect(),
             },
             source: None,
+            docs: None,
         })?;
 
         self.install_type_info(
@@ -711,7 +836,7 @@ impl Context {
 
         for variant in &internal_enum.variants {
             let item = enum_item.extended(variant.name);
-            let hash = Hash::type_hash(&item);
+            let hash = self.item_hash(&item)?;
 
             self.install_type_info(
                 hash,
@@ -734,6 +859,7 @@ impl Context {
                     },
                 },
                 source: None,
+                docs: None,
             })?;
 
             let signature = ContextSignature::Function {
@@ -767,7 +893,7 @@ impl Context {
         C::Return: TypeOf,
     {
         let type_hash = <C::Return as TypeOf>::type_hash();
-        let hash = Hash::type_hash(&item);
+        let hash = self.item_hash(&item)?;
 
         let tuple = CompileMetaTuple { args, hash };
 
@@ -780,11 +906,13 @@ impl Context {
                     tuple,
                 },
                 source: None,
+                docs: None,
             },
             None => CompileMeta {
                 item: Arc::new(item.clone().into()),
                 kind: CompileMetaKind::TupleStruct { type_hash, tuple },
                 source: None,
+                docs: None,
             },
         };
 
@@ -823,3 +951,39 @@ impl fmt::Debug for Context {
 
 #[cfg(test)]
 static_assertions::assert_impl_all!(Context: Send, Sync);
+
+#[cfg(test)]
+mod tests {
+    use super::{Context, ContextError};
+    use crate::{Hash, Item, Module};
+
+    #[test]
+    fn test_conflicting_item_hash() {
+        let mut context = Context::new();
+
+        // NB: a real collision can't be constructed, so pretend that another
+        // item has already claimed the hash.
+        let item = Item::with_item(&["foo"]);
+        let existing = Item::with_item(&["bar"]);
+        context.hashes.insert(Hash::type_hash(&item));
+        context
+            .item_hashes
+            .insert(existing.clone(), Hash::type_hash(&item));
+
+        let mut module = Module::new();
+        module.function(&["foo"], || ()).unwrap();
+
+        match context.install(&module) {
+            Err(ContextError::ConflictingItemHash {
+                item: actual,
+                existing: actual_existing,
+                hash,
+            }) => {
+                assert_eq!(actual, item);
+                assert_eq!(actual_existing, existing);
+                assert_eq!(hash, Hash::type_hash(&item));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
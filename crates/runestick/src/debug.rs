@@ -14,6 +14,10 @@ pub struct DebugInfo {
     pub functions: HashMap<Hash, DebugSignature>,
     /// Reverse lookup of a function.
     pub functions_rev: HashMap<usize, Hash>,
+    /// The names of the variables captured by closures, in the order they are
+    /// stored in their environment.
+    #[serde(default)]
+    pub captures: HashMap<Hash, Vec<String>>,
 }
 
 impl DebugInfo {
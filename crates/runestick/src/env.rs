@@ -5,10 +5,16 @@
 //! * [super::Value::string_debug]
 //! * [super::Value::into_type_name]
 //!
+//! It also exposes the call depth of the virtual machine that is currently
+//! executing, through [call_depth], and its output sink through
+//! [with_output].
+//!
 //! See the corresponding function for documentation.
 
-use crate::{RuntimeContext, Unit, VmError, VmErrorKind};
+use crate::vm::{Output, Trace};
+use crate::{CallFrame, RuntimeContext, Unit, VmError, VmErrorKind};
 use std::cell::Cell;
+use std::io;
 use std::ptr;
 use std::sync::Arc;
 
@@ -20,7 +26,7 @@ where
     F: FnOnce(&Arc<RuntimeContext>, &Arc<Unit>) -> Result<T, VmError>,
 {
     let env = ENV.with(|env| env.get());
-    let Env { context, unit } = env;
+    let Env { context, unit, .. } = env;
 
     if context.is_null() || unit.is_null() {
         return Err(VmError::from(VmErrorKind::MissingInterfaceEnvironment));
@@ -32,18 +38,92 @@ where
     c(unsafe { &*context }, unsafe { &*unit })
 }
 
+/// Get the number of call frames in the virtual machine that is currently
+/// executing.
+///
+/// Note that tail calls replace the frame of their caller instead of pushing
+/// a new one, so this under-reports the depth of tail recursive calls.
+pub(crate) fn call_depth() -> Result<usize, VmError> {
+    let env = ENV.with(|env| env.get());
+
+    if env.call_frames.is_null() {
+        return Err(VmError::from(VmErrorKind::MissingInterfaceEnvironment));
+    }
+
+    // Safety: the call frames can only be registered publicly through
+    // [Guard], which makes sure that they are live for the duration of the
+    // registration.
+    Ok(unsafe { (*env.call_frames).len() })
+}
+
+/// Get the output sink of the virtual machine that is currently executing, if
+/// it has one.
+///
+/// This is used so that virtual machines started from native functions, like
+/// closures called by `map`, write to the same sink.
+pub(crate) fn output() -> Option<Output> {
+    let env = ENV.with(|env| env.get());
+    // Safety: the output can only be registered publicly through [Guard],
+    // which makes sure that it is live for the duration of the registration.
+    unsafe { env.output.as_ref() }.and_then(Option::clone)
+}
+
+/// Get the trace callback of the virtual machine that is currently executing,
+/// if it has one.
+pub(crate) fn trace() -> Option<Trace> {
+    let env = ENV.with(|env| env.get());
+    // Safety: the trace can only be registered publicly through [Guard],
+    // which makes sure that it is live for the duration of the registration.
+    unsafe { env.trace.as_ref() }.and_then(Option::clone)
+}
+
+/// Call the given closure with the output sink of the virtual machine that is
+/// currently executing, or stdout if it doesn't have one.
+pub(crate) fn with_output<F, T>(f: F) -> T
+where
+    F: FnOnce(&mut dyn io::Write) -> T,
+{
+    let env = ENV.with(|env| env.get());
+
+    // Safety: the output can only be registered publicly through [Guard],
+    // which makes sure that it is live for the duration of the registration.
+    if let Some(Output(output)) = unsafe { env.output.as_ref() }.and_then(Option::as_ref) {
+        let mut output = output.lock().unwrap_or_else(|e| e.into_inner());
+        return f(&mut *output);
+    }
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    f(&mut stdout)
+}
+
 pub(crate) struct Guard {
     old: Env,
 }
 
 impl Guard {
-    /// Construct a new environment guard with the given context and unit.
+    /// Construct a new environment guard with the given context, unit, call
+    /// frames, trace and output.
     ///
     /// # Safety
     ///
     /// The returned guard must be dropped before the pointed to elements are.
-    pub(crate) fn new(context: *const Arc<RuntimeContext>, unit: *const Arc<Unit>) -> Guard {
-        let old = ENV.with(|e| e.replace(Env { context, unit }));
+    pub(crate) fn new(
+        context: *const Arc<RuntimeContext>,
+        unit: *const Arc<Unit>,
+        call_frames: *const Vec<CallFrame>,
+        trace: *const Option<Trace>,
+        output: *const Option<Output>,
+    ) -> Guard {
+        let old = ENV.with(|e| {
+            e.replace(Env {
+                context,
+                unit,
+                call_frames,
+                trace,
+                output,
+            })
+        });
 
         Guard { old }
     }
@@ -59,6 +139,9 @@ impl Drop for Guard {
 struct Env {
     context: *const Arc<RuntimeContext>,
     unit: *const Arc<Unit>,
+    call_frames: *const Vec<CallFrame>,
+    trace: *const Option<Trace>,
+    output: *const Option<Output>,
 }
 
 impl Env {
@@ -66,6 +149,9 @@ impl Env {
         Self {
             context: ptr::null(),
             unit: ptr::null(),
+            call_frames: ptr::null(),
+            trace: ptr::null(),
+            output: ptr::null(),
         }
     }
 }
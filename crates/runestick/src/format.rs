@@ -48,7 +48,7 @@ impl FromValue for Format {
 }
 
 /// A format specification.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct FormatSpec {
     /// Formatting flags.
@@ -186,7 +186,7 @@ impl FormatSpec {
         value: &Value,
         out: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<(), VmError> {
         match value {
             Value::Char(c) => {
@@ -225,7 +225,7 @@ impl FormatSpec {
         value: &Value,
         out: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<(), VmError> {
         match value {
             Value::String(s) => {
@@ -340,7 +340,7 @@ impl FormatSpec {
         value: &Value,
         out: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<(), VmError> {
         match self.format_type {
             Type::Display => self.format_display(value, out, buf, caller)?,
@@ -356,7 +356,7 @@ impl FormatSpec {
 }
 
 /// The type of formatting requested.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum Type {
     /// Display type (default).
@@ -423,7 +423,7 @@ impl fmt::Display for Type {
 }
 
 /// The alignment requested.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum Alignment {
     /// Left alignment.
@@ -487,7 +487,7 @@ pub enum Flag {
 }
 
 /// Format specification flags.
-#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 #[repr(transparent)]
 pub struct Flags(u32);
 
@@ -546,3 +546,141 @@ impl fmt::Debug for Flags {
         Ok(())
     }
 }
+
+/// Error raised when a format string used by `std::fmt::format` is invalid.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum FormatStrError {
+    /// An unsupported character in a placeholder.
+    #[error("unsupported char `{0}` in format string")]
+    UnsupportedChar(char),
+    /// A placeholder which is never closed.
+    #[error("unexpected end of format string")]
+    UnexpectedEnd,
+    /// A closing brace without a matching opening brace.
+    #[error("unmatched `}}` in format string")]
+    Unmatched,
+    /// A placeholder refers to an argument which is not present.
+    #[error("missing positional argument #{0}")]
+    MissingArgument(usize),
+    /// An argument which is not referred to by any placeholder.
+    #[error("unused positional argument #{0}")]
+    UnusedArgument(usize),
+}
+
+/// A piece of a parsed [FormatStr].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatStrPiece {
+    /// Literal text.
+    Literal(String),
+    /// The argument at the given index.
+    Argument(usize),
+}
+
+/// A format string, as used by `std::fmt::format`.
+///
+/// In it, `{}` refers to the next positional argument and `{<n>}` to the
+/// argument at index `n`. `{{` and `}}` are escapes for `{` and `}`.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::format::{FormatStr, FormatStrError, FormatStrPiece};
+///
+/// let format = FormatStr::parse("{1} {{{}}}", 2).unwrap();
+///
+/// assert_eq!(format.pieces(), &[
+///     FormatStrPiece::Argument(1),
+///     FormatStrPiece::Literal(String::from(" {")),
+///     FormatStrPiece::Argument(0),
+///     FormatStrPiece::Literal(String::from("}")),
+/// ]);
+///
+/// assert_eq!(FormatStr::parse("{} {}", 1).unwrap_err(), FormatStrError::MissingArgument(1));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatStr {
+    pieces: Vec<FormatStrPiece>,
+}
+
+impl FormatStr {
+    /// Parse a format string, checking that it uses exactly `args` arguments.
+    pub fn parse(format: &str, args: usize) -> Result<Self, FormatStrError> {
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+        let mut used = vec![false; args];
+        let mut count = 0;
+        let mut chars = format.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut index = None::<usize>;
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => {
+                                let digit =
+                                    c.to_digit(10).ok_or(FormatStrError::UnsupportedChar(c))?;
+
+                                let n = index.unwrap_or_default();
+                                let n = n
+                                    .checked_mul(10)
+                                    .and_then(|n| n.checked_add(digit as usize))
+                                    .ok_or(FormatStrError::UnsupportedChar(c))?;
+
+                                index = Some(n);
+                            }
+                            None => return Err(FormatStrError::UnexpectedEnd),
+                        }
+                    }
+
+                    let n = match index {
+                        Some(n) => n,
+                        None => {
+                            count += 1;
+                            count - 1
+                        }
+                    };
+
+                    match used.get_mut(n) {
+                        Some(used) => *used = true,
+                        None => return Err(FormatStrError::MissingArgument(n)),
+                    }
+
+                    if !literal.is_empty() {
+                        pieces.push(FormatStrPiece::Literal(std::mem::take(&mut literal)));
+                    }
+
+                    pieces.push(FormatStrPiece::Argument(n));
+                }
+                '}' => return Err(FormatStrError::Unmatched),
+                c => literal.push(c),
+            }
+        }
+
+        if let Some(n) = used.iter().position(|used| !used) {
+            return Err(FormatStrError::UnusedArgument(n));
+        }
+
+        if !literal.is_empty() {
+            pieces.push(FormatStrPiece::Literal(literal));
+        }
+
+        Ok(Self { pieces })
+    }
+
+    /// Get the pieces of the format string.
+    pub fn pieces(&self) -> &[FormatStrPiece] {
+        &self.pieces
+    }
+}
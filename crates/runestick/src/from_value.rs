@@ -416,7 +416,16 @@ macro_rules! impl_map {
                 let mut output = <$ty>::with_capacity(object.len());
 
                 for (key, value) in object {
-                    output.insert(key, T::from_value(value)?);
+                    let value = match T::from_value(value) {
+                        Ok(value) => value,
+                        Err(error) => {
+                            return Err($crate::VmError::from(
+                                $crate::VmErrorKind::BadObjectValue { error, key },
+                            ));
+                        }
+                    };
+
+                    output.insert(key, value);
                 }
 
                 Ok(output)
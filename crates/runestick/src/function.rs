@@ -106,14 +106,19 @@ where
     ///
     /// A stop reason will be returned in case the function call results in
     /// a need to suspend the execution.
-    pub(crate) fn call_with_vm(&self, vm: &mut Vm, args: usize) -> Result<Option<VmHalt>, VmError> {
+    pub(crate) fn call_with_vm(
+        &self,
+        vm: &mut Vm,
+        args: usize,
+        tail: bool,
+    ) -> Result<Option<VmHalt>, VmError> {
         let reason = match &self.inner {
             Inner::FnHandler(handler) => {
                 (handler.handler)(&mut vm.stack, args)?;
                 None
             }
             Inner::FnOffset(fn_offset) => {
-                if let Some(vm_call) = fn_offset.call_with_vm(vm, args, ())? {
+                if let Some(vm_call) = fn_offset.call_with_vm(vm, args, (), tail)? {
                     return Ok(Some(VmHalt::VmCall(vm_call)));
                 }
 
@@ -124,6 +129,7 @@ where
                     vm,
                     args,
                     (Tuple::from(closure.environment.clone()),),
+                    tail,
                 )? {
                     return Ok(Some(VmHalt::VmCall(vm_call)));
                 }
@@ -177,6 +183,8 @@ where
         offset: usize,
         call: Call,
         args: usize,
+        defaults: usize,
+        rest: bool,
         hash: Hash,
     ) -> Self {
         Self {
@@ -186,6 +194,8 @@ where
                 offset,
                 call,
                 args,
+                defaults,
+                rest,
                 hash,
             }),
         }
@@ -209,6 +219,8 @@ where
                     offset,
                     call,
                     args,
+                    defaults: 0,
+                    rest: false,
                     hash,
                 },
                 environment,
@@ -256,6 +268,32 @@ where
         Ok(())
     }
 
+    /// Iterate over the environment captured by a closure, as pairs of the name
+    /// of each captured variable and its value.
+    ///
+    /// Names are only available if the unit the closure belongs to has debug
+    /// information. Functions which aren't closures, and closures which don't
+    /// capture anything, have an empty environment.
+    pub fn environment(&self) -> impl Iterator<Item = (Option<&str>, &V)> + '_ {
+        let (names, environment) = match &self.inner {
+            Inner::FnClosureOffset(closure) => {
+                let names = closure
+                    .fn_offset
+                    .unit
+                    .debug_info()
+                    .and_then(|debug| debug.captures.get(&closure.fn_offset.hash));
+
+                (names, &closure.environment[..])
+            }
+            _ => (None, &[][..]),
+        };
+
+        environment.iter().enumerate().map(move |(index, value)| {
+            let name = names.and_then(|names| names.get(index)).map(String::as_str);
+            (name, value)
+        })
+    }
+
     #[inline]
     pub fn type_hash(&self) -> Hash {
         match &self.inner {
@@ -382,6 +420,10 @@ struct FnOffset {
     call: Call,
     /// The number of arguments the function takes.
     args: usize,
+    /// The number of trailing arguments which can be omitted.
+    defaults: usize,
+    /// Whether additional arguments are collected into a vector.
+    rest: bool,
     /// Hash for the function type
     hash: Hash,
 }
@@ -393,10 +435,15 @@ impl FnOffset {
         A: Args,
         E: Args,
     {
-        Function::check_args(args.count(), self.args)?;
+        Vm::check_offset_args(args.count(), self.args, self.defaults, self.rest)?;
 
         let mut vm = Vm::new(self.context.clone(), self.unit.clone());
 
+        // NB: a function called from a native function, like a closure
+        // passed to `map`, runs on a new virtual machine. Inherit the trace
+        // and output of the one that is currently executing.
+        vm.trace = crate::env::trace();
+        vm.output = crate::env::output();
         vm.set_ip(self.offset);
         args.into_stack(vm.stack_mut())?;
         extra.into_stack(vm.stack_mut())?;
@@ -407,17 +454,29 @@ impl FnOffset {
     /// Perform a potentially optimized call into the specified vm.
     ///
     /// This will cause a halt in case the vm being called into isn't the same
-    /// as the context and unit of the function.
-    fn call_with_vm<E>(&self, vm: &mut Vm, args: usize, extra: E) -> Result<Option<VmCall>, VmError>
+    /// as the context and unit of the function. If `tail` is set, the current
+    /// call frame of the vm is reused if possible.
+    fn call_with_vm<E>(
+        &self,
+        vm: &mut Vm,
+        args: usize,
+        extra: E,
+        tail: bool,
+    ) -> Result<Option<VmCall>, VmError>
     where
         E: Args,
     {
-        Function::check_args(args, self.args)?;
+        Vm::check_offset_args(args, self.args, self.defaults, self.rest)?;
 
         // Fast past, just allocate a call frame and keep running.
         if let Call::Immediate = self.call {
             if vm.is_same(&self.context, &self.unit) {
-                vm.push_call_frame(self.offset, args)?;
+                if tail {
+                    vm.replace_call_frame(self.offset, args)?;
+                } else {
+                    vm.push_call_frame(self.offset, args)?;
+                }
+
                 extra.into_stack(vm.stack_mut())?;
                 return Ok(None);
             }
@@ -425,8 +484,12 @@ impl FnOffset {
 
         let mut new_stack = vm.stack_mut().drain_stack_top(args)?.collect::<Stack>();
         extra.into_stack(&mut new_stack)?;
+        let trace = vm.trace.clone();
+        let output = vm.output.clone();
         let mut vm = Vm::new_with_stack(self.context.clone(), self.unit.clone(), new_stack);
         vm.set_ip(self.offset);
+        vm.trace = trace;
+        vm.output = output;
         Ok(Some(VmCall::new(self.call, vm)))
     }
 }
@@ -439,6 +502,8 @@ impl fmt::Debug for FnOffset {
             .field("offset", &self.offset)
             .field("call", &self.call)
             .field("args", &self.args)
+            .field("defaults", &self.defaults)
+            .field("rest", &self.rest)
             .finish()
     }
 }
@@ -51,7 +51,12 @@ impl Hash {
     pub fn from_type_id(type_id: any::TypeId) -> Self {
         // Safety: a type id is exactly a 64-bit unsigned integer.
         // And has an identical bit pattern to `Hash`.
-        unsafe { mem::transmute(type_id) }
+        {
+            use std::hash::{Hash as _, Hasher as _};
+            let mut h = std::collections::hash_map::DefaultHasher::new();
+            type_id.hash(&mut h);
+            Self(h.finish())
+        }
     }
 
     /// Construct a hash to an instance function, where the instance is a
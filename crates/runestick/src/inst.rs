@@ -5,7 +5,7 @@ use std::fmt;
 /// Pre-canned panic reasons.
 ///
 /// To formulate a custom reason, use [crate::Panic::custom].
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PanicReason {
     /// Not implemented.
     NotImplemented,
@@ -41,7 +41,7 @@ impl fmt::Display for PanicReason {
 }
 
 /// An encoded type check.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TypeCheck {
     /// Matches a unit type.
     Unit,
@@ -80,7 +80,7 @@ impl fmt::Display for TypeCheck {
 }
 
 /// An operation in the stack-based virtual machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Inst {
     /// Not operator. Takes a boolean from the top of the stack  and inverts its
     /// logical value.
@@ -126,6 +126,18 @@ pub enum Inst {
         /// The number of arguments expected on the stack for this call.
         args: usize,
     },
+    /// Perform a function call in tail position.
+    ///
+    /// This behaves like [Inst::Call], except that calls to functions in the
+    /// current unit reuse the current stack frame instead of constructing a
+    /// new one. It must therefore only be used when the value produced by the
+    /// call is immediately returned.
+    TailCall {
+        /// The hash of the function to call.
+        hash: Hash,
+        /// The number of arguments expected on the stack for this call.
+        args: usize,
+    },
     /// Perform a instance function call.
     ///
     /// The instance being called on should be on top of the stack, followed by
@@ -167,6 +179,22 @@ pub enum Inst {
         /// The number of arguments expected on the stack for this call.
         args: usize,
     },
+    /// Perform a function call on a function pointer stored on the stack in
+    /// tail position.
+    ///
+    /// See [Inst::TailCall] for how this differs from [Inst::CallFn].
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <fn>
+    /// <args...>
+    /// => <ret>
+    /// ```
+    TailCallFn {
+        /// The number of arguments expected on the stack for this call.
+        args: usize,
+    },
     /// Perform an index get operation. Pushing the result on the stack.
     ///
     /// # Operation
@@ -222,6 +250,40 @@ pub enum Inst {
         /// The index to fetch.
         index: usize,
     },
+    /// Get the given index counting from the back, where `0` is the last
+    /// element, out of a tuple or vector from the given variable slot.
+    /// Errors if the item doesn't exist or the item is not a tuple or vector.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    TupleIndexGetBackAt {
+        /// The slot offset to load the tuple from.
+        offset: usize,
+        /// The index to fetch, counting from the back.
+        index: usize,
+    },
+    /// Get the elements of a vector from the given variable slot as a new
+    /// vector, leaving out `start` elements at the start and `end` elements at
+    /// the end. This is used for rest patterns like `[first, ..rest]`.
+    ///
+    /// Errors if the item is not a vector.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <vec>
+    /// ```
+    VecRestAt {
+        /// The slot offset to load the vector from.
+        offset: usize,
+        /// The number of elements to leave out at the start.
+        start: usize,
+        /// The number of elements to leave out at the end.
+        end: usize,
+    },
     /// Get the given index out of an object on the top of the stack.
     /// Errors if the item doesn't exist or the item is not an object.
     ///
@@ -499,6 +561,39 @@ pub enum Inst {
         /// The offset to jump.
         offset: isize,
     },
+    /// Jump to `offset` if the current call frame holds at least `len`
+    /// values.
+    ///
+    /// This is used when a function is entered to skip evaluating the default
+    /// values of arguments that were provided by the caller.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => *nothing*
+    /// ```
+    JumpIfFrameLen {
+        /// The number of values the call frame has to hold.
+        len: usize,
+        /// The offset to jump.
+        offset: isize,
+    },
+    /// Collect all values in the current call frame past the first `args`
+    /// into a vector and push it onto the stack.
+    ///
+    /// This is used when a function is entered to collect the arguments bound
+    /// to a rest argument like `..rest`.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value..>
+    /// => <vec>
+    /// ```
+    CollectRest {
+        /// The number of arguments which are not collected.
+        args: usize,
+    },
     /// Construct a push a vector value onto the stack. The number of elements
     /// in the vector are determined by `count` and are popped from the stack.
     ///
@@ -598,6 +693,25 @@ pub enum Inst {
         /// The static slot of the object keys.
         slot: usize,
     },
+    /// Construct a push an object onto the stack, starting from a copy of the
+    /// entries of a base object. The number of elements in the object are
+    /// determined the slot of the object keys `slot` and are popped from the
+    /// stack, after which the base object is popped.
+    ///
+    /// For each element, a value is popped corresponding to the object key,
+    /// replacing any entry with the same key copied from the base object.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value..>
+    /// <object>
+    /// => <object>
+    /// ```
+    ObjectSpread {
+        /// The static slot of the object keys.
+        slot: usize,
+    },
     /// Construct a range. This will pop the start and end of the range from the
     /// stack.
     ///
@@ -694,6 +808,19 @@ pub enum Inst {
         /// The static byte string slot to load the string from.
         slot: usize,
     },
+    /// Load the big integer in the given static slot. This is used for integer
+    /// literals which don't fit in an `i64` when the compiler is in bignum
+    /// mode.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <bigint>
+    /// ```
+    BigInt {
+        /// The static big integer slot to load the number from.
+        slot: usize,
+    },
     /// Pop the given number of values from the stack, and concatenate a string
     /// from them.
     ///
@@ -738,6 +865,16 @@ pub enum Inst {
     /// => <boolean>
     /// ```
     IsValue,
+    /// Test if the top of the stack is present, which is any value except
+    /// `None` or unit.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    IsPresent,
     /// Unwrap a result from the top of the stack.
     /// This causes a vm error if the top of the stack is not an ok result.
     ///
@@ -748,6 +885,21 @@ pub enum Inst {
     /// => <value>
     /// ```
     Unwrap,
+    /// Unwrap the top of the stack as a link in an optional chain, pushing
+    /// the branch that was taken after the resulting value.
+    ///
+    /// * `Some(value)` and `Ok(value)` are unwrapped with the branch `0`.
+    /// * `None` and unit are left as-is with the branch `1`.
+    /// * `Err(error)` is left as-is with the branch `2`.
+    /// * Any other value is left as-is with the branch `0`.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <value> <branch>
+    /// ```
+    OptionalUnwrap,
     /// Test if the top of the stack is a specific byte.
     ///
     /// # Operation
@@ -991,6 +1143,9 @@ impl fmt::Display for Inst {
             Self::Call { hash, args } => {
                 write!(fmt, "call {}, {}", hash, args)?;
             }
+            Self::TailCall { hash, args } => {
+                write!(fmt, "tail-call {}, {}", hash, args)?;
+            }
             Self::CallInstance { hash, args } => {
                 write!(fmt, "call-instance {}, {}", hash, args)?;
             }
@@ -1000,6 +1155,9 @@ impl fmt::Display for Inst {
             Self::CallFn { args } => {
                 write!(fmt, "call-fn {}", args)?;
             }
+            Self::TailCallFn { args } => {
+                write!(fmt, "tail-call-fn {}", args)?;
+            }
             Self::LoadInstanceFn { hash } => {
                 write!(fmt, "load-instance-fn {}", hash)?;
             }
@@ -1015,6 +1173,12 @@ impl fmt::Display for Inst {
             Self::TupleIndexGetAt { offset, index } => {
                 write!(fmt, "tuple-index-get-at {}, {}", offset, index)?;
             }
+            Self::TupleIndexGetBackAt { offset, index } => {
+                write!(fmt, "tuple-index-get-back-at {}, {}", offset, index)?;
+            }
+            Self::VecRestAt { offset, start, end } => {
+                write!(fmt, "vec-rest-at {}, {}, {}", offset, start, end)?;
+            }
             Self::ObjectIndexGet { slot } => {
                 write!(fmt, "object-index-get {}", slot)?;
             }
@@ -1084,6 +1248,12 @@ impl fmt::Display for Inst {
             Self::JumpIfBranch { branch, offset } => {
                 write!(fmt, "jump-if-branch {}, {}", branch, offset)?;
             }
+            Self::JumpIfFrameLen { len, offset } => {
+                write!(fmt, "jump-if-frame-len {}, {}", len, offset)?;
+            }
+            Self::CollectRest { args } => {
+                write!(fmt, "collect-rest {}", args)?;
+            }
             Self::Vec { count } => {
                 write!(fmt, "vec {}", count)?;
             }
@@ -1120,6 +1290,9 @@ impl fmt::Display for Inst {
             Self::Object { slot } => {
                 write!(fmt, "object {}", slot)?;
             }
+            Self::ObjectSpread { slot } => {
+                write!(fmt, "object-spread {}", slot)?;
+            }
             Self::Range { limits } => {
                 write!(fmt, "range {}", limits)?;
             }
@@ -1129,6 +1302,9 @@ impl fmt::Display for Inst {
             Self::Bytes { slot } => {
                 write!(fmt, "bytes {}", slot)?;
             }
+            Self::BigInt { slot } => {
+                write!(fmt, "bigint {}", slot)?;
+            }
             Self::StringConcat { len, size_hint } => {
                 write!(fmt, "string-concat {}, {}", len, size_hint)?;
             }
@@ -1150,9 +1326,15 @@ impl fmt::Display for Inst {
             Self::IsValue => {
                 write!(fmt, "is-value")?;
             }
+            Self::IsPresent => {
+                write!(fmt, "is-present")?;
+            }
             Self::Unwrap => {
                 write!(fmt, "unwrap")?;
             }
+            Self::OptionalUnwrap => {
+                write!(fmt, "optional-unwrap")?;
+            }
             Self::EqByte { byte } => {
                 write!(fmt, "eq-byte {:?}", byte)?;
             }
@@ -1228,7 +1410,7 @@ impl fmt::Display for Inst {
 }
 
 /// How an instruction addresses a value.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstAddress {
     /// Addressed from the top of the stack.
     Top,
@@ -1246,7 +1428,7 @@ impl fmt::Display for InstAddress {
 }
 
 /// Range limits of a range expression.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstRangeLimits {
     /// A half-open range `a .. b`.
     HalfOpen,
@@ -1264,7 +1446,7 @@ impl fmt::Display for InstRangeLimits {
 }
 
 /// The target of an operation.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstTarget {
     /// Target is an offset to the current call frame.
     Offset(usize),
@@ -1285,7 +1467,7 @@ impl fmt::Display for InstTarget {
 }
 
 /// An operation between two values on the machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstAssignOp {
     /// The add operation. `a + b`.
     Add,
@@ -1349,7 +1531,7 @@ impl fmt::Display for InstAssignOp {
 }
 
 /// An operation between two values on the machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstOp {
     /// The add operation. `a + b`.
     Add,
@@ -1520,7 +1702,7 @@ impl fmt::Display for InstOp {
 }
 
 /// A literal value that can be pushed.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstValue {
     /// A unit.
     Unit,
@@ -1576,7 +1758,7 @@ impl fmt::Display for InstValue {
 }
 
 /// A variant that can be constructed.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstVariant {
     /// `Option::Some`, which uses one value.
     Some,
@@ -65,7 +65,9 @@ mod args;
 mod awaited;
 pub mod budget;
 mod bytes;
+mod bytes_view;
 mod call;
+mod clock;
 mod compile_meta;
 mod const_value;
 pub mod debug;
@@ -91,6 +93,7 @@ pub mod modules;
 mod named;
 mod names;
 mod object;
+mod opaque;
 mod panic;
 mod protocol;
 mod protocol_caller;
@@ -111,6 +114,7 @@ mod tuple;
 mod type_info;
 mod type_of;
 mod unit;
+mod value_ref;
 mod variant;
 mod vec;
 mod vec_tuple;
@@ -119,6 +123,7 @@ mod vm_call;
 mod vm_error;
 mod vm_execution;
 mod vm_halt;
+mod vm_snapshot;
 
 /// Construct a span that can be used during pattern matching.
 ///
@@ -197,7 +202,9 @@ pub use crate::access::{
 pub use crate::any::Any;
 pub use crate::awaited::Awaited;
 pub use crate::bytes::Bytes;
+pub use crate::bytes_view::BytesView;
 pub use crate::call::Call;
+pub use crate::clock::{Clock, ClockFuture, MockClock, SystemClock};
 pub use crate::context::{Context, ContextError, ContextSignature, ContextTypeInfo};
 pub use crate::debug::{DebugInfo, DebugInst};
 pub use crate::function::{Function, SyncFunction};
@@ -210,6 +217,7 @@ pub use crate::inst::{
 pub use crate::item::{Component, ComponentRef, IntoComponent, Item};
 pub use crate::names::Names;
 pub use crate::object::Object;
+pub use crate::opaque::Opaque;
 pub use crate::panic::Panic;
 pub use crate::protocol::Protocol;
 pub use crate::range::{Range, RangeLimits};
@@ -218,6 +226,7 @@ pub use crate::stack::{Stack, StackError};
 pub use crate::type_of::TypeOf;
 pub use crate::unit::{Unit, UnitFn};
 pub use crate::value::{Rtti, Struct, TupleStruct, UnitStruct, Value, VariantRtti};
+pub use crate::value_ref::ValueRef;
 pub use crate::vec_tuple::VecTuple;
 pub use crate::visibility::Visibility;
 pub use crate::vm::{CallFrame, Vm};
@@ -225,11 +234,12 @@ pub use crate::vm_call::VmCall;
 pub use crate::vm_error::{VmError, VmErrorKind, VmIntegerRepr};
 pub use crate::vm_execution::{VmExecution, VmSendExecution};
 pub use crate::vm_halt::{VmHalt, VmHaltInfo};
+pub use crate::vm_snapshot::VmSnapshot;
 pub(crate) use runestick_macros::__internal_impl_any;
 pub use runestick_macros::{Any, FromValue};
 
 mod collections {
     pub use hashbrown::{hash_map, HashMap};
     pub use hashbrown::{hash_set, HashSet};
-    pub use std::collections::{btree_map, BTreeMap};
+    pub use indexmap::{map as index_map, IndexMap};
 }
@@ -6,10 +6,11 @@
 use crate::context::{ContextError, Handler, Macro};
 use crate::{collections::HashMap, ConstValue};
 use crate::{
-    FromValue, Future, GeneratorState, Hash, IntoComponent, Item, Named, Protocol, Stack,
+    FromValue, Future, GeneratorState, Hash, IntoComponent, Item, Named, Opaque, Protocol, Stack,
     StaticType, ToValue, TypeCheck, TypeInfo, TypeOf, UnsafeFromValue, Value, VmError, VmErrorKind,
 };
 use std::any;
+use std::fmt;
 use std::future;
 use std::sync::Arc;
 
@@ -129,6 +130,9 @@ pub(crate) struct ModuleAssocKey {
 pub(crate) struct ModuleFn {
     pub(crate) handler: Arc<Handler>,
     pub(crate) args: Option<usize>,
+    /// The variant of the function used for direct calls, where each argument
+    /// is preceded by a string describing its call site.
+    pub(crate) call_site: Option<Arc<Handler>>,
 }
 
 pub(crate) struct ModuleMacro {
@@ -261,6 +265,52 @@ impl Module {
         Ok(())
     }
 
+    /// Register a foreign type which can't implement [Any][crate::Any] under
+    /// the given `name`.
+    ///
+    /// Values of the type are wrapped in [Opaque][crate::Opaque], and can be
+    /// passed through scripts without exposing any of their fields.
+    ///
+    /// Registering the same type more than once results in an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::{Module, Opaque};
+    /// use std::collections::VecDeque;
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let mut module = Module::default();
+    ///
+    /// module.opaque_ty::<VecDeque<i64>>("VecDeque")?;
+    /// module.function(&["new_queue"], || Opaque(VecDeque::<i64>::new()))?;
+    /// module.function(&["queue_len"], |q: &Opaque<VecDeque<i64>>| q.len())?;
+    ///
+    /// assert!(module.opaque_ty::<VecDeque<i64>>("VecDeque").is_err());
+    /// # Ok(()) }
+    /// ```
+    pub fn opaque_ty<T>(&mut self, name: &str) -> Result<(), ContextError>
+    where
+        T: 'static,
+    {
+        let type_hash = <Opaque<T> as TypeOf>::type_hash();
+        let type_info = <Opaque<T> as TypeOf>::type_info();
+
+        let ty = ModuleType {
+            name: name.into(),
+            type_info,
+        };
+
+        if let Some(old) = self.types.insert(type_hash, ty) {
+            return Err(ContextError::ConflictingType {
+                item: Item::with_item(&[name]),
+                existing: old.type_info,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Construct type information for the `unit` type.
     ///
     /// Registering this allows the given type to be used in Rune scripts when
@@ -429,11 +479,69 @@ impl Module {
             return Err(ContextError::ConflictingFunctionName { name });
         }
 
+        if self.constants.contains_key(&name) {
+            return Err(ContextError::ConflictingConstantName { name });
+        }
+
+        self.functions.insert(
+            name,
+            ModuleFn {
+                handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
+                args: Some(Func::args()),
+                call_site: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Register a fallible function that returns a [Result].
+    ///
+    /// If the function fails, its error is converted into a string and
+    /// returned to the caller as an `Err`, which can be handled by scripts
+    /// through pattern matching or the `?` operator. An unhandled error shows
+    /// up with its message when it is unwrapped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// fn checked_sqrt(value: f64) -> Result<f64, String> {
+    ///     if value < 0.0 {
+    ///         return Err(format!("cannot take the square root of {}", value));
+    ///     }
+    ///
+    ///     Ok(value.sqrt())
+    /// }
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let mut module = runestick::Module::default();
+    ///
+    /// module.fallible_fn(&["checked_sqrt"], checked_sqrt)?;
+    /// module.fallible_fn(&["parse"], |s: &str| s.parse::<i64>())?;
+    /// # Ok(()) }
+    /// ```
+    pub fn fallible_fn<Func, Args, N>(&mut self, name: N, f: Func) -> Result<(), ContextError>
+    where
+        Func: FallibleFunction<Args>,
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        let name = Item::with_item(name);
+
+        if self.functions.contains_key(&name) {
+            return Err(ContextError::ConflictingFunctionName { name });
+        }
+
+        if self.constants.contains_key(&name) {
+            return Err(ContextError::ConflictingConstantName { name });
+        }
+
         self.functions.insert(
             name,
             ModuleFn {
                 handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
                 args: Some(Func::args()),
+                call_site: None,
             },
         );
 
@@ -442,6 +550,9 @@ impl Module {
 
     /// Register a constant value, at a crate, module or associated level.
     ///
+    /// Constants can be used both at runtime and in constant expressions. A
+    /// constant can't share its name with a function in the same module.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -466,6 +577,10 @@ impl Module {
             return Err(ContextError::ConflictingConstantName { name });
         }
 
+        if self.functions.contains_key(&name) {
+            return Err(ContextError::ConflictingFunctionName { name });
+        }
+
         let value = match value.to_value() {
             Ok(v) => v,
             Err(e) => return Err(ContextError::ValueError { error: e }),
@@ -515,7 +630,12 @@ impl Module {
         Ok(())
     }
 
-    /// Register a function.
+    /// Register an asynchronous function.
+    ///
+    /// Calling the function produces a future which must be `.await`ed from
+    /// an async context in Rune. The output of the future is converted the
+    /// same way as the return value of a regular function, so a returned
+    /// `Err(VmError)` causes the virtual machine to error.
     ///
     /// # Examples
     ///
@@ -541,11 +661,16 @@ impl Module {
             return Err(ContextError::ConflictingFunctionName { name });
         }
 
+        if self.constants.contains_key(&name) {
+            return Err(ContextError::ConflictingConstantName { name });
+        }
+
         self.functions.insert(
             name,
             ModuleFn {
                 handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
                 args: Some(Func::args()),
+                call_site: None,
             },
         );
 
@@ -566,11 +691,56 @@ impl Module {
             return Err(ContextError::ConflictingFunctionName { name });
         }
 
+        if self.constants.contains_key(&name) {
+            return Err(ContextError::ConflictingConstantName { name });
+        }
+
         self.functions.insert(
             name,
             ModuleFn {
                 handler: Arc::new(move |stack, args| f(stack, args)),
                 args: None,
+                call_site: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Register a raw function like [Module::raw_fn], along with a variant
+    /// `at` which is used when the function is called directly.
+    ///
+    /// The compiler calls `at` with a string describing the call site of each
+    /// argument preceding it, like `[main:2] a + b`, which is what allows
+    /// `dbg` to print the expressions it was called with.
+    pub fn raw_fn_with_call_site<F, A, N>(
+        &mut self,
+        name: N,
+        f: F,
+        at: A,
+    ) -> Result<(), ContextError>
+    where
+        F: 'static + Copy + Fn(&mut Stack, usize) -> Result<(), VmError> + Send + Sync,
+        A: 'static + Copy + Fn(&mut Stack, usize) -> Result<(), VmError> + Send + Sync,
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        let name = Item::with_item(name);
+
+        if self.functions.contains_key(&name) {
+            return Err(ContextError::ConflictingFunctionName { name });
+        }
+
+        if self.constants.contains_key(&name) {
+            return Err(ContextError::ConflictingConstantName { name });
+        }
+
+        self.functions.insert(
+            name,
+            ModuleFn {
+                handler: Arc::new(move |stack, args| f(stack, args)),
+                args: None,
+                call_site: Some(Arc::new(move |stack, args| at(stack, args))),
             },
         );
 
@@ -776,6 +946,20 @@ pub trait Function<Args>: 'static + Copy + Send + Sync {
     fn fn_call(self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
 }
 
+/// Trait used to provide the [fallible_fn][Module::fallible_fn] function.
+pub trait FallibleFunction<Args>: 'static + Copy + Send + Sync {
+    /// The value returned on success.
+    type Ok;
+    /// The error returned on failure.
+    type Error;
+
+    /// Get the number of arguments.
+    fn args() -> usize;
+
+    /// Perform the vm call.
+    fn fn_call(self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
+}
+
 /// Trait used to provide the [async_function][Module::async_function] function.
 pub trait AsyncFunction<Args>: 'static + Copy + Send + Sync {
     /// The return type of the function.
@@ -880,6 +1064,47 @@ macro_rules! impl_register {
             }
         }
 
+        impl<Func, Ok, Error, $($ty,)*> FallibleFunction<($($ty,)*)> for Func
+        where
+            Func: 'static + Copy + Send + Sync + Fn($($ty,)*) -> Result<Ok, Error>,
+            Ok: ToValue,
+            Error: fmt::Display,
+            $($ty: UnsafeFromValue,)*
+        {
+            type Ok = Ok;
+            type Error = Error;
+
+            fn args() -> usize {
+                $count
+            }
+
+            fn fn_call(
+                self,
+                stack: &mut Stack,
+                args: usize
+            ) -> Result<(), VmError> {
+                impl_register!{@check-args $count, args}
+
+                #[allow(unused_mut)]
+                let mut it = stack.drain_stack_top($count)?;
+                $(let $var = it.next().unwrap();)*
+                drop(it);
+
+                // Safety: We hold a reference to the stack, so we can
+                // guarantee that it won't be modified.
+                #[allow(unused)]
+                let ret = unsafe {
+                    impl_register!{@unsafe-vars $count, $($ty, $var, $num,)*}
+
+                    self($(<$ty>::unsafe_coerce($var.0),)*)
+                };
+
+                let ret = ret.map_err(|error| error.to_string());
+                impl_register!{@return stack, ret, Result<Ok, String>}
+                Ok(())
+            }
+        }
+
         impl<Func, Return, $($ty,)*> AsyncFunction<($($ty,)*)> for Func
         where
             Func: 'static + Copy + Send + Sync + Fn($($ty,)*) -> Return,
@@ -8,10 +8,10 @@ use std::fmt::Write as _;
 #[derive(Any, Debug)]
 #[rune(module = "crate")]
 #[repr(transparent)]
-struct TypeId(StdTypeId);
+struct TypeId(crate::Hash);
 
 fn type_id_of_val(item: Value) -> TypeId {
-    unsafe { std::mem::transmute(item.type_hash().expect("no type known for item!")) }
+    TypeId(item.type_hash().expect("no type known for item!"))
 }
 
 fn format_type_id(item: &TypeId, buf: &mut String) -> fmt::Result {
@@ -0,0 +1,182 @@
+//! The `std::bigint` module.
+//!
+//! This module is not part of the default context, it has to be installed
+//! explicitly. It provides the type which integer literals that don't fit in
+//! an `i64` are compiled to when the `bignum` compiler option is enabled.
+
+use crate::{ContextError, Module, Protocol, TypeInfo, TypeOf, Value, VmError, VmErrorKind};
+use num_bigint::{BigInt, ParseBigIntError};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Construct the `std::bigint` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", &["bigint"]);
+
+    module.ty::<BigInt>()?;
+    module.ty::<ParseBigIntError>()?;
+
+    module.function(&["BigInt", "new"], <BigInt as From<i64>>::from)?;
+    module.function(&["BigInt", "parse"], parse)?;
+
+    module.inst_fn("to_int", to_int)?;
+    module.inst_fn("to_string", BigInt::to_string)?;
+    module.inst_fn("pow", BigInt::pow)?;
+
+    module.inst_fn(Protocol::STRING_DISPLAY, format_bigint)?;
+    module.inst_fn(Protocol::EQ, eq)?;
+    module.inst_fn(Protocol::CMP, cmp)?;
+    module.inst_fn(Protocol::ADD, |a: &BigInt, b: Value| {
+        op(Protocol::ADD, a, b, add)
+    })?;
+    module.inst_fn(Protocol::SUB, |a: &BigInt, b: Value| {
+        op(Protocol::SUB, a, b, sub)
+    })?;
+    module.inst_fn(Protocol::MUL, |a: &BigInt, b: Value| {
+        op(Protocol::MUL, a, b, mul)
+    })?;
+    module.inst_fn(Protocol::DIV, |a: &BigInt, b: Value| {
+        op(Protocol::DIV, a, b, div)
+    })?;
+    module.inst_fn(Protocol::REM, |a: &BigInt, b: Value| {
+        op(Protocol::REM, a, b, rem)
+    })?;
+
+    module.inst_fn(Protocol::ADD_ASSIGN, |a: &mut BigInt, b: Value| {
+        op_assign(Protocol::ADD_ASSIGN, a, b, add)
+    })?;
+    module.inst_fn(Protocol::SUB_ASSIGN, |a: &mut BigInt, b: Value| {
+        op_assign(Protocol::SUB_ASSIGN, a, b, sub)
+    })?;
+    module.inst_fn(Protocol::MUL_ASSIGN, |a: &mut BigInt, b: Value| {
+        op_assign(Protocol::MUL_ASSIGN, a, b, mul)
+    })?;
+    module.inst_fn(Protocol::DIV_ASSIGN, |a: &mut BigInt, b: Value| {
+        op_assign(Protocol::DIV_ASSIGN, a, b, div)
+    })?;
+    module.inst_fn(Protocol::REM_ASSIGN, |a: &mut BigInt, b: Value| {
+        op_assign(Protocol::REM_ASSIGN, a, b, rem)
+    })?;
+
+    // NB: integers are promoted to big integers when they are on the
+    // left-hand side of an operation, through the right-hand side protocols.
+    module.inst_fn(Protocol::ADD_RHS, |b: &BigInt, a: i64| {
+        add(&BigInt::from(a), b)
+    })?;
+    module.inst_fn(Protocol::SUB_RHS, |b: &BigInt, a: i64| {
+        sub(&BigInt::from(a), b)
+    })?;
+    module.inst_fn(Protocol::MUL_RHS, |b: &BigInt, a: i64| {
+        mul(&BigInt::from(a), b)
+    })?;
+    module.inst_fn(Protocol::DIV_RHS, |b: &BigInt, a: i64| {
+        div(&BigInt::from(a), b)
+    })?;
+    module.inst_fn(Protocol::REM_RHS, |b: &BigInt, a: i64| {
+        rem(&BigInt::from(a), b)
+    })?;
+    Ok(module)
+}
+
+/// Parse a big integer.
+fn parse(s: &str) -> Result<BigInt, ParseBigIntError> {
+    str::parse::<BigInt>(s)
+}
+
+/// Convert a big integer into an integer, if it fits.
+fn to_int(value: &BigInt) -> Option<i64> {
+    use std::convert::TryFrom as _;
+    i64::try_from(value).ok()
+}
+
+fn format_bigint(value: &BigInt, buf: &mut String) -> fmt::Result {
+    write!(buf, "{}", value)
+}
+
+fn eq(a: &BigInt, b: Value) -> Result<bool, VmError> {
+    match coerce(&b)? {
+        Some(b) => Ok(*a == b),
+        None => Ok(false),
+    }
+}
+
+fn cmp(a: &BigInt, b: Value) -> Result<Ordering, VmError> {
+    match coerce(&b)? {
+        Some(b) => Ok(a.cmp(&b)),
+        None => Err(unsupported(Protocol::CMP, BigInt::type_info(), &b)),
+    }
+}
+
+/// Coerce a value into a big integer, promoting integers.
+fn coerce(value: &Value) -> Result<Option<BigInt>, VmError> {
+    Ok(match value {
+        Value::Integer(n) => Some(BigInt::from(*n)),
+        Value::Any(any) => any.borrow_ref()?.downcast_borrow_ref::<BigInt>().cloned(),
+        _ => None,
+    })
+}
+
+fn unsupported(protocol: Protocol, lhs: TypeInfo, rhs: &Value) -> VmError {
+    match rhs.type_info() {
+        Ok(rhs) => VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+            op: protocol.name,
+            lhs,
+            rhs,
+        }),
+        Err(error) => error,
+    }
+}
+
+fn op(
+    protocol: Protocol,
+    a: &BigInt,
+    b: Value,
+    f: fn(&BigInt, &BigInt) -> Result<BigInt, VmError>,
+) -> Result<BigInt, VmError> {
+    match coerce(&b)? {
+        Some(b) => f(a, &b),
+        None => Err(unsupported(protocol, BigInt::type_info(), &b)),
+    }
+}
+
+fn op_assign(
+    protocol: Protocol,
+    a: &mut BigInt,
+    b: Value,
+    f: fn(&BigInt, &BigInt) -> Result<BigInt, VmError>,
+) -> Result<(), VmError> {
+    *a = op(protocol, a, b, f)?;
+    Ok(())
+}
+
+fn add(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    Ok(a + b)
+}
+
+fn sub(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    Ok(a - b)
+}
+
+fn mul(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    Ok(a * b)
+}
+
+fn div(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    if *b == BigInt::default() {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    Ok(a / b)
+}
+
+fn rem(a: &BigInt, b: &BigInt) -> Result<BigInt, VmError> {
+    if *b == BigInt::default() {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    Ok(a % b)
+}
+
+crate::__internal_impl_any!(BigInt);
+crate::__internal_impl_any!(ParseBigIntError);
@@ -1,6 +1,6 @@
 //! `std::bytes` module.
 
-use crate::{Bytes, ContextError, Module};
+use crate::{Bytes, BytesView, ContextError, Module};
 
 /// Construct the `std::bytes` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -24,5 +24,13 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("reserve_exact", Bytes::reserve_exact)?;
     module.inst_fn("clone", Bytes::clone)?;
     module.inst_fn("shrink_to_fit", Bytes::shrink_to_fit)?;
+
+    module.ty::<BytesView>()?;
+    module.inst_fn("len", BytesView::len)?;
+    module.inst_fn("is_empty", BytesView::is_empty)?;
+    module.inst_fn("is_mutable", BytesView::is_mutable)?;
+    module.inst_fn("get", BytesView::get)?;
+    module.inst_fn("set", BytesView::set)?;
+    module.inst_fn("to_bytes", BytesView::to_bytes)?;
     Ok(module)
 }
@@ -18,6 +18,9 @@ pub fn module() -> Result<Module, ContextError> {
     module.function(&["is_whitespace"], char::is_whitespace)?;
 
     module.function(&["to_digit"], char::to_digit)?;
+    module.function(&["to_int"], to_int)?;
+
+    module.inst_fn("to_int", to_int)?;
 
     Ok(module)
 }
@@ -32,4 +35,9 @@ fn char_from_int_impl(value: i64) -> Result<Option<Value>, VmError> {
     }
 }
 
+/// Convert a character to its unicode scalar value.
+fn to_int(c: char) -> i64 {
+    c as i64
+}
+
 crate::__internal_impl_any!(ParseCharError);
@@ -0,0 +1,19 @@
+//! The `std::clone` module.
+
+use crate::{ContextError, Module, Value, VmError};
+
+/// Construct the `std::clone` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", &["clone"]);
+    module.function(&["clone"], clone_impl)?;
+    module.function(&["deep_clone"], deep_clone_impl)?;
+    Ok(module)
+}
+
+fn clone_impl(value: Value) -> Result<Value, VmError> {
+    value.shallow_clone()
+}
+
+fn deep_clone_impl(value: Value) -> Result<Value, VmError> {
+    value.deep_clone()
+}
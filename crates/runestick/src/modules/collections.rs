@@ -1,6 +1,8 @@
 //! `std::collections` module.
 
-use crate::{Any, ContextError, Iterator, Key, Module, Ref, Value, VmError, VmErrorKind};
+use crate::{
+    Any, AnyObj, ContextError, Iterator, Key, Module, Ref, ToValue, Value, VmError, VmErrorKind,
+};
 use std::fmt;
 
 #[derive(Any, Clone)]
@@ -94,8 +96,8 @@ impl HashMap {
     }
 
     #[inline]
-    fn remove(&mut self, key: Key) {
-        self.map.remove(&key);
+    fn remove(&mut self, key: Key) -> Option<Value> {
+        self.map.remove(&key)
     }
 
     #[inline]
@@ -103,6 +105,21 @@ impl HashMap {
         use std::fmt::Write as _;
         write!(s, "{:?}", self.map)
     }
+
+    /// Clone the hashmap, deep cloning all of its values.
+    fn deep_clone(&self) -> Result<Self, VmError> {
+        self.deep_clone_with(&mut Vec::new())
+    }
+
+    fn deep_clone_with(&self, visited: &mut Vec<usize>) -> Result<Self, VmError> {
+        let map = self
+            .map
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), v.deep_clone_with(visited)?)))
+            .collect::<Result<_, VmError>>()?;
+
+        Ok(Self { map })
+    }
 }
 
 #[derive(Any, Clone)]
@@ -163,8 +180,8 @@ impl HashSet {
     }
 
     #[inline]
-    fn remove(&mut self, key: Key) {
-        self.set.remove(&key);
+    fn remove(&mut self, key: Key) -> bool {
+        self.set.remove(&key)
     }
 
     #[inline]
@@ -331,6 +348,21 @@ impl VecDeque {
         Ok(())
     }
 
+    /// Clone the deque, deep cloning all of its values.
+    fn deep_clone(&self) -> Result<Self, VmError> {
+        self.deep_clone_with(&mut Vec::new())
+    }
+
+    fn deep_clone_with(&self, visited: &mut Vec<usize>) -> Result<Self, VmError> {
+        let inner = self
+            .inner
+            .iter()
+            .map(|v| v.deep_clone_with(visited))
+            .collect::<Result<_, VmError>>()?;
+
+        Ok(Self { inner })
+    }
+
     fn rotate_left(&mut self, mid: usize) {
         self.inner.rotate_left(mid);
     }
@@ -427,6 +459,8 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn(crate::Protocol::INDEX_SET, HashMap::insert)?;
     module.inst_fn(crate::Protocol::INDEX_GET, HashMap::fallible_get)?;
     module.inst_fn(crate::Protocol::STRING_DEBUG, HashMap::string_debug)?;
+    module.inst_fn(crate::Protocol::CLONE, HashMap::clone)?;
+    module.inst_fn(crate::Protocol::DEEP_CLONE, HashMap::deep_clone)?;
 
     module.ty::<HashSet>()?;
     module.function(&["HashSet", "new"], HashSet::new)?;
@@ -446,6 +480,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn(crate::Protocol::INTO_ITER, HashSet::iter)?;
     module.inst_fn(crate::Protocol::STRING_DEBUG, HashSet::string_debug)?;
     module.inst_fn(crate::Protocol::EQ, HashSet::eq)?;
+    module.inst_fn(crate::Protocol::CLONE, HashSet::clone)?;
 
     module.ty::<VecDeque>()?;
     module.function(&["VecDeque", "new"], VecDeque::new)?;
@@ -468,10 +503,32 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn(crate::Protocol::INDEX_SET, VecDeque::set)?;
     module.inst_fn(crate::Protocol::INTO_ITER, VecDeque::iter)?;
     module.inst_fn(crate::Protocol::STRING_DEBUG, VecDeque::string_debug)?;
+    module.inst_fn(crate::Protocol::CLONE, VecDeque::clone)?;
+    module.inst_fn(crate::Protocol::DEEP_CLONE, VecDeque::deep_clone)?;
 
     Ok(module)
 }
 
+/// Deep clone the given value if it's one of the collections in this module.
+///
+/// This bypasses [Protocol::DEEP_CLONE][crate::Protocol::DEEP_CLONE] so that
+/// the containers being visited by the outer deep clone are tracked through
+/// the collection.
+pub(crate) fn deep_clone_with(
+    any: &AnyObj,
+    visited: &mut Vec<usize>,
+) -> Result<Option<Value>, VmError> {
+    if let Some(map) = any.downcast_borrow_ref::<HashMap>() {
+        return Ok(Some(map.deep_clone_with(visited)?.to_value()?));
+    }
+
+    if let Some(deque) = any.downcast_borrow_ref::<VecDeque>() {
+        return Ok(Some(deque.deep_clone_with(visited)?.to_value()?));
+    }
+
+    Ok(None)
+}
+
 fn hashmap_from(value: Value) -> Result<HashMap, VmError> {
     use crate::FromValue as _;
 
@@ -1,6 +1,6 @@
 //! The core `std` module.
 
-use crate::{ContextError, Module, Panic, Value};
+use crate::{ComponentRef, ContextError, Module, Panic, Value, VariantData, VmError, VmErrorKind};
 
 /// Construct the `std` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -14,8 +14,14 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<i64>()?;
 
     module.function(&["panic"], panic_impl)?;
+    module.function(&["ensure"], ensure_impl)?;
+    // NB: lives next to the `assert!` macro so that the prelude resolves both.
+    module.function(&["test", "assert"], assert_impl)?;
     module.function(&["is_readable"], is_readable)?;
     module.function(&["is_writable"], is_writable)?;
+    module.function(&["fields"], fields)?;
+    module.function(&["variant_name"], variant_name)?;
+    module.function(&["call_depth"], call_depth)?;
     Ok(module)
 }
 
@@ -23,6 +29,26 @@ fn panic_impl(m: &str) -> Result<(), Panic> {
     Err(Panic::custom(m.to_owned()))
 }
 
+/// Raise an error with the given message unless the condition holds.
+fn ensure_impl(condition: bool, message: &str) -> Result<(), VmError> {
+    if condition {
+        return Ok(());
+    }
+
+    Err(VmError::from(VmErrorKind::EnsureFailed {
+        message: message.to_owned(),
+    }))
+}
+
+/// Panic with the given message unless the condition holds.
+fn assert_impl(condition: bool, message: &str) -> Result<(), Panic> {
+    if condition {
+        return Ok(());
+    }
+
+    Err(Panic::custom(message.to_owned()))
+}
+
 fn is_readable(value: Value) -> bool {
     match value {
         Value::Any(any) => any.is_readable(),
@@ -54,3 +80,75 @@ fn is_writable(value: Value) -> bool {
         _ => true,
     }
 }
+
+/// Get the names of the fields of a value, sorted by name.
+///
+/// External types only report fields which have a getter registered. Values
+/// without named fields, like tuples and vectors, have no fields.
+fn fields(value: Value) -> Result<Vec<String>, VmError> {
+    let mut fields: Vec<String> = match value {
+        Value::Object(object) => object.borrow_ref()?.keys().cloned().collect(),
+        Value::Struct(object) => object.borrow_ref()?.data().keys().cloned().collect(),
+        Value::Variant(variant) => match variant.borrow_ref()?.data() {
+            VariantData::Struct(object) => object.keys().cloned().collect(),
+            _ => Vec::new(),
+        },
+        Value::Any(any) => {
+            let type_hash = any.borrow_ref()?.type_hash();
+
+            crate::env::with(|context, _| {
+                Ok(match context.fields.get(&type_hash) {
+                    Some(fields) => fields.iter().cloned().collect(),
+                    None => Vec::new(),
+                })
+            })?
+        }
+        _ => Vec::new(),
+    };
+
+    fields.sort();
+    Ok(fields)
+}
+
+/// Get the name of the variant of an enum value, like `"Some"` for
+/// `Some(1)`.
+///
+/// Errors if the value isn't an enum variant.
+fn variant_name(value: Value) -> Result<String, VmError> {
+    let name = match value {
+        Value::Variant(variant) => {
+            let variant = variant.borrow_ref()?;
+
+            match variant.rtti().item.last() {
+                Some(ComponentRef::Str(name)) => return Ok(name.to_owned()),
+                _ => return Ok(variant.rtti().item.to_string()),
+            }
+        }
+        Value::Option(option) => match &*option.borrow_ref()? {
+            Some(..) => "Some",
+            None => "None",
+        },
+        Value::Result(result) => match &*result.borrow_ref()? {
+            Ok(..) => "Ok",
+            Err(..) => "Err",
+        },
+        actual => {
+            return Err(VmError::from(VmErrorKind::ExpectedVariant {
+                actual: actual.type_info()?,
+            }))
+        }
+    };
+
+    Ok(name.to_owned())
+}
+
+/// Get the current call depth of the virtual machine.
+///
+/// This is the number of call frames which are active below the function
+/// calling it, so it is `0` in the entry function of the virtual machine.
+///
+/// Tail calls re-use the frame of their caller and don't increase the depth,
+/// so this under-reports the depth during tail recursion.
+fn call_depth() -> Result<usize, VmError> {
+    crate::env::call_depth()
+}
@@ -1,18 +1,56 @@
 //! The `std::float` module.
 
-use crate::{ContextError, Module};
+use crate::{ContextError, Module, Panic};
 use std::num::ParseFloatError;
 
-/// Parse an integer.
+/// Parse a float.
+///
+/// Surrounding whitespace is not trimmed and causes an error, as does an empty
+/// string.
 fn parse(s: &str) -> Result<f64, ParseFloatError> {
     str::parse::<f64>(s)
 }
 
+/// Restrict a value to the range `lo..=hi`, panicking the virtual machine if
+/// `lo > hi` or either bound is NaN. A NaN value is returned as-is.
+fn clamp(value: f64, lo: f64, hi: f64) -> Result<f64, Panic> {
+    if lo.is_nan() || hi.is_nan() {
+        return Err(Panic::custom("clamp bounds must not be NaN"));
+    }
+
+    if lo > hi {
+        return Err(Panic::custom(format!(
+            "clamp lower bound `{}` is greater than upper bound `{}`",
+            lo, hi
+        )));
+    }
+
+    Ok(value.clamp(lo, hi))
+}
+
 /// Convert a float to a whole number.
 fn to_integer(value: f64) -> i64 {
     value as i64
 }
 
+/// Convert a float to a whole number by truncating it, panicking the virtual
+/// machine if the value is NaN or out of range.
+fn to_int(value: f64) -> Result<i64, Panic> {
+    if value.is_nan() {
+        return Err(Panic::custom("cannot convert NaN to an integer"));
+    }
+
+    // NB: `i64::MAX as f64` rounds up to 2^63, which is out of range.
+    if value < i64::MIN as f64 || value >= i64::MAX as f64 {
+        return Err(Panic::custom(format!(
+            "`{}` is out of range for an integer",
+            value
+        )));
+    }
+
+    Ok(value as i64)
+}
+
 crate::__internal_impl_any!(ParseFloatError);
 
 /// Install the core package into the given functions namespace.
@@ -21,13 +59,20 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.ty::<ParseFloatError>()?;
     module.function(&["parse"], parse)?;
+    module.function(&["max"], f64::max)?;
+    module.function(&["min"], f64::min)?;
+    module.function(&["clamp"], clamp)?;
     module.inst_fn("max", f64::max)?;
     module.inst_fn("min", f64::min)?;
+    module.inst_fn("clamp", clamp)?;
     module.inst_fn("abs", f64::abs)?;
     module.inst_fn("powf", f64::powf)?;
     module.inst_fn("powi", f64::powi)?;
 
+    module.function(&["to_int"], to_int)?;
+
     module.inst_fn("to_integer", to_integer)?;
+    module.inst_fn("to_int", to_int)?;
 
     Ok(module)
 }
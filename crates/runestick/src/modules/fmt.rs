@@ -1,6 +1,7 @@
 //! The `std::fmt` module.
 
-use crate::{ContextError, Module, Protocol};
+use crate::format::{FormatStr, FormatStrPiece};
+use crate::{ContextError, FromValue as _, Module, Protocol, Stack, Value, VmError, VmErrorKind};
 use std::fmt;
 use std::fmt::Write as _;
 
@@ -11,9 +12,48 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn(Protocol::STRING_DISPLAY, format_fmt_error)?;
 
     module.ty::<crate::Format>()?;
+    module.raw_fn(&["format"], format_impl)?;
     Ok(module)
 }
 
 fn format_fmt_error(error: &std::fmt::Error, buf: &mut String) -> fmt::Result {
     write!(buf, "{}", error)
 }
+
+/// Format arguments into a string at runtime.
+///
+/// The first argument is a [FormatStr], and its placeholders are replaced with
+/// the display of the remaining arguments.
+fn format_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    let mut values = stack.drain_stack_top(args)?;
+
+    let format = match values.next() {
+        Some(format) => String::from_value(format)?,
+        None => {
+            return Err(VmError::from(VmErrorKind::BadArgumentCount {
+                actual: args,
+                expected: 1,
+            }));
+        }
+    };
+
+    let values = values.collect::<Vec<_>>();
+    let format = FormatStr::parse(&format, values.len()).map_err(VmError::panic)?;
+
+    let mut out = String::new();
+    let mut buf = String::new();
+
+    for piece in format.pieces() {
+        match piece {
+            FormatStrPiece::Literal(literal) => out.push_str(literal),
+            FormatStrPiece::Argument(n) => {
+                if let Err(fmt::Error) = values[*n].string_display(&mut out, &mut buf)? {
+                    return Err(VmError::panic("failed to format argument"));
+                }
+            }
+        }
+    }
+
+    stack.push(Value::from(out));
+    Ok(())
+}
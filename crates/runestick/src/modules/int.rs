@@ -1,6 +1,7 @@
 //! The `std::int` module.
 
-use crate::{ContextError, Module};
+use crate::{ContextError, Module, Panic};
+use std::convert::TryFrom;
 use std::num::ParseIntError;
 
 /// Construct the `std::int` module.
@@ -10,13 +11,22 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<ParseIntError>()?;
 
     module.function(&["parse"], parse)?;
+    module.function(&["parse_radix"], parse_radix)?;
     module.function(&["max"], i64::max)?;
     module.function(&["min"], i64::min)?;
     module.function(&["abs"], i64::abs)?;
+    module.function(&["clamp"], clamp)?;
+
+    module.function(&["to_float"], to_float)?;
+    module.function(&["to_char"], to_char)?;
 
     module.inst_fn("to_float", to_float)?;
+    module.inst_fn("to_char", to_char)?;
 
     module.inst_fn("abs", i64::abs)?;
+    module.inst_fn("min", i64::min)?;
+    module.inst_fn("max", i64::max)?;
+    module.inst_fn("clamp", clamp)?;
     module.inst_fn("checked_add", i64::checked_add)?;
     module.inst_fn("checked_sub", i64::checked_sub)?;
     module.inst_fn("checked_div", i64::checked_div)?;
@@ -40,13 +50,56 @@ pub fn module() -> Result<Module, ContextError> {
 }
 
 /// Parse an integer.
+///
+/// Surrounding whitespace is not trimmed and causes an error, as does an empty
+/// string or a number which doesn't fit in an integer.
 fn parse(s: &str) -> Result<i64, ParseIntError> {
     str::parse::<i64>(s)
 }
 
+/// Parse an integer in the given radix, which must be in the range `2..=36`.
+///
+/// This follows the same rules as [parse], but panics the virtual machine if
+/// the radix is out of range.
+fn parse_radix(s: &str, radix: u32) -> Result<Result<i64, ParseIntError>, Panic> {
+    if !(2..=36).contains(&radix) {
+        return Err(Panic::custom(format!(
+            "radix `{}` is not in the range 2..=36",
+            radix
+        )));
+    }
+
+    Ok(i64::from_str_radix(s, radix))
+}
+
+/// Restrict a value to the range `lo..=hi`, panicking the virtual machine if
+/// `lo > hi`.
+fn clamp(value: i64, lo: i64, hi: i64) -> Result<i64, Panic> {
+    if lo > hi {
+        return Err(Panic::custom(format!(
+            "clamp lower bound `{}` is greater than upper bound `{}`",
+            lo, hi
+        )));
+    }
+
+    Ok(value.clamp(lo, hi))
+}
+
 /// Convert a whole number to float.
 fn to_float(value: i64) -> f64 {
     value as f64
 }
 
+/// Convert a whole number to the character with the same unicode scalar value,
+/// panicking the virtual machine if there is no such character.
+fn to_char(value: i64) -> Result<char, Panic> {
+    match u32::try_from(value).ok().and_then(std::char::from_u32) {
+        Some(c) => Ok(c),
+        None => Err(Panic::custom(format!(
+            "`{}` is not a valid unicode scalar value",
+            value
+        ))),
+    }
+}
+
 crate::__internal_impl_any!(ParseIntError);
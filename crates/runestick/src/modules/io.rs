@@ -1,10 +1,9 @@
 //! The `std::io` module.
 
-use crate::{ContextError, Module, Panic, Protocol, Stack, Value, VmError};
+use crate::{ContextError, FromValue, Module, Panic, Protocol, Stack, Tuple, Value, VmError};
 use std::fmt;
 use std::fmt::Write as _;
 use std::io;
-use std::io::Write as _;
 
 /// Construct the `std::io` module.
 pub fn module(stdio: bool) -> Result<Module, ContextError> {
@@ -16,7 +15,7 @@ pub fn module(stdio: bool) -> Result<Module, ContextError> {
     if stdio {
         module.function(&["print"], print_impl)?;
         module.function(&["println"], println_impl)?;
-        module.raw_fn(&["dbg"], dbg_impl)?;
+        module.raw_fn_with_call_site(&["dbg"], dbg_impl, dbg_at_impl)?;
     }
 
     Ok(module)
@@ -27,25 +26,80 @@ fn format_io_error(error: &std::io::Error, buf: &mut String) -> fmt::Result {
 }
 
 fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
+    // NB: formatting might call back into the virtual machine, so the output
+    // is buffered to avoid holding on to it in the meantime.
+    let mut buf = Vec::new();
+    dbg_to(&mut buf, stack, args)?;
+    write_output(&buf)
+}
+
+fn dbg_at_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    let mut buf = Vec::new();
+    dbg_at_to(&mut buf, stack, args)?;
+    write_output(&buf)
+}
+
+fn write_output(buf: &[u8]) -> Result<(), VmError> {
+    crate::env::with_output(|out| out.write_all(buf)).map_err(VmError::panic)
+}
 
-    for value in stack.drain_stack_top(args)? {
-        writeln!(stdout, "{:?}", value).map_err(VmError::panic)?;
+/// Format the given value using the [Protocol::STRING_DEBUG] protocol.
+fn string_debug(value: &Value) -> Result<String, VmError> {
+    let mut s = String::new();
+    value.string_debug(&mut s)?.map_err(VmError::panic)?;
+    Ok(s)
+}
+
+/// Implementation of `dbg` which writes to the given output.
+///
+/// Prints the debug representation of each argument as formatted by the
+/// [Protocol::STRING_DEBUG] protocol and returns them, so that calls can be
+/// wrapped around existing expressions. This can be used to
+/// provide `dbg` in modules which redirect the output somewhere else.
+pub fn dbg_to(out: &mut dyn io::Write, stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    let values = stack.drain_stack_top(args)?.collect::<Vec<_>>();
+
+    for value in &values {
+        writeln!(out, "{}", string_debug(value)?).map_err(VmError::panic)?;
     }
 
-    stack.push(Value::Unit);
+    stack.push(dbg_return(values));
     Ok(())
 }
 
+/// Implementation of the variant of `dbg` used by the compiler for direct
+/// calls which writes to the given output, where each argument is preceded by
+/// a string describing its call site.
+pub fn dbg_at_to(out: &mut dyn io::Write, stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    let mut values = Vec::with_capacity(args / 2);
+    let mut it = stack.drain_stack_top(args)?;
+
+    while let (Some(location), Some(value)) = (it.next(), it.next()) {
+        let location = String::from_value(location)?;
+        let debug = string_debug(&value)?;
+        writeln!(out, "{} = {}", location, debug).map_err(VmError::panic)?;
+        values.push(value);
+    }
+
+    drop(it);
+    stack.push(dbg_return(values));
+    Ok(())
+}
+
+/// Construct the return value of `dbg`, which is unit for no arguments, the
+/// argument itself for one argument, or a tuple of all arguments.
+fn dbg_return(mut values: Vec<Value>) -> Value {
+    match values.len() {
+        0 => Value::Unit,
+        1 => values.pop().unwrap(),
+        _ => Value::from(Tuple::from(values)),
+    }
+}
+
 fn print_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    write!(stdout, "{}", m).map_err(Panic::custom)
+    crate::env::with_output(|out| write!(out, "{}", m)).map_err(Panic::custom)
 }
 
 fn println_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    writeln!(stdout, "{}", m).map_err(Panic::custom)
+    crate::env::with_output(|out| writeln!(out, "{}", m)).map_err(Panic::custom)
 }
@@ -2,8 +2,10 @@
 //! machines.
 
 pub mod any;
+pub mod bigint;
 pub mod bytes;
 pub mod char;
+pub mod clone;
 pub mod cmp;
 pub mod collections;
 pub mod core;
@@ -21,4 +23,5 @@ pub mod option;
 pub mod result;
 pub mod stream;
 pub mod string;
+pub mod time;
 pub mod vec;
@@ -11,6 +11,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("len", Object::len)?;
     module.inst_fn("insert", Object::insert)?;
     module.inst_fn("clear", Object::clear)?;
+    module.inst_fn("clone", Object::clone)?;
     module.inst_fn("contains_key", contains_key)?;
     module.inst_fn("get", get)?;
 
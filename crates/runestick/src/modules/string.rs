@@ -1,6 +1,7 @@
 //! The `std::string` module.
 
 use crate::{Any, Bytes, ContextError, Iterator, Module, Protocol, Value, VmError, VmErrorKind};
+use std::fmt;
 
 /// Construct the `std::string` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -13,7 +14,8 @@ pub fn module() -> Result<Module, ContextError> {
     module.function(&["String", "with_capacity"], String::with_capacity)?;
 
     module.inst_fn("cmp", str::cmp)?;
-    module.inst_fn("len", String::len)?;
+    module.inst_fn("len", string_len)?;
+    module.inst_fn("byte_len", String::len)?;
     module.inst_fn("starts_with", str::starts_with::<&str>)?;
     module.inst_fn("ends_with", str::ends_with::<&str>)?;
     module.inst_fn("capacity", String::capacity)?;
@@ -35,7 +37,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("is_empty", str::is_empty)?;
     module.inst_fn("chars", string_chars)?;
     module.inst_fn(Protocol::ADD, add)?;
-    module.inst_fn(Protocol::ADD_ASSIGN, String::push_str)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, add_assign)?;
     module.inst_fn(Protocol::INDEX_GET, string_index_get)?;
     module.inst_fn("get", string_get)?;
 
@@ -67,12 +69,20 @@ fn into_bytes(s: String) -> Bytes {
     Bytes::from_vec(s.into_bytes())
 }
 
-fn char_at(s: &str, index: usize) -> Option<char> {
-    if !s.is_char_boundary(index) {
-        return None;
-    }
+/// Get the number of characters in a string.
+///
+/// The number of bytes is available through `byte_len`.
+fn string_len(s: &str) -> usize {
+    s.chars().count()
+}
 
-    s[index..].chars().next()
+/// Get the character at the given character index.
+///
+/// Note that this counts characters, unlike indexing a string with a range or
+/// `get`, which use byte offsets. Indexing past the last character returns
+/// `None`.
+fn char_at(s: &str, index: usize) -> Option<char> {
+    s.chars().nth(index)
 }
 
 fn string_split(this: &str, value: Value) -> Result<Iterator, VmError> {
@@ -112,13 +122,31 @@ fn parse_char(s: &str) -> Result<char, std::char::ParseCharError> {
 }
 
 /// The add operation for strings.
-fn add(a: &str, b: &str) -> String {
-    let mut string = String::with_capacity(a.len() + b.len());
-    string.push_str(a);
-    string.push_str(b);
-    string
+/// Add a value to a string. Values which aren't strings are formatted using
+/// the display protocol, just like when concatenating with a string literal.
+fn add(a: &str, b: Value) -> Result<String, VmError> {
+    let mut string = String::from(a);
+    add_assign(&mut string, b)?;
+    Ok(string)
+}
+
+fn add_assign(a: &mut String, b: Value) -> Result<(), VmError> {
+    match b {
+        Value::String(b) => a.push_str(&*b.borrow_ref()?),
+        Value::StaticString(b) => a.push_str(&**b),
+        b => {
+            let mut buf = String::new();
+
+            if let Err(fmt::Error) = b.string_display(a, &mut buf)? {
+                return Err(VmError::from(VmErrorKind::FormatError));
+            }
+        }
+    }
+
+    Ok(())
 }
 
+/// Iterate over the characters of a string.
 fn string_chars(s: &str) -> Iterator {
     let iter = s.chars().collect::<Vec<_>>().into_iter();
     Iterator::from_double_ended("std::str::Chars", iter)
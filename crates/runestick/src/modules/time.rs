@@ -0,0 +1,76 @@
+//! The `std::time` module.
+
+use crate::{ContextError, Future, Module, Protocol, VmError, VmErrorKind};
+use std::fmt;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+/// Construct the `std::time` module.
+///
+/// Time is read from the clock configured through
+/// [Context::set_clock][crate::Context::set_clock].
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", &["time"]);
+
+    module.ty::<Duration>()?;
+
+    module.function(&["Duration", "from_secs"], Duration::from_secs)?;
+    module.function(&["Duration", "from_millis"], Duration::from_millis)?;
+    module.function(&["Duration", "from_secs_f64"], from_secs_f64)?;
+
+    module.inst_fn("as_secs", Duration::as_secs)?;
+    module.inst_fn("as_millis", Duration::as_millis)?;
+    module.inst_fn("as_secs_f64", Duration::as_secs_f64)?;
+
+    module.inst_fn(Protocol::STRING_DEBUG, format_duration)?;
+    module.inst_fn(Protocol::EQ, |a: &Duration, b: &Duration| a == b)?;
+    module.inst_fn(Protocol::ADD, add)?;
+    module.inst_fn(Protocol::SUB, sub)?;
+
+    module.function(&["now"], now)?;
+    module.function(&["sleep"], sleep)?;
+    Ok(module)
+}
+
+/// Get the current time of the configured clock.
+fn now() -> Result<Duration, VmError> {
+    crate::env::with(|context, _| Ok(context.clock().now()))
+}
+
+/// Sleep for the given duration, according to the configured clock.
+///
+/// The clock is resolved when the function is called, so the returned future
+/// can be awaited outside of the virtual machine.
+fn sleep(duration: &Duration) -> Result<Future, VmError> {
+    let clock = crate::env::with(|context, _| Ok(context.clock()))?;
+    let sleep = clock.sleep(*duration);
+
+    Ok(Future::new(async move {
+        sleep.await;
+        Ok::<_, VmError>(())
+    }))
+}
+
+fn from_secs_f64(secs: f64) -> Result<Duration, VmError> {
+    if !secs.is_finite() || secs < 0.0 {
+        return Err(VmError::panic("duration must be a finite positive number"));
+    }
+
+    Ok(Duration::from_secs_f64(secs))
+}
+
+fn format_duration(duration: &Duration, buf: &mut String) -> fmt::Result {
+    write!(buf, "{:?}", duration)
+}
+
+fn add(a: &Duration, b: &Duration) -> Result<Duration, VmError> {
+    a.checked_add(*b)
+        .ok_or_else(|| VmError::from(VmErrorKind::Overflow))
+}
+
+fn sub(a: &Duration, b: &Duration) -> Result<Duration, VmError> {
+    a.checked_sub(*b)
+        .ok_or_else(|| VmError::from(VmErrorKind::Underflow))
+}
+
+crate::__internal_impl_any!(Duration);
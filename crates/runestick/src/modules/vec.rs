@@ -1,6 +1,8 @@
 //! The `std::vec` module.
 
-use crate::{ContextError, Module, Protocol, Value, Vec};
+use crate::protocol_caller::EnvProtocolCaller;
+use crate::{ContextError, Function, Module, Protocol, Value, Vec, VmError};
+use std::cmp::Ordering;
 
 /// Construct the `std::vec` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -18,6 +20,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("pop", Vec::pop)?;
     module.inst_fn("push", Vec::push)?;
     module.inst_fn("remove", Vec::remove)?;
+    module.inst_fn("sort", sort)?;
     module.inst_fn("sort_by", sort_by)?;
     module.inst_fn("insert", Vec::insert)?;
     module.inst_fn(Protocol::INTO_ITER, Vec::into_iterator)?;
@@ -42,10 +45,50 @@ fn vec_get(vec: &Vec, index: usize) -> Option<Value> {
     vec.get(index).cloned()
 }
 
-fn sort_by(vec: &mut Vec, comparator: &crate::Function) {
-    vec.sort_by(|a, b| {
-        comparator
-            .call::<_, std::cmp::Ordering>((a, b))
-            .expect("an ordering")
+/// Sort a vector, using the [Protocol::CMP] protocol for values which are
+/// not primitives.
+fn sort(vec: &mut Vec) -> Result<(), VmError> {
+    merge_sort_by(vec, &mut |a, b| {
+        Value::value_ptr_cmp(&mut EnvProtocolCaller, a, b)
     })
 }
+
+fn sort_by(vec: &mut Vec, comparator: &Function) -> Result<(), VmError> {
+    merge_sort_by(vec, &mut |a, b| comparator.call::<_, Ordering>((a, b)))
+}
+
+/// A stable merge sort.
+///
+/// Unlike the sorts in the standard library, this never panics if `compare`
+/// doesn't implement a total order. The order of the values is unspecified in
+/// that case.
+fn merge_sort_by<F>(values: &mut [Value], compare: &mut F) -> Result<(), VmError>
+where
+    F: FnMut(&Value, &Value) -> Result<Ordering, VmError>,
+{
+    if values.len() <= 1 {
+        return Ok(());
+    }
+
+    let mid = values.len() / 2;
+    merge_sort_by(&mut values[..mid], compare)?;
+    merge_sort_by(&mut values[mid..], compare)?;
+
+    let mut merged = std::vec::Vec::with_capacity(values.len());
+    let (mut a, mut b) = (0, mid);
+
+    while a < mid && b < values.len() {
+        if compare(&values[b], &values[a])? == Ordering::Less {
+            merged.push(values[b].clone());
+            b += 1;
+        } else {
+            merged.push(values[a].clone());
+            a += 1;
+        }
+    }
+
+    merged.extend_from_slice(&values[a..mid]);
+    merged.extend_from_slice(&values[b..]);
+    values.clone_from_slice(&merged);
+    Ok(())
+}
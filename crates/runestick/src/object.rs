@@ -1,4 +1,4 @@
-use crate::collections::{btree_map, BTreeMap};
+use crate::collections::{index_map, IndexMap};
 use crate::{
     FromValue, InstallWith, Item, Mut, Named, RawMut, RawRef, RawStr, Ref, ToValue,
     UnsafeFromValue, Value, Vm, VmError,
@@ -14,7 +14,7 @@ use std::hash;
 ///
 /// [`into_iter`]: struct.Object.html#method.into_iter
 /// [`Object`]: struct.Object.html
-pub type IntoIter = btree_map::IntoIter<String, Value>;
+pub type IntoIter = index_map::IntoIter<String, Value>;
 
 /// A mutable iterator over the entries of a `Object`.
 ///
@@ -23,7 +23,7 @@ pub type IntoIter = btree_map::IntoIter<String, Value>;
 ///
 /// [`iter_mut`]: struct.Object.html#method.iter_mut
 /// [`Object`]: struct.Object.html
-pub type IterMut<'a> = btree_map::IterMut<'a, String, Value>;
+pub type IterMut<'a> = index_map::IterMut<'a, String, Value>;
 
 /// An iterator over the entries of a `Object`.
 ///
@@ -32,7 +32,7 @@ pub type IterMut<'a> = btree_map::IterMut<'a, String, Value>;
 ///
 /// [`iter`]: struct.Object.html#method.iter
 /// [`Object`]: struct.Object.html
-pub type Iter<'a> = btree_map::Iter<'a, String, Value>;
+pub type Iter<'a> = index_map::Iter<'a, String, Value>;
 
 /// An iterator over the keys of a `HashMap`.
 ///
@@ -41,7 +41,7 @@ pub type Iter<'a> = btree_map::Iter<'a, String, Value>;
 ///
 /// [`keys`]: struct.Object.html#method.keys
 /// [`Object`]: struct.Object.html
-pub type Keys<'a> = btree_map::Keys<'a, String, Value>;
+pub type Keys<'a> = index_map::Keys<'a, String, Value>;
 
 /// An iterator over the values of a `HashMap`.
 ///
@@ -50,10 +50,14 @@ pub type Keys<'a> = btree_map::Keys<'a, String, Value>;
 ///
 /// [`values`]: struct.Object.html#method.values
 /// [`Object`]: struct.Object.html
-pub type Values<'a> = btree_map::Values<'a, String, Value>;
+pub type Values<'a> = index_map::Values<'a, String, Value>;
 
 /// Struct representing a dynamic anonymous object.
 ///
+/// Entries are kept in the order in which they were first inserted. Inserting
+/// a key which is already present replaces its value but keeps its position,
+/// while removing a key shifts the entries after it.
+///
 /// # Examples
 ///
 /// ```rust
@@ -73,7 +77,7 @@ pub type Values<'a> = btree_map::Values<'a, String, Value>;
 #[derive(Default, Clone)]
 #[repr(transparent)]
 pub struct Object {
-    inner: BTreeMap<String, Value>,
+    inner: IndexMap<String, Value>,
 }
 
 impl Object {
@@ -81,16 +85,15 @@ impl Object {
     #[inline]
     pub fn new() -> Self {
         Self {
-            inner: BTreeMap::new(),
+            inner: IndexMap::new(),
         }
     }
 
     /// Construct a new object with the given capacity.
     #[inline]
-    pub fn with_capacity(_cap: usize) -> Self {
-        /* BTreeMap doesn't support setting capacity on creation but we keep this here in case we want to switch store later */
+    pub fn with_capacity(cap: usize) -> Self {
         Self {
-            inner: BTreeMap::new(),
+            inner: IndexMap::with_capacity(cap),
         }
     }
 
@@ -152,14 +155,15 @@ impl Object {
     }
 
     /// Removes a key from the object, returning the value at the key if the key
-    /// was previously in the object.
+    /// was previously in the object. The order of the remaining entries is
+    /// preserved.
     #[inline]
     pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<Value>
     where
         String: borrow::Borrow<Q>,
         Q: hash::Hash + cmp::Eq + cmp::Ord,
     {
-        self.inner.remove(k)
+        self.inner.shift_remove(k)
     }
 
     /// Inserts a key-value pair into the dynamic object, converting it as
@@ -187,29 +191,29 @@ impl Object {
     }
 
     /// Convert into inner.
-    pub fn into_inner(self) -> BTreeMap<String, Value> {
+    pub fn into_inner(self) -> IndexMap<String, Value> {
         self.inner
     }
 
-    /// An iterator visiting all key-value pairs in arbitrary order.
+    /// An iterator visiting all key-value pairs in insertion order.
     /// The iterator element type is `(&'a String, &'a Value)`.
     pub fn iter(&self) -> Iter<'_> {
         self.inner.iter()
     }
 
-    /// An iterator visiting all keys in arbitrary order.
+    /// An iterator visiting all keys in insertion order.
     /// The iterator element type is `&'a String`.
     pub fn keys(&self) -> Keys<'_> {
         self.inner.keys()
     }
 
-    /// An iterator visiting all values in arbitrary order.
+    /// An iterator visiting all values in insertion order.
     /// The iterator element type is `&'a Value`.
     pub fn values(&self) -> Values<'_> {
         self.inner.values()
     }
 
-    /// An iterator visiting all key-value pairs in arbitrary order,
+    /// An iterator visiting all key-value pairs in insertion order,
     /// with mutable references to the values.
     /// The iterator element type is `(&'a String, &'a mut Value)`.
     pub fn iter_mut(&mut self) -> IterMut<'_> {
@@ -256,7 +260,7 @@ impl IntoIterator for Object {
     type IntoIter = IntoIter;
 
     /// Creates a consuming iterator, that is, one that moves each key-value
-    /// pair out of the object in arbitrary order. The object cannot be used
+    /// pair out of the object in insertion order. The object cannot be used
     /// after calling this.
     fn into_iter(self) -> Self::IntoIter {
         self.inner.into_iter()
@@ -352,11 +356,12 @@ impl fmt::Display for DebugStruct<'_> {
     }
 }
 
-/// Helper function two compare two hashmaps of values.
+/// Helper function two compare two maps of values, regardless of the order of
+/// their entries.
 pub(crate) fn map_ptr_eq<K>(
     vm: &mut Vm,
-    a: &BTreeMap<K, Value>,
-    b: &BTreeMap<K, Value>,
+    a: &IndexMap<K, Value>,
+    b: &IndexMap<K, Value>,
 ) -> Result<bool, VmError>
 where
     K: cmp::Eq + cmp::Ord,
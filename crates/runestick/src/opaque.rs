@@ -0,0 +1,111 @@
+use crate::{
+    Any, Hash, InstallWith, Named, RawMut, RawRef, RawStr, TypeInfo, TypeOf, UnsafeFromValue,
+    Value, VmError,
+};
+use std::ops;
+
+/// A wrapper which allows values of a foreign type, which can't implement
+/// [Any], to be passed through the virtual machine.
+///
+/// Scripts can't access the fields of an opaque value, but they can store it
+/// and pass it back to native functions. The type is registered with
+/// [Module::opaque_ty][crate::Module::opaque_ty].
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{Module, Opaque};
+/// use std::time::Instant;
+///
+/// # fn main() -> runestick::Result<()> {
+/// let mut module = Module::default();
+/// module.opaque_ty::<Instant>("Instant")?;
+/// module.function(&["now"], || Opaque(Instant::now()))?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Opaque<T>(pub T);
+
+impl<T> Opaque<T> {
+    /// Coerce into the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> ops::Deref for Opaque<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for Opaque<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Named for Opaque<T>
+where
+    T: 'static,
+{
+    const BASE_NAME: RawStr = RawStr::from_str("Opaque");
+}
+
+impl<T> Any for Opaque<T>
+where
+    T: 'static,
+{
+    fn type_hash() -> Hash {
+        Hash::from_type_id(std::any::TypeId::of::<Self>())
+    }
+}
+
+impl<T> InstallWith for Opaque<T> where T: 'static {}
+
+impl<T> TypeOf for Opaque<T>
+where
+    T: 'static,
+{
+    fn type_hash() -> Hash {
+        <Self as Any>::type_hash()
+    }
+
+    fn type_info() -> TypeInfo {
+        TypeInfo::Any(RawStr::from_str(std::any::type_name::<T>()))
+    }
+}
+
+impl<T> UnsafeFromValue for &Opaque<T>
+where
+    T: 'static,
+{
+    type Output = *const Opaque<T>;
+    type Guard = RawRef;
+
+    fn from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        value.into_any_ptr()
+    }
+
+    unsafe fn unsafe_coerce(output: Self::Output) -> Self {
+        &*output
+    }
+}
+
+impl<T> UnsafeFromValue for &mut Opaque<T>
+where
+    T: 'static,
+{
+    type Output = *mut Opaque<T>;
+    type Guard = RawMut;
+
+    fn from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        value.into_any_mut()
+    }
+
+    unsafe fn unsafe_coerce(output: Self::Output) -> Self {
+        &mut *output
+    }
+}
@@ -67,6 +67,15 @@ impl Protocol {
         hash: Hash::new(0x418f5becbf885806),
     };
 
+    /// Compare two values, returning an [Ordering][std::cmp::Ordering].
+    ///
+    /// This is used for the `<`, `<=`, `>` and `>=` operations, and when
+    /// sorting vectors.
+    pub const CMP: Protocol = Protocol {
+        name: "cmp",
+        hash: Hash::new(0x923a95b84fabc746),
+    };
+
     /// The function to access a field.
     pub const GET: Protocol = Protocol {
         name: "get",
@@ -79,13 +88,19 @@ impl Protocol {
         hash: Hash::new(0x7d13d47fd8efef5a),
     };
 
-    /// The function to access an index.
+    /// The function to access an index, as in `value[index]`.
+    ///
+    /// Implemented with an instance function taking the index, and returning
+    /// the value stored at it.
     pub const INDEX_GET: Protocol = Protocol {
         name: "index_get",
         hash: Hash::new(0xadb5b27e2a4d2dec),
     };
 
-    /// The function to set an index.
+    /// The function to set an index, as in `value[index] = other`.
+    ///
+    /// Implemented with an instance function taking the index and the value to
+    /// store at it.
     pub const INDEX_SET: Protocol = Protocol {
         name: "index_set",
         hash: Hash::new(0x162943f7bd03ad36),
@@ -103,6 +118,13 @@ impl Protocol {
         hash: Hash::new(0x42451ccb0a2071a9),
     };
 
+    /// The function to implement for the addition operation when the value is
+    /// on the right-hand side, and the left-hand side doesn't support it.
+    pub const ADD_RHS: Protocol = Protocol {
+        name: "rhs +",
+        hash: Hash::new(0xba3561c5b2391267),
+    };
+
     /// The function to implement for the subtraction operation.
     pub const SUB: Protocol = Protocol {
         name: "-",
@@ -115,6 +137,13 @@ impl Protocol {
         hash: Hash::new(0x5939bb56a1415284),
     };
 
+    /// The function to implement for the subtraction operation when the value is
+    /// on the right-hand side, and the left-hand side doesn't support it.
+    pub const SUB_RHS: Protocol = Protocol {
+        name: "rhs -",
+        hash: Hash::new(0xcfc4a159a6a29d03),
+    };
+
     /// The function to implement for the multiply operation.
     pub const MUL: Protocol = Protocol {
         name: "*",
@@ -127,6 +156,13 @@ impl Protocol {
         hash: Hash::new(0x29a54b727f980ebf),
     };
 
+    /// The function to implement for the multiply operation when the value is
+    /// on the right-hand side, and the left-hand side doesn't support it.
+    pub const MUL_RHS: Protocol = Protocol {
+        name: "rhs *",
+        hash: Hash::new(0x42c56af9d6f7e711),
+    };
+
     /// The function to implement for the division operation.
     pub const DIV: Protocol = Protocol {
         name: "/",
@@ -139,6 +175,13 @@ impl Protocol {
         hash: Hash::new(0x4dd087a8281c04e6),
     };
 
+    /// The function to implement for the division operation when the value is
+    /// on the right-hand side, and the left-hand side doesn't support it.
+    pub const DIV_RHS: Protocol = Protocol {
+        name: "rhs /",
+        hash: Hash::new(0x39beee7df15c164b),
+    };
+
     /// The function to implement for the remainder operation.
     pub const REM: Protocol = Protocol {
         name: "%",
@@ -151,6 +194,13 @@ impl Protocol {
         hash: Hash::new(0x3a8695980e77baf4),
     };
 
+    /// The function to implement for the remainder operation when the value is
+    /// on the right-hand side, and the left-hand side doesn't support it.
+    pub const REM_RHS: Protocol = Protocol {
+        name: "rhs %",
+        hash: Hash::new(0x6419854110abb36f),
+    };
+
     /// The function to implement for the bitwise and operation.
     pub const BIT_AND: Protocol = Protocol {
         name: "&",
@@ -211,6 +261,19 @@ impl Protocol {
         hash: Hash::new(0x61ff7c46ff00e74a),
     };
 
+    /// Function used to clone an external value.
+    pub const CLONE: Protocol = Protocol {
+        name: "clone",
+        hash: Hash::new(0x2af2c875e36971eb),
+    };
+
+    /// Function used to deep clone an external value, like a collection
+    /// which contains other values.
+    pub const DEEP_CLONE: Protocol = Protocol {
+        name: "deep_clone",
+        hash: Hash::new(0x5d1c2c7e4f9a83b6),
+    };
+
     /// Protocol function used by template strings.
     pub const STRING_DISPLAY: Protocol = Protocol {
         name: "string_display",
@@ -246,4 +309,10 @@ impl Protocol {
         name: "into_type_name",
         hash: Hash::new(0xbffd08b816c24682),
     };
+
+    /// Function used when a value is called like a function.
+    pub const CALL: Protocol = Protocol {
+        name: "call",
+        hash: Hash::new(0x40f11737f559f2ef),
+    };
 }
@@ -4,7 +4,7 @@ use crate::{GuardedArgs, Hash, Protocol, Stack, UnitFn, Value, Vm, VmError, VmEr
 pub(crate) trait ProtocolCaller {
     /// Call the given protocol function.
     fn call_protocol_fn<A>(
-        self,
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
@@ -20,7 +20,7 @@ pub(crate) struct EnvProtocolCaller;
 
 impl ProtocolCaller for EnvProtocolCaller {
     fn call_protocol_fn<A>(
-        self,
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
@@ -35,10 +35,12 @@ impl ProtocolCaller for EnvProtocolCaller {
             if let Some(UnitFn::Offset {
                 offset,
                 args: expected,
+                defaults,
+                rest,
                 call,
             }) = unit.lookup(hash)
             {
-                check_args(count, expected)?;
+                Vm::check_offset_args(count, expected, defaults, rest)?;
 
                 let mut stack = Stack::with_capacity(count);
                 stack.push(target);
@@ -65,24 +67,12 @@ impl ProtocolCaller for EnvProtocolCaller {
             handler(&mut stack, count)?;
             Ok(stack.pop()?)
         });
-
-        /// Check that arguments matches expected or raise the appropriate error.
-        fn check_args(args: usize, expected: usize) -> Result<(), VmError> {
-            if args != expected {
-                return Err(VmError::from(VmErrorKind::BadArgumentCount {
-                    actual: args,
-                    expected,
-                }));
-            }
-
-            Ok(())
-        }
     }
 }
 
-impl ProtocolCaller for &mut Vm {
+impl ProtocolCaller for Vm {
     fn call_protocol_fn<A>(
-        self,
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
@@ -1,6 +1,7 @@
 use crate::collections::HashMap;
 use crate::context::Handler;
-use crate::{ConstValue, Hash, Item, TypeCheck};
+use crate::{Clock, ConstValue, Hash, Item, SystemClock, TypeCheck};
+use std::collections::BTreeSet;
 use std::fmt;
 use std::sync::Arc;
 
@@ -20,6 +21,12 @@ pub struct RuntimeContext {
 
     /// Named constant values
     pub(crate) constants: HashMap<Hash, ConstValue>,
+
+    /// The clock used by the `std::time` module, if overridden.
+    pub(crate) clock: Option<Arc<dyn Clock>>,
+
+    /// Names of fields which have getters registered, by type hash.
+    pub(crate) fields: HashMap<Hash, BTreeSet<String>>,
 }
 
 impl RuntimeContext {
@@ -42,6 +49,14 @@ impl RuntimeContext {
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
+
+    /// Get the clock used by the `std::time` module.
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        match &self.clock {
+            Some(clock) => clock.clone(),
+            None => Arc::new(SystemClock),
+        }
+    }
 }
 
 impl fmt::Debug for RuntimeContext {
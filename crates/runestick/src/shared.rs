@@ -13,6 +13,11 @@ use std::ptr;
 use std::task::{Context, Poll};
 
 /// A shared value.
+///
+/// Shared values are reference counted and freed once the last reference to
+/// them is dropped. There is no cycle collection, so values which refer to
+/// themselves, directly or through other values, are leaked unless the cycle
+/// is broken first.
 pub struct Shared<T: ?Sized> {
     inner: ptr::NonNull<SharedBox<T>>,
 }
@@ -268,6 +273,12 @@ impl<T> Shared<T> {
 }
 
 impl<T: ?Sized> Shared<T> {
+    /// Get the address of the shared allocation, which uniquely identifies
+    /// it for as long as the value is live.
+    pub(crate) fn addr(&self) -> usize {
+        self.inner.as_ptr() as *const () as usize
+    }
+
     /// Get a reference to the interior value while checking for shared access.
     ///
     /// This prevents other exclusive accesses from being performed while the
@@ -36,6 +36,23 @@ impl Source {
         }
     }
 
+    /// Construct a new source with the given name and the path it's
+    /// associated with.
+    ///
+    /// Modules declared in the source with `mod foo;` are loaded relative to
+    /// this path, even if the source doesn't live in the filesystem.
+    pub fn with_path<N, S, P>(name: N, source: S, path: P) -> Self
+    where
+        N: AsRef<str>,
+        S: AsRef<str>,
+        P: AsRef<Path>,
+    {
+        Self {
+            path: Some(path.as_ref().to_owned()),
+            ..Self::new(name, source)
+        }
+    }
+
     /// Access all line starts in the source.
     pub fn line_starts(&self) -> &[usize] {
         &self.line_starts
@@ -105,6 +122,39 @@ impl Source {
         &mut self.path
     }
 
+    /// Convert the given byte offset to a 1-based line and column.
+    ///
+    /// Columns are counted in unicode code points (`char`s), not bytes, so
+    /// multi-byte characters earlier on the line count as a single column. An
+    /// offset in the middle of a character maps to that character, and an
+    /// offset past the end of the source maps to the end of the last line.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let mut offset = offset.min(self.source.len());
+
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let (line, column) = self.position_to_unicode_line_char(offset);
+        (line + 1, column + 1)
+    }
+
+    /// Get the text of the given 1-based line, without its line ending.
+    ///
+    /// Returns `None` if the line doesn't exist.
+    pub fn line_text(&self, line: usize) -> Option<&str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+
+        let end = match self.line_starts.get(line) {
+            Some(end) => *end,
+            None => self.source.len(),
+        };
+
+        let text = self.source.get(start..end)?;
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
     /// Convert the given offset to a utf-16 line and character.
     pub fn position_to_utf16cu_line_char(&self, offset: usize) -> Option<(usize, usize)> {
         if offset == 0 {
@@ -30,6 +30,31 @@ impl Stack {
         }
     }
 
+    /// Construct a stack from its raw parts, making sure that the stack bottom
+    /// is within bounds.
+    pub(crate) fn from_parts(stack: Vec<Value>, stack_bottom: usize) -> Result<Self, StackError> {
+        let stack = Self {
+            stack,
+            stack_bottom: 0,
+        };
+
+        stack.check_stack_bottom(stack_bottom)?;
+
+        Ok(Self {
+            stack_bottom,
+            ..stack
+        })
+    }
+
+    /// Check that the given stack bottom is within the bounds of the stack.
+    pub(crate) fn check_stack_bottom(&self, stack_bottom: usize) -> Result<(), StackError> {
+        if stack_bottom > self.stack.len() {
+            return Err(StackError(()));
+        }
+
+        Ok(())
+    }
+
     /// Extend the current stack.
     pub fn extend<I>(&mut self, iter: I)
     where
@@ -183,6 +208,20 @@ impl Stack {
         }
     }
 
+    /// Drop all values in the current stack frame except for the top `count`
+    /// ones, which are moved to the bottom of the frame.
+    ///
+    /// This is used when the current call frame is reused by a tail call.
+    pub(crate) fn retain_top(&mut self, count: usize) -> Result<(), StackError> {
+        match self.stack.len().checked_sub(count) {
+            Some(start) if start >= self.stack_bottom => {
+                self.stack.drain(self.stack_bottom..start);
+                Ok(())
+            }
+            _ => Err(StackError(())),
+        }
+    }
+
     /// Modify stack top by subtracting the given count from it while checking
     /// that it is in bounds of the stack.
     ///
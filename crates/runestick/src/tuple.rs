@@ -190,17 +190,25 @@ macro_rules! impl_tuple {
             $($ty: $crate::FromValue,)*
         {
             fn from_value(value: $crate::Value) -> Result<Self, $crate::VmError> {
-                let tuple = value.into_tuple()?.take()?;
-
-                if tuple.len() != $count {
+                // NB: vectors are accepted as well, since it's common for
+                // scripts to return multiple values as an array.
+                let values = match value {
+                    $crate::Value::Tuple(tuple) => Vec::from(tuple.take()?.into_inner()),
+                    $crate::Value::Vec(vec) => vec.take()?.into_inner(),
+                    actual => {
+                        return Err($crate::VmError::expected::<Self>(actual.type_info()?));
+                    }
+                };
+
+                if values.len() != $count {
                     return Err($crate::VmError::from($crate::VmErrorKind::ExpectedTupleLength {
-                        actual: tuple.len(),
+                        actual: values.len(),
                         expected: $count,
                     }));
                 }
 
                 #[allow(unused_mut, unused_variables)]
-                let mut it = Vec::from(tuple.into_inner()).into_iter();
+                let mut it = values.into_iter();
 
                 $(
                     let $var = match it.next() {
@@ -5,9 +5,11 @@
 
 use crate::collections::HashMap;
 use crate::{
-    Call, ConstValue, DebugInfo, Hash, Inst, Rtti, StaticString, VariantRtti, VmError, VmErrorKind,
+    Call, ComponentRef, ConstValue, DebugInfo, DebugInst, Hash, Inst, Item, Rtti, Source, SourceId,
+    StaticString, VariantRtti, VmError, VmErrorKind,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fmt;
 use std::sync::Arc;
 
@@ -22,6 +24,8 @@ pub struct Unit {
     static_strings: Vec<Arc<StaticString>>,
     /// A static byte string.
     static_bytes: Vec<Vec<u8>>,
+    /// A static big integer, used for integer literals in bignum mode.
+    static_bigints: Vec<num_bigint::BigInt>,
     /// Slots used for object keys.
     ///
     /// This is used when an object is used in a pattern match, to avoid having
@@ -37,6 +41,8 @@ pub struct Unit {
     debug: Option<Box<DebugInfo>>,
     /// Named constants
     constants: HashMap<Hash, ConstValue>,
+    /// Functions annotated with `#[test]`, sorted by their path.
+    tests: Vec<(Item, Hash)>,
 }
 
 impl Unit {
@@ -47,22 +53,26 @@ impl Unit {
         functions: HashMap<Hash, UnitFn>,
         static_strings: Vec<Arc<StaticString>>,
         static_bytes: Vec<Vec<u8>>,
+        static_bigints: Vec<num_bigint::BigInt>,
         static_object_keys: Vec<Box<[String]>>,
         rtti: HashMap<Hash, Arc<Rtti>>,
         variant_rtti: HashMap<Hash, Arc<VariantRtti>>,
         debug: Option<Box<DebugInfo>>,
         constants: HashMap<Hash, ConstValue>,
+        tests: Vec<(Item, Hash)>,
     ) -> Self {
         Self {
             instructions,
             functions,
             static_strings,
             static_bytes,
+            static_bigints,
             static_object_keys,
             rtti,
             variant_rtti,
             debug,
             constants,
+            tests,
         }
     }
 
@@ -72,6 +82,14 @@ impl Unit {
         Some(&**debug)
     }
 
+    /// Get the debug information for the instruction at the given instruction
+    /// pointer, like the source and span it was compiled from.
+    ///
+    /// Returns `None` if the unit was compiled without debug information.
+    pub fn debug_info_at(&self, ip: usize) -> Option<&DebugInst> {
+        self.debug.as_ref()?.instruction_at(ip)
+    }
+
     /// Get the instruction at the given instruction pointer.
     pub fn instruction_at(&self, ip: usize) -> Option<&Inst> {
         self.instructions.get(ip)
@@ -102,6 +120,85 @@ impl Unit {
         self.functions.iter().map(|(h, f)| (*h, f))
     }
 
+    /// Get all functions in the unit which are nested under the given path
+    /// prefix, sorted by their path.
+    ///
+    /// Functions are matched on whole path components, so the prefix
+    /// `commands` matches `commands::run` and `commands::db::migrate` but not
+    /// `commands_old::run`. Anonymous functions like closures are not
+    /// included.
+    ///
+    /// This reads the paths recorded in the debug information of the unit, so
+    /// it returns nothing if the unit doesn't have any.
+    pub fn functions_with_prefix(&self, prefix: &Item) -> Vec<(Item, Hash)> {
+        let debug = match &self.debug {
+            Some(debug) => debug,
+            None => return Vec::new(),
+        };
+
+        let mut functions = debug
+            .functions
+            .values()
+            .map(|signature| &signature.path)
+            .filter(|path| *path != prefix && path.starts_with(prefix))
+            .filter(|path| !path.iter().any(|c| matches!(c, ComponentRef::Id(..))))
+            .map(|path| (path.clone(), Hash::type_hash(path)))
+            .filter(|(_, hash)| self.functions.contains_key(hash))
+            .collect::<Vec<_>>();
+
+        functions.sort_by(|(a, _), (b, _)| a.iter().cmp(b.iter()));
+        functions.dedup();
+        functions
+    }
+
+    /// Get all functions in the unit which are annotated with `#[test]`,
+    /// sorted by their path.
+    ///
+    /// Test functions are always compiled, so they can be called through
+    /// [Vm::execute][crate::Vm::execute] using the returned hash.
+    pub fn tests(&self) -> &[(Item, Hash)] {
+        &self.tests
+    }
+
+    /// Get the 1-based lines of the given source which any instruction was
+    /// emitted for, which are the lines a line coverage tool reports on.
+    ///
+    /// The `source` is used to translate spans into lines, and must be the
+    /// source identified by `source_id`. An instruction which spans multiple
+    /// lines marks all of them, except for lines which are empty or only
+    /// contain a comment.
+    ///
+    /// Returns an empty set if the unit doesn't have debug information.
+    pub fn executable_lines(&self, source_id: SourceId, source: &Source) -> BTreeSet<usize> {
+        let mut lines = BTreeSet::new();
+
+        let debug = match self.debug_info() {
+            Some(debug) => debug,
+            None => return lines,
+        };
+
+        for inst in &debug.instructions {
+            if inst.source_id != source_id {
+                continue;
+            }
+
+            let start = inst.span.start.into_usize();
+            // NB: the end of a span is exclusive.
+            let end = inst.span.end.into_usize().saturating_sub(1).max(start);
+
+            let (start, _) = source.position(start);
+            let (end, _) = source.position(end);
+
+            for line in start..=end {
+                if source.line_text(line).is_some_and(is_code) {
+                    lines.insert(line);
+                }
+            }
+        }
+
+        lines
+    }
+
     /// Lookup the static string by slot, if it exists.
     pub fn lookup_string(&self, slot: usize) -> Result<&Arc<StaticString>, VmError> {
         Ok(self
@@ -119,6 +216,14 @@ impl Unit {
             .as_ref())
     }
 
+    /// Lookup the static big integer by slot, if it exists.
+    pub fn lookup_bigint(&self, slot: usize) -> Result<&num_bigint::BigInt, VmError> {
+        Ok(self
+            .static_bigints
+            .get(slot)
+            .ok_or(VmErrorKind::MissingStaticBigInt { slot })?)
+    }
+
     /// Lookup the static object keys by slot, if it exists.
     pub fn lookup_object_keys(&self, slot: usize) -> Option<&[String]> {
         self.static_object_keys.get(slot).map(|keys| &keys[..])
@@ -156,6 +261,11 @@ pub enum UnitFn {
         call: Call,
         /// The number of arguments the function takes.
         args: usize,
+        /// The number of trailing arguments which have default values, and
+        /// can be omitted by the caller.
+        defaults: usize,
+        /// Whether any arguments past `args` are collected into a vector.
+        rest: bool,
     },
     /// An empty constructor.
     UnitStruct {
@@ -186,8 +296,22 @@ pub enum UnitFn {
 impl fmt::Display for UnitFn {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Offset { offset, call, args } => {
+            Self::Offset {
+                offset,
+                call,
+                args,
+                defaults,
+                rest,
+            } => {
                 write!(f, "offset {}, {}, {}", offset, call, args)?;
+
+                if *defaults > 0 {
+                    write!(f, " ({} defaults)", defaults)?;
+                }
+
+                if *rest {
+                    write!(f, " (rest)")?;
+                }
             }
             Self::UnitStruct { hash } => {
                 write!(f, "unit {}", hash)?;
@@ -209,3 +333,9 @@ impl fmt::Display for UnitFn {
 
 #[cfg(test)]
 static_assertions::assert_impl_all!(Unit: Send, Sync);
+
+/// Test if the given line contains anything but whitespace and comments.
+fn is_code(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with("//")
+}
@@ -3,13 +3,14 @@ use crate::protocol_caller::{EnvProtocolCaller, ProtocolCaller};
 use crate::{
     Any, AnyObj, Bytes, ConstValue, Format, Function, Future, Generator, GeneratorState, Hash,
     Item, Iterator, Mut, Object, Protocol, Range, RawMut, RawRef, Ref, Shared, StaticString,
-    Stream, Tuple, TypeInfo, Variant, Vec, Vm, VmError, VmErrorKind,
+    Stream, Tuple, TypeInfo, ValueRef, Variant, VariantData, Vec, Vm, VmError, VmErrorKind,
 };
 use serde::{de, ser, Deserialize, Serialize};
 use std::cmp;
 use std::fmt;
 use std::fmt::Write;
 use std::hash;
+use std::iter;
 use std::sync::Arc;
 use std::vec;
 
@@ -286,6 +287,10 @@ pub enum Value {
 impl Value {
     /// Format the value using the [Protocol::STRING_DISPLAY] protocol.
     ///
+    /// Scalars, strings and the built-in containers have a default display
+    /// implementation. Elements of containers are formatted recursively, so
+    /// any external types they contain use their own protocol implementation.
+    ///
     /// Requires a work buffer `buf` which will be used in case the value
     /// provided requires out-of-line formatting. This must be cleared between
     /// calls and can be re-used.
@@ -297,7 +302,7 @@ impl Value {
     ///
     /// This function will panic if called outside of a virtual machine.
     pub fn string_display(&self, s: &mut String, buf: &mut String) -> Result<fmt::Result, VmError> {
-        self.string_display_with(s, buf, EnvProtocolCaller)
+        self.string_display_with(s, buf, &mut EnvProtocolCaller)
     }
 
     /// Internal impl of string_display with a customizable caller.
@@ -305,7 +310,7 @@ impl Value {
         &self,
         s: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<fmt::Result, VmError> {
         use crate::FromValue as _;
 
@@ -336,6 +341,48 @@ impl Value {
             Value::Byte(byte) => {
                 return Ok(write!(s, "{:#04X}", byte));
             }
+            Value::Unit => {
+                s.push_str("()");
+            }
+            Value::Vec(vec) => {
+                let vec = vec.borrow_ref()?;
+                return display_seq(s, buf, caller, "[", vec.iter(), "]");
+            }
+            Value::Tuple(tuple) => {
+                let tuple = tuple.borrow_ref()?;
+                return display_seq(s, buf, caller, "(", tuple.iter(), ")");
+            }
+            Value::Object(object) => {
+                let object = object.borrow_ref()?;
+                s.push('{');
+
+                for (n, (key, value)) in object.iter().enumerate() {
+                    if n > 0 {
+                        s.push_str(", ");
+                    }
+
+                    s.push_str(key);
+                    s.push_str(": ");
+
+                    if let Err(error) = value.string_display_with(s, buf, caller)? {
+                        return Ok(Err(error));
+                    }
+                }
+
+                s.push('}');
+            }
+            Value::Option(option) => match &*option.borrow_ref()? {
+                Some(value) => {
+                    return display_seq(s, buf, caller, "Some(", iter::once(value), ")");
+                }
+                None => {
+                    s.push_str("None");
+                }
+            },
+            Value::Result(result) => match &*result.borrow_ref()? {
+                Ok(value) => return display_seq(s, buf, caller, "Ok(", iter::once(value), ")"),
+                Err(value) => return display_seq(s, buf, caller, "Err(", iter::once(value), ")"),
+            },
             value => {
                 let b = Shared::new(std::mem::take(s));
 
@@ -363,14 +410,14 @@ impl Value {
     ///
     /// This function will panic if called outside of a virtual machine.
     pub fn string_debug(&self, s: &mut String) -> Result<fmt::Result, VmError> {
-        self.string_debug_with(s, EnvProtocolCaller)
+        self.string_debug_with(s, &mut EnvProtocolCaller)
     }
 
     /// Internal impl of string_debug with a customizable caller.
     pub(crate) fn string_debug_with(
         &self,
         s: &mut String,
-        caller: impl ProtocolCaller,
+        caller: &mut impl ProtocolCaller,
     ) -> Result<fmt::Result, VmError> {
         use crate::FromValue as _;
         use std::fmt::Write as _;
@@ -406,14 +453,34 @@ impl Value {
             Value::Bytes(value) => {
                 write!(s, "{:?}", value)
             }
-            Value::Vec(value) => {
-                write!(s, "{:?}", value)
+            Value::Vec(vec) => {
+                let vec = vec.borrow_ref()?;
+                return debug_seq(s, caller, "[", vec.iter(), "]");
             }
-            Value::Tuple(value) => {
-                write!(s, "{:?}", value)
+            Value::Tuple(tuple) => {
+                let tuple = tuple.borrow_ref()?;
+                return debug_seq(s, caller, "(", tuple.iter(), ")");
             }
-            Value::Object(value) => {
-                write!(s, "{:?}", value)
+            Value::Object(object) => {
+                let object = object.borrow_ref()?;
+                s.push('{');
+
+                for (n, (key, value)) in object.iter().enumerate() {
+                    if n > 0 {
+                        s.push_str(", ");
+                    }
+
+                    if let Err(error) = write!(s, "{:?}: ", key) {
+                        return Ok(Err(error));
+                    }
+
+                    if let Err(error) = value.string_debug_with(s, caller)? {
+                        return Ok(Err(error));
+                    }
+                }
+
+                s.push('}');
+                Ok(())
             }
             Value::Range(value) => {
                 write!(s, "{:?}", value)
@@ -430,12 +497,14 @@ impl Value {
             Value::GeneratorState(value) => {
                 write!(s, "{:?}", value)
             }
-            Value::Option(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Result(value) => {
-                write!(s, "{:?}", value)
-            }
+            Value::Option(option) => match &*option.borrow_ref()? {
+                Some(value) => return debug_seq(s, caller, "Some(", iter::once(value), ")"),
+                None => write!(s, "None"),
+            },
+            Value::Result(result) => match &*result.borrow_ref()? {
+                Ok(value) => return debug_seq(s, caller, "Ok(", iter::once(value), ")"),
+                Err(value) => return debug_seq(s, caller, "Err(", iter::once(value), ")"),
+            },
             Value::UnitStruct(value) => {
                 write!(s, "{:?}", value)
             }
@@ -540,6 +609,218 @@ impl Value {
         Ok(Shared::new(Future::from_value(value)?))
     }
 
+    /// Clone the value using the [Protocol::CLONE] protocol for external
+    /// types.
+    ///
+    /// Collections are cloned shallowly, so the returned collection contains
+    /// the same values as the original.
+    ///
+    /// You must use [Vm::with] to specify which virtual machine this function
+    /// is called inside.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside of a virtual machine.
+    pub fn shallow_clone(&self) -> Result<Value, VmError> {
+        self.clone_inner(None)
+    }
+
+    /// Perform a deep clone of the value, recursively cloning collections and
+    /// using the [Protocol::DEEP_CLONE] protocol for external types, falling
+    /// back to [Protocol::CLONE] if it's not registered.
+    ///
+    /// Errors if the value contains something which can't be cloned, like an
+    /// external type without a registered [Protocol::CLONE] function, or if
+    /// it contains a collection which contains itself.
+    ///
+    /// You must use [Vm::with] to specify which virtual machine this function
+    /// is called inside.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside of a virtual machine.
+    pub fn deep_clone(&self) -> Result<Value, VmError> {
+        self.deep_clone_with(&mut vec::Vec::new())
+    }
+
+    /// Perform a deep clone of the value like [Value::deep_clone], where
+    /// `visited` holds the addresses of the containers which are currently
+    /// being cloned.
+    ///
+    /// This is intended for implementations of [Protocol::DEEP_CLONE] which
+    /// are invoked as part of an outer deep clone, so that a container which
+    /// contains itself is detected instead of recursing forever.
+    pub fn deep_clone_with(&self, visited: &mut vec::Vec<usize>) -> Result<Value, VmError> {
+        let addr = match self {
+            Value::Vec(value) => value.addr(),
+            Value::Tuple(value) => value.addr(),
+            Value::Object(value) => value.addr(),
+            Value::Range(value) => value.addr(),
+            Value::Option(value) => value.addr(),
+            Value::Result(value) => value.addr(),
+            Value::TupleStruct(value) => value.addr(),
+            Value::Struct(value) => value.addr(),
+            Value::Variant(value) => value.addr(),
+            Value::Any(value) => value.addr(),
+            _ => return self.clone_inner(Some(visited)),
+        };
+
+        if visited.contains(&addr) {
+            return Err(VmError::from(VmErrorKind::CyclicClone {
+                actual: self.type_info()?,
+            }));
+        }
+
+        visited.push(addr);
+        let result = self.clone_inner(Some(visited));
+        visited.pop();
+        result
+    }
+
+    /// Internal impl of clone, which is deep if `visited` is specified.
+    fn clone_inner(&self, mut visited: Option<&mut vec::Vec<usize>>) -> Result<Value, VmError> {
+        fn inner(
+            value: &Value,
+            visited: &mut Option<&mut vec::Vec<usize>>,
+        ) -> Result<Value, VmError> {
+            match visited {
+                Some(visited) => value.deep_clone_with(visited),
+                None => Ok(value.clone()),
+            }
+        }
+
+        fn clone_all(
+            values: &[Value],
+            visited: &mut Option<&mut vec::Vec<usize>>,
+        ) -> Result<vec::Vec<Value>, VmError> {
+            values.iter().map(|v| inner(v, visited)).collect()
+        }
+
+        fn clone_object(
+            object: &Object,
+            visited: &mut Option<&mut vec::Vec<usize>>,
+        ) -> Result<Object, VmError> {
+            object
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), inner(v, visited)?)))
+                .collect()
+        }
+
+        let visited = &mut visited;
+
+        Ok(match self {
+            Value::Unit => Value::Unit,
+            Value::Bool(value) => Value::Bool(*value),
+            Value::Byte(value) => Value::Byte(*value),
+            Value::Char(value) => Value::Char(*value),
+            Value::Integer(value) => Value::Integer(*value),
+            Value::Float(value) => Value::Float(*value),
+            Value::Type(value) => Value::Type(*value),
+            Value::StaticString(value) => Value::StaticString(value.clone()),
+            // NB: functions are immutable, so they can be shared.
+            Value::Function(value) => Value::Function(value.clone()),
+            Value::String(value) => Value::from(value.borrow_ref()?.clone()),
+            Value::Bytes(value) => Value::from(value.borrow_ref()?.clone()),
+            Value::Vec(value) => Value::vec(clone_all(&value.borrow_ref()?, visited)?),
+            Value::Tuple(value) => Value::tuple(clone_all(&value.borrow_ref()?, visited)?),
+            Value::Object(value) => Value::from(clone_object(&*value.borrow_ref()?, visited)?),
+            Value::Range(value) => {
+                let range = value.borrow_ref()?;
+                let start = range
+                    .start
+                    .as_ref()
+                    .map(|v| inner(v, visited))
+                    .transpose()?;
+                let end = range.end.as_ref().map(|v| inner(v, visited)).transpose()?;
+                Value::from(Range::new(start, end, range.limits))
+            }
+            Value::GeneratorState(value) => Value::from(match &*value.borrow_ref()? {
+                GeneratorState::Yielded(value) => GeneratorState::Yielded(inner(value, visited)?),
+                GeneratorState::Complete(value) => GeneratorState::Complete(inner(value, visited)?),
+            }),
+            Value::Option(value) => Value::Option(Shared::new(
+                value
+                    .borrow_ref()?
+                    .as_ref()
+                    .map(|v| inner(v, visited))
+                    .transpose()?,
+            )),
+            Value::Result(value) => Value::Result(Shared::new(match &*value.borrow_ref()? {
+                Ok(value) => Ok(inner(value, visited)?),
+                Err(value) => Err(inner(value, visited)?),
+            })),
+            Value::UnitStruct(value) => Value::unit_struct(value.borrow_ref()?.rtti.clone()),
+            Value::TupleStruct(value) => {
+                let value = value.borrow_ref()?;
+                Value::tuple_struct(value.rtti.clone(), clone_all(&value.data, visited)?)
+            }
+            Value::Struct(value) => {
+                let value = value.borrow_ref()?;
+
+                Value::from(Struct {
+                    rtti: value.rtti.clone(),
+                    data: clone_object(&value.data, visited)?,
+                })
+            }
+            Value::Variant(value) => {
+                let value = value.borrow_ref()?;
+                let rtti = value.rtti.clone();
+
+                Value::from(match &value.data {
+                    VariantData::Unit => Variant::unit(rtti),
+                    VariantData::Tuple(tuple) => {
+                        Variant::tuple(rtti, Tuple::from(clone_all(tuple, visited)?))
+                    }
+                    VariantData::Struct(object) => {
+                        Variant::struct_(rtti, clone_object(object, visited)?)
+                    }
+                })
+            }
+            Value::Format(format) => Value::Format(Box::new(Format {
+                value: inner(&format.value, visited)?,
+                spec: format.spec,
+            })),
+            Value::Any(any) => {
+                if let Some(visited) = visited {
+                    if let Some(value) =
+                        crate::modules::collections::deep_clone_with(&*any.borrow_ref()?, visited)?
+                    {
+                        return Ok(value);
+                    }
+                }
+
+                let type_hash = any.borrow_ref()?.type_hash();
+
+                let exists = |protocol: Protocol| {
+                    let hash = Hash::instance_function(type_hash, protocol);
+
+                    crate::env::with(|context, unit| {
+                        Ok(unit.lookup(hash).is_some() || context.lookup(hash).is_some())
+                    })
+                };
+
+                let protocol = if visited.is_some() && exists(Protocol::DEEP_CLONE)? {
+                    Protocol::DEEP_CLONE
+                } else if exists(Protocol::CLONE)? {
+                    Protocol::CLONE
+                } else {
+                    return Err(VmError::from(VmErrorKind::MissingProtocol {
+                        protocol: Protocol::CLONE,
+                        actual: self.type_info()?,
+                    }));
+                };
+
+                EnvProtocolCaller.call_protocol_fn(protocol, self.clone(), ())?
+            }
+            Value::Future(..) | Value::Stream(..) | Value::Generator(..) | Value::Iterator(..) => {
+                return Err(VmError::from(VmErrorKind::MissingProtocol {
+                    protocol: Protocol::CLONE,
+                    actual: self.type_info()?,
+                }));
+            }
+        })
+    }
+
     /// Retrieves a human readable type name for the current value.
     ///
     /// You must use [Vm::with] to specify which virtual machine this function
@@ -767,6 +1048,14 @@ impl Value {
         }
     }
 
+    /// Borrow the contents of the value, see [ValueRef].
+    ///
+    /// Errors if the value is a collection which is exclusively borrowed
+    /// elsewhere.
+    pub fn borrow_ref(&self) -> Result<ValueRef<'_>, VmError> {
+        ValueRef::new(self)
+    }
+
     /// Try to coerce value into a vector.
     #[inline]
     pub fn into_vec(self) -> Result<Shared<Vec>, VmError> {
@@ -968,19 +1257,13 @@ impl Value {
             (Self::Integer(a), Self::Integer(b)) => return Ok(a == b),
             (Self::Float(a), Self::Float(b)) => return Ok(a == b),
             (Self::Vec(a), Self::Vec(b)) => {
-                let a = a.borrow_ref()?;
-                let b = b.borrow_ref()?;
-                return Vec::value_ptr_eq(vm, &*a, &*b);
+                return container_eq(vm, a, b, Vec::value_ptr_eq);
             }
             (Self::Tuple(a), Self::Tuple(b)) => {
-                let a = a.borrow_ref()?;
-                let b = b.borrow_ref()?;
-                return Tuple::value_ptr_eq(vm, &*a, &*b);
+                return container_eq(vm, a, b, Tuple::value_ptr_eq);
             }
             (Self::Object(a), Self::Object(b)) => {
-                let a = a.borrow_ref()?;
-                let b = b.borrow_ref()?;
-                return Object::value_ptr_eq(vm, &*a, &*b);
+                return container_eq(vm, a, b, Object::value_ptr_eq);
             }
             (Self::Range(a), Self::Range(b)) => {
                 let a = a.borrow_ref()?;
@@ -998,27 +1281,22 @@ impl Value {
                 }
             }
             (Self::TupleStruct(a), Self::TupleStruct(b)) => {
-                let a = a.borrow_ref()?;
-                let b = b.borrow_ref()?;
-
-                if a.rtti.hash == b.rtti.hash {
-                    return Tuple::value_ptr_eq(vm, &a.data, &b.data);
+                if a.borrow_ref()?.rtti.hash == b.borrow_ref()?.rtti.hash {
+                    return container_eq(vm, a, b, |vm, a, b| {
+                        Tuple::value_ptr_eq(vm, &a.data, &b.data)
+                    });
                 }
             }
             (Self::Struct(a), Self::Struct(b)) => {
-                let a = a.borrow_ref()?;
-                let b = b.borrow_ref()?;
-
-                if a.rtti.hash == b.rtti.hash {
-                    return Object::value_ptr_eq(vm, &a.data, &b.data);
+                if a.borrow_ref()?.rtti.hash == b.borrow_ref()?.rtti.hash {
+                    return container_eq(vm, a, b, |vm, a, b| {
+                        Object::value_ptr_eq(vm, &a.data, &b.data)
+                    });
                 }
             }
             (Self::Variant(a), Self::Variant(b)) => {
-                let a = a.borrow_ref()?;
-                let b = b.borrow_ref()?;
-
-                if a.rtti().enum_hash == b.rtti().enum_hash {
-                    return Variant::value_ptr_eq(vm, &*a, &*b);
+                if a.borrow_ref()?.rtti().enum_hash == b.borrow_ref()?.rtti().enum_hash {
+                    return container_eq(vm, a, b, Variant::value_ptr_eq);
                 }
             }
             (Self::String(a), Self::String(b)) => {
@@ -1060,6 +1338,117 @@ impl Value {
             rhs: b.type_info()?,
         }))
     }
+
+    /// Compare two values for ordering, using `caller` to call the
+    /// [Protocol::CMP] protocol of external values.
+    ///
+    /// Floats which can't be compared, like `NaN`, are considered equal.
+    pub(crate) fn value_ptr_cmp(
+        caller: &mut impl ProtocolCaller,
+        a: &Value,
+        b: &Value,
+    ) -> Result<cmp::Ordering, VmError> {
+        use crate::FromValue as _;
+
+        Ok(match (a, b) {
+            (Self::Unit, Self::Unit) => cmp::Ordering::Equal,
+            (Self::Bool(a), Self::Bool(b)) => a.cmp(b),
+            (Self::Byte(a), Self::Byte(b)) => a.cmp(b),
+            (Self::Char(a), Self::Char(b)) => a.cmp(b),
+            (Self::Integer(a), Self::Integer(b)) => a.cmp(b),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b).unwrap_or(cmp::Ordering::Equal),
+            (Self::String(a), Self::String(b)) => (*a.borrow_ref()?).cmp(&*b.borrow_ref()?),
+            (Self::StaticString(a), Self::String(b)) => (***a).cmp(&*b.borrow_ref()?),
+            (Self::String(a), Self::StaticString(b)) => (*a.borrow_ref()?).cmp(&***b),
+            (Self::StaticString(a), Self::StaticString(b)) => (***a).cmp(&***b),
+            (Self::Any(..), b) => {
+                let ordering = caller.call_protocol_fn(Protocol::CMP, a.clone(), (b.clone(),))?;
+                cmp::Ordering::from_value(ordering)?
+            }
+            (a, b) => {
+                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                    op: "cmp",
+                    lhs: a.type_info()?,
+                    rhs: b.type_info()?,
+                }));
+            }
+        })
+    }
+}
+
+/// Display a sequence of values separated by commas, using `caller` to format
+/// each element.
+fn display_seq<'a>(
+    s: &mut String,
+    buf: &mut String,
+    caller: &mut impl ProtocolCaller,
+    open: &str,
+    values: impl iter::Iterator<Item = &'a Value>,
+    close: &str,
+) -> Result<fmt::Result, VmError> {
+    s.push_str(open);
+
+    for (n, value) in values.enumerate() {
+        if n > 0 {
+            s.push_str(", ");
+        }
+
+        if let Err(error) = value.string_display_with(s, buf, caller)? {
+            return Ok(Err(error));
+        }
+    }
+
+    s.push_str(close);
+    Ok(Ok(()))
+}
+
+/// Debug format a sequence of values separated by commas, using `caller` to
+/// format each element.
+fn debug_seq<'a>(
+    s: &mut String,
+    caller: &mut impl ProtocolCaller,
+    open: &str,
+    values: impl iter::Iterator<Item = &'a Value>,
+    close: &str,
+) -> Result<fmt::Result, VmError> {
+    s.push_str(open);
+
+    for (n, value) in values.enumerate() {
+        if n > 0 {
+            s.push_str(", ");
+        }
+
+        if let Err(error) = value.string_debug_with(s, caller)? {
+            return Ok(Err(error));
+        }
+    }
+
+    s.push_str(close);
+    Ok(Ok(()))
+}
+
+/// Compare the contents of two shared containers for equality.
+///
+/// A pair of containers which is already being compared further up is
+/// considered equal, so that comparing self-referential structures
+/// terminates.
+fn container_eq<T, F>(vm: &mut Vm, a: &Shared<T>, b: &Shared<T>, eq: F) -> Result<bool, VmError>
+where
+    F: FnOnce(&mut Vm, &T, &T) -> Result<bool, VmError>,
+{
+    let key = (a.addr(), b.addr());
+
+    if vm.eq_visited.contains(&key) {
+        return Ok(true);
+    }
+
+    let a = a.borrow_ref()?;
+    let b = b.borrow_ref()?;
+
+    vm.eq_visited.push(key);
+    let result = eq(vm, &*a, &*b);
+    vm.eq_visited.pop();
+    result
 }
 
 impl fmt::Debug for Value {
@@ -1484,6 +1873,22 @@ impl<'de> de::Visitor<'de> for VmVisitor {
         Ok(Value::Integer(v as i64))
     }
 
+    #[inline]
+    fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v as f64))
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v))
+    }
+
     #[inline]
     fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
     where
@@ -0,0 +1,75 @@
+use crate::{BorrowRef, Object, Tuple, Value, Vec, VmError};
+use std::slice;
+
+/// A borrowed view of a [Value], which gives access to the contents of
+/// collections without cloning them. Constructed through [Value::borrow_ref].
+///
+/// The borrow is tied to the value, so a value borrowed from a virtual machine
+/// through [Vm::slot][crate::Vm::slot] keeps the virtual machine from being
+/// used until the borrow is released. Collections are also marked as borrowed
+/// for as long as the view is live, so any attempt to modify them through
+/// another reference fails with an access error.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{Value, ValueRef};
+///
+/// # fn main() -> runestick::Result<()> {
+/// let value = Value::vec(vec![Value::from(1i64), Value::from(2i64)]);
+///
+/// let sum = match value.borrow_ref()? {
+///     ValueRef::Vec(vec) => vec
+///         .iter()
+///         .map(|v| v.clone().into_integer())
+///         .sum::<Result<i64, _>>()?,
+///     _ => panic!("expected vector"),
+/// };
+///
+/// assert_eq!(sum, 3);
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub enum ValueRef<'a> {
+    /// A borrowed vector.
+    Vec(BorrowRef<'a, Vec>),
+    /// A borrowed tuple.
+    Tuple(BorrowRef<'a, Tuple>),
+    /// A borrowed object.
+    Object(BorrowRef<'a, Object>),
+    /// Any other value, which doesn't have contents to borrow.
+    Other(&'a Value),
+}
+
+impl<'a> ValueRef<'a> {
+    /// Borrow the given value.
+    pub(crate) fn new(value: &'a Value) -> Result<Self, VmError> {
+        Ok(match value {
+            Value::Vec(vec) => Self::Vec(vec.borrow_ref()?),
+            Value::Tuple(tuple) => Self::Tuple(tuple.borrow_ref()?),
+            Value::Object(object) => Self::Object(object.borrow_ref()?),
+            value => Self::Other(value),
+        })
+    }
+
+    /// Iterate over the elements of a vector or a tuple.
+    ///
+    /// Returns `None` if the value is neither.
+    pub fn elements(&self) -> Option<slice::Iter<'_, Value>> {
+        match self {
+            Self::Vec(vec) => Some(vec.iter()),
+            Self::Tuple(tuple) => Some(tuple.iter()),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the entries of an object, in insertion order.
+    ///
+    /// Returns `None` if the value is not an object.
+    pub fn entries(&self) -> Option<crate::object::Iter<'_>> {
+        match self {
+            Self::Object(object) => Some(object.iter()),
+            _ => None,
+        }
+    }
+}
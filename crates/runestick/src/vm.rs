@@ -5,13 +5,16 @@ use crate::{
     Args, Awaited, BorrowMut, Bytes, Call, Format, FormatSpec, FromValue, Function, Future,
     Generator, GuardedArgs, Hash, Inst, InstAddress, InstAssignOp, InstFnNameHash, InstOp,
     InstRangeLimits, InstTarget, InstValue, InstVariant, IntoTypeHash, Object, Panic, Protocol,
-    Range, RangeLimits, RuntimeContext, Select, Shared, Stack, Stream, Struct, Tuple, TypeCheck,
-    Unit, UnitStruct, Value, Variant, VariantData, Vec, VmError, VmErrorKind, VmExecution, VmHalt,
-    VmIntegerRepr, VmSendExecution,
+    Range, RangeLimits, RuntimeContext, Select, Shared, Span, Stack, Stream, Struct, Tuple,
+    TypeCheck, Unit, UnitStruct, Value, Variant, VariantData, Vec, VmError, VmErrorKind,
+    VmExecution, VmHalt, VmIntegerRepr, VmSendExecution, VmSnapshot,
 };
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fmt;
+use std::io;
 use std::mem;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::vec;
 
 enum TargetFallback<'a> {
@@ -58,6 +61,27 @@ macro_rules! target_value {
     }};
 }
 
+/// A callback which is invoked before every instruction, see
+/// [Vm::set_trace].
+#[derive(Clone)]
+pub(crate) struct Trace(Arc<dyn Fn(usize, Option<Span>) + Send + Sync>);
+
+impl fmt::Debug for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Trace")
+    }
+}
+
+/// The sink which builtins like `print` write to, see [Vm::set_output].
+#[derive(Clone)]
+pub(crate) struct Output(pub(crate) Arc<Mutex<dyn io::Write + Send>>);
+
+impl fmt::Debug for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Output")
+    }
+}
+
 /// A stack which references variables indirectly from a slab.
 #[derive(Debug, Clone)]
 pub struct Vm {
@@ -71,6 +95,12 @@ pub struct Vm {
     pub(crate) stack: Stack,
     /// Frames relative to the stack.
     call_frames: vec::Vec<CallFrame>,
+    /// Addresses of containers currently being compared for equality.
+    pub(crate) eq_visited: vec::Vec<(usize, usize)>,
+    /// Callback invoked before every instruction.
+    pub(crate) trace: Option<Trace>,
+    /// Sink for output written by builtins, or stdout if not set.
+    pub(crate) output: Option<Output>,
 }
 
 impl Vm {
@@ -91,6 +121,9 @@ impl Vm {
             ip: 0,
             stack,
             call_frames: vec::Vec::new(),
+            eq_visited: vec::Vec::new(),
+            trace: None,
+            output: None,
         }
     }
 
@@ -108,6 +141,88 @@ impl Vm {
         execution.async_complete().await
     }
 
+    /// Take a snapshot of the current execution state of the virtual machine,
+    /// which can later be resumed with [restore][Vm::restore].
+    ///
+    /// See [VmSnapshot] for which values can be included in a snapshot.
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmError> {
+        VmSnapshot::new(self.ip, &self.stack, &self.call_frames)
+    }
+
+    /// Restore a virtual machine from a snapshot taken with
+    /// [snapshot][Vm::snapshot].
+    ///
+    /// The unit must be the same as the one the snapshot was taken from.
+    pub fn restore(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        snapshot: VmSnapshot,
+    ) -> Result<Self, VmError> {
+        let VmSnapshot {
+            ip,
+            stack_bottom,
+            shared,
+            stack,
+            call_frames,
+        } = snapshot;
+
+        let stack = VmSnapshot::restore_stack(shared, stack, &unit)?;
+        let stack = Stack::from_parts(stack, stack_bottom)?;
+
+        for frame in &call_frames {
+            stack.check_stack_bottom(frame.stack_bottom)?;
+        }
+
+        Ok(Self {
+            context,
+            unit,
+            ip,
+            stack,
+            call_frames,
+            eq_visited: vec::Vec::new(),
+            trace: None,
+            output: None,
+        })
+    }
+
+    /// Set a callback which is invoked with the instruction pointer and span
+    /// of every instruction right before it's executed, which can be used to
+    /// profile a script or to collect its coverage.
+    ///
+    /// The span is only available if the unit has debug information. Virtual
+    /// machines started by this one, like the ones running generators and
+    /// async functions, inherit the callback.
+    pub fn set_trace<F>(&mut self, trace: F)
+    where
+        F: Fn(usize, Option<Span>) + Send + Sync + 'static,
+    {
+        self.trace = Some(Trace(Arc::new(trace)));
+    }
+
+    /// Remove the callback set with [set_trace][Vm::set_trace].
+    pub fn remove_trace(&mut self) {
+        self.trace = None;
+    }
+
+    /// Set the sink that builtins like `print`, `println` and `dbg` write to
+    /// instead of stdout.
+    ///
+    /// Errors raised by the sink are reported as panics which can be caught
+    /// like any other error. Virtual machines started by this one inherit the
+    /// sink.
+    pub fn set_output<W>(&mut self, output: W)
+    where
+        W: io::Write + Send + 'static,
+    {
+        self.output = Some(Output(Arc::new(Mutex::new(output))));
+    }
+
+    /// Remove the sink set with [set_output][Vm::set_output], so that
+    /// builtins write to stdout again.
+    pub fn remove_output(&mut self) {
+        self.output = None;
+    }
+
     /// Test if the virtual machine is the same context and unit as specified.
     pub fn is_same(&self, context: &Arc<RuntimeContext>, unit: &Arc<Unit>) -> bool {
         Arc::ptr_eq(&self.context, context) && Arc::ptr_eq(&self.unit, unit)
@@ -131,6 +246,15 @@ impl Vm {
         &self.stack
     }
 
+    /// Resolve the given slot in the current call frame into the value stored
+    /// in it, like the local variables of the function being executed.
+    ///
+    /// Errors if the slot is not on the stack of the current call frame.
+    #[inline]
+    pub fn slot(&self, slot: usize) -> Result<&Value, VmError> {
+        Ok(self.stack.at_offset(slot)?)
+    }
+
     /// Get the stack mutably.
     #[inline]
     pub fn stack_mut(&mut self) -> &mut Stack {
@@ -162,6 +286,27 @@ impl Vm {
     }
 
     /// Reset this virtual machine, freeing all memory used.
+    ///
+    /// The unit and runtime context are kept, so the virtual machine can be
+    /// reused for any number of executions. Note that starting an execution
+    /// through [Vm::execute] or [Vm::call] always starts from a clean stack.
+    ///
+    /// A virtual machine can't be cleared while an execution is suspended,
+    /// since the execution holds on to it until it's dropped:
+    ///
+    /// ```rust,compile_fail
+    /// use runestick::{Context, Unit, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let context = Arc::new(Context::with_default_modules()?.runtime());
+    /// let mut vm = Vm::new(context, Arc::new(Unit::default()));
+    ///
+    /// let mut execution = vm.execute(&["main"], ())?;
+    /// vm.clear();
+    /// execution.complete()?;
+    /// # Ok(()) }
+    /// ```
     pub fn clear(&mut self) {
         self.ip = 0;
         self.stack.clear();
@@ -362,9 +507,11 @@ impl Vm {
             UnitFn::Offset {
                 offset,
                 args: expected,
+                defaults,
+                rest,
                 ..
             } => {
-                Self::check_args(count, expected)?;
+                Self::check_offset_args(count, expected, defaults, rest)?;
                 offset
             }
             _ => {
@@ -403,9 +550,11 @@ impl Vm {
             offset,
             call,
             args: expected,
+            defaults,
+            rest,
         }) = self.unit.lookup(hash)
         {
-            Self::check_args(count, expected)?;
+            Self::check_offset_args(count, expected, defaults, rest)?;
             self.call_offset_fn(offset, call, count)?;
             return Ok(true);
         }
@@ -459,6 +608,7 @@ impl Vm {
         &mut self,
         int_op: fn(i64, i64) -> bool,
         float_op: fn(f64, f64) -> bool,
+        ordering_op: fn(Ordering) -> bool,
         op: &'static str,
         lhs: InstAddress,
         rhs: InstAddress,
@@ -470,11 +620,15 @@ impl Vm {
             (Value::Integer(lhs), Value::Integer(rhs)) => int_op(lhs, rhs),
             (Value::Float(lhs), Value::Float(rhs)) => float_op(lhs, rhs),
             (lhs, rhs) => {
-                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                    op,
-                    lhs: lhs.type_info()?,
-                    rhs: rhs.type_info()?,
-                }))
+                if !self.call_instance_fn(lhs.clone(), Protocol::CMP, (&rhs,))? {
+                    return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                        op,
+                        lhs: lhs.type_info()?,
+                        rhs: rhs.type_info()?,
+                    }));
+                }
+
+                ordering_op(Ordering::from_value(self.stack.pop()?)?)
             }
         };
 
@@ -498,6 +652,17 @@ impl Vm {
         Ok(())
     }
 
+    /// Replace the current call frame with a call to the given offset.
+    ///
+    /// The `args` number of elements on top of the stack are kept as the
+    /// arguments of the new call, everything else in the current frame is
+    /// dropped.
+    pub(crate) fn replace_call_frame(&mut self, ip: usize, args: usize) -> Result<(), VmError> {
+        self.stack.retain_top(args)?;
+        self.ip = ip.overflowing_sub(1).0;
+        Ok(())
+    }
+
     /// Pop a call frame and return it.
     fn pop_call_frame(&mut self) -> Result<bool, VmError> {
         let frame = match self.call_frames.pop() {
@@ -1065,6 +1230,8 @@ impl Vm {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.trace = self.trace.clone();
+        vm.output = self.output.clone();
         self.stack.push(Generator::new(vm));
         Ok(())
     }
@@ -1074,6 +1241,8 @@ impl Vm {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.trace = self.trace.clone();
+        vm.output = self.output.clone();
         self.stack.push(Stream::new(vm));
         Ok(())
     }
@@ -1083,6 +1252,8 @@ impl Vm {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
         let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.trace = self.trace.clone();
+        vm.output = self.output.clone();
         self.stack.push(Future::new(vm.async_complete()));
         Ok(())
     }
@@ -1186,6 +1357,7 @@ impl Vm {
     fn internal_num(
         &mut self,
         protocol: Protocol,
+        rhs_protocol: Protocol,
         error: fn() -> VmErrorKind,
         integer_op: fn(i64, i64) -> Option<i64>,
         float_op: fn(f64, f64) -> f64,
@@ -1207,7 +1379,13 @@ impl Vm {
             (lhs, rhs) => (lhs, rhs),
         };
 
-        if !self.call_instance_fn(lhs.clone(), protocol, (&rhs,))? {
+        if self.call_instance_fn(lhs.clone(), protocol, (&rhs,))? {
+            return Ok(());
+        }
+
+        // NB: fall back to the right-hand side, which is called with the
+        // left-hand side as its argument.
+        if !self.call_instance_fn(rhs.clone(), rhs_protocol, (&lhs,))? {
             return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
                 op: protocol.name,
                 lhs: lhs.type_info()?,
@@ -1373,6 +1551,26 @@ impl Vm {
         Ok(())
     }
 
+    /// Check the number of arguments passed to a function in the unit, where
+    /// the last `defaults` arguments can be omitted and any number of
+    /// additional arguments are accepted if the function has `rest`.
+    #[inline(always)]
+    pub(crate) fn check_offset_args(
+        args: usize,
+        expected: usize,
+        defaults: usize,
+        rest: bool,
+    ) -> Result<(), VmError> {
+        if (args > expected && !rest) || args + defaults < expected {
+            return Err(VmError::from(VmErrorKind::BadArgumentCount {
+                actual: args,
+                expected,
+            }));
+        }
+
+        Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_await(&mut self) -> Result<Shared<Future>, VmError> {
         let value = self.stack.pop()?;
@@ -1539,6 +1737,26 @@ impl Vm {
         Ok(())
     }
 
+    /// Perform a jump if the current call frame holds at least `len` values.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_jump_if_frame_len(&mut self, len: usize, offset: isize) -> Result<(), VmError> {
+        if self.stack.len() - self.stack.stack_bottom() >= len {
+            self.modify_ip(offset)?;
+        }
+
+        Ok(())
+    }
+
+    /// Collect the values in the current call frame past the first `args`
+    /// into a vector.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_collect_rest(&mut self, args: usize) -> Result<(), VmError> {
+        let len = self.stack.len() - self.stack.stack_bottom();
+        let vec = Vec::from(self.stack.pop_sequence(len.saturating_sub(args))?);
+        self.stack.push(Shared::new(vec));
+        Ok(())
+    }
+
     /// Construct a new vec.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_vec(&mut self, count: usize) -> Result<(), VmError> {
@@ -1624,6 +1842,7 @@ impl Vm {
             InstOp::Add => {
                 self.internal_num(
                     Protocol::ADD,
+                    Protocol::ADD_RHS,
                     || VmErrorKind::Overflow,
                     i64::checked_add,
                     std::ops::Add::add,
@@ -1634,6 +1853,7 @@ impl Vm {
             InstOp::Sub => {
                 self.internal_num(
                     Protocol::SUB,
+                    Protocol::SUB_RHS,
                     || VmErrorKind::Underflow,
                     i64::checked_sub,
                     std::ops::Sub::sub,
@@ -1644,6 +1864,7 @@ impl Vm {
             InstOp::Mul => {
                 self.internal_num(
                     Protocol::MUL,
+                    Protocol::MUL_RHS,
                     || VmErrorKind::Overflow,
                     i64::checked_mul,
                     std::ops::Mul::mul,
@@ -1654,6 +1875,7 @@ impl Vm {
             InstOp::Div => {
                 self.internal_num(
                     Protocol::DIV,
+                    Protocol::DIV_RHS,
                     || VmErrorKind::DivideByZero,
                     i64::checked_div,
                     std::ops::Div::div,
@@ -1664,6 +1886,7 @@ impl Vm {
             InstOp::Rem => {
                 self.internal_num(
                     Protocol::REM,
+                    Protocol::REM_RHS,
                     || VmErrorKind::DivideByZero,
                     i64::checked_rem,
                     std::ops::Rem::rem,
@@ -1714,16 +1937,44 @@ impl Vm {
                 self.internal_infallible_bitwise(Protocol::SHR, std::ops::Shr::shr, lhs, rhs)?;
             }
             InstOp::Gt => {
-                self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, ">", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a > b,
+                    |a, b| a > b,
+                    |o| o == Ordering::Greater,
+                    ">",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Gte => {
-                self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, ">=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a >= b,
+                    |a, b| a >= b,
+                    |o| o != Ordering::Less,
+                    ">=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lt => {
-                self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, "<", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a < b,
+                    |a, b| a < b,
+                    |o| o == Ordering::Less,
+                    "<",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lte => {
-                self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, "<=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a <= b,
+                    |a, b| a <= b,
+                    |o| o != Ordering::Greater,
+                    "<=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Eq => {
                 let rhs = self.stack.address(rhs)?;
@@ -2047,6 +2298,44 @@ impl Vm {
         }))
     }
 
+    /// Get the given index of a tuple or vector, counting from the back.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_tuple_index_get_back_at(&mut self, offset: usize, index: usize) -> Result<(), VmError> {
+        fn back(values: &[Value], index: usize) -> Option<Value> {
+            let index = values.len().checked_sub(index + 1)?;
+            values.get(index).cloned()
+        }
+
+        let value = self.stack.at_offset(offset)?;
+
+        let out = match value {
+            Value::Tuple(tuple) => back(&*tuple.borrow_ref()?, index),
+            Value::Vec(vec) => back(&*vec.borrow_ref()?, index),
+            _ => None,
+        };
+
+        if let Some(out) = out {
+            self.stack.push(out);
+            return Ok(());
+        }
+
+        Err(VmError::from(VmErrorKind::UnsupportedTupleIndexGet {
+            target: value.type_info()?,
+        }))
+    }
+
+    /// Get the elements of a vector, leaving out `start` elements at the start
+    /// and `end` elements at the end.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_vec_rest_at(&mut self, offset: usize, start: usize, end: usize) -> Result<(), VmError> {
+        let target = self.stack.at_offset(offset)?.clone().into_vec()?;
+        let target = target.borrow_ref()?;
+        let len = target.len().saturating_sub(end);
+        let rest = target.iter().take(len).skip(start).cloned().collect();
+        self.stack.push(Value::vec(rest));
+        Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_eq_bool(&mut self, boolean: bool) -> Result<(), VmError> {
         let value = self.stack.pop()?;
@@ -2126,6 +2415,29 @@ impl Vm {
         Ok(())
     }
 
+    /// Operation to allocate an object from the entries of a base object.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_object_spread(&mut self, slot: usize) -> Result<(), VmError> {
+        let keys = self
+            .unit
+            .lookup_object_keys(slot)
+            .ok_or(VmErrorKind::MissingStaticObjectKeys { slot })?;
+
+        let values = self
+            .stack
+            .drain_stack_top(keys.len())?
+            .collect::<vec::Vec<_>>();
+        let base = self.stack.pop()?.into_object()?;
+        let mut object = base.borrow_ref()?.clone();
+
+        for (key, value) in keys.iter().zip(values) {
+            object.insert(key.clone(), value);
+        }
+
+        self.stack.push(Shared::new(object));
+        Ok(())
+    }
+
     /// Operation to allocate an object.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_range(&mut self, limits: InstRangeLimits) -> Result<(), VmError> {
@@ -2232,6 +2544,13 @@ impl Vm {
         Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_bigint(&mut self, slot: usize) -> Result<(), VmError> {
+        let number = self.unit.lookup_bigint(slot)?.clone();
+        self.stack.push(number);
+        Ok(())
+    }
+
     /// Optimize operation to perform string concatenation.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_string_concat(&mut self, len: usize, size_hint: usize) -> Result<(), VmError> {
@@ -2241,7 +2560,7 @@ impl Vm {
         let mut buf = String::with_capacity(16);
 
         for value in values {
-            if let Err(fmt::Error) = value.string_display_with(&mut out, &mut buf, &mut *self)? {
+            if let Err(fmt::Error) = value.string_display_with(&mut out, &mut buf, self)? {
                 return Err(VmError::from(VmErrorKind::FormatError));
             }
         }
@@ -2288,6 +2607,28 @@ impl Vm {
         Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_optional_unwrap(&mut self) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        let (value, branch) = match value {
+            Value::Option(option) => match &*option.borrow_ref()? {
+                Some(value) => (value.clone(), 0),
+                None => (Value::Option(option.clone()), 1),
+            },
+            Value::Result(result) => match &*result.borrow_ref()? {
+                Ok(value) => (value.clone(), 0),
+                Err(..) => (Value::Result(result.clone()), 2),
+            },
+            Value::Unit => (Value::Unit, 1),
+            other => (other, 0),
+        };
+
+        self.stack.push(value);
+        self.stack.push(Value::Integer(branch));
+        Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_is_unit(&mut self) -> Result<(), VmError> {
         let value = self.stack.pop()?;
@@ -2295,6 +2636,18 @@ impl Vm {
         Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_is_present(&mut self) -> Result<(), VmError> {
+        let present = match self.stack.pop()? {
+            Value::Option(option) => option.borrow_ref()?.is_some(),
+            Value::Unit => false,
+            _ => true,
+        };
+
+        self.stack.push(present);
+        Ok(())
+    }
+
     /// Test if the top of the stack is an error.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_is_value(&mut self) -> Result<(), VmError> {
@@ -2451,12 +2804,20 @@ impl Vm {
     fn op_load_fn(&mut self, hash: Hash) -> Result<(), VmError> {
         let function = match self.unit.lookup(hash) {
             Some(info) => match info {
-                UnitFn::Offset { offset, call, args } => Function::from_offset(
+                UnitFn::Offset {
+                    offset,
+                    call,
+                    args,
+                    defaults,
+                    rest,
+                } => Function::from_offset(
                     self.context.clone(),
                     self.unit.clone(),
                     offset,
                     call,
                     args,
+                    defaults,
+                    rest,
                     hash,
                 ),
                 UnitFn::UnitStruct { hash } => {
@@ -2515,7 +2876,9 @@ impl Vm {
             .ok_or(VmErrorKind::MissingFunction { hash })?;
 
         let (offset, call, args) = match info {
-            UnitFn::Offset { offset, call, args } => (offset, call, args),
+            UnitFn::Offset {
+                offset, call, args, ..
+            } => (offset, call, args),
             _ => return Err(VmError::from(VmErrorKind::MissingFunction { hash })),
         };
 
@@ -2535,6 +2898,28 @@ impl Vm {
         Ok(())
     }
 
+    /// Implementation of a function call in tail position.
+    ///
+    /// Immediate functions in the current unit reuse the current call frame,
+    /// anything else is called like usual.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_tail_call(&mut self, hash: Hash, args: usize) -> Result<(), VmError> {
+        if let Some(UnitFn::Offset {
+            offset,
+            call: Call::Immediate,
+            args: expected,
+            defaults,
+            rest,
+        }) = self.unit.lookup(hash)
+        {
+            Self::check_offset_args(args, expected, defaults, rest)?;
+            self.replace_call_frame(offset, args)?;
+            return Ok(());
+        }
+
+        self.op_call(hash, args)
+    }
+
     /// Implementation of a function call.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_call(&mut self, hash: Hash, args: usize) -> Result<(), VmError> {
@@ -2544,8 +2929,10 @@ impl Vm {
                     offset,
                     call,
                     args: expected,
+                    defaults,
+                    rest,
                 } => {
-                    Self::check_args(args, expected)?;
+                    Self::check_offset_args(args, expected, defaults, rest)?;
                     self.call_offset_fn(offset, call, args)?;
                 }
                 UnitFn::UnitStruct { hash } => {
@@ -2633,8 +3020,10 @@ impl Vm {
                     offset,
                     call,
                     args: expected,
+                    defaults,
+                    rest,
                 } => {
-                    Self::check_args(args, expected)?;
+                    Self::check_offset_args(args, expected, defaults, rest)?;
                     self.call_offset_fn(offset, call, args)?;
                 }
                 _ => {
@@ -2663,24 +3052,42 @@ impl Vm {
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_call_fn(&mut self, args: usize) -> Result<Option<VmHalt>, VmError> {
+    fn op_call_fn(&mut self, args: usize, tail: bool) -> Result<Option<VmHalt>, VmError> {
         let function = self.stack.pop()?;
 
         let hash = match function {
             Value::Type(hash) => hash,
             Value::Function(function) => {
                 let function = function.into_ref()?;
-                return function.call_with_vm(self, args);
+                return function.call_with_vm(self, args, tail);
             }
             actual => {
-                let actual_type = actual.type_info()?;
-                return Err(VmError::from(VmErrorKind::UnsupportedCallFn {
-                    actual_type,
-                }));
+                let hash = Hash::instance_function(actual.type_hash()?, Protocol::CALL);
+
+                let handler = match self.context.lookup(hash) {
+                    Some(handler) => handler.clone(),
+                    None => {
+                        return Err(VmError::from(VmErrorKind::NotCallable {
+                            type_info: actual.type_info()?,
+                        }));
+                    }
+                };
+
+                // NB: the called value is passed in as the first argument.
+                let values = self.stack.pop_sequence(args)?;
+                self.stack.push(actual);
+                self.stack.extend(values);
+                handler(&mut self.stack, args + 1)?;
+                return Ok(None);
             }
         };
 
-        self.op_call(hash, args)?;
+        if tail {
+            self.op_tail_call(hash, args)?;
+        } else {
+            self.op_call(hash, args)?;
+        }
+
         Ok(None)
     }
 
@@ -2747,7 +3154,13 @@ impl Vm {
     where
         F: FnOnce() -> T,
     {
-        let _guard = crate::env::Guard::new(&self.context, &self.unit);
+        let _guard = crate::env::Guard::new(
+            &self.context,
+            &self.unit,
+            &self.call_frames,
+            &self.trace,
+            &self.output,
+        );
         f()
     }
 
@@ -2755,7 +3168,13 @@ impl Vm {
     pub(crate) fn run(&mut self) -> Result<VmHalt, VmError> {
         // NB: set up environment so that native function can access context and
         // unit.
-        let _guard = crate::env::Guard::new(&self.context, &self.unit);
+        let _guard = crate::env::Guard::new(
+            &self.context,
+            &self.unit,
+            &self.call_frames,
+            &self.trace,
+            &self.output,
+        );
 
         loop {
             if !budget::take() {
@@ -2769,6 +3188,10 @@ impl Vm {
 
             log::trace!("{}: {}", self.ip, inst);
 
+            if let Some(Trace(trace)) = &self.trace {
+                trace(self.ip, self.unit.debug_info_at(self.ip).map(|d| d.span));
+            }
+
             match inst {
                 Inst::Not => {
                     self.op_not()?;
@@ -2782,11 +3205,19 @@ impl Vm {
                 Inst::Call { hash, args } => {
                     self.op_call(hash, args)?;
                 }
+                Inst::TailCall { hash, args } => {
+                    self.op_tail_call(hash, args)?;
+                }
                 Inst::CallInstance { hash, args } => {
                     self.op_call_instance(hash, args)?;
                 }
                 Inst::CallFn { args } => {
-                    if let Some(reason) = self.op_call_fn(args)? {
+                    if let Some(reason) = self.op_call_fn(args, false)? {
+                        return Ok(reason);
+                    }
+                }
+                Inst::TailCallFn { args } => {
+                    if let Some(reason) = self.op_call_fn(args, true)? {
                         return Ok(reason);
                     }
                 }
@@ -2805,6 +3236,12 @@ impl Vm {
                 Inst::TupleIndexGetAt { offset, index } => {
                     self.op_tuple_index_get_at(offset, index)?;
                 }
+                Inst::TupleIndexGetBackAt { offset, index } => {
+                    self.op_tuple_index_get_back_at(offset, index)?;
+                }
+                Inst::VecRestAt { offset, start, end } => {
+                    self.op_vec_rest_at(offset, start, end)?;
+                }
                 Inst::ObjectIndexGet { slot } => {
                     self.op_object_index_get(slot)?;
                 }
@@ -2888,6 +3325,12 @@ impl Vm {
                 Inst::JumpIfBranch { branch, offset } => {
                     self.op_jump_if_branch(branch, offset)?;
                 }
+                Inst::JumpIfFrameLen { len, offset } => {
+                    self.op_jump_if_frame_len(len, offset)?;
+                }
+                Inst::CollectRest { args } => {
+                    self.op_collect_rest(args)?;
+                }
                 Inst::Vec { count } => {
                     self.op_vec(count)?;
                 }
@@ -2912,6 +3355,9 @@ impl Vm {
                 Inst::Object { slot } => {
                     self.op_object(slot)?;
                 }
+                Inst::ObjectSpread { slot } => {
+                    self.op_object_spread(slot)?;
+                }
                 Inst::Range { limits } => {
                     self.op_range(limits)?;
                 }
@@ -2933,6 +3379,9 @@ impl Vm {
                 Inst::Bytes { slot } => {
                     self.op_bytes(slot)?;
                 }
+                Inst::BigInt { slot } => {
+                    self.op_bigint(slot)?;
+                }
                 Inst::StringConcat { len, size_hint } => {
                     self.op_string_concat(len, size_hint)?;
                 }
@@ -2945,9 +3394,15 @@ impl Vm {
                 Inst::IsValue => {
                     self.op_is_value()?;
                 }
+                Inst::IsPresent => {
+                    self.op_is_present()?;
+                }
                 Inst::Unwrap => {
                     self.op_unwrap()?;
                 }
+                Inst::OptionalUnwrap => {
+                    self.op_optional_unwrap()?;
+                }
                 Inst::EqByte { byte } => {
                     self.op_eq_byte(byte)?;
                 }
@@ -3025,7 +3480,7 @@ impl AsRef<Vm> for Vm {
 /// A call frame.
 ///
 /// This is used to store the return point after an instruction has been run.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CallFrame {
     /// The stored instruction pointer.
     ip: usize,
@@ -205,8 +205,14 @@ pub enum VmErrorKind {
         protocol: Protocol,
         actual: TypeInfo,
     },
+    #[error("`{actual}` can't be deep cloned since it contains itself")]
+    CyclicClone { actual: TypeInfo },
+    #[error("ensure failed: {message}")]
+    EnsureFailed { message: String },
     #[error("static string slot `{slot}` does not exist")]
     MissingStaticString { slot: usize },
+    #[error("static big integer slot `{slot}` does not exist")]
+    MissingStaticBigInt { slot: usize },
     #[error("static object keys slot `{slot}` does not exist")]
     MissingStaticObjectKeys { slot: usize },
     #[error("missing runtime information for variant with hash `{hash}`")]
@@ -227,13 +233,19 @@ pub enum VmErrorKind {
         error: VmError,
         arg: usize,
     },
-    #[error("the index set operation `{target}[{index}] = {value}` is not supported")]
+    #[error("bad value for key `{key}`: {error}")]
+    BadObjectValue {
+        #[source]
+        error: VmError,
+        key: String,
+    },
+    #[error("type `{target}` does not support indexing with `{index}` to assign `{value}`")]
     UnsupportedIndexSet {
         target: TypeInfo,
         index: TypeInfo,
         value: TypeInfo,
     },
-    #[error("the index get operation `{target}[{index}]` is not supported")]
+    #[error("type `{target}` does not support indexing with `{index}`")]
     UnsupportedIndexGet { target: TypeInfo, index: TypeInfo },
     #[error("the tuple index get operation is not supported on `{target}`")]
     UnsupportedTupleIndexGet { target: TypeInfo },
@@ -248,8 +260,8 @@ pub enum VmErrorKind {
         value: TypeInfo,
         test_type: TypeInfo,
     },
-    #[error("`{actual_type}` cannot be called since it's not a function")]
-    UnsupportedCallFn { actual_type: TypeInfo },
+    #[error("`{type_info}` is not callable")]
+    NotCallable { type_info: TypeInfo },
     #[error("missing index by static string slot `{slot}` in object")]
     ObjectIndexMissing { slot: usize },
     #[error("`{target}` missing index `{index}`")]
@@ -313,6 +325,10 @@ pub enum VmErrorKind {
     ExpectedVariant { actual: TypeInfo },
     #[error("{actual} can't be converted to a constant value")]
     ConstNotSupported { actual: TypeInfo },
+    #[error("`{actual}` can't be included in a snapshot")]
+    SnapshotNotSupported { actual: TypeInfo },
+    #[error("missing shared value `{index}` in snapshot")]
+    MissingSnapshotValue { index: usize },
     #[error("{actual} can't be converted to a hash key")]
     KeyNotSupported { actual: TypeInfo },
     #[error("missing interface environment")]
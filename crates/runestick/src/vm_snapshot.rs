@@ -0,0 +1,335 @@
+use crate::collections::HashMap;
+use crate::{
+    Bytes, CallFrame, Hash, Object, Range, RangeLimits, Shared, Stack, Struct, Tuple, Unit, Value,
+    Variant, VariantData, VmError, VmErrorKind,
+};
+use serde::{Deserialize, Serialize};
+use std::vec;
+
+/// A snapshot of the execution state of a virtual machine, as produced by
+/// [Vm::snapshot][crate::Vm::snapshot].
+///
+/// A snapshot can be serialized using any serde format, and restored later
+/// using [Vm::restore][crate::Vm::restore] to resume execution where it left
+/// off. It must be restored against the same unit that it was taken from.
+///
+/// Values which are referenced from multiple places are stored once, so that
+/// they are still shared after being restored. They are numbered in the order
+/// in which they are first reached from the bottom of the stack rather than by
+/// where they happen to be allocated, so identical executions produce
+/// identical snapshots.
+///
+/// Values which hold on to host resources, like external types, function
+/// pointers, iterators, and futures, can't be snapshotted. Neither can values
+/// which contain themselves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VmSnapshot {
+    /// The instruction pointer.
+    pub(crate) ip: usize,
+    /// The bottom of the current stack frame.
+    pub(crate) stack_bottom: usize,
+    /// Shared values, which are referenced by index from the stack. A shared
+    /// value can only reference shared values which come before it.
+    pub(crate) shared: vec::Vec<SnapshotValue>,
+    /// Values on the stack.
+    pub(crate) stack: vec::Vec<SnapshotValue>,
+    /// Call frames.
+    pub(crate) call_frames: vec::Vec<CallFrame>,
+}
+
+impl VmSnapshot {
+    /// Take a snapshot of the given execution state.
+    pub(crate) fn new(
+        ip: usize,
+        stack: &Stack,
+        call_frames: &[CallFrame],
+    ) -> Result<Self, VmError> {
+        let mut snapshotter = Snapshotter::default();
+        let stack_bottom = stack.stack_bottom();
+        let stack = snapshotter.all(stack.iter())?;
+
+        Ok(Self {
+            ip,
+            stack_bottom,
+            shared: snapshotter.shared,
+            stack,
+            call_frames: call_frames.to_vec(),
+        })
+    }
+
+    /// Restore the stack stored in the snapshot.
+    pub(crate) fn restore_stack(
+        shared: vec::Vec<SnapshotValue>,
+        stack: vec::Vec<SnapshotValue>,
+        unit: &Unit,
+    ) -> Result<vec::Vec<Value>, VmError> {
+        let mut restored = vec::Vec::with_capacity(shared.len());
+
+        for value in shared {
+            let value = value.into_value(unit, &restored)?;
+            restored.push(value);
+        }
+
+        stack
+            .into_iter()
+            .map(|value| value.into_value(unit, &restored))
+            .collect()
+    }
+}
+
+/// A value stored in a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum SnapshotValue {
+    Unit,
+    Bool(bool),
+    Byte(u8),
+    Char(char),
+    Integer(i64),
+    Float(f64),
+    Type(Hash),
+    /// A reference to a shared value by index.
+    Shared(usize),
+    String(String),
+    Bytes(vec::Vec<u8>),
+    Vec(vec::Vec<SnapshotValue>),
+    Tuple(vec::Vec<SnapshotValue>),
+    Object(vec::Vec<(String, SnapshotValue)>),
+    Range {
+        start: Option<Box<SnapshotValue>>,
+        end: Option<Box<SnapshotValue>>,
+        closed: bool,
+    },
+    Option(Option<Box<SnapshotValue>>),
+    Result(Result<Box<SnapshotValue>, Box<SnapshotValue>>),
+    UnitStruct(Hash),
+    TupleStruct(Hash, vec::Vec<SnapshotValue>),
+    Struct(Hash, vec::Vec<(String, SnapshotValue)>),
+    UnitVariant(Hash),
+    TupleVariant(Hash, vec::Vec<SnapshotValue>),
+    StructVariant(Hash, vec::Vec<(String, SnapshotValue)>),
+}
+
+/// Helper used to convert values into snapshot values, keeping track of
+/// shared values which have already been visited.
+#[derive(Default)]
+struct Snapshotter {
+    /// Shared values which have been converted.
+    shared: vec::Vec<SnapshotValue>,
+    /// Index of shared values by address. `None` indicates that the value is
+    /// currently being converted.
+    seen: HashMap<usize, Option<usize>>,
+}
+
+impl Snapshotter {
+    /// Convert a value into a snapshot value without modifying it.
+    fn snapshot_value(&mut self, value: &Value) -> Result<SnapshotValue, VmError> {
+        Ok(match value {
+            Value::Unit => SnapshotValue::Unit,
+            Value::Bool(b) => SnapshotValue::Bool(*b),
+            Value::Byte(b) => SnapshotValue::Byte(*b),
+            Value::Char(c) => SnapshotValue::Char(*c),
+            Value::Integer(n) => SnapshotValue::Integer(*n),
+            Value::Float(n) => SnapshotValue::Float(*n),
+            Value::Type(hash) => SnapshotValue::Type(*hash),
+            Value::StaticString(s) => SnapshotValue::String(String::clone(s)),
+            Value::String(s) => {
+                self.shared(value, s, |_, s| Ok(SnapshotValue::String(s.clone())))?
+            }
+            Value::Bytes(b) => {
+                self.shared(value, b, |_, b| Ok(SnapshotValue::Bytes(b.to_vec())))?
+            }
+            Value::Vec(vec) => self.shared(value, vec, |this, vec| {
+                Ok(SnapshotValue::Vec(this.all(vec.iter())?))
+            })?,
+            Value::Tuple(tuple) => self.shared(value, tuple, |this, tuple| {
+                Ok(SnapshotValue::Tuple(this.all(tuple.iter())?))
+            })?,
+            Value::Object(object) => self.shared(value, object, |this, object| {
+                Ok(SnapshotValue::Object(this.object(object)?))
+            })?,
+            Value::Range(range) => self.shared(value, range, |this, range| {
+                Ok(SnapshotValue::Range {
+                    start: this.boxed(range.start.as_ref())?,
+                    end: this.boxed(range.end.as_ref())?,
+                    closed: matches!(range.limits, RangeLimits::Closed),
+                })
+            })?,
+            Value::Option(option) => self.shared(value, option, |this, option| {
+                Ok(SnapshotValue::Option(this.boxed(option.as_ref())?))
+            })?,
+            Value::Result(result) => self.shared(value, result, |this, result| {
+                Ok(SnapshotValue::Result(match result {
+                    Ok(value) => Ok(Box::new(this.snapshot_value(value)?)),
+                    Err(value) => Err(Box::new(this.snapshot_value(value)?)),
+                }))
+            })?,
+            Value::UnitStruct(data) => self.shared(value, data, |_, data| {
+                Ok(SnapshotValue::UnitStruct(data.rtti.hash))
+            })?,
+            Value::TupleStruct(data) => self.shared(value, data, |this, data| {
+                Ok(SnapshotValue::TupleStruct(
+                    data.rtti.hash,
+                    this.all(data.data.iter())?,
+                ))
+            })?,
+            Value::Struct(data) => self.shared(value, data, |this, data| {
+                Ok(SnapshotValue::Struct(
+                    data.rtti.hash,
+                    this.object(&data.data)?,
+                ))
+            })?,
+            Value::Variant(data) => self.shared(value, data, |this, data| {
+                let hash = data.rtti.hash;
+
+                Ok(match &data.data {
+                    VariantData::Unit => SnapshotValue::UnitVariant(hash),
+                    VariantData::Tuple(tuple) => {
+                        SnapshotValue::TupleVariant(hash, this.all(tuple.iter())?)
+                    }
+                    VariantData::Struct(data) => {
+                        SnapshotValue::StructVariant(hash, this.object(data)?)
+                    }
+                })
+            })?,
+            value => return Err(Self::not_supported(value)),
+        })
+    }
+
+    /// Convert a shared value, making sure that it's only stored once.
+    fn shared<T, F>(
+        &mut self,
+        value: &Value,
+        shared: &Shared<T>,
+        f: F,
+    ) -> Result<SnapshotValue, VmError>
+    where
+        T: ?Sized,
+        F: FnOnce(&mut Self, &T) -> Result<SnapshotValue, VmError>,
+    {
+        let addr = shared.addr();
+
+        match self.seen.get(&addr) {
+            Some(Some(index)) => return Ok(SnapshotValue::Shared(*index)),
+            Some(None) => return Err(Self::not_supported(value)),
+            None => (),
+        }
+
+        self.seen.insert(addr, None);
+        let snapshot = f(self, &*shared.borrow_ref()?)?;
+        let index = self.shared.len();
+        self.shared.push(snapshot);
+        self.seen.insert(addr, Some(index));
+        Ok(SnapshotValue::Shared(index))
+    }
+
+    fn all<'a, I>(&mut self, values: I) -> Result<vec::Vec<SnapshotValue>, VmError>
+    where
+        I: IntoIterator<Item = &'a Value>,
+    {
+        values.into_iter().map(|v| self.snapshot_value(v)).collect()
+    }
+
+    fn object(&mut self, object: &Object) -> Result<vec::Vec<(String, SnapshotValue)>, VmError> {
+        object
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), self.snapshot_value(v)?)))
+            .collect()
+    }
+
+    fn boxed(&mut self, value: Option<&Value>) -> Result<Option<Box<SnapshotValue>>, VmError> {
+        Ok(match value {
+            Some(value) => Some(Box::new(self.snapshot_value(value)?)),
+            None => None,
+        })
+    }
+
+    fn not_supported(value: &Value) -> VmError {
+        match value.type_info() {
+            Ok(actual) => VmError::from(VmErrorKind::SnapshotNotSupported { actual }),
+            Err(error) => error,
+        }
+    }
+}
+
+impl SnapshotValue {
+    /// Convert a snapshot value back into a value, looking up runtime type
+    /// information in the given unit and shared values among the ones that
+    /// have already been restored.
+    fn into_value(self, unit: &Unit, shared: &[Value]) -> Result<Value, VmError> {
+        let all = |values: vec::Vec<Self>| -> Result<vec::Vec<Value>, VmError> {
+            values
+                .into_iter()
+                .map(|v| v.into_value(unit, shared))
+                .collect()
+        };
+
+        let object = |values: vec::Vec<(String, Self)>| -> Result<Object, VmError> {
+            values
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.into_value(unit, shared)?)))
+                .collect()
+        };
+
+        let boxed = |value: Option<Box<Self>>| -> Result<Option<Value>, VmError> {
+            value.map(|v| v.into_value(unit, shared)).transpose()
+        };
+
+        let rtti = |hash: Hash| {
+            unit.lookup_rtti(hash)
+                .cloned()
+                .ok_or_else(|| VmError::from(VmErrorKind::MissingRtti { hash }))
+        };
+
+        let variant_rtti = |hash: Hash| {
+            unit.lookup_variant_rtti(hash)
+                .cloned()
+                .ok_or_else(|| VmError::from(VmErrorKind::MissingVariantRtti { hash }))
+        };
+
+        Ok(match self {
+            Self::Unit => Value::Unit,
+            Self::Bool(b) => Value::Bool(b),
+            Self::Byte(b) => Value::Byte(b),
+            Self::Char(c) => Value::Char(c),
+            Self::Integer(n) => Value::Integer(n),
+            Self::Float(n) => Value::Float(n),
+            Self::Type(hash) => Value::Type(hash),
+            Self::Shared(index) => match shared.get(index) {
+                Some(value) => value.clone(),
+                None => return Err(VmError::from(VmErrorKind::MissingSnapshotValue { index })),
+            },
+            Self::String(s) => Value::from(s),
+            Self::Bytes(b) => Value::from(Bytes::from_vec(b)),
+            Self::Vec(vec) => Value::vec(all(vec)?),
+            Self::Tuple(tuple) => Value::tuple(all(tuple)?),
+            Self::Object(o) => Value::from(object(o)?),
+            Self::Range { start, end, closed } => {
+                let limits = if closed {
+                    RangeLimits::Closed
+                } else {
+                    RangeLimits::HalfOpen
+                };
+
+                Value::from(Range::new(boxed(start)?, boxed(end)?, limits))
+            }
+            Self::Option(option) => Value::Option(Shared::new(boxed(option)?)),
+            Self::Result(result) => Value::Result(Shared::new(match result {
+                Ok(value) => Ok(value.into_value(unit, shared)?),
+                Err(value) => Err(value.into_value(unit, shared)?),
+            })),
+            Self::UnitStruct(hash) => Value::unit_struct(rtti(hash)?),
+            Self::TupleStruct(hash, data) => Value::tuple_struct(rtti(hash)?, all(data)?),
+            Self::Struct(hash, data) => Value::from(Struct {
+                rtti: rtti(hash)?,
+                data: object(data)?,
+            }),
+            Self::UnitVariant(hash) => Value::from(Variant::unit(variant_rtti(hash)?)),
+            Self::TupleVariant(hash, data) => {
+                Value::from(Variant::tuple(variant_rtti(hash)?, Tuple::from(all(data)?)))
+            }
+            Self::StructVariant(hash, data) => {
+                Value::from(Variant::struct_(variant_rtti(hash)?, object(data)?))
+            }
+        })
+    }
+}
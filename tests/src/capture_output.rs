@@ -1,6 +1,7 @@
 //! Utilities related to testing
 
-use runestick::{ContextError, Module, Panic, Stack, Value, VmError};
+use runestick::modules::io;
+use runestick::{ContextError, Module, Panic, Stack, VmError};
 use std::io::Write as _;
 
 /// Provide a bunch of `std` functions that can be used during tests to capture output.
@@ -8,7 +9,7 @@ pub fn output_redirect_module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", &["io"]);
     module.function(&["print"], print_impl)?;
     module.function(&["println"], println_impl)?;
-    module.raw_fn(&["dbg"], dbg_impl)?;
+    module.raw_fn_with_call_site(&["dbg"], dbg_impl, dbg_at_impl)?;
     Ok(module)
 }
 
@@ -33,12 +34,15 @@ fn println_impl(m: &str) -> Result<(), Panic> {
 }
 
 fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
-    let mut o = OUT.lock();
-
-    for value in stack.drain_stack_top(args)? {
-        writeln!(o, "{:?}", value).map_err(VmError::panic)?;
-    }
+    let mut buf = Vec::new();
+    io::dbg_to(&mut buf, stack, args)?;
+    OUT.lock().extend_from_slice(&buf);
+    Ok(())
+}
 
-    stack.push(Value::Unit);
+fn dbg_at_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    let mut buf = Vec::new();
+    io::dbg_at_to(&mut buf, stack, args)?;
+    OUT.lock().extend_from_slice(&buf);
     Ok(())
 }
@@ -98,6 +98,16 @@ pub fn vm_with_source(
     vm(context, &mut sources)
 }
 
+/// Construct the default context with the given native module installed,
+/// allowing testing of native Rust data through [run].
+pub fn context_with_module(
+    module: &runestick::Module,
+) -> runestick::Result<Arc<runestick::Context>> {
+    let mut context = rune_modules::default_context()?;
+    context.install(module)?;
+    Ok(Arc::new(context))
+}
+
 /// Call the specified function in the given script.
 async fn internal_run_async<N, A, T>(
     context: &Arc<runestick::Context>,
@@ -369,9 +379,7 @@ macro_rules! rune_s {
 #[macro_export]
 macro_rules! rune_n {
     ($module:expr, $args:expr, $ty:ty => $($tt:tt)*) => {{
-        let mut context = $crate::macros::rune_modules::default_context().expect("failed to build context");
-        context.install(&$module).expect("failed to install native module");
-        let context = std::sync::Arc::new(context);
+        let context = $crate::context_with_module(&$module).expect("failed to build context");
 
         $crate::run_with_diagnostics::<_, _, $ty>(&context, stringify!($($tt)*), &["main"], $args)
             .expect("program to run successfully")
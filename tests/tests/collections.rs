@@ -36,6 +36,88 @@ fn test_hash_set_tuple() {
 
             assert!(m.contains((0, 1)));
             assert!(!m.contains((0, 2)));
+
+            assert!(m.remove((0, 1)));
+            assert!(!m.remove((0, 1)));
         }
     }
 }
+
+#[test]
+fn test_hash_map_scalar_keys() {
+    assert_eq! {
+        rune! { (Option<i64>, Option<i64>, Option<i64>, i64, i64) =>
+            pub fn main() {
+                use std::collections::HashMap;
+
+                let m = HashMap::new();
+
+                m.insert(1, 10);
+                m.insert(-1, 20);
+                m.insert('a', 30);
+                m.insert(true, 40);
+                m.insert((1, 2), 50);
+
+                let removed = m.remove(-1);
+
+                (m.get(1), removed, m.get(-1), m[(1, 2)], m.len())
+            }
+        },
+        (Some(10), Some(20), None, 50, 4),
+    };
+}
+
+#[test]
+fn test_hash_map_tuple_keys() {
+    assert_eq! {
+        rune! { (Option<i64>, Option<i64>, Option<i64>) =>
+            pub fn main() {
+                use std::collections::HashMap;
+
+                let m = HashMap::new();
+
+                for x in 0..3 {
+                    for y in 0..3 {
+                        m.insert((x, y), x * 3 + y);
+                    }
+                }
+
+                (m.get((2, 1)), m.get((1, 2)), m.get((3, 0)))
+            }
+        },
+        (Some(7), Some(5), None),
+    };
+}
+
+#[test]
+fn test_hash_map_unsupported_keys() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let m = std::collections::HashMap::new();
+            m.insert(1.0, 10);
+        }
+        "#,
+        BadArgument { error, arg: 1 } => {
+            match error.into_kind() {
+                KeyNotSupported { actual } => assert_eq!(actual.to_string(), "float"),
+                kind => panic!("unexpected error {:?}", kind),
+            }
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let m = std::collections::HashMap::new();
+            m.insert(#{}, 10);
+        }
+        "#,
+        BadArgument { error, arg: 1 } => {
+            match error.into_kind() {
+                KeyNotSupported { actual } => assert_eq!(actual.to_string(), "Object"),
+                kind => panic!("unexpected error {:?}", kind),
+            }
+        }
+    );
+}
@@ -0,0 +1,197 @@
+use rune::{CompileVisitor, Diagnostics, FileSourceLoader, ItemKind, Options, Sources};
+use runestick::{Context, Item, Source, SourceId, Span};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A visitor which collects the edges of the call graph.
+#[derive(Default)]
+struct CallVisitor {
+    calls: RefCell<Vec<(Item, Item)>>,
+}
+
+impl CompileVisitor for CallVisitor {
+    fn visit_call(&self, _source_id: SourceId, from: &Item, to: &Item, _span: Span) {
+        self.calls.borrow_mut().push((from.clone(), to.clone()));
+    }
+}
+
+#[test]
+fn test_visit_call() {
+    let context = Context::with_default_modules().unwrap();
+    let visitor = Rc::new(CallVisitor::default());
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "main",
+        r#"
+        fn add(a, b) { a + b }
+        fn double(n) { add(n, n) }
+
+        pub fn main() {
+            let f = double;
+            let g = |n| n;
+            add(1, 2) + f(3) + g(4) + [5].len()
+        }
+        "#,
+    ));
+
+    let mut diagnostics = Diagnostics::new();
+
+    rune::load_sources_with_visitor(
+        &context,
+        &Options::default(),
+        &mut sources,
+        &mut diagnostics,
+        visitor.clone(),
+        Rc::new(FileSourceLoader::new()),
+    )
+    .unwrap();
+
+    let mut calls = visitor.calls.take();
+    calls.sort_by(|a, b| a.0.iter().cmp(b.0.iter()));
+
+    assert_eq!(
+        calls,
+        vec![
+            (Item::with_item(&["double"]), Item::with_item(&["add"])),
+            (Item::with_item(&["main"]), Item::with_item(&["add"])),
+        ]
+    );
+}
+
+/// A visitor which collects variable definitions and uses.
+#[derive(Default)]
+struct VariableVisitor {
+    defs: RefCell<Vec<(String, Span)>>,
+    uses: RefCell<Vec<(Span, Span)>>,
+}
+
+impl CompileVisitor for VariableVisitor {
+    fn visit_variable_def(&self, _source_id: SourceId, name: &str, span: Span) {
+        self.defs.borrow_mut().push((name.to_owned(), span));
+    }
+
+    fn visit_variable_use(&self, _source_id: SourceId, var_span: Span, span: Span) {
+        self.uses.borrow_mut().push((var_span, span));
+    }
+}
+
+#[test]
+fn test_visit_variable_def() {
+    let context = Context::with_default_modules().unwrap();
+    let visitor = Rc::new(VariableVisitor::default());
+
+    let source = r#"
+    pub fn main() {
+        let a = 1;
+        let b = a;
+        let a = b + 1;
+        a
+    }
+    "#;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let mut diagnostics = Diagnostics::new();
+
+    rune::load_sources_with_visitor(
+        &context,
+        &Options::default(),
+        &mut sources,
+        &mut diagnostics,
+        visitor.clone(),
+        Rc::new(FileSourceLoader::new()),
+    )
+    .unwrap();
+
+    let defs = visitor.defs.take();
+    let names = defs
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["a", "b", "a"]);
+
+    for (name, span) in &defs {
+        assert_eq!(&source[span.range()], name);
+    }
+
+    // Every use resolves to the closest preceding definition, so the shadowed
+    // `a` is only used by the definition of `b`.
+    let uses = visitor
+        .uses
+        .take()
+        .into_iter()
+        .map(|(var_span, span)| {
+            let def = defs.iter().position(|(_, s)| *s == var_span).unwrap();
+            (def, &source[span.range()])
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(uses, vec![(0, "a"), (1, "b"), (2, "a")]);
+}
+
+/// A visitor which collects the declared items.
+#[derive(Default)]
+struct ItemVisitor {
+    items: RefCell<Vec<(Item, Span, ItemKind)>>,
+}
+
+impl CompileVisitor for ItemVisitor {
+    fn visit_item(&self, _source_id: SourceId, item: &Item, span: Span, kind: ItemKind) {
+        self.items.borrow_mut().push((item.clone(), span, kind));
+    }
+}
+
+#[test]
+fn test_visit_item() {
+    let context = Context::with_default_modules().unwrap();
+    let visitor = Rc::new(ItemVisitor::default());
+
+    let source = r#"
+    struct Point { x, y }
+    const LIMIT = 10;
+    fn unused() { }
+    pub fn main() { Point { x: 1, y: LIMIT } }
+    "#;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let mut diagnostics = Diagnostics::new();
+
+    rune::load_sources_with_visitor(
+        &context,
+        &Options::default(),
+        &mut sources,
+        &mut diagnostics,
+        visitor.clone(),
+        Rc::new(FileSourceLoader::new()),
+    )
+    .unwrap();
+
+    let items = visitor
+        .items
+        .take()
+        .into_iter()
+        .map(|(item, span, kind)| (item.to_string(), &source[span.range()], kind))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        items,
+        vec![
+            (
+                String::from("Point"),
+                "struct Point { x, y }",
+                ItemKind::Struct
+            ),
+            (String::from("LIMIT"), "const LIMIT = 10", ItemKind::Const),
+            (String::from("unused"), "fn unused() { }", ItemKind::Function),
+            (
+                String::from("main"),
+                "pub fn main() { Point { x: 1, y: LIMIT } }",
+                ItemKind::Function
+            ),
+        ]
+    );
+}
@@ -10,3 +10,14 @@ fn test_bad_attributes() {
         }
     };
 }
+
+#[test]
+fn test_allow_unsupported_lint() {
+    assert_compile_error! {
+        r#"#[allow(unused, dead_code)] pub fn main() { let x = 1; }"#,
+        span, CompileErrorKind::ParseError { error: ParseErrorKind::Custom { message } } => {
+            assert_eq!(message, "unsupported lint");
+            assert_eq!(span, Span::new(16, 25));
+        }
+    };
+}
@@ -0,0 +1,109 @@
+use rune::{Diagnostic, Diagnostics, ErrorKind, Options, Sources};
+use runestick::{FromValue, Source, Unit, Vm};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+#[cfg(feature = "debug")]
+pub fn debug_only() {
+    "debug"
+}
+
+struct Logger;
+
+impl Logger {
+    #[cfg(feature = "debug")]
+    fn level(self) { "debug" }
+
+    #[cfg(feature = "release")]
+    fn level(self) { "release" }
+}
+
+pub fn main() {
+    #[cfg(feature = "debug")]
+    fn mode() { "debug" }
+
+    #[cfg(feature = "release")]
+    fn mode() { "release" }
+
+    (mode(), Logger.level())
+}
+"#;
+
+fn compile(source: &str, features: &[&str]) -> Result<Unit, Diagnostics> {
+    let context = runestick::Context::with_default_modules().unwrap();
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let mut options = Options::default();
+
+    for feature in features {
+        options.feature(feature, true);
+    }
+
+    let mut diagnostics = Diagnostics::new();
+
+    match rune::load_sources(&context, &options, &mut sources, &mut diagnostics) {
+        Ok(unit) => Ok(unit),
+        Err(_) => Err(diagnostics),
+    }
+}
+
+fn run(unit: Unit) -> runestick::Result<(String, String)> {
+    let context = runestick::Context::with_default_modules()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    Ok(<(String, String)>::from_value(vm.call(&["main"], ())?)?)
+}
+
+#[test]
+fn test_cfg_feature_enabled() -> runestick::Result<()> {
+    let unit = compile(SOURCE, &["debug"]).expect("source should compile");
+    assert!(unit
+        .lookup(runestick::Hash::type_hash(&["debug_only"]))
+        .is_some());
+
+    let output = run(unit)?;
+    assert_eq!(output, (String::from("debug"), String::from("debug")));
+    Ok(())
+}
+
+#[test]
+fn test_cfg_feature_disabled() -> runestick::Result<()> {
+    let unit = compile(SOURCE, &["release"]).expect("source should compile");
+    assert!(unit
+        .lookup(runestick::Hash::type_hash(&["debug_only"]))
+        .is_none());
+
+    let output = run(unit)?;
+    assert_eq!(output, (String::from("release"), String::from("release")));
+    Ok(())
+}
+
+#[test]
+fn test_cfg_disabled_item_is_missing() {
+    let source = r#"
+    #[cfg(feature = "debug")]
+    fn helper() { 42 }
+
+    pub fn main() { helper() }
+    "#;
+
+    assert!(compile(source, &["debug"]).is_ok());
+
+    let diagnostics = compile(source, &[]).expect_err("source should not compile");
+
+    let error = match diagnostics.into_diagnostics().into_iter().next() {
+        Some(Diagnostic::Error(error)) => error,
+        other => panic!("expected error but got {:?}", other),
+    };
+
+    match error.into_kind() {
+        ErrorKind::CompileError(error) => match error.into_kind() {
+            rune::CompileErrorKind::MissingItem { item, .. } => {
+                assert_eq!(item, runestick::Item::with_item(&["helper"]));
+            }
+            kind => panic!("unexpected error {:?}", kind),
+        },
+        kind => panic!("unexpected error {:?}", kind),
+    }
+}
@@ -1,4 +1,6 @@
+use rune::{Diagnostics, EmitDiagnostics as _, Sources};
 use rune_tests::*;
+use runestick::{Item, Source};
 
 #[test]
 fn test_use_variant_as_type() {
@@ -55,6 +57,24 @@ fn test_wrong_arguments() {
             assert_eq!(actual, 1);
         }
     };
+
+    assert_compile_error! {
+        r#"fn add(a) { a } pub fn main() { add(1, 2) }"#,
+        span, UnsupportedArgumentCount { expected, actual, .. } => {
+            assert_eq!(span, Span::new(32, 41));
+            assert_eq!(expected, 1);
+            assert_eq!(actual, 2);
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { std::string::String::new(1) }"#,
+        span, UnsupportedArgumentCount { expected, actual, .. } => {
+            assert_eq!(span, Span::new(16, 43));
+            assert_eq!(expected, 0);
+            assert_eq!(actual, 1);
+        }
+    };
 }
 
 #[test]
@@ -84,3 +104,133 @@ fn test_bad_struct_declaration() {
         }
     };
 }
+
+#[test]
+fn test_missing_item_suggestion() {
+    assert_compile_error! {
+        r#"fn foo() { 1 } pub fn main() { fooo() }"#,
+        span, MissingItem { item, suggestion } => {
+            assert_eq!(span, Span::new(31, 35));
+            assert_eq!(item, Item::with_item(&["fooo"]));
+            assert_eq!(suggestion, Some(Item::with_item(&["foo"])));
+        }
+    };
+
+    assert_compile_error! {
+        r#"mod util { pub fn add(a, b) { a + b } } pub fn main() { util::ad(1, 2) }"#,
+        span, MissingItem { suggestion, .. } => {
+            assert_eq!(span, Span::new(56, 64));
+            assert_eq!(suggestion, Some(Item::with_item(&["util", "add"])));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn foo() { 1 } pub fn main() { something_else() }"#,
+        span, MissingItem { suggestion, .. } => {
+            assert_eq!(span, Span::new(31, 45));
+            assert_eq!(suggestion, None);
+        }
+    };
+
+    let context = rune_modules::default_context().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "main",
+        "fn foo() { 1 } pub fn main() { fooo() }",
+    ));
+
+    let mut diagnostics = Diagnostics::new();
+    let result = rune::load_sources(
+        &context,
+        &Default::default(),
+        &mut sources,
+        &mut diagnostics,
+    );
+    assert!(result.is_err());
+
+    let mut buffer = rune::termcolor::Buffer::no_color();
+    diagnostics.emit_diagnostics(&mut buffer, &sources).unwrap();
+
+    let output = String::from_utf8(buffer.into_inner()).unwrap();
+    assert!(output.contains("did you mean `foo`?"), "{}", output);
+}
+
+#[test]
+fn test_struct_field_conflict() {
+    assert_compile_error! {
+        r#"struct Foo { x, x } pub fn main() { Foo { x: 1 } }"#,
+        span, QueryError { error: FieldConflict { field, existing_span } } => {
+            assert_eq!(span, Span::new(16, 17));
+            assert_eq!(existing_span, Span::new(13, 14));
+            assert_eq!(&*field, "x");
+        }
+    };
+
+    assert_compile_error! {
+        r#"enum Foo { Bar { a, b, a } } pub fn main() { Foo::Bar { a: 1, b: 2 } }"#,
+        span, QueryError { error: FieldConflict { field, existing_span } } => {
+            assert_eq!(span, Span::new(23, 24));
+            assert_eq!(existing_span, Span::new(17, 18));
+            assert_eq!(&*field, "a");
+        }
+    };
+}
+
+#[test]
+fn test_unknown_struct_field() {
+    assert_compile_error! {
+        r#"struct Foo { a } pub fn main() { let foo = Foo { a: 1 }; foo.b }"#,
+        span, UnknownStructField { field, item } => {
+            assert_eq!(span, Span::new(61, 62));
+            assert_eq!(&*field, "b");
+            assert_eq!(item, runestick::Item::with_item(&["Foo"]));
+        }
+    };
+
+    assert_compile_error! {
+        r#"struct Foo { a } pub fn main() { let foo = Foo { a: 1 }; for _ in 0..2 { foo.a + foo.c; } }"#,
+        span, UnknownStructField { field, .. } => {
+            assert_eq!(span, Span::new(85, 86));
+            assert_eq!(&*field, "c");
+        }
+    };
+
+    // Field accesses on values of unknown type are checked at runtime.
+    assert_eq! {
+        rune! { i64 =>
+            struct Foo { a }
+
+            fn get(foo) { foo.b }
+
+            pub fn main() {
+                let foo = Foo { a: 1 };
+                let bar = foo;
+                let foo = #{ b: 2 };
+                bar.a + get(foo) + foo.b
+            }
+        },
+        5,
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            struct Foo { a }
+
+            pub fn main() {
+                let foo = Foo { a: 1 };
+                let n = 0;
+
+                for i in 0..2 {
+                    if i == 1 {
+                        n += foo.b;
+                    }
+
+                    foo = #{ b: 2 };
+                }
+
+                n
+            }
+        },
+        2,
+    };
+}
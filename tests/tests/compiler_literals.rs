@@ -38,4 +38,104 @@ fn test_number_literals() {
             assert_eq!(span, Span::new(16, 82));
         }
     };
+
+    assert_compile_error! {
+        r#"pub fn main() { 0xffff_ffff_ffff_ffff }"#,
+        span, CompileErrorKind::ParseError { error: BadNumberOutOfBounds { .. }} => {
+            assert_eq!(span, Span::new(16, 37));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { 0x }"#,
+        span, CompileErrorKind::ResolveError { error: BadNumberLiteral { .. } } => {
+            assert_eq!(span, Span::new(16, 18));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { 0b102 }"#,
+        span, CompileErrorKind::ResolveError { error: BadNumberLiteral { .. } } => {
+            assert_eq!(span, Span::new(16, 21));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { 1.5e- }"#,
+        span, CompileErrorKind::ResolveError { error: BadNumberLiteral { .. } } => {
+            assert_eq!(span, Span::new(16, 21));
+        }
+    };
+}
+
+#[test]
+fn test_bad_escapes() {
+    assert_compile_error! {
+        r#"pub fn main() { "\u{}" }"#,
+        span, CompileErrorKind::ResolveError { error: BadUnicodeEscape { .. } } => {
+            assert_eq!(span, Span::new(17, 21));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { "\u{110000}" }"#,
+        span, CompileErrorKind::ResolveError { error: BadUnicodeEscape { .. } } => {
+            assert_eq!(span, Span::new(17, 27));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { '\u{1000041}' }"#,
+        span, CompileErrorKind::ResolveError { error: BadUnicodeEscape { .. } } => {
+            assert_eq!(span, Span::new(17, 27));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { "abc\u{1F6" }"#,
+        span, CompileErrorKind::ResolveError { error: BadUnicodeEscape { .. } } => {
+            assert_eq!(span, Span::new(20, 26));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { "\q" }"#,
+        span, CompileErrorKind::ResolveError { error: BadEscapeSequence { .. } } => {
+            assert_eq!(span, Span::new(17, 19));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { b'\u{41}' }"#,
+        span, CompileErrorKind::ResolveError { error: BadUnicodeEscapeInByteString { .. } } => {
+            assert_eq!(span, Span::new(18, 20));
+        }
+    };
+}
+
+#[test]
+fn test_unterminated_raw_string() {
+    assert_parse_error! {
+        r##"pub fn main() { r#"foo" }"##,
+        span, UnterminatedStrLit => {
+            assert_eq!(span, Span::new(16, 19));
+        }
+    };
+}
+
+#[test]
+fn test_non_ascii_byte_string() {
+    assert_compile_error! {
+        r#"pub fn main() { b"abé" }"#,
+        span, CompileErrorKind::ResolveError { error: BadByteStrChar { .. } } => {
+            assert_eq!(span, Span::new(20, 22));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { b"\né" }"#,
+        span, CompileErrorKind::ResolveError { error: BadByteStrChar { .. } } => {
+            assert_eq!(span, Span::new(20, 22));
+        }
+    };
 }
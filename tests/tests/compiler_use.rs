@@ -120,3 +120,54 @@ fn test_reexport_fn() {
 
     assert_eq!(result, 8);
 }
+
+#[test]
+fn test_alias_context_module() {
+    let result = rune! { (usize, usize) =>
+        use std::bytes as b;
+
+        pub fn main() {
+            let bytes = b::Bytes::with_capacity(3);
+            (b::Bytes::new().len(), bytes.capacity())
+        }
+    };
+
+    assert_eq!(result, (0, 3));
+}
+
+#[test]
+fn test_alias_installed_module() {
+    fn answer() -> i64 {
+        42
+    }
+
+    let mut module = runestick::Module::with_item(&["acme", "status"]);
+    module.function(&["answer"], answer).unwrap();
+
+    let result = rune_n! { module, (), i64 =>
+        use acme::status as s;
+
+        pub fn main() {
+            s::answer()
+        }
+    };
+
+    assert_eq!(result, 42);
+}
+
+#[test]
+fn test_alias_context_module_missing_member() {
+    assert_compile_error! {
+        r#"
+        use std::bytes as b;
+
+        pub fn main() {
+            b::missing()
+        }
+        "#,
+        span, MissingItem { item, .. } => {
+            assert_eq!(span, Span::new(67, 77));
+            assert_eq!(item, runestick::Item::with_crate_item("std", &["bytes", "missing"]));
+        }
+    };
+}
@@ -10,6 +10,23 @@ fn test_let_pattern_might_panic() {
     };
 }
 
+#[test]
+fn test_irrefutable_let_condition() {
+    assert_warnings! {
+        r#"pub fn main() { if let x = 1 { x } else { 2 } }"#,
+        IrrefutableLetCondition { span, .. } => {
+            assert_eq!(span, Span::new(23, 24));
+        }
+    };
+
+    assert_warnings! {
+        r#"pub fn main() { while let x = 1 { return x; } }"#,
+        IrrefutableLetCondition { span, .. } => {
+            assert_eq!(span, Span::new(26, 27));
+        }
+    };
+}
+
 #[test]
 fn test_template_without_variables() {
     assert_warnings! {
@@ -29,3 +46,226 @@ fn test_remove_variant_parens() {
         }
     };
 }
+
+#[test]
+fn test_non_exhaustive_match() {
+    assert_warnings! {
+        r#"
+        enum Op { Add, Sub(a), Mul { a } }
+
+        pub fn main(op) {
+            match op {
+                Op::Add => 1,
+                Op::Sub(0) => 2,
+                Op::Mul { a } if a > 0 => 3,
+                Op::Mul { .. } => 4,
+            }
+        }
+        "#,
+        NonExhaustiveMatch { missing, .. } => {
+            assert_eq!(missing, vec![runestick::Item::with_item(&["Op", "Sub"])]);
+        }
+    };
+}
+
+#[test]
+fn test_exhaustive_match() {
+    let context = rune_modules::default_context().unwrap();
+
+    let sources = [
+        r#"
+        enum Op { Add, Sub(a), Mul { a } }
+        pub fn main(op) { match op { Op::Add => 1, Op::Sub(..) => 2, Op::Mul { a } => a } }
+        "#,
+        r#"
+        enum Op { Add, Sub(a), Mul { a } }
+        pub fn main(op) { match op { Op::Add => 1, _ => 2 } }
+        "#,
+        r#"
+        enum Op { Add, Sub(a), Mul { a } }
+        pub fn main(op) { match op { Op::Add => 1, _other => 2 } }
+        "#,
+        r#"
+        enum Op { Add, Sub(a), Mul { a } }
+        pub fn main(op) { match op { Op::Add | Op::Sub(..) => 1, Op::Mul { a } => a } }
+        "#,
+    ];
+
+    for source in &sources {
+        let (_, diagnostics) = compile_source(&context, source).expect("source should compile");
+        assert!(
+            !diagnostics.has_warning(),
+            "expected no warnings for: {}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_non_exhaustive_match_strict() {
+    let context = rune_modules::default_context().unwrap();
+
+    let mut sources = rune::Sources::new();
+    sources.insert(runestick::Source::new(
+        "main",
+        r#"
+        enum Op { Add, Sub(a), Mul { a } }
+        pub fn main(op) { match op { Op::Sub(a) => a } }
+        "#,
+    ));
+
+    let mut options = rune::Options::default();
+    options.strict(true);
+
+    let mut diagnostics = rune::Diagnostics::new();
+    let result = rune::load_sources(&context, &options, &mut sources, &mut diagnostics);
+    assert!(result.is_err());
+
+    let error = match diagnostics.into_diagnostics().into_iter().next() {
+        Some(rune::Diagnostic::Error(error)) => error,
+        other => panic!("expected error but got {:?}", other),
+    };
+
+    match error.into_kind() {
+        rune::ErrorKind::CompileError(error) => match error.into_kind() {
+            rune::CompileErrorKind::NonExhaustiveMatchPatterns { missing } => {
+                let missing = missing.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+                assert_eq!(missing, vec!["Op::Add", "Op::Mul"]);
+            }
+            kind => panic!("unexpected error {:?}", kind),
+        },
+        kind => panic!("unexpected error {:?}", kind),
+    }
+}
+
+#[test]
+fn test_unused_variable() {
+    assert_warnings! {
+        r#"pub fn main() { let x = 1; }"#,
+        UnusedVariable { span, name } => {
+            assert_eq!(span, Span::new(20, 21));
+            assert_eq!(&*name, "x");
+        }
+    };
+}
+
+#[test]
+fn test_used_variables() {
+    let context = rune_modules::default_context().unwrap();
+
+    let sources = [
+        r#"pub fn main() { let x = 1; x }"#,
+        r#"pub fn main() { let _x = 1; }"#,
+        r#"pub fn main() { let x = 1; let f = || x; f() }"#,
+    ];
+
+    for source in &sources {
+        let (_, diagnostics) = compile_source(&context, source).expect("source should compile");
+        assert!(
+            !diagnostics.has_warning(),
+            "expected no warnings for: {}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_closure_to_fn() {
+    let context = rune_modules::default_context().unwrap();
+
+    let warnings = |source: &str, enabled: bool| {
+        let mut sources = rune::Sources::new();
+        sources.insert(runestick::Source::new("main", source));
+
+        let mut options = rune::Options::default();
+        options.closure_to_fn(enabled);
+
+        let mut diagnostics = rune::Diagnostics::new();
+        rune::load_sources(&context, &options, &mut sources, &mut diagnostics).unwrap();
+
+        diagnostics
+            .into_diagnostics()
+            .into_iter()
+            .filter_map(|d| match d {
+                rune::Diagnostic::Warning(warning) => Some(warning.into_kind()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let source = r#"pub fn main() { let add = |a, b| a + b; add(1, 2) }"#;
+
+    match &warnings(source, true)[..] {
+        [ClosureWithoutCaptures { span, .. }] => assert_eq!(*span, Span::new(26, 38)),
+        warnings => panic!("unexpected warnings {:?}", warnings),
+    }
+
+    assert!(warnings(source, false).is_empty());
+
+    let source = r#"pub fn main() { let n = 1; let add = |a| a + n; add(2) }"#;
+    assert!(warnings(source, true).is_empty());
+}
+
+#[test]
+fn test_warnings_as_errors() {
+    let context = rune_modules::default_context().unwrap();
+
+    let load = |warnings_as_errors, mut diagnostics: rune::Diagnostics| {
+        let mut sources = rune::Sources::new();
+        sources.insert(runestick::Source::new(
+            "main",
+            r#"pub fn main() { let unused = 1; 42 }"#,
+        ));
+
+        let mut options = rune::Options::default();
+        options.warnings_as_errors(warnings_as_errors);
+
+        let result = rune::load_sources(&context, &options, &mut sources, &mut diagnostics);
+        (result, diagnostics.into_diagnostics())
+    };
+
+    let (result, diagnostics) = load(false, rune::Diagnostics::new());
+    assert!(result.is_ok());
+    assert!(matches!(
+        &diagnostics[..],
+        [rune::Diagnostic::Warning(warning)]
+            if matches!(warning.kind(), rune::WarningKind::UnusedVariable { .. })
+    ));
+
+    let (result, diagnostics) = load(false, rune::Diagnostics::without_warnings());
+    assert!(result.is_ok());
+    assert!(diagnostics.is_empty());
+
+    // NB: warnings are promoted even if they would otherwise be ignored.
+    for diagnostics in vec![rune::Diagnostics::new(), rune::Diagnostics::without_warnings()] {
+        let (result, diagnostics) = load(true, diagnostics);
+        assert!(result.is_err());
+
+        match &diagnostics[..] {
+            [rune::Diagnostic::Error(error)] => match error.kind() {
+                rune::ErrorKind::Warning(rune::WarningKind::UnusedVariable { span, name }) => {
+                    assert_eq!(*span, runestick::Span::new(20, 26));
+                    assert_eq!(&**name, "unused");
+                }
+                kind => panic!("unexpected error {:?}", kind),
+            },
+            other => panic!("expected a single error but got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_allow_unused() {
+    assert_warnings! {
+        r#"
+        #[allow(unused)]
+        fn allowed() { let a = 1; fn nested() { let b = 2; } }
+        fn block() { #[allow(unused, unused_parens)] { let c = 3; } }
+        pub fn main() { allowed(); block(); let d = 4; }
+        "#,
+        UnusedVariable { span, name } => {
+            assert_eq!(span, Span::new(207, 208));
+            assert_eq!(&*name, "d");
+        }
+    };
+}
@@ -0,0 +1,27 @@
+use runestick::{Context, Hash, Item, Module};
+
+#[test]
+fn test_cached_item_hashes() {
+    fn nop() {}
+
+    let mut module = Module::with_item(&["synthetic"]);
+
+    for n in 0..1000 {
+        module.function(&[format!("f{}", n)], nop).unwrap();
+    }
+
+    let mut context = Context::new();
+    context.install(&module).unwrap();
+
+    for n in 0..1000 {
+        let item = Item::with_item(&["synthetic", &format!("f{}", n)]);
+        let hash = context.lookup_item_hash(&item);
+        assert_eq!(hash, Some(Hash::type_hash(&item)));
+        assert!(context.lookup(hash.unwrap()).is_some());
+    }
+
+    assert_eq!(
+        context.lookup_item_hash(&Item::with_item(&["synthetic", "missing"])),
+        None
+    );
+}
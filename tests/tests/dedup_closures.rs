@@ -0,0 +1,61 @@
+use rune::{Diagnostics, Options, Sources};
+use runestick::{Context, FromValue, Source, Unit, UnitFn, Vm};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+fn other() {
+    let f = |a| a + 1;
+    f(2)
+}
+
+pub fn main() {
+    let f = |a| a + 1;
+    let g = |a| a + 2;
+    f(1) + g(1) + other()
+}
+"#;
+
+fn compile(dedup_closures: bool) -> Unit {
+    let context = Context::with_default_modules().unwrap();
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("test", SOURCE));
+
+    let mut options = Options::default();
+    options.dedup_closures(dedup_closures);
+
+    let mut diagnostics = Diagnostics::new();
+    rune::load_sources(&context, &options, &mut sources, &mut diagnostics).unwrap()
+}
+
+fn function_offsets(unit: &Unit) -> (usize, HashSet<usize>) {
+    let mut count = 0;
+    let mut offsets = HashSet::new();
+
+    for (_, f) in unit.iter_functions() {
+        if let UnitFn::Offset { offset, .. } = f {
+            count += 1;
+            offsets.insert(*offset);
+        }
+    }
+
+    (count, offsets)
+}
+
+#[test]
+fn test_dedup_closures() {
+    let (count, offsets) = function_offsets(&compile(false));
+    assert_eq!(count, 5);
+    assert_eq!(offsets.len(), 5);
+
+    let unit = compile(true);
+    let (count, offsets) = function_offsets(&unit);
+    assert_eq!(count, 5);
+    assert_eq!(offsets.len(), 4);
+
+    let context = Context::with_default_modules().unwrap();
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = i64::from_value(vm.call(&["main"], ()).unwrap()).unwrap();
+    assert_eq!(output, 8);
+}
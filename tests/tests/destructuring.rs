@@ -52,3 +52,123 @@ fn test_fn_destructuring() {
     test_case!((Foo::Var {a, b}), (Foo::Var {a, b}), enum Foo { Var{a, b} };);
     test_case!((Foo::Var(a, b)), (Foo::Var(a, b)), enum Foo { Var(a, b) };);
 }
+
+#[test]
+fn test_let_nested_destructuring() {
+    assert_eq! {
+        rune!((i64, i64, i64) => pub fn main() {
+            let pair = (1, (2, [3, 4]));
+            let (a, (b, [c, d])) = pair;
+            (a, b, c + d)
+        }),
+        (1, 2, 7),
+    };
+
+    assert_eq! {
+        rune!(i64 => pub fn main() {
+            let [x, y, z] = [1, 2, 3];
+            x + y + z
+        }),
+        6,
+    };
+
+    assert_vm_error!(
+        "pub fn main() { let [x, y] = [1, 2, 3]; }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
+    );
+}
+
+#[test]
+fn test_let_rest_binding() {
+    assert_eq! {
+        rune!((i64, Vec<i64>) => pub fn main() {
+            let [first, ..rest] = [1, 2, 3];
+            (first, rest)
+        }),
+        (1, vec![2, 3]),
+    };
+
+    assert_eq! {
+        rune!((i64, i64, Vec<i64>) => pub fn main() {
+            let [a, b, ..rest] = [1, 2];
+            (a, b, rest)
+        }),
+        (1, 2, vec![]),
+    };
+
+    assert_eq! {
+        rune!(i64 => pub fn main() {
+            match [1, 2, 3] {
+                [] => 0,
+                [first, ..rest] => first + rest.len(),
+            }
+        }),
+        3,
+    };
+
+    assert_compile_error! {
+        "pub fn main() { let (a, ..rest) = (1, 2); }",
+        span, UnsupportedPatternRestBinding => {
+            assert_eq!(span, Span::new(26, 30));
+        }
+    };
+}
+
+#[test]
+fn test_rest_in_any_position() {
+    assert_eq! {
+        rune!((i64, i64) => pub fn main() {
+            let [.., last] = [1, 2, 3];
+            let [a, .., b] = [4, 5, 6, 7];
+            (last, a + b)
+        }),
+        (3, 11),
+    };
+
+    assert_eq! {
+        rune!((i64, Vec<i64>, i64) => pub fn main() {
+            let [a, ..middle, b] = [1, 2, 3, 4];
+            (a, middle, b)
+        }),
+        (1, vec![2, 3], 4),
+    };
+
+    assert_eq! {
+        rune!((i64, i64) => pub fn main() {
+            let (a, .., b) = (1, 2, 3, 4);
+            (a, b)
+        }),
+        (1, 4),
+    };
+
+    assert_eq! {
+        rune!((i64, i64, i64) => pub fn main() {
+            fn ends(values) {
+                match values {
+                    [a, .., b] => a + b,
+                    [a] => a,
+                    _ => 0,
+                }
+            }
+
+            (ends([1, 2, 3]), ends([5]), ends([]))
+        }),
+        (4, 5, 0),
+    };
+
+    assert_vm_error!(
+        "pub fn main() { let [a, .., b, c] = [1, 2]; }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
+    );
+
+    assert_compile_error! {
+        "pub fn main() { let [a, .., b, ..] = [1, 2, 3]; }",
+        span, MultiplePatternRest => {
+            assert_eq!(span, Span::new(31, 33));
+        }
+    };
+}
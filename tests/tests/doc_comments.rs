@@ -0,0 +1,91 @@
+use rune::{CompileVisitor, Diagnostics, FileSourceLoader, Options, Sources};
+use rune_tests::*;
+use runestick::{CompileMeta, Context, Item, Source};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A visitor which collects the doc comments of all registered items.
+#[derive(Default)]
+struct DocsVisitor {
+    docs: RefCell<HashMap<Item, Option<Box<str>>>>,
+}
+
+impl CompileVisitor for DocsVisitor {
+    fn register_meta(&self, meta: &CompileMeta) {
+        self.docs
+            .borrow_mut()
+            .insert(meta.item.item.clone(), meta.docs.clone());
+    }
+}
+
+fn docs(source: &str) -> HashMap<Item, Option<Box<str>>> {
+    let context = Context::with_default_modules().unwrap();
+    let visitor = Rc::new(DocsVisitor::default());
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+    let mut diagnostics = Diagnostics::new();
+
+    rune::load_sources_with_visitor(
+        &context,
+        &Options::default(),
+        &mut sources,
+        &mut diagnostics,
+        visitor.clone(),
+        Rc::new(FileSourceLoader::new()),
+    )
+    .unwrap();
+
+    visitor.docs.take()
+}
+
+#[test]
+fn test_doc_comments() {
+    let docs = docs(
+        r#"
+        /// Adds two numbers.
+        ///
+        ///Returns their sum.
+        fn add(a, b) { a + b }
+
+        // Not a doc comment.
+        //// Not a doc comment either.
+        fn sub(a, b) { a - b }
+
+        /// A point.
+        #[test]
+        fn check() { add(1, 2) }
+
+        /// The answer.
+        const ANSWER = 42;
+
+        /// A struct.
+        struct Foo;
+
+        pub fn main() { sub(add(1, ANSWER), 2); Foo }
+        "#,
+    );
+
+    let get = |path: &[&str]| docs.get(&Item::with_item(path)).cloned().flatten();
+
+    assert_eq!(
+        get(&["add"]).as_deref(),
+        Some("Adds two numbers.\n\nReturns their sum.")
+    );
+    assert_eq!(get(&["sub"]), None);
+    assert_eq!(get(&["check"]).as_deref(), Some("A point."));
+    assert_eq!(get(&["ANSWER"]).as_deref(), Some("The answer."));
+    assert_eq!(get(&["Foo"]).as_deref(), Some("A struct."));
+}
+
+#[test]
+fn test_unused_doc_comment() {
+    assert_warnings! {
+        r#"pub fn main() { /// Not an item.
+        let a = 1; a }"#,
+        UnusedDocComment { span } => {
+            assert_eq!(span, Span::new(16, 32));
+        }
+    };
+}
@@ -0,0 +1,166 @@
+use rune_tests::*;
+use runestick::{Any, Module, Protocol, Value, VmError, VmErrorKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Any)]
+struct Vector3 {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Vector3 {
+    fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    fn zip(&self, other: &Self, f: impl Fn(f64, f64) -> f64) -> Self {
+        Self::new(f(self.x, other.x), f(self.y, other.y), f(self.z, other.z))
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a + b)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a - b)
+    }
+
+    fn mul(&self, scalar: f64) -> Self {
+        Self::new(self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+
+    /// Reflected multiplication, which also accepts integers.
+    fn mul_rhs(&self, scalar: Value) -> Result<Self, VmError> {
+        match scalar {
+            Value::Integer(scalar) => Ok(self.mul(scalar as f64)),
+            Value::Float(scalar) => Ok(self.mul(scalar)),
+            scalar => Err(VmError::bad_argument::<f64>(1, &scalar)?),
+        }
+    }
+
+    fn div(&self, scalar: f64) -> Self {
+        Self::new(self.x / scalar, self.y / scalar, self.z / scalar)
+    }
+}
+
+#[derive(Debug, Any)]
+struct Other;
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Vector3>()?;
+    module.function(&["Vector3", "new"], Vector3::new)?;
+    module.inst_fn(Protocol::ADD, Vector3::add)?;
+    module.inst_fn(Protocol::SUB, Vector3::sub)?;
+    module.inst_fn(Protocol::MUL, Vector3::mul)?;
+    module.inst_fn(Protocol::MUL_RHS, Vector3::mul_rhs)?;
+    module.inst_fn(Protocol::DIV, Vector3::div)?;
+    module.ty::<Other>()?;
+    module.function(&["Other", "new"], || Other)?;
+    Ok(module)
+}
+
+#[test]
+fn test_external_arithmetic() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: (Vector3, Vector3, Vector3, Vector3) = run(
+        &context,
+        r#"
+        pub fn main() {
+            let a = Vector3::new(1.0, 2.0, 3.0);
+            let b = Vector3::new(4.0, 5.0, 6.0);
+            (a + b, b - a, a * 2.0, b / 2.0)
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(
+        output,
+        (
+            Vector3::new(5.0, 7.0, 9.0),
+            Vector3::new(3.0, 3.0, 3.0),
+            Vector3::new(2.0, 4.0, 6.0),
+            Vector3::new(2.0, 2.5, 3.0),
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_external_arithmetic_rhs() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: Vector3 = run(
+        &context,
+        r#"pub fn main() { 3.0 * Vector3::new(1.0, 2.0, 3.0) }"#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(output, Vector3::new(3.0, 6.0, 9.0));
+
+    let output: Vector3 = run(
+        &context,
+        r#"pub fn main() { 3 * Vector3::new(1.0, 2.0, 3.0) }"#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(output, Vector3::new(3.0, 6.0, 9.0));
+
+    // Only the reflected multiplication is implemented.
+    let error = run::<_, _, Vector3>(
+        &context,
+        r#"pub fn main() { 3.0 + Vector3::new(1.0, 2.0, 3.0) }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err()
+    .expect_vm_error("expected vm error");
+
+    assert!(matches!(
+        error.into_unwound().0.into_kind(),
+        VmErrorKind::UnsupportedBinaryOperation { op: "+", .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_external_arithmetic_mismatched() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let error = run::<_, _, Vector3>(
+        &context,
+        r#"pub fn main() { Vector3::new(1.0, 2.0, 3.0) + Other::new() }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err()
+    .expect_vm_error("expected vm error");
+
+    // The left-hand side implements the protocol, so its argument is checked.
+    assert!(matches!(
+        error.into_unwound().0.into_kind(),
+        VmErrorKind::BadArgument { arg: 1, .. }
+    ));
+
+    let error = run::<_, _, Vector3>(
+        &context,
+        r#"pub fn main() { Other::new() - Vector3::new(1.0, 2.0, 3.0) }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err()
+    .expect_vm_error("expected vm error");
+
+    assert!(matches!(
+        error.into_unwound().0.into_kind(),
+        VmErrorKind::UnsupportedBinaryOperation { op: "-", .. }
+    ));
+
+    Ok(())
+}
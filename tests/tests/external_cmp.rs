@@ -0,0 +1,167 @@
+use rune_tests::*;
+use runestick::{Any, Module, Protocol, VmErrorKind};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Any)]
+struct Version {
+    major: i64,
+    minor: i64,
+}
+
+impl Version {
+    fn new(major: i64, minor: i64) -> Self {
+        Self { major, minor }
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(self, other)
+    }
+}
+
+#[derive(Debug, Any)]
+struct Chaos(i64);
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Version>()?;
+    module.function(&["Version", "new"], Version::new)?;
+    module.inst_fn(Protocol::CMP, Version::cmp)?;
+
+    // A comparator which doesn't implement a total order.
+    module.ty::<Chaos>()?;
+    module.function(&["Chaos", "new"], Chaos)?;
+    module.inst_fn(Protocol::CMP, |a: &Chaos, b: &Chaos| {
+        if (a.0 + b.0) % 3 == 0 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    })?;
+    Ok(module)
+}
+
+#[test]
+fn test_external_cmp() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: (bool, bool, bool, bool) = run(
+        &context,
+        r#"
+        pub fn main() {
+            let a = Version::new(1, 2);
+            let b = Version::new(1, 10);
+            (a < b, a <= a, a > b, b >= a)
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(output, (true, true, false, true));
+    Ok(())
+}
+
+#[test]
+fn test_external_sort() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: Vec<Version> = run(
+        &context,
+        r#"
+        pub fn main() {
+            let versions = [
+                Version::new(2, 0),
+                Version::new(1, 10),
+                Version::new(1, 2),
+                Version::new(0, 9),
+            ];
+
+            versions.sort();
+            versions
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(
+        output,
+        vec![
+            Version::new(0, 9),
+            Version::new(1, 2),
+            Version::new(1, 10),
+            Version::new(2, 0),
+        ]
+    );
+
+    let output: Vec<i64> = run(
+        &context,
+        r#"
+        pub fn main() {
+            let values = [3, 1, 2];
+            values.sort();
+            values
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(output, vec![1, 2, 3]);
+    Ok(())
+}
+
+#[test]
+fn test_external_sort_inconsistent() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: usize = run(
+        &context,
+        r#"
+        pub fn main() {
+            let values = [];
+
+            for n in 0..100 {
+                values.push(Chaos::new(n));
+            }
+
+            values.sort();
+            values.len()
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(output, 100);
+
+    let error = run::<_, _, ()>(
+        &context,
+        r#"pub fn main() { [Version::new(1, 0), 1].sort() }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err()
+    .expect_vm_error("expected vm error");
+
+    assert!(matches!(
+        error.into_unwound().0.into_kind(),
+        VmErrorKind::UnsupportedBinaryOperation { op: "cmp", .. }
+    ));
+
+    let error = run::<_, _, ()>(
+        &context,
+        r#"pub fn main() { [1, Version::new(1, 0)].sort() }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err()
+    .expect_vm_error("expected vm error");
+
+    assert!(matches!(
+        error.into_unwound().0.into_kind(),
+        VmErrorKind::BadArgument { .. }
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use rune_tests::*;
+use runestick::{Any, Module, Protocol, VmErrorKind};
+
+#[derive(Debug, Any)]
+struct Matrix {
+    columns: usize,
+    values: Vec<i64>,
+}
+
+impl Matrix {
+    fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            columns,
+            values: vec![0; rows * columns],
+        }
+    }
+
+    fn offset(&self, (row, column): (usize, usize)) -> usize {
+        row * self.columns + column
+    }
+
+    fn get(&self, index: (usize, usize)) -> Option<i64> {
+        self.values.get(self.offset(index)).copied()
+    }
+
+    fn set(&mut self, index: (usize, usize), value: i64) {
+        let offset = self.offset(index);
+        self.values[offset] = value;
+    }
+}
+
+#[derive(Debug, Any)]
+struct Opaque;
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Matrix>()?;
+    module.function(&["Matrix", "new"], Matrix::new)?;
+    module.inst_fn(Protocol::INDEX_GET, Matrix::get)?;
+    module.inst_fn(Protocol::INDEX_SET, Matrix::set)?;
+    module.ty::<Opaque>()?;
+    module.function(&["Opaque", "new"], || Opaque)?;
+    Ok(module)
+}
+
+#[test]
+fn test_external_index() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let matrix: Matrix = run(
+        &context,
+        r#"
+        pub fn main() {
+            let m = Matrix::new(2, 2);
+            m[(0, 0)] = 1;
+            m[(1, 1)] = m[(0, 0)]? + 1;
+            m[(0, 1)] = m[(2, 0)].unwrap_or(3);
+            m
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(matrix.values, vec![1, 3, 0, 2]);
+    Ok(())
+}
+
+#[test]
+fn test_external_index_unsupported() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let error = run::<_, _, ()>(
+        &context,
+        r#"pub fn main() { let o = Opaque::new(); o[0] }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err()
+    .expect_vm_error("expected vm error");
+
+    let kind = error.into_unwound().0.into_kind();
+    assert!(matches!(kind, VmErrorKind::UnsupportedIndexGet { .. }));
+    assert!(kind.to_string().contains("does not support indexing"));
+
+    let error = run::<_, _, ()>(
+        &context,
+        r#"pub fn main() { let o = Opaque::new(); o[0] = 1; }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err()
+    .expect_vm_error("expected vm error");
+
+    let kind = error.into_unwound().0.into_kind();
+    assert!(matches!(kind, VmErrorKind::UnsupportedIndexSet { .. }));
+    assert!(kind.to_string().contains("does not support indexing"));
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use runestick::Source;
+
+fn format(source: &str) -> String {
+    rune::format_source(&Source::new("main", source)).unwrap()
+}
+
+#[test]
+fn test_format_source() {
+    let source = r#"// Leading comment.
+use std::collections::HashMap;
+
+
+
+/// Adds two numbers.
+fn add(a,b){a+b}
+struct Point { x , y }
+
+pub fn main( ) {
+        let map = HashMap :: new ( ) ;   // trailing comment
+  map.insert("a",add(1,2));
+
+    // A comment on its own line.
+    let values = [1,2,3].iter().map(|v| v * 2).collect_vec();
+    let point = Point{x:1,y:2};
+    let name = `world ${  point.x }`;
+        if values.len()==3&&point.y>=2 {
+    match point { Point { x, .. } => x , }
+        } else { 0 }
+}
+// Final comment.
+"#;
+
+    let expected = r#"// Leading comment.
+use std::collections::HashMap;
+
+/// Adds two numbers.
+fn add(a, b) { a + b }
+struct Point { x, y }
+
+pub fn main() {
+    let map = HashMap::new(); // trailing comment
+    map.insert("a", add(1, 2));
+
+    // A comment on its own line.
+    let values = [1, 2, 3].iter().map(|v| v * 2).collect_vec();
+    let point = Point { x: 1, y: 2 };
+    let name = `world ${  point.x }`;
+    if values.len() == 3 && point.y >= 2 {
+        match point { Point { x, .. } => x, }
+    } else { 0 }
+}
+// Final comment.
+"#;
+
+    let formatted = format(source);
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), formatted);
+}
+
+#[test]
+fn test_format_source_nested_indentation() {
+    let source = "fn foo() {\nbar(baz(|| {\n1\n}))\n}\n";
+    let expected = "fn foo() {\n    bar(baz(|| {\n        1\n    }))\n}\n";
+    assert_eq!(format(source), expected);
+    assert_eq!(format(expected), expected);
+}
+
+#[test]
+fn test_format_source_invalid() {
+    assert!(rune::format_source(&Source::new("main", "fn foo( {")).is_err());
+}
+
+#[test]
+fn test_format_source_operators() {
+    let source = "fn foo(b, x, y) {\nlet a = b - 1;\na-1\n}\nfn bar(x, y) {\nlet f = |a, b|a-b;\n-x*-y\n}\nfn baz() {\nfoo::<i64>(1)\n}\n";
+    let expected = "fn foo(b, x, y) {\n    let a = b - 1;\n    a - 1\n}\nfn bar(x, y) {\n    let f = |a, b| a - b;\n    -x * -y\n}\nfn baz() {\n    foo::<i64>(1)\n}\n";
+    assert_eq!(format(source), expected);
+    assert_eq!(format(expected), expected);
+}
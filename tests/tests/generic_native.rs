@@ -1,7 +1,7 @@
 //! Tests for derive(Any) on generic types
 
 use rune_tests::*;
-use runestick::{Any, ContextError, Module, Named, ToValue, UnsafeFromValue};
+use runestick::{Any, ContextError, Module, Named, ToValue, TypeOf, UnsafeFromValue};
 
 #[derive(Any)]
 struct Generic<T>
@@ -114,3 +114,36 @@ fn test_generic_f64_set() {
         10.0
     );
 }
+
+#[derive(Any)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+impl Wrapper<i64> {
+    fn inner(&self) -> i64 {
+        self.inner
+    }
+}
+
+#[test]
+fn test_generic_without_bounds() {
+    assert_ne!(
+        <Wrapper<i64> as TypeOf>::type_hash(),
+        <Wrapper<f64> as TypeOf>::type_hash()
+    );
+
+    let mut module = Module::with_crate("native_crate");
+    module.ty::<Wrapper<i64>>().unwrap();
+    module.inst_fn("inner", Wrapper::<i64>::inner).unwrap();
+
+    assert_eq!(
+        rune_n! {
+            module,
+            (Wrapper { inner: 42i64 }, ),
+            i64 =>
+                pub fn main(v) { v.inner() }
+        },
+        42
+    );
+}
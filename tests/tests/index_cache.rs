@@ -0,0 +1,166 @@
+use rune::{Diagnostics, FileSourceLoader, IndexCache, NoopCompileVisitor, Options, Sources};
+use runestick::{Context, FromValue as _, Source, Unit, Vm};
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn load(
+    context: &Context,
+    cache: &mut IndexCache,
+    lib: &str,
+    main: &str,
+) -> (Result<Unit, rune::LoadSourcesError>, Diagnostics) {
+    load_with_options(context, cache, &Options::default(), lib, main)
+}
+
+fn load_with_options(
+    context: &Context,
+    cache: &mut IndexCache,
+    options: &Options,
+    lib: &str,
+    main: &str,
+) -> (Result<Unit, rune::LoadSourcesError>, Diagnostics) {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("lib", lib));
+    sources.insert(Source::new("main", main));
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = rune::load_sources_with_cache(
+        context,
+        options,
+        &mut sources,
+        &mut diagnostics,
+        Rc::new(NoopCompileVisitor::new()),
+        Rc::new(FileSourceLoader::new()),
+        cache,
+    );
+
+    (result, diagnostics)
+}
+
+fn run(context: &Context, unit: Unit) -> i64 {
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    i64::from_value(output).unwrap()
+}
+
+#[test]
+fn test_index_cache_reuses_unchanged_sources() {
+    let context = Context::with_default_modules().unwrap();
+    let mut cache = IndexCache::new();
+
+    let lib = r#"
+    pub fn helper() { 1 }
+    mod util { pub fn add(a, b) { a + b } }
+    struct Foo;
+    impl Foo { fn get(self) { 10 } }
+    "#;
+
+    let (unit, _) = load(&context, &mut cache, lib, "pub fn main() { helper() }");
+    assert_eq!(cache.indexed(), &[0, 1]);
+    assert_eq!(run(&context, unit.unwrap()), 1);
+
+    let (unit, _) = load(
+        &context,
+        &mut cache,
+        lib,
+        "pub fn main() { helper() + util::add(2, 3) }",
+    );
+    assert_eq!(cache.indexed(), &[1]);
+    assert_eq!(run(&context, unit.unwrap()), 6);
+
+    let (unit, _) = load(
+        &context,
+        &mut cache,
+        lib,
+        "pub fn main() { helper() + util::add(2, 3) }",
+    );
+    assert!(cache.indexed().is_empty());
+    assert_eq!(run(&context, unit.unwrap()), 6);
+
+    let (unit, _) = load(&context, &mut cache, lib, "pub fn main() { Foo.get() }");
+    assert_eq!(cache.indexed(), &[1]);
+    assert_eq!(run(&context, unit.unwrap()), 10);
+}
+
+#[test]
+fn test_index_cache_removed_item() {
+    let context = Context::with_default_modules().unwrap();
+    let mut cache = IndexCache::new();
+
+    let main = "pub fn main() { helper() }";
+
+    let (unit, _) = load(&context, &mut cache, "fn helper() { 1 }", main);
+    assert_eq!(run(&context, unit.unwrap()), 1);
+
+    let (unit, diagnostics) = load(&context, &mut cache, "fn other() { 1 }", main);
+    assert_eq!(cache.indexed(), &[0]);
+    assert!(unit.is_err());
+    assert!(diagnostics.has_error());
+
+    let (unit, _) = load(&context, &mut cache, "fn helper() { 2 }", main);
+    assert_eq!(run(&context, unit.unwrap()), 2);
+}
+
+#[test]
+fn test_index_cache_import_dependents() {
+    let context = Context::with_default_modules().unwrap();
+    let mut cache = IndexCache::new();
+
+    let main = "use util::add; pub fn main() { add(1, 2) }";
+
+    let (unit, _) = load(
+        &context,
+        &mut cache,
+        "mod util { pub fn add(a, b) { a + b } }",
+        main,
+    );
+    assert_eq!(run(&context, unit.unwrap()), 3);
+
+    // Sources importing from a changed source are indexed again.
+    let (unit, _) = load(
+        &context,
+        &mut cache,
+        "mod util { pub fn add(a, b) { a * b } }",
+        main,
+    );
+    assert_eq!(cache.indexed(), &[0, 1]);
+    assert_eq!(run(&context, unit.unwrap()), 2);
+
+    let (unit, diagnostics) = load(&context, &mut cache, "mod util { }", main);
+    assert!(unit.is_err());
+    assert!(diagnostics.has_error());
+}
+
+#[test]
+fn test_index_cache_options() {
+    let context = Context::with_default_modules().unwrap();
+    let mut cache = IndexCache::new();
+
+    let lib = r#"
+    #[cfg(feature = "extra")]
+    fn helper() { 2 }
+    "#;
+
+    let main = "pub fn main() { helper() }";
+
+    let mut options = Options::default();
+    options.feature("extra", true);
+
+    let (unit, _) = load_with_options(&context, &mut cache, &options, lib, main);
+    assert_eq!(run(&context, unit.unwrap()), 2);
+
+    // The enabled features affect indexing, so nothing is reused.
+    let (unit, diagnostics) = load(&context, &mut cache, lib, main);
+    assert_eq!(cache.indexed(), &[0, 1]);
+    assert!(unit.is_err());
+    assert!(diagnostics.has_error());
+
+    let (unit, _) = load_with_options(&context, &mut cache, &options, lib, main);
+    assert_eq!(cache.indexed(), &[0, 1]);
+    assert_eq!(run(&context, unit.unwrap()), 2);
+
+    let (unit, _) = load_with_options(&context, &mut cache, &options, lib, main);
+    assert!(cache.indexed().is_empty());
+    assert_eq!(run(&context, unit.unwrap()), 2);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "full")]
+
+use rune_tests::*;
+
+#[test]
+fn test_json_round_trip() {
+    let out: Result<(bool, String), runestick::Error> = rune! {
+        Result<(bool, String), runestick::Error> =>
+        pub fn main() {
+            let value = #{
+                name: "rune",
+                version: [0, 9, 1],
+                nested: #{ z: [#{ a: 1.5 }, [[[true]]]], y: false },
+                empty: #{},
+            };
+
+            let string = json::to_string(value)?;
+            Ok((json::from_string(string)? == value, string))
+        }
+    };
+
+    assert_eq!(
+        out.unwrap(),
+        (
+            true,
+            String::from(
+                r#"{"name":"rune","version":[0,9,1],"nested":{"z":[{"a":1.5},[[[true]]]],"y":false},"empty":{}}"#
+            )
+        )
+    );
+}
+
+#[test]
+fn test_json_non_finite_float() {
+    let out: Result<String, runestick::Error> = rune! {
+        Result<String, runestick::Error> =>
+        pub fn main() {
+            json::to_string([1.0, #{ a: 0.0 / 0.0 }])
+        }
+    };
+
+    let error = out.unwrap_err();
+    assert_eq!(error.to_string(), "cannot serialize `NaN` as json");
+}
+
+#[test]
+fn test_json_deep_nesting() {
+    let out: Result<bool, runestick::Error> = rune! {
+        Result<bool, runestick::Error> =>
+        pub fn main() {
+            let value = [];
+
+            for n in 0..100 {
+                value = [value];
+            }
+
+            Ok(json::from_string(json::to_string(value)?)? == value)
+        }
+    };
+
+    assert!(out.unwrap());
+
+    // NB: deeply nested input is rejected instead of overflowing the stack.
+    let out: Result<(), runestick::Error> = rune! {
+        Result<(), runestick::Error> =>
+        pub fn main() {
+            let string = String::new();
+
+            for n in 0..1000 {
+                string += "[";
+            }
+
+            for n in 0..1000 {
+                string += "]";
+            }
+
+            json::from_string(string)?;
+            Ok(())
+        }
+    };
+
+    let error = out.unwrap_err();
+    assert!(
+        error.to_string().starts_with("recursion limit exceeded"),
+        "expected recursion limit error but was `{}`",
+        error
+    );
+}
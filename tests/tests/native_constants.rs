@@ -0,0 +1,78 @@
+use rune_tests::*;
+use runestick::{ContextError, Module};
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("math");
+    module.constant(&["PI"], std::f64::consts::PI)?;
+    module.constant(&["NAME"], String::from("math"))?;
+    Ok(module)
+}
+
+#[test]
+fn test_native_constant() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: (f64, String) = run(
+        &context,
+        r#"
+        pub fn main() {
+            (math::PI, math::NAME)
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(output, (std::f64::consts::PI, String::from("math")));
+    Ok(())
+}
+
+#[test]
+fn test_native_constant_in_const_expr() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: (f64, String) = run(
+        &context,
+        r#"
+        use math::PI;
+
+        const TAU = PI * 2.0;
+        const LABEL = `${math::NAME}::TAU`;
+
+        pub fn main() {
+            (TAU, LABEL)
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(
+        output,
+        (std::f64::consts::PI * 2.0, String::from("math::TAU"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_native_constant_conflicting_function() {
+    let mut module = Module::new();
+    module.function(&["value"], || 42i64).unwrap();
+
+    match module.constant(&["value"], 42i64) {
+        Err(ContextError::ConflictingFunctionName { name }) => {
+            assert_eq!(name.to_string(), "value");
+        }
+        actual => panic!("unexpected result `{:?}`", actual),
+    }
+
+    let mut module = Module::new();
+    module.constant(&["value"], 42i64).unwrap();
+
+    match module.function(&["value"], || 42i64) {
+        Err(ContextError::ConflictingConstantName { name }) => {
+            assert_eq!(name.to_string(), "value");
+        }
+        actual => panic!("unexpected result `{:?}`", actual),
+    }
+}
@@ -0,0 +1,76 @@
+use rune_tests::*;
+use runestick::{Module, VmErrorKind};
+
+fn checked_sqrt(value: f64) -> Result<f64, String> {
+    if value < 0.0 {
+        return Err(format!("cannot take the square root of {}", value));
+    }
+
+    Ok(value.sqrt())
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("math");
+    module.fallible_fn(&["checked_sqrt"], checked_sqrt)?;
+    module.fallible_fn(&["parse"], |s: &str| s.parse::<i64>())?;
+    Ok(module)
+}
+
+#[test]
+fn test_fallible_fn() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: (f64, String, String) = run(
+        &context,
+        r#"
+        fn describe(result) {
+            match result {
+                Ok(value) => `ok: ${value}`,
+                Err(error) => `error: ${error}`,
+            }
+        }
+
+        pub fn main() {
+            let root = math::checked_sqrt(16.0)?;
+            (root, describe(math::checked_sqrt(-4.0)), describe(math::parse("nope")))
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(
+        output,
+        (
+            4.0,
+            String::from("error: cannot take the square root of -4"),
+            String::from("error: invalid digit found in string"),
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fallible_fn_unwrap() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let error = run::<_, _, f64>(
+        &context,
+        r#"pub fn main() { math::checked_sqrt(-1.0).unwrap() }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err()
+    .expect_vm_error("expected vm error");
+
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::Panic { reason } => {
+            assert!(reason
+                .to_string()
+                .contains("cannot take the square root of -1"));
+        }
+        actual => panic!("unexpected error `{:?}`", actual),
+    }
+
+    Ok(())
+}
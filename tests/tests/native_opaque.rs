@@ -0,0 +1,62 @@
+use rune_tests::context_with_module;
+use runestick::{ContextError, Module, Opaque};
+use std::collections::VecDeque;
+
+type Queue = Opaque<VecDeque<i64>>;
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("queue");
+    module.opaque_ty::<VecDeque<i64>>("Queue")?;
+    module.function(&["new"], Queue::default)?;
+    module.function(&["push"], |queue: &mut Queue, value: i64| {
+        queue.push_back(value)
+    })?;
+    module.inst_fn("len", |queue: &Queue| queue.len())?;
+    Ok(module)
+}
+
+#[test]
+fn test_opaque_ty() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let (len, queue): (usize, Queue) = rune_tests::run(
+        &context,
+        r#"
+        fn fill(queue, n) {
+            for value in 0..n {
+                queue::push(queue, value);
+            }
+
+            queue
+        }
+
+        pub fn main() {
+            let queue = fill(queue::new(), 3);
+            (queue.len(), queue)
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(len, 3);
+    assert_eq!(queue.into_inner(), vec![0, 1, 2]);
+    Ok(())
+}
+
+#[test]
+fn test_opaque_ty_conflict() -> runestick::Result<()> {
+    let mut module = Module::new();
+    module.opaque_ty::<VecDeque<i64>>("Queue")?;
+
+    match module.opaque_ty::<VecDeque<i64>>("Other") {
+        Err(ContextError::ConflictingType { item, .. }) => {
+            assert_eq!(item, runestick::Item::with_item(&["Other"]));
+        }
+        actual => panic!("unexpected result `{:?}`", actual),
+    }
+
+    // Different instantiations are different types.
+    module.opaque_ty::<VecDeque<String>>("StringQueue")?;
+    Ok(())
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "full")]
+
+use rune_tests::*;
+use runestick::Object;
+
+#[test]
+fn test_parse_key_values() {
+    let out: Result<(Vec<String>, String, i64, f64, bool), runestick::Error> = rune! {
+        Result<(Vec<String>, String, i64, f64, bool), runestick::Error> =>
+        pub fn main() {
+            let config = parse::key_values(
+                "# server settings\nname = \"rune # 1\"  # trailing\n\nthreads = 4\nratio = 0.5\ndebug = true\n"
+            )?;
+
+            Ok((config.keys().collect_vec(), config.name, config.threads, config.ratio, config.debug))
+        }
+    };
+
+    assert_eq!(
+        out.unwrap(),
+        (
+            vec![
+                String::from("name"),
+                String::from("threads"),
+                String::from("ratio"),
+                String::from("debug")
+            ],
+            String::from("rune # 1"),
+            4,
+            0.5,
+            true
+        )
+    );
+}
+
+#[test]
+fn test_parse_key_values_errors() -> runestick::Result<()> {
+    let context = std::sync::Arc::new(rune_modules::default_context()?);
+
+    let cases = [
+        ("a = 1\nb 2", "line 2: expected `key = value`"),
+        ("a = 1\n\na = 2", "line 3: duplicate key `a`"),
+        ("a = nan", "line 1: invalid value `nan`"),
+        ("a b = 1", "line 1: invalid key `a b`"),
+        ("a = \"open", "line 1: invalid value `\"open`"),
+    ];
+
+    for (input, expected) in cases.iter() {
+        let out: Result<Object, runestick::Error> = run(
+            &context,
+            "pub fn main(input) { parse::key_values(input) }",
+            &["main"],
+            (input.to_string(),),
+        )?;
+
+        assert_eq!(out.unwrap_err().to_string(), *expected);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use rune::{CompileError, CompileErrorKind, Diagnostics, NoopCompileVisitor, Options, Sources};
+use runestick::{Context, FromValue as _, Item, Source, Span, Vm};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A source loader which loads modules from memory.
+struct MemorySourceLoader {
+    sources: HashMap<PathBuf, &'static str>,
+}
+
+impl rune::SourceLoader for MemorySourceLoader {
+    fn load(&self, root: &Path, item: &Item, span: Span) -> Result<Source, CompileError> {
+        let mut path = root.parent().unwrap_or(root).to_owned();
+
+        for c in item {
+            path.push(c.to_string());
+        }
+
+        let path = path.with_extension("rn");
+
+        match self.sources.get(&path) {
+            Some(source) => Ok(Source::with_path(path.display().to_string(), source, &path)),
+            None => Err(CompileError::new(
+                span,
+                CompileErrorKind::ModNotFound { path },
+            )),
+        }
+    }
+}
+
+fn load(
+    main: &str,
+    modules: &[(&str, &'static str)],
+) -> (Result<runestick::Unit, rune::LoadSourcesError>, Diagnostics) {
+    let context = Context::with_default_modules().unwrap();
+
+    let loader = MemorySourceLoader {
+        sources: modules
+            .iter()
+            .map(|(path, source)| (PathBuf::from(path), *source))
+            .collect(),
+    };
+
+    let mut sources = Sources::new();
+    sources.insert(Source::with_path("main", main, "/scripts/main.rn"));
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = rune::load_sources_with_visitor(
+        &context,
+        &Options::default(),
+        &mut sources,
+        &mut diagnostics,
+        Rc::new(NoopCompileVisitor::new()),
+        Rc::new(loader),
+    );
+
+    (result, diagnostics)
+}
+
+#[test]
+fn test_use_across_module_files() {
+    let context = Context::with_default_modules().unwrap();
+
+    let (unit, _) = load(
+        "mod lib; use lib::util::add; pub fn main() { add(1, 2) + lib::VALUE }",
+        &[
+            ("/scripts/lib.rn", "pub mod util; pub const VALUE = 10;"),
+            ("/scripts/lib/util.rn", "pub fn add(a, b) { a + b }"),
+        ],
+    );
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit.unwrap()));
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    assert_eq!(i64::from_value(output).unwrap(), 13);
+}
+
+#[test]
+fn test_import_cycle_across_module_files() {
+    let (unit, diagnostics) = load(
+        "mod lib; use lib::a as b; pub fn main() { b() }",
+        &[("/scripts/lib.rn", "pub use super::b as a;")],
+    );
+
+    assert!(unit.is_err());
+    let error = query_error(diagnostics);
+    assert!(matches!(error, rune::QueryErrorKind::ImportCycle { .. }));
+}
+
+#[test]
+fn test_private_item_across_module_files() {
+    let context = Context::with_default_modules().unwrap();
+
+    // Private items are accessible from within the module that defines them.
+    let (unit, _) = load(
+        "mod lib; pub fn main() { lib::public() }",
+        &[(
+            "/scripts/lib.rn",
+            "fn secret() { 42 } pub fn public() { secret() }",
+        )],
+    );
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit.unwrap()));
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    assert_eq!(i64::from_value(output).unwrap(), 42);
+
+    let (unit, diagnostics) = load(
+        "mod lib; pub fn main() { lib::secret() }",
+        &[("/scripts/lib.rn", "fn secret() { 42 }")],
+    );
+
+    assert!(unit.is_err());
+
+    match query_error(diagnostics) {
+        rune::QueryErrorKind::NotVisible {
+            item,
+            from,
+            visibility,
+            ..
+        } => {
+            assert!(matches!(visibility, runestick::Visibility::Inherited));
+            assert_eq!(item, Item::with_item(&["lib", "secret"]));
+            assert_eq!(from, Item::new());
+        }
+        kind => panic!("expected private item error, but was `{:?}`", kind),
+    }
+}
+
+/// Extract the first query error from the given diagnostics.
+fn query_error(diagnostics: Diagnostics) -> rune::QueryErrorKind {
+    let error =
+        diagnostics
+            .into_diagnostics()
+            .into_iter()
+            .find_map(|diagnostic| match diagnostic {
+                rune::Diagnostic::Error(error) => Some(error.into_kind()),
+                _ => None,
+            });
+
+    match error {
+        Some(rune::ErrorKind::CompileError(error)) => match error.into_kind() {
+            CompileErrorKind::QueryError { error } => error,
+            kind => panic!("expected query error, but was `{:?}`", kind),
+        },
+        kind => panic!("expected compile error, but was `{:?}`", kind),
+    }
+}
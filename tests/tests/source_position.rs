@@ -0,0 +1,38 @@
+use runestick::Source;
+
+#[test]
+fn test_source_position() {
+    let source = Source::new("main", "fn main() {\r\n    let å = \"ö\";\n    å\n}");
+
+    assert_eq!(source.position(0), (1, 1));
+    assert_eq!(source.position(3), (1, 4));
+
+    let offset = source.as_str().find('=').unwrap();
+    assert_eq!(source.position(offset), (2, 11));
+
+    let offset = source.as_str().find(';').unwrap();
+    assert_eq!(source.position(offset), (2, 16));
+
+    let offset = source.as_str().rfind('å').unwrap();
+    assert_eq!(source.position(offset), (3, 5));
+    assert_eq!(source.position(source.len()), (4, 2));
+    assert_eq!(source.position(source.len() + 10), (4, 2));
+
+    // NB: an offset in the middle of a character maps to that character.
+    let offset = source.as_str().find('å').unwrap();
+    assert_eq!(source.position(offset), (2, 9));
+    assert_eq!(source.position(offset + 1), (2, 9));
+    assert_eq!(source.position(offset + 2), (2, 10));
+}
+
+#[test]
+fn test_source_line_text() {
+    let source = Source::new("main", "fn main() {\r\n    let å = \"ö\";\n    å\n}");
+
+    assert_eq!(source.line_text(0), None);
+    assert_eq!(source.line_text(1), Some("fn main() {"));
+    assert_eq!(source.line_text(2), Some("    let å = \"ö\";"));
+    assert_eq!(source.line_text(3), Some("    å"));
+    assert_eq!(source.line_text(4), Some("}"));
+    assert_eq!(source.line_text(5), None);
+}
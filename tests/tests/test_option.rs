@@ -14,6 +14,30 @@ fn test_map() {
     )
 }
 
+#[test]
+fn test_map_none() {
+    assert_eq!(
+        rune! { Option<u32> =>
+            pub fn main() {
+                None.map(|v| v + 1)
+            }
+        },
+        None
+    )
+}
+
+#[test]
+fn test_is_some_unwrap_or() {
+    assert_eq!(
+        rune! { (bool, bool, i32, i32) =>
+            pub fn main() {
+                (Some(1).is_some(), None.is_none(), Some(1).unwrap_or(2), None.unwrap_or(2))
+            }
+        },
+        (true, true, 1, 2)
+    )
+}
+
 #[test]
 fn test_and_then() {
     assert_eq!(
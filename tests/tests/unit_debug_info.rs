@@ -0,0 +1,56 @@
+use rune_tests::*;
+use runestick::{Context, Inst, InstOp};
+
+#[test]
+fn test_debug_info_at() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let source = r#"pub fn main(a, b) { a + b }"#;
+    let unit = compile_source(&context, source).expect("compile").0;
+
+    let ip = unit
+        .iter_instructions()
+        .position(|inst| {
+            matches!(
+                inst,
+                Inst::Op {
+                    op: InstOp::Add,
+                    ..
+                }
+            )
+        })
+        .expect("add instruction");
+
+    let debug = unit.debug_info_at(ip).expect("debug info");
+    assert_eq!(debug.source_id, 0);
+    assert_eq!(debug.span, Span::new(20, 25));
+    assert_eq!(&source[debug.span.range()], "a + b");
+
+    assert!(unit
+        .debug_info_at(unit.iter_instructions().count())
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn test_executable_lines() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let source = r#"pub fn main(a, b) {
+    // Add the numbers.
+
+    let c = a
+        + b;
+
+    c * 2
+}
+"#;
+    let unit = compile_source(&context, source).expect("compile").0;
+    let lines = unit.executable_lines(0, &runestick::Source::new("main", source));
+
+    assert_eq!(lines.into_iter().collect::<Vec<_>>(), vec![1, 4, 5, 7, 8]);
+    assert!(unit
+        .executable_lines(1, &runestick::Source::new("main", source))
+        .is_empty());
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use rune::{Diagnostics, DumpInstructions as _, Options, Sources};
+use runestick::{Context, Source};
+
+#[test]
+fn test_disassemble() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "main",
+        r#"
+        fn add(a, b) { a + b }
+
+        pub fn main() {
+            let f = add;
+            f(1, 2)
+        }
+        "#,
+    ));
+
+    let mut diagnostics = Diagnostics::new();
+    let unit = rune::load_sources(
+        &context,
+        &Options::default(),
+        &mut sources,
+        &mut diagnostics,
+    )?;
+
+    let output = unit.disassemble(&sources);
+
+    assert!(output.contains("fn add(a, b)"), "{}", output);
+    assert!(output.contains("fn main()"), "{}", output);
+    assert!(output.contains("= load-fn"), "{}", output);
+    assert!(output.contains("// fn add"), "{}", output);
+    assert!(output.contains("= return"), "{}", output);
+    assert!(output.contains("main:5"), "{}", output);
+    Ok(())
+}
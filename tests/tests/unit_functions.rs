@@ -0,0 +1,105 @@
+use rune_tests::*;
+use runestick::{Context, FromValue, Hash, Item, Vm};
+use std::sync::Arc;
+
+#[test]
+fn test_functions_with_prefix() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = compile_source(
+        &context,
+        r#"
+        pub mod commands {
+            pub fn start() { 1 }
+            pub fn stop() { 2 }
+
+            pub mod db {
+                pub fn migrate() { let f = || 3; f() }
+            }
+        }
+
+        pub mod commands_old {
+            pub fn start() { 4 }
+        }
+
+        pub fn commands() { 5 }
+        "#,
+    )
+    .expect("compile")
+    .0;
+
+    let unit = Arc::new(unit);
+    let functions = unit.functions_with_prefix(&Item::with_item(&["commands"]));
+
+    let expected = vec![
+        Item::with_item(&["commands", "db", "migrate"]),
+        Item::with_item(&["commands", "start"]),
+        Item::with_item(&["commands", "stop"]),
+    ];
+
+    let items = functions
+        .iter()
+        .map(|(item, _)| item.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(items, expected);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+    let mut outputs = Vec::new();
+
+    for (item, hash) in functions {
+        assert_eq!(hash, Hash::type_hash(&item));
+        outputs.push(i64::from_value(vm.call(hash, ())?)?);
+    }
+
+    assert_eq!(outputs, vec![3, 1, 2]);
+    Ok(())
+}
+
+#[test]
+fn test_unit_tests() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = compile_source(
+        &context,
+        r#"
+        fn helper() { 1 }
+
+        #[test]
+        fn check_helper() { helper() }
+
+        mod math {
+            #[test]
+            fn check_add() { 1 + 1 }
+        }
+
+        pub fn main() { helper() }
+        "#,
+    )
+    .expect("compile")
+    .0;
+
+    let unit = Arc::new(unit);
+
+    let expected = vec![
+        Item::with_item(&["check_helper"]),
+        Item::with_item(&["math", "check_add"]),
+    ];
+
+    let items = unit
+        .tests()
+        .iter()
+        .map(|(item, _)| item.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(items, expected);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit.clone());
+
+    for (item, hash) in unit.tests() {
+        assert_eq!(*hash, Hash::type_hash(item));
+        vm.call(*hash, ())?;
+    }
+
+    Ok(())
+}
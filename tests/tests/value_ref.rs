@@ -0,0 +1,33 @@
+use rune_tests::*;
+use runestick::{Value, ValueRef};
+
+#[test]
+fn test_value_ref() -> runestick::Result<()> {
+    let value: Value = rune!(Value => pub fn main() { [1, 2, #{ "b": 3, "a": 4 }] });
+
+    let borrowed = value.borrow_ref()?;
+    let elements = borrowed.elements().expect("elements");
+    assert_eq!(elements.len(), 3);
+
+    let mut sum = 0;
+
+    for element in elements {
+        match element.borrow_ref()? {
+            ValueRef::Object(object) => {
+                let keys = object.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>();
+                assert_eq!(keys, vec!["b", "a"]);
+            }
+            ValueRef::Other(value) => sum += value.clone().into_integer()?,
+            other => panic!("unexpected value: {:?}", other),
+        }
+    }
+
+    assert_eq!(sum, 3);
+
+    // NB: the collection can't be modified while it's borrowed.
+    let vec = value.clone().into_vec()?;
+    assert!(vec.borrow_mut().is_err());
+    drop(borrowed);
+    assert!(vec.borrow_mut().is_ok());
+    Ok(())
+}
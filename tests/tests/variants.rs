@@ -34,3 +34,74 @@ fn assert_variant_comparisons() {
         }
     }
 }
+
+#[test]
+fn test_variant_construction() {
+    let out: (i64, i64) = rune! {
+        (i64, i64) =>
+        enum Shape { Empty, Line(a, b), Rect { w, h } }
+
+        fn area(shape) {
+            match shape {
+                Shape::Empty => 0,
+                Shape::Line(a, b) => b - a,
+                Shape::Rect { w, h } => w * h,
+            }
+        }
+
+        pub fn main() {
+            let line = Shape::Line(1, 4);
+            let rect = Shape::Rect { w: 2, h: 5 };
+            assert_eq!(area(Shape::Empty), 0);
+            (area(line), area(rect))
+        }
+    };
+
+    assert_eq!(out, (3, 10));
+}
+
+#[test]
+fn test_variant_wrong_arity() {
+    assert_compile_error! {
+        r#"enum Foo { A(a, b) } pub fn main() { Foo::A(1) }"#,
+        span, UnsupportedArgumentCount { expected, actual, .. } => {
+            assert_eq!(span, Span::new(37, 46));
+            assert_eq!(expected, 2);
+            assert_eq!(actual, 1);
+        }
+    };
+}
+
+#[test]
+fn test_variant_name() {
+    let out: (String, String, String, String) = rune! {
+        (String, String, String, String) =>
+        enum Shape { Line(a, b), Rect { w, h } }
+
+        pub fn main() {
+            (
+                variant_name(Shape::Line(1, 2)),
+                variant_name(Shape::Rect { w: 1, h: 2 }),
+                variant_name(Some(1)),
+                variant_name(Err(1)),
+            )
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            String::from("Line"),
+            String::from("Rect"),
+            String::from("Some"),
+            String::from("Err")
+        )
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { variant_name(#{ a: 1 }) }"#,
+        ExpectedVariant { actual } => {
+            assert_eq!(actual.to_string(), "Object");
+        }
+    );
+}
@@ -0,0 +1,55 @@
+use rune_tests::*;
+use runestick::{Context, FromValue, Span, Vm, VmErrorKind};
+use std::sync::Arc;
+
+#[test]
+fn test_assert() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let source = r#"
+    pub fn main(n) {
+        assert(n > 0, "n must be positive");
+        n
+    }
+    "#;
+    let unit = Arc::new(compile_source(&context, source).expect("compile").0);
+
+    let mut vm = Vm::new(runtime, unit);
+
+    let output = vm.execute(&["main"], (1i64,))?.complete()?;
+    assert_eq!(i64::from_value(output)?, 1);
+
+    let error = vm.execute(&["main"], (0i64,))?.complete().unwrap_err();
+    let (kind, unwound) = error.as_unwound();
+
+    match kind {
+        VmErrorKind::Panic { reason } => assert_eq!(reason.to_string(), "n must be positive"),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    let (unit, ip, _) = unwound.expect("unwound error");
+    let inst = unit.debug_info_at(ip).expect("debug info");
+
+    let start = source.find("assert(").unwrap();
+    let end = source.find(");").unwrap() + 1;
+    assert_eq!(inst.span, Span::new(start, end));
+    Ok(())
+}
+
+#[test]
+fn test_assert_bad_condition() {
+    assert_vm_error!(
+        r#"pub fn main() { assert(1, "not a bool") }"#,
+        BadArgument { arg: 0, .. } => {}
+    );
+}
+
+#[test]
+fn test_panic() {
+    assert_vm_error!(
+        r#"pub fn main() { panic("oh no") }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "oh no");
+        }
+    );
+}
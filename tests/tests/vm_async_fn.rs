@@ -0,0 +1,121 @@
+#![cfg(feature = "tokio")]
+
+use rune_tests::*;
+use runestick::{Context, FromValue, Module, Vm, VmError, VmErrorKind};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+async fn sleep_and_double(ms: u64, value: i64) -> i64 {
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+    value * 2
+}
+
+async fn checked_div(a: i64, b: i64) -> Result<i64, String> {
+    tokio::task::yield_now().await;
+
+    if b == 0 {
+        return Err(String::from("division by zero"));
+    }
+
+    Ok(a / b)
+}
+
+async fn fail(message: String) -> Result<(), VmError> {
+    tokio::task::yield_now().await;
+    Err(VmError::panic(message))
+}
+
+fn vm(source: &str) -> runestick::Result<Vm> {
+    let mut module = Module::with_crate("host");
+    module.async_function(&["sleep_and_double"], sleep_and_double)?;
+    module.async_function(&["checked_div"], checked_div)?;
+    module.async_function(&["fail"], fail)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let unit = compile_source(&context, source).expect("compile").0;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[tokio::test]
+async fn test_async_fn() -> runestick::Result<()> {
+    let mut vm = vm(r#"
+        pub async fn main() {
+            let a = host::sleep_and_double(10, 10).await;
+            let b = host::sleep_and_double(10, 11).await;
+            a + b
+        }
+    "#)?;
+
+    let output = vm.async_call(&["main"], ()).await?;
+    assert_eq!(i64::from_value(output)?, 42);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_fn_result() -> runestick::Result<()> {
+    let mut vm = vm(r#"
+        async fn div(a, b) {
+            Ok(host::checked_div(a, b).await? + 1)
+        }
+
+        pub async fn main() {
+            (div(40, 4).await, div(1, 0).await)
+        }
+    "#)?;
+
+    let output = vm.async_call(&["main"], ()).await?;
+    let output = <(Result<i64, String>, Result<i64, String>)>::from_value(output)?;
+    assert_eq!(output, (Ok(11), Err(String::from("division by zero"))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_fn_error() -> runestick::Result<()> {
+    let mut vm = vm(r#"
+        pub async fn main() {
+            host::fail("oh no").await;
+            42
+        }
+    "#)?;
+
+    let error = vm.async_call(&["main"], ()).await.unwrap_err();
+
+    match error.into_kind() {
+        VmErrorKind::Panic { reason } => {
+            assert_eq!(reason.to_string(), "oh no");
+        }
+        actual => panic!("unexpected error `{:?}`", actual),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_fn_does_not_block() -> runestick::Result<()> {
+    let source = r#"
+        pub async fn main(n) {
+            host::sleep_and_double(100, n).await
+        }
+    "#;
+
+    let mut a = vm(source)?;
+    let mut b = vm(source)?;
+    let mut c = vm(source)?;
+
+    let start = Instant::now();
+
+    let (a, b, c) = tokio::join!(
+        a.async_call(&["main"], (1i64,)),
+        b.async_call(&["main"], (2i64,)),
+        c.async_call(&["main"], (3i64,)),
+    );
+
+    // The sleeps run concurrently on a single-threaded executor.
+    assert!(start.elapsed() < Duration::from_millis(300));
+    assert_eq!(i64::from_value(a?)?, 2);
+    assert_eq!(i64::from_value(b?)?, 4);
+    assert_eq!(i64::from_value(c?)?, 6);
+    Ok(())
+}
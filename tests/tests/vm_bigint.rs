@@ -0,0 +1,84 @@
+use rune::{Diagnostics, Options, Sources};
+use runestick::{FromValue, Source, Vm};
+use std::sync::Arc;
+
+fn run_bignum(source: &str) -> runestick::Result<String> {
+    let mut context = rune_modules::default_context()?;
+    context.install(&runestick::modules::bigint::module()?)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("test", source));
+
+    let mut options = Options::default();
+    options.bignum(true);
+
+    let mut diagnostics = Diagnostics::new();
+    let unit = rune::load_sources(&context, &options, &mut sources, &mut diagnostics)?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    Ok(String::from_value(vm.call(&["main"], ())?)?)
+}
+
+#[test]
+fn test_bignum_factorial() -> runestick::Result<()> {
+    let output = run_bignum(
+        r#"
+        fn factorial(n) {
+            let output = std::bigint::BigInt::new(1);
+            let i = 2;
+
+            while i <= n {
+                output *= i;
+                i += 1;
+            }
+
+            output
+        }
+
+        pub fn main() {
+            let n = factorial(25);
+            assert!(n == 15511210043330985984000000);
+            n.to_string()
+        }
+        "#,
+    )?;
+
+    assert_eq!(output, "15511210043330985984000000");
+    Ok(())
+}
+
+#[test]
+fn test_bignum_literals() -> runestick::Result<()> {
+    let output = run_bignum(
+        r#"
+        pub fn main() {
+            let a = 18446744073709551616;
+            let b = -18446744073709551616;
+            format!("{} {} {} {}", a - 1, 2 * b, 100000000000000000000 / 7, 9223372036854775807 + a)
+        }
+        "#,
+    )?;
+
+    assert_eq!(
+        output,
+        "18446744073709551615 -36893488147419103232 14285714285714285714 27670116110564327423"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_bignum_cmp() -> runestick::Result<()> {
+    let output = run_bignum(
+        r#"
+        pub fn main() {
+            let a = 18446744073709551616;
+            let b = -18446744073709551616;
+            let values = [a > b, a >= 1, b < 0, a <= a, a < b];
+            format!("{:?}", values)
+        }
+        "#,
+    )?;
+
+    assert_eq!(output, "[true, true, true, true, false]");
+    Ok(())
+}
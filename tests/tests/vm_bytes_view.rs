@@ -0,0 +1,127 @@
+use rune_tests::*;
+use runestick::{Any, BytesView, ContextError, Module, Mut, Ref, VmErrorKind};
+
+#[derive(Any)]
+struct Buffer {
+    data: Vec<u8>,
+}
+
+impl Buffer {
+    fn new() -> Self {
+        Self {
+            data: vec![1, 2, 3],
+        }
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.data.push(byte);
+    }
+
+    fn get(&self, index: usize) -> Option<u8> {
+        self.data.get(index).copied()
+    }
+}
+
+fn view(this: Ref<Buffer>) -> BytesView {
+    BytesView::from(Ref::map(this, |buffer| &buffer.data[1..]))
+}
+
+fn view_mut(this: Mut<Buffer>) -> BytesView {
+    BytesView::from(Mut::map(this, |buffer| &mut buffer.data[..]))
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Buffer>()?;
+    module.function(&["Buffer", "new"], Buffer::new)?;
+    module.inst_fn("push", Buffer::push)?;
+    module.inst_fn("get", Buffer::get)?;
+    module.inst_fn("view", view)?;
+    module.inst_fn("view_mut", view_mut)?;
+    Ok(module)
+}
+
+#[test]
+fn test_bytes_view_read() {
+    assert_eq! {
+        rune_n! {
+            make_module().expect("failed making module"),
+            (),
+            (usize, Option<u8>, Option<u8>, bool) => pub fn main() {
+                let buffer = Buffer::new();
+                let view = buffer.view();
+                (view.len(), view.get(0), view.get(2), view.is_mutable())
+            }
+        },
+        (2, Some(2), None, false),
+    };
+}
+
+#[test]
+fn test_bytes_view_write() {
+    assert_eq! {
+        rune_n! {
+            make_module().expect("failed making module"),
+            (),
+            (Option<u8>, Vec<u8>) => pub fn main() {
+                let buffer = Buffer::new();
+                let view = buffer.view_mut();
+                view.set(0, b'\x09');
+                let bytes = view.to_bytes();
+                drop(view);
+                (buffer.get(0), bytes.into_vec())
+            }
+        },
+        (Some(9), vec![9, 2, 3]),
+    };
+}
+
+#[test]
+fn test_bytes_view_outlives_owner() {
+    assert_eq! {
+        rune_n! {
+            make_module().expect("failed making module"),
+            (),
+            Option<u8> => pub fn main() {
+                fn make() {
+                    let buffer = Buffer::new();
+                    buffer.view()
+                }
+
+                make().get(1)
+            }
+        },
+        Some(3),
+    };
+}
+
+#[test]
+fn test_bytes_view_prevents_mutation() {
+    let context = context_with_module(&make_module().expect("failed making module"))
+        .expect("failed to build context");
+
+    let result = run::<_, _, ()>(
+        &context,
+        r#"
+        pub fn main() {
+            let buffer = Buffer::new();
+            let view = buffer.view();
+            buffer.push(b'\x04');
+        }
+        "#,
+        &["main"],
+        (),
+    );
+
+    let error = result
+        .expect_err("mutating a viewed buffer should fail")
+        .expect_vm_error("expected vm error");
+
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::BadArgument { error, arg: 0 } => match error.into_kind() {
+            VmErrorKind::AccessError { .. } => (),
+            kind => panic!("unexpected error {:?}", kind),
+        },
+        kind => panic!("unexpected error {:?}", kind),
+    }
+}
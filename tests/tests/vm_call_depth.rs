@@ -0,0 +1,70 @@
+use rune_tests::*;
+
+#[test]
+fn test_call_depth_top_level() {
+    assert_eq! {
+        rune!(usize => pub fn main() {
+            call_depth()
+        }),
+        0,
+    };
+}
+
+#[test]
+fn test_call_depth_recursion() {
+    assert_eq! {
+        rune!(Vec<usize> => fn recurse(n, depths) {
+            depths.push(call_depth());
+
+            if n > 0 {
+                recurse(n - 1, depths);
+            }
+
+            depths
+        }
+
+        pub fn main() {
+            let depths = recurse(4, []);
+            depths
+        }),
+        vec![1, 2, 3, 4, 5],
+    };
+}
+
+#[test]
+fn test_call_depth_restored_after_return() {
+    assert_eq! {
+        rune!((usize, usize, usize) => fn nested() {
+            call_depth()
+        }
+
+        pub fn main() {
+            let before = call_depth();
+            let inside = nested();
+            (before, inside, call_depth())
+        }),
+        (0, 1, 0),
+    };
+}
+
+#[test]
+fn test_call_depth_tail_call() {
+    // NB: tail calls replace the frame of their caller, so the depth doesn't
+    // increase during tail recursion. Even the call in `main` is a tail call.
+    assert_eq! {
+        rune!(Vec<usize> => fn recurse(n, depths) {
+            depths.push(call_depth());
+
+            if n == 0 {
+                return depths;
+            }
+
+            recurse(n - 1, depths)
+        }
+
+        pub fn main() {
+            recurse(4, [])
+        }),
+        vec![0, 0, 0, 0, 0],
+    };
+}
@@ -0,0 +1,241 @@
+use rune_tests::*;
+use runestick::{Any, ContextError, Module, Protocol, VmErrorKind};
+
+#[derive(Any, Clone)]
+struct Counter {
+    value: i64,
+}
+
+impl Counter {
+    fn new(value: i64) -> Self {
+        Self { value }
+    }
+
+    fn inc(&mut self) {
+        self.value += 1;
+    }
+
+    fn get(&self) -> i64 {
+        self.value
+    }
+}
+
+#[derive(Any)]
+struct Opaque;
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("native_crate");
+    module.ty::<Counter>()?;
+    module.function(&["Counter", "new"], Counter::new)?;
+    module.inst_fn("inc", Counter::inc)?;
+    module.inst_fn("get", Counter::get)?;
+    module.inst_fn(Protocol::CLONE, Counter::clone)?;
+    module.ty::<Opaque>()?;
+    module.function(&["Opaque", "new"], || Opaque)?;
+    Ok(module)
+}
+
+#[test]
+fn test_clone() {
+    assert_eq! {
+        rune! { (Vec<i64>, Vec<i64>) =>
+            pub fn main() {
+                let a = [1, 2];
+                let b = std::clone::clone(a);
+                b.push(3);
+                (a, b)
+            }
+        },
+        (vec![1, 2], vec![1, 2, 3])
+    };
+
+    assert_eq! {
+        rune! { (i64, i64) =>
+            pub fn main() {
+                let a = [[1]];
+                let b = std::clone::clone(a);
+                b[0].push(2);
+                (a[0].len(), b[0].len())
+            }
+        },
+        (2, 2)
+    };
+}
+
+#[test]
+fn test_deep_clone() {
+    assert_eq! {
+        rune! { (i64, i64) =>
+            pub fn main() {
+                let a = [[1], #{b: [2]}];
+                let b = std::clone::deep_clone(a);
+                b[0].push(2);
+                b[1].b.push(3);
+                (a[0].len() + a[1].b.len(), b[0].len() + b[1].b.len())
+            }
+        },
+        (2, 4)
+    };
+}
+
+#[test]
+fn test_deep_clone_nested() {
+    assert_eq! {
+        rune! { (Vec<Vec<Vec<i64>>>, Vec<Vec<Vec<i64>>>) =>
+            pub fn main() {
+                let a = [[[1, 2]], [[3]]];
+                let b = std::clone::deep_clone(a);
+                b[0][0].push(4);
+                b[1].push([5]);
+                (a, b)
+            }
+        },
+        (
+            vec![vec![vec![1, 2]], vec![vec![3]]],
+            vec![vec![vec![1, 2, 4]], vec![vec![3], vec![5]]]
+        )
+    };
+}
+
+#[test]
+fn test_deep_clone_collections() {
+    assert_eq! {
+        rune! { (i64, i64) =>
+            use std::collections::{HashMap, VecDeque};
+
+            pub fn main() {
+                let map = HashMap::new();
+                map.insert("a", [1]);
+                let deque = VecDeque::new();
+                deque.push_back([2]);
+
+                let a = [map, deque];
+                let b = std::clone::deep_clone(a);
+                b[0]["a"].push(3);
+                b[1][0].push(4);
+                (a[0]["a"].len() + a[1][0].len(), b[0]["a"].len() + b[1][0].len())
+            }
+        },
+        (2, 4)
+    };
+}
+
+#[test]
+fn test_deep_clone_cyclic() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let a = [1];
+            let b = #{ a };
+            a.push(b);
+            std::clone::deep_clone(a)
+        }
+        "#,
+        CyclicClone { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let map = std::collections::HashMap::new();
+            let a = [map];
+            map.insert("a", a);
+            std::clone::deep_clone(a)
+        }
+        "#,
+        CyclicClone { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let deque = std::collections::VecDeque::new();
+            deque.push_back(#{ deque });
+            std::clone::deep_clone(deque)
+        }
+        "#,
+        CyclicClone { .. } => {}
+    );
+
+    // NB: a container which is referenced more than once isn't a cycle.
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                let a = [1];
+                let b = std::clone::deep_clone([a, a]);
+                b[0].push(2);
+                a.len() + b[0].len() + b[1].len()
+            }
+        },
+        4
+    };
+}
+
+#[test]
+fn test_object_clone() {
+    assert_eq! {
+        rune! { (i64, i64) =>
+            pub fn main() {
+                let a = #{ b: 1 };
+                let b = a.clone();
+                b.c = 2;
+                (a.len(), b.len())
+            }
+        },
+        (1, 2)
+    };
+}
+
+#[test]
+fn test_deep_clone_external() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let output: (i64, i64) = run(
+        &context,
+        r#"
+        use native_crate::Counter;
+
+        pub fn main() {
+            let a = [Counter::new(1)];
+            let b = std::clone::deep_clone(a);
+            b[0].inc();
+            (a[0].get(), b[0].get())
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(output, (1, 2));
+    Ok(())
+}
+
+#[test]
+fn test_deep_clone_missing_protocol() -> runestick::Result<()> {
+    let context = context_with_module(&make_module()?)?;
+
+    let result = run::<_, _, ()>(
+        &context,
+        r#"
+        pub fn main() {
+            std::clone::deep_clone([native_crate::Opaque::new()]);
+        }
+        "#,
+        &["main"],
+        (),
+    );
+
+    let (e, _) = match result {
+        Err(RunError::VmError(e)) => e.into_unwound(),
+        actual => panic!("expected vm error but was `{:?}`", actual),
+    };
+
+    match e.into_kind() {
+        VmErrorKind::MissingProtocol { protocol, .. } => {
+            assert_eq!(protocol, Protocol::CLONE);
+        }
+        actual => panic!("expected missing protocol error but was `{:?}`", actual),
+    }
+
+    Ok(())
+}
@@ -212,3 +212,72 @@ fn test_closure_in_lit_object() -> runestick::Result<()> {
     assert_eq!(3, proxy.d);
     Ok(())
 }
+
+#[test]
+fn test_capture_shared_container() {
+    assert_eq! {
+        (vec![1, 2, 3], 3),
+        rune! { (Vec<i64>, i64) =>
+            pub fn main() {
+                let values = [1, 2];
+                let object = #{};
+
+                let push = |n| {
+                    values.push(n);
+                    object.len = values.len();
+                };
+
+                push(3);
+                (values, object.len)
+            }
+        }
+    };
+}
+
+#[test]
+fn test_capture_copied_scalar() {
+    assert_eq! {
+        (1, 2),
+        rune! { (i64, i64) =>
+            pub fn main() {
+                let n = 1;
+
+                let increment = || {
+                    n += 1;
+                    n
+                };
+
+                let inner = increment();
+                (n, inner)
+            }
+        }
+    };
+}
+
+#[test]
+fn test_closure_environment() {
+    let function = rune! { Function =>
+        pub fn main() {
+            let a = 41;
+            let b = "unused";
+            |n| a + n
+        }
+    };
+
+    let environment = function
+        .environment()
+        .map(|(name, value)| (name.map(String::from), value.clone()))
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        &environment[..],
+        [(Some(name), Value::Integer(41))] if name == "a"
+    ));
+
+    assert_eq!(function.call::<_, i64>((1i64,)).unwrap(), 42);
+
+    // NB: closures which don't capture anything are loaded as plain function
+    // pointers.
+    let function = rune!(Function => pub fn main() { |n| n + 1 });
+    assert_eq!(function.environment().count(), 0);
+}
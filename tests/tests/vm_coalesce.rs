@@ -0,0 +1,80 @@
+use rune_tests::*;
+
+#[test]
+fn test_coalesce() {
+    assert_eq! {
+        rune!((i64, Option<i64>, i64, i64) => pub fn main() {
+            (None ?? 1, Some(2) ?? 1, () ?? 3, 0 ?? 4)
+        }),
+        (1, Some(2), 3, 0),
+    };
+}
+
+#[test]
+fn test_coalesce_lazy() {
+    assert_eq! {
+        rune!((i64, i64, i64) => pub fn main() {
+            let calls = [];
+            let a = Some(1) ?? { calls.push("a"); 2 };
+            let b = None ?? { calls.push("b"); 2 };
+            (calls.len(), a?, b)
+        }),
+        (1, 1, 2),
+    };
+
+    assert_eq! {
+        rune!(bool => pub fn main() { (Some(true) ?? return false)? }),
+        true,
+    };
+}
+
+#[test]
+fn test_coalesce_chained() {
+    assert_eq! {
+        rune!((i64, i64, i64) => pub fn main() {
+            let calls = [];
+            let a = None ?? () ?? 3;
+            let b = None ?? 2 ?? { calls.push("c"); 3 };
+            (a, b, calls.len())
+        }),
+        (3, 2, 0),
+    };
+}
+
+#[test]
+fn test_coalesce_precedence() {
+    assert_eq! {
+        rune!((i64, bool, String) => pub fn main() {
+            let user = #{address: None};
+
+            (
+                None ?? 1 + 2,
+                None ?? false || true,
+                user.address?.city ?? "unknown",
+            )
+        }),
+        (3, true, String::from("unknown")),
+    };
+}
+
+#[test]
+fn test_double_try() {
+    assert_eq! {
+        rune!(i64 => pub fn main() {
+            let a = Ok(Ok(1))??;
+            let b = Some(Some(2))?? + 3;
+            let c = Some(#{inner: Some(#{value: 4})})??.inner?.value;
+            a + b + c ?? 0
+        }),
+        10,
+    };
+
+    assert_eq! {
+        rune!(Option<i64> => pub fn main() {
+            let a = Some(None);
+            a??;
+            Some(1)
+        }),
+        None,
+    };
+}
@@ -158,6 +158,60 @@ fn test_if_else() {
     assert_eq!(result, 3);
 }
 
+#[test]
+fn test_const_match() {
+    let result = rune! { (String, String, i64) =>
+        const ZERO = 0;
+        const ONE = 1;
+
+        const fn describe(n) {
+            match n {
+                0 => "zero",
+                n if n < 0 => "negative",
+                _ => "other",
+            }
+        }
+
+        const FIRST = describe(ZERO);
+        const SECOND = describe(ONE + 1);
+        const THIRD = match "b" { "a" => 1, "b" => 2, _ => 3 };
+
+        pub fn main() { (FIRST, SECOND, THIRD) }
+    };
+    assert_eq!(result, (String::from("zero"), String::from("other"), 2));
+
+    assert_compile_error! {
+        r#"const N = 2; const VALUE = match N { 0 => "zero", 1 => "one" }; pub fn main() { VALUE }"#,
+        span, QueryError { error: QueryErrorKind::IrError { error: rune::IrErrorKind::NoMatchingBranch } } => {
+            assert_eq!(span, Span::new(27, 62));
+        }
+    };
+}
+
+#[test]
+fn test_const_if_condition() {
+    let result = rune! { i64 =>
+        const DEBUG = true;
+        const LIMIT = if DEBUG { 10 } else { 1000 };
+        pub fn main() { LIMIT }
+    };
+    assert_eq!(result, 10);
+
+    let result = rune! { i64 =>
+        const DEBUG = false;
+        const LIMIT = if DEBUG { 10 } else { 1000 };
+        pub fn main() { LIMIT }
+    };
+    assert_eq!(result, 1000);
+
+    assert_compile_error! {
+        r#"fn limit() { 10 } const LIMIT = if limit() > 5 { 10 } else { 1000 }; pub fn main() { LIMIT }"#,
+        span, QueryError { error: QueryErrorKind::IrError { error: rune::IrErrorKind::NotConstCondition { .. } } } => {
+            assert_eq!(span, Span::new(35, 46));
+        }
+    };
+}
+
 #[test]
 fn test_const_fn() {
     let result = rune! { i64 =>
@@ -266,3 +320,69 @@ fn test_const_block() {
 
     assert_eq!(result, "Hello World");
 }
+
+#[test]
+fn test_const_budget() {
+    let source = r#"
+    const VALUE = {
+        let n = 0;
+
+        while n < 1000 {
+            n += 1;
+        }
+
+        n
+    };
+
+    pub fn main() { VALUE }
+    "#;
+
+    let context = runestick::Context::with_default_modules().unwrap();
+
+    let compile = |budget| {
+        let mut options = rune::Options::default();
+        options.const_budget(budget);
+
+        let mut sources = rune::Sources::new();
+        sources.insert(runestick::Source::new("main", source));
+
+        let mut diagnostics = rune::Diagnostics::new();
+        let result = rune::load_sources(&context, &options, &mut sources, &mut diagnostics);
+        (result, diagnostics)
+    };
+
+    let (result, diagnostics) = compile(1000);
+    assert!(result.is_err());
+
+    let error = match diagnostics.into_diagnostics().into_iter().next() {
+        Some(rune::Diagnostic::Error(error)) => error,
+        diagnostic => panic!("expected error but got {:?}", diagnostic),
+    };
+
+    let error = match error.into_kind() {
+        rune::ErrorKind::CompileError(error) => error,
+        kind => panic!("expected compile error but got {:?}", kind),
+    };
+
+    match error.kind() {
+        QueryError {
+            error: QueryErrorKind::IrError { error },
+        } => {
+            assert!(matches!(
+                error,
+                rune::IrErrorKind::BudgetExceeded { budget: 1000 }
+            ));
+            assert_eq!(
+                error.to_string(),
+                "evaluation budget of 1000 steps exceeded"
+            );
+        }
+        kind => panic!("expected budget to be exceeded but got {:?}", kind),
+    }
+
+    let (result, _) = compile(10_000);
+    let unit = std::sync::Arc::new(result.unwrap());
+    let mut vm = runestick::Vm::new(std::sync::Arc::new(context.runtime()), unit);
+    let output = vm.execute(&["main"], ()).unwrap().complete().unwrap();
+    assert_eq!(i64::from_value(output).unwrap(), 1000);
+}
@@ -0,0 +1,81 @@
+use rune_tests::*;
+use runestick::{Any, FromValue, Module, Protocol};
+use std::fmt;
+use std::fmt::Write as _;
+
+#[derive(Any)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+impl Point {
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        write!(s, "Point({}, {})", self.x, self.y)
+    }
+}
+
+#[test]
+fn test_dbg() -> runestick::Result<()> {
+    let mut vm = rune_vm_capture!(
+        pub fn main() {
+            let a = dbg(1 + 2) * 2;
+            let (b, c) = dbg(a, [a, #{ "b": (1, 2) }]);
+            dbg();
+            let d = dbg;
+            d(b)
+        }
+    );
+
+    let output = vm.execute(&["main"], ())?.complete()?;
+    assert_eq!(i64::from_value(output)?, 6);
+
+    let output = capture_output::drain_output().expect("utf-8 output");
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("[main:"));
+    assert!(lines[0].ends_with("] 1 + 2 = 3"));
+    assert!(lines[1].ends_with("] a = 6"));
+    assert!(lines[2].ends_with("] [a, #{ \"b\": (1, 2) }] = [6, {\"b\": (1, 2)}]"));
+    // NB: calls through a function pointer don't know their call site.
+    assert_eq!(lines[3], "6");
+
+    // NB: captured output is shared, so this can't run as a separate test.
+    dbg_string_debug_protocol()
+}
+
+fn dbg_string_debug_protocol() -> runestick::Result<()> {
+    let mut module = Module::with_crate("native_crate");
+    module.ty::<Point>()?;
+    module.function(&["Point", "new"], |x, y| Point { x, y })?;
+    module.inst_fn(Protocol::STRING_DEBUG, Point::string_debug)?;
+
+    let mut context = rune_modules::with_config(false)?;
+    context.install(&capture_output::output_redirect_module()?)?;
+    context.install(&module)?;
+
+    let mut vm = vm_with_source(
+        &context,
+        r#"
+        use native_crate::Point;
+
+        pub fn main() {
+            let p = Point::new(1, 2);
+            let d = dbg;
+            d(p);
+            dbg([p]);
+        }
+        "#,
+    )?;
+
+    vm.execute(&["main"], ())?.complete()?;
+
+    let output = capture_output::drain_output().expect("utf-8 output");
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "Point(1, 2)");
+    assert!(lines[1].ends_with("] [p] = [Point(1, 2)]"));
+    Ok(())
+}
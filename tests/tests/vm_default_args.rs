@@ -0,0 +1,120 @@
+use rune_tests::*;
+
+#[test]
+fn test_default_args() {
+    assert_eq! {
+        rune! { (String, String) =>
+            fn greet(name, greeting = "hello") {
+                greeting + " " + name
+            }
+
+            pub fn main() {
+                (greet("world"), greet("world", "goodbye"))
+            }
+        },
+        (String::from("hello world"), String::from("goodbye world")),
+    };
+
+    assert_eq! {
+        rune! { (i64, i64) =>
+            fn area(w, h = w) {
+                w * h
+            }
+
+            pub fn main() {
+                (area(3), area(3, 4))
+            }
+        },
+        (9, 12),
+    };
+
+    // Defaults are evaluated every time the function is called.
+    assert_eq! {
+        rune! { (i64, i64) =>
+            fn push(value, values = []) {
+                values.push(value);
+                values
+            }
+
+            pub fn main() {
+                push(1);
+                (push(2).len(), push(3, [1, 2]).len())
+            }
+        },
+        (1, 3),
+    };
+}
+
+#[test]
+fn test_default_args_instance_fn() {
+    assert_eq! {
+        rune! { (i64, i64) =>
+            struct Counter { value }
+
+            impl Counter {
+                fn add(self, n = 1) {
+                    self.value + n
+                }
+            }
+
+            pub fn main() {
+                let counter = Counter { value: 10 };
+                (counter.add(), counter.add(5))
+            }
+        },
+        (11, 15),
+    };
+}
+
+#[test]
+fn test_default_args_fn_ptr() {
+    let function = rune! { Function =>
+        fn add(a, b = 2) { a + b }
+
+        pub fn main() { add }
+    };
+
+    assert_eq!(function.call::<_, i64>((1i64,)).unwrap(), 3);
+    assert_eq!(function.call::<_, i64>((1i64, 5i64)).unwrap(), 6);
+    assert!(function.call::<_, i64>(()).is_err());
+    assert!(function.call::<_, i64>((1i64, 2i64, 3i64)).is_err());
+}
+
+#[test]
+fn test_default_args_unused() {
+    // `w` is only used by the default value of `h`.
+    assert_warnings! {
+        r#"fn area(w, h = w) { h } fn add(a, b = 1) { b } pub fn main() { area(2) + add(1) }"#,
+        UnusedVariable { span, name } => {
+            assert_eq!(span, Span::new(31, 32));
+            assert_eq!(&*name, "a");
+        }
+    };
+}
+
+#[test]
+fn test_default_args_errors() {
+    assert_compile_error! {
+        r#"fn foo(a = 1, b) {} pub fn main() {}"#,
+        span, FnArgMissingDefault { default_span } => {
+            assert_eq!(span, Span::new(14, 15));
+            assert_eq!(default_span, Span::new(7, 12));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn foo(a, b = 1) {} pub fn main() { foo() }"#,
+        span, UnsupportedArgumentCount { expected, actual, .. } => {
+            assert_eq!(span, Span::new(36, 41));
+            assert_eq!(expected, 2);
+            assert_eq!(actual, 0);
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { let f = |a = 1, b| a; f(1, 2) }"#,
+        span, UnsupportedFnArgDefault => {
+            assert_eq!(span, Span::new(25, 30));
+        }
+    };
+}
@@ -0,0 +1,41 @@
+use rune_tests::*;
+use runestick::{Context, FromValue, Vm, VmErrorKind};
+use std::sync::Arc;
+
+#[test]
+fn test_entrypoint_argument_count() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let source = r#"pub fn main(n) { n + 1 }"#;
+    let unit = Arc::new(compile_source(&context, source).expect("compile").0);
+
+    let mut vm = Vm::new(runtime, unit);
+
+    // NB: the arguments are validated before anything is executed.
+    let error = vm.execute(&["main"], ()).err().expect("expected error");
+
+    assert!(matches!(
+        error.kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 0,
+            expected: 1
+        }
+    ));
+
+    let error = vm
+        .execute(&["main"], (1i64, 2i64))
+        .err()
+        .expect("expected error");
+
+    assert!(matches!(
+        error.kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 2,
+            expected: 1
+        }
+    ));
+
+    let output = vm.execute(&["main"], (1i64,))?.complete()?;
+    assert_eq!(i64::from_value(output)?, 2);
+    Ok(())
+}
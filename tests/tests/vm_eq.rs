@@ -0,0 +1,52 @@
+use rune_tests::*;
+
+#[test]
+fn test_eq_vec() {
+    assert_eq! {
+        rune!((bool, bool, bool, bool) => pub fn main() {
+            let a = [1, [2, "three"], #{four: 4}];
+            let b = [];
+            b.push(1);
+            b.push([2, "three"]);
+            b.push(#{four: 4});
+
+            (a == b, a == [1, [2, "three"]], [1, [2]] == [1, [3]], [] == [])
+        }),
+        (true, false, false, true),
+    };
+}
+
+#[test]
+fn test_eq_object() {
+    assert_eq! {
+        rune!((bool, bool, bool) => pub fn main() {
+            let a = #{first: 1, second: [2, 3]};
+            let b = #{};
+            b.second = [2, 3];
+            b.first = 1;
+
+            (a == b, a == #{first: 1}, a == #{first: 1, second: [2, 4]})
+        }),
+        (true, false, false),
+    };
+}
+
+#[test]
+fn test_eq_self_referential() {
+    assert_eq! {
+        rune!((bool, bool, bool, bool) => pub fn main() {
+            let a = [1];
+            a.push(a);
+            let b = [1];
+            b.push(b);
+            let c = [2];
+            c.push(c);
+
+            let o = #{};
+            o.me = o;
+
+            (a == a, a == b, a == c, o == o)
+        }),
+        (true, true, false, true),
+    };
+}
@@ -0,0 +1,72 @@
+use rune_tests::*;
+use runestick::{Any, ContextError, Module};
+
+#[derive(Any, Default)]
+struct External {
+    #[rune(get)]
+    number: i64,
+    #[rune(get, set)]
+    string: String,
+    #[allow(unused)]
+    hidden: bool,
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("host");
+    module.ty::<External>()?;
+    Ok(module)
+}
+
+#[test]
+fn test_fields_object() {
+    assert_eq!(
+        rune!(Vec<String> => pub fn main() {
+            let object = #{b: 1, a: 2};
+            object.c = 3;
+            fields(object)
+        }),
+        vec!["a", "b", "c"]
+    );
+}
+
+#[test]
+fn test_fields_struct() {
+    assert_eq!(
+        rune!((Vec<String>, Vec<String>) =>
+            struct Point { y, x }
+            enum Shape { Circle { radius, center } }
+
+            pub fn main() {
+                let point = Point { x: 1, y: 2 };
+                let shape = Shape::Circle { radius: 1, center: point };
+                (fields(point), fields(shape))
+            }
+        ),
+        (
+            vec![String::from("x"), String::from("y")],
+            vec![String::from("center"), String::from("radius")]
+        )
+    );
+}
+
+#[test]
+fn test_fields_external() {
+    assert_eq!(
+        rune_n! {
+            make_module().expect("failed making module"),
+            (External::default(),),
+            Vec<String> => pub fn main(external) { fields(external) }
+        },
+        vec!["number", "string"]
+    );
+}
+
+#[test]
+fn test_fields_empty() {
+    assert_eq!(
+        rune!((Vec<String>, Vec<String>) => pub fn main() {
+            (fields((1, 2)), fields([1, 2]))
+        }),
+        (vec![], vec![])
+    );
+}
@@ -0,0 +1,78 @@
+use rune_tests::*;
+use runestick::format;
+
+#[test]
+fn test_fmt_format() {
+    assert_eq! {
+        rune!(String => pub fn main() {
+            std::fmt::format("{} + {} = {}", 1, 2, 1 + 2)
+        }),
+        "1 + 2 = 3",
+    };
+
+    assert_eq! {
+        rune!(String => pub fn main() {
+            let format = "{1}, {0}! {{{}}}";
+            std::fmt::format(format, "world", "hello")
+        }),
+        "hello, world! {world}",
+    };
+
+    assert_eq! {
+        rune!(String => pub fn main() {
+            std::fmt::format("no arguments")
+        }),
+        "no arguments",
+    };
+}
+
+#[test]
+fn test_fmt_format_errors() {
+    // Format strings which are not literals are checked at runtime.
+    assert_vm_error!(
+        r#"pub fn main() { let f = "{} and {}"; std::fmt::format(f, 1) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "missing positional argument #1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let f = "{2}"; std::fmt::format(f, 1, 2) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "missing positional argument #2");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let f = "{}"; std::fmt::format(f, 1, 2) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "unused positional argument #1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let f = "oops }"; std::fmt::format(f, 1) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "unmatched `}` in format string");
+        }
+    );
+}
+
+#[test]
+fn test_fmt_format_literal_errors() {
+    assert_compile_error! {
+        r#"pub fn main() { std::fmt::format("{} {}", 1) }"#,
+        span, FormatStrError { error } => {
+            assert_eq!(span, Span::new(33, 40));
+            assert_eq!(error, format::FormatStrError::MissingArgument(1));
+        }
+    };
+
+    assert_compile_error! {
+        r#"use std::fmt::format; pub fn main() { format("{}", 1, 2) }"#,
+        span, FormatStrError { error } => {
+            assert_eq!(span, Span::new(45, 49));
+            assert_eq!(error, format::FormatStrError::UnusedArgument(1));
+        }
+    };
+}
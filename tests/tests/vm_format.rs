@@ -0,0 +1,87 @@
+use rune_tests::*;
+use runestick::{Any, ContextError, Module, Protocol};
+use std::fmt::{self, Write as _};
+
+#[derive(Any)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+impl Point {
+    fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    fn string_display(&self, s: &mut String) -> fmt::Result {
+        write!(s, "({}, {})", self.x, self.y)
+    }
+
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        write!(s, "Point {{ x: {}, y: {} }}", self.x, self.y)
+    }
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Point>()?;
+    module.function(&["Point", "new"], Point::new)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Point::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Point::string_debug)?;
+    Ok(module)
+}
+
+#[test]
+fn test_format_vec() {
+    assert_eq! {
+        rune!((String, String) => pub fn main() {
+            let vec = [1, "two", 'c', [4.5], ()];
+            (format!("{:?}", vec), format!("{}", vec))
+        }),
+        (
+            String::from(r#"[1, "two", 'c', [4.5], ()]"#),
+            String::from("[1, two, c, [4.5], ()]"),
+        ),
+    };
+}
+
+#[test]
+fn test_format_object() {
+    assert_eq! {
+        rune!((String, String) => pub fn main() {
+            let object = #{b: "two", a: (1, Some(true))};
+            (format!("{:?}", object), format!("{}", object))
+        }),
+        (
+            String::from(r#"{"b": "two", "a": (1, Some(true))}"#),
+            String::from("{b: two, a: (1, Some(true))}"),
+        ),
+    };
+}
+
+#[test]
+fn test_format_external() {
+    assert_eq! {
+        rune_n! {
+            make_module().expect("failed making module"),
+            (),
+            (String, String, String, String) => pub fn main() {
+                let point = Point::new(1, 2);
+                let points = [point, Some(Point::new(3, 4))];
+
+                (
+                    format!("{}", point),
+                    format!("{:?}", point),
+                    format!("{}", points),
+                    format!("{:?}", points),
+                )
+            }
+        },
+        (
+            String::from("(1, 2)"),
+            String::from("Point { x: 1, y: 2 }"),
+            String::from("[(1, 2), Some((3, 4))]"),
+            String::from("[Point { x: 1, y: 2 }, Some(Point { x: 3, y: 4 })]"),
+        ),
+    };
+}
@@ -1,6 +1,20 @@
 use rune_tests::*;
+use runestick::{Any, ContextError, Module, Protocol};
 use std::sync::Arc;
 
+#[derive(Any)]
+struct Adder {
+    base: i64,
+}
+
+fn make_adder_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("host");
+    module.ty::<Adder>()?;
+    module.function(&["Adder", "new"], |base: i64| Adder { base })?;
+    module.inst_fn(Protocol::CALL, |adder: &Adder, n: i64| adder.base + n)?;
+    Ok(module)
+}
+
 #[test]
 fn test_function() {
     let context = Arc::new(rune_modules::default_context().unwrap());
@@ -63,3 +77,51 @@ fn test_function() {
     let value: Value = function.call(()).unwrap();
     assert!(matches!(value, Value::Integer(3)));
 }
+
+#[test]
+fn test_function_wrong_argument_count() {
+    assert_vm_error!(
+        r#"
+        fn add(a) { a }
+
+        pub fn main() {
+            let f = add;
+            f(1, 2)
+        }
+        "#,
+        BadArgumentCount { actual, expected } => {
+            assert_eq!(actual, 2);
+            assert_eq!(expected, 1);
+        }
+    );
+}
+
+#[test]
+fn test_call_not_callable() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let x = 5;
+            x()
+        }
+        "#,
+        NotCallable { type_info } => {
+            assert_eq!(type_info.to_string(), "integer");
+        }
+    );
+}
+
+#[test]
+fn test_call_protocol() {
+    assert_eq!(
+        rune_n! {
+            make_adder_module().expect("failed making module"),
+            (),
+            i64 => pub fn main() {
+                let add = host::Adder::new(40);
+                add(2)
+            }
+        },
+        42
+    );
+}
@@ -468,6 +468,33 @@ fn test_template_string() {
     };
 }
 
+#[test]
+fn test_template_string_display_holes() {
+    assert_eq! {
+        rune_s! { String => r#"
+            pub fn main() {
+                let name = "John";
+                let messages = [1, "two", Some('c')];
+                `hello ${name}, you have ${messages.len()} messages: ${messages}`
+            }
+        "#},
+        "hello John, you have 3 messages: [1, two, Some(c)]",
+    };
+}
+
+#[test]
+fn test_template_string_escapes() {
+    assert_eq! {
+        rune_s! { String => r#"
+            pub fn main() {
+                let value = 42;
+                `{literal} \${value} \` ${value}`
+            }
+        "#},
+        "{literal} ${value} ` 42",
+    };
+}
+
 #[test]
 fn test_variants_as_functions() {
     assert_eq! {
@@ -583,3 +610,46 @@ fn test_index_get() {
         32,
     };
 }
+
+#[test]
+fn test_while_let_drain_option() {
+    assert_eq! {
+        rune! { (Vec<i64>, i64) =>
+            pub fn main() {
+                let slot = Some(3);
+                let seen = [];
+                let iterations = 0;
+
+                while let Some(n) = slot {
+                    let doubled = n * 2;
+                    seen.push(doubled);
+                    slot = if n > 1 { Some(n - 1) } else { None };
+                    iterations += 1;
+                }
+
+                (seen, iterations)
+            }
+        },
+        (vec![6, 4, 2], 3),
+    };
+}
+
+#[test]
+fn test_if_let_else() {
+    assert_eq! {
+        rune! { (i64, i64) =>
+            fn get(value) {
+                if let Some(n) = value {
+                    n + 1
+                } else {
+                    0
+                }
+            }
+
+            pub fn main() {
+                (get(Some(41)), get(None))
+            }
+        },
+        (42, 0),
+    };
+}
@@ -0,0 +1,130 @@
+use rune_tests::*;
+
+#[test]
+fn test_ensure() {
+    assert_eq! {
+        rune! { i64 =>
+            fn check(n) {
+                ensure(n > 0, "expected positive number");
+                n
+            }
+
+            pub fn main() {
+                check(42)
+            }
+        },
+        42
+    };
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let n = -1;
+            ensure(n > 0, "expected positive number");
+            n
+        }
+        "#,
+        EnsureFailed { message } => {
+            assert_eq!(message, "expected positive number");
+        }
+    );
+}
+
+#[test]
+fn test_guard_let() {
+    assert_eq! {
+        rune! { i64 =>
+            fn get(value) {
+                let base = 10;
+                guard let Some(n) = value else { return base; };
+                base + n
+            }
+
+            pub fn main() {
+                get(Some(32)) + get(None)
+            }
+        },
+        52
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            pub fn main() {
+                let sum = 0;
+
+                for value in [Some((1, 2)), None, Some((3, 4)), Some(5), Some((6, 7))] {
+                    guard let Some(n) = value else { continue; };
+                    guard let (a, b) = n else { break; };
+                    sum += a + b;
+                }
+
+                sum
+            }
+        },
+        10
+    };
+
+    assert_eq! {
+        rune! { i64 =>
+            fn get(value, fallback) {
+                guard let Some(n) = value else {
+                    if fallback { return 1; } else { return 2; }
+                };
+
+                n
+            }
+
+            pub fn main() {
+                get(Some(10), true) + get(None, true) + get(None, false)
+            }
+        },
+        13
+    };
+}
+
+#[test]
+fn test_guard_identifier() {
+    assert_eq! {
+        rune! { i64 =>
+            fn guard(n) {
+                n + 1
+            }
+
+            pub fn main() {
+                let guard = guard(1);
+                guard + 1
+            }
+        },
+        3
+    };
+}
+
+#[test]
+fn test_guard_let_not_diverging() {
+    assert_compile_error! {
+        r#"pub fn main() { guard let Some(n) = None else { 1 }; n }"#,
+        span, GuardElseNotDiverging => {
+            assert_eq!(span, Span::new(41, 51));
+        }
+    };
+}
+
+#[test]
+fn test_guard_let_missing_else() {
+    assert_parse_error! {
+        r#"pub fn main() { guard let Some(n) = None; n }"#,
+        span, ParseErrorKind::Expected { .. } => {
+            assert_eq!(span, Span::new(40, 41));
+        }
+    };
+}
+
+#[test]
+fn test_guard_let_not_guard() {
+    assert_compile_error! {
+        r#"pub fn main() { foo let Some(n) = None else { return; }; n }"#,
+        span, CompileErrorKind::ParseError { error: ParseErrorKind::Expected { .. } } => {
+            assert_eq!(span, Span::new(16, 19));
+        }
+    };
+}
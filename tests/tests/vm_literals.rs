@@ -19,6 +19,15 @@ fn test_literals() {
     assert_eq!(rune!(i64 => pub fn main() { -0b10010001 }), -0b10010001);
     assert_eq!(rune!(i64 => pub fn main() { 0o77 }), 0o77);
     assert_eq!(rune!(i64 => pub fn main() { -0o77 }), -0o77);
+    assert_eq!(rune!(i64 => pub fn main() { 0xe1 }), 0xe1);
+    assert_eq!(rune!(i64 => pub fn main() { 1_000_000 }), 1_000_000);
+    assert_eq!(rune!(i64 => pub fn main() { 0b1010_1010 }), 0b1010_1010);
+    assert_eq!(rune!(i64 => pub fn main() { 0x_ff_ff }), 0xffff);
+    assert_eq!(rune!(f64 => pub fn main() { 1_000.5 }), 1_000.5);
+    assert_eq!(rune!(bool => pub fn main() { 1.5e3 == 1500.0 }), true);
+    assert_eq!(rune!(f64 => pub fn main() { 2E-4 }), 2E-4);
+    assert_eq!(rune!(f64 => pub fn main() { 1_000.0e+1_0 }), 1_000.0e+1_0);
+    assert_eq!(rune!(i64 => pub fn main() { 1.max(2) }), 2);
 
     assert_eq!(rune!(u8 => pub fn main() { b'0' }), b'0');
     assert_eq!(rune!(u8 => pub fn main() { b'\xaf' }), b'\xaf');
@@ -54,6 +63,34 @@ fn test_string_literals() {
     b" }),
         "a b"
     );
+
+    assert_eq!(
+        rune!(String => pub fn main() { "a\nb\t\\\"\u{1F600}\0\x41\r" }),
+        "a\nb\t\\\"\u{1F600}\0\x41\r"
+    );
+
+    assert_eq!(
+        rune!((char, char, char, char, char) => pub fn main() { ('\n', '\t', '\\', '\'', '"') }),
+        ('\n', '\t', '\\', '\'', '"')
+    );
+}
+
+#[test]
+fn test_raw_string_literals() {
+    assert_eq!(
+        rune!(String => pub fn main() { r"C:\path\n" }),
+        "C:\\path\\n"
+    );
+
+    assert_eq!(
+        rune!(String => pub fn main() { r#"has "quotes" inside"# }),
+        "has \"quotes\" inside"
+    );
+
+    assert_eq!(
+        rune!(String => pub fn main() { r##"a "# b"## + r"" }),
+        "a \"# b"
+    );
 }
 
 #[test]
@@ -77,4 +114,8 @@ fn test_byte_string_literals() {
     b" }),
         b"a b"[..]
     );
+    assert_eq!(
+        rune!(Bytes => pub fn main() { b"\x41BC" }),
+        runestick::Bytes::from_vec(vec![65, 66, 67])
+    );
 }
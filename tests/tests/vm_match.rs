@@ -97,3 +97,118 @@ fn test_struct_matching() {
         3,
     };
 }
+
+#[test]
+fn test_match_guards() {
+    assert_eq! {
+        rune! { (i64, i64, i64, i64) =>
+            fn classify(x) {
+                match x {
+                    (a, b) if a == b => a,
+                    (a, b) => a + b,
+                    n if n > 0 => n * 10,
+                    _ => -1,
+                }
+            }
+
+            pub fn main() {
+                (classify((2, 2)), classify((2, 3)), classify(4), classify(-3))
+            }
+        },
+        (2, 5, 40, -1),
+    };
+
+    assert_eq! {
+        rune! { (i64, i64, i64) =>
+            pub fn main() {
+                let limit = 10;
+                let is_even = |n| n % 2 == 0;
+
+                let classify = |x| match x {
+                    n if n > limit => 2,
+                    n if is_even(n) => 1,
+                    _ => 0,
+                };
+
+                (classify(20), classify(4), classify(3))
+            }
+        },
+        (2, 1, 0),
+    };
+}
+
+#[test]
+fn test_match_or_patterns() {
+    assert_eq! {
+        rune! { (String, String, String) =>
+            fn size(x) {
+                match x {
+                    1 | 2 | 3 => "small",
+                    4 | 5 => "medium",
+                    _ => "big",
+                }
+            }
+
+            pub fn main() {
+                (size(2), size(5), size(10))
+            }
+        },
+        (
+            String::from("small"),
+            String::from("medium"),
+            String::from("big"),
+        ),
+    };
+
+    assert_eq! {
+        rune! { (i64, i64, i64, i64) =>
+            enum Shape { Square(side), Rect(w, h), Circle(r) }
+
+            fn size(shape) {
+                match shape {
+                    Shape::Square(n) | Shape::Circle(n) if n > 10 => 100,
+                    Shape::Square(n) | Shape::Circle(n) => n,
+                    Shape::Rect(w, h) | Shape::Rect(h, w) => w - h,
+                }
+            }
+
+            pub fn main() {
+                (size(Shape::Square(2)), size(Shape::Circle(3)), size(Shape::Circle(11)), size(Shape::Rect(5, 3)))
+            }
+        },
+        (2, 3, 100, 2),
+    };
+
+    assert_eq! {
+        rune! { (i64, i64, i64) =>
+            fn sum(value) {
+                match value {
+                    [a, b] | (a, b) | #{ a, b } => a + b,
+                    [_, a, b] | (b, a) => a * b,
+                    _ => 0,
+                }
+            }
+
+            pub fn main() {
+                (sum([1, 2]), sum(#{ a: 3, b: 4 }), sum([1, 5, 6]))
+            }
+        },
+        (3, 7, 30),
+    };
+
+    assert_compile_error! {
+        "pub fn main() { match (1, 2) { (a, b) | (a, _) => a, } }",
+        span, OrPatternBindingMismatch { name } => {
+            assert_eq!(span, Span::new(40, 46));
+            assert_eq!(name, "b");
+        }
+    };
+
+    assert_compile_error! {
+        "pub fn main() { match (1, 2) { (a, _) | (a, b) => a, } }",
+        span, OrPatternBindingMismatch { name } => {
+            assert_eq!(span, Span::new(44, 45));
+            assert_eq!(name, "b");
+        }
+    };
+}
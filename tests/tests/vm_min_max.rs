@@ -0,0 +1,86 @@
+use rune_tests::*;
+use runestick::VmErrorKind;
+
+#[test]
+fn test_int_min_max_clamp() {
+    assert_eq! {
+        rune!((i64, i64, i64, i64, i64, i64, i64) => pub fn main() {
+            (
+                1.min(2),
+                2.min(1),
+                1.max(2),
+                int::max(2, 1),
+                5.clamp(0, 3),
+                (-5).clamp(0, 3),
+                int::clamp(2, 0, 3),
+            )
+        }),
+        (1, 1, 2, 2, 3, 0, 2),
+    };
+}
+
+#[test]
+fn test_float_min_max_clamp() {
+    assert_eq! {
+        rune!((f64, f64, f64, f64, f64, f64) => pub fn main() {
+            (
+                1.5.min(2.5),
+                float::min(2.5, 1.5),
+                1.5.max(2.5),
+                float::max(2.5, 1.5),
+                5.0.clamp(0.0, 3.0),
+                float::clamp(-5.0, 0.0, 3.0),
+            )
+        }),
+        (1.5, 1.5, 2.5, 2.5, 3.0, 0.0),
+    };
+}
+
+#[test]
+fn test_clamp_reversed_bounds() {
+    assert_vm_error!(
+        "pub fn main() { 5.clamp(3, 0) }",
+        Panic { reason } => {
+            assert_eq!(
+                reason.to_string(),
+                "clamp lower bound `3` is greater than upper bound `0`"
+            );
+        }
+    );
+
+    assert_vm_error!(
+        "pub fn main() { 5.0.clamp(3.0, 0.0) }",
+        Panic { reason } => {
+            assert_eq!(
+                reason.to_string(),
+                "clamp lower bound `3` is greater than upper bound `0`"
+            );
+        }
+    );
+}
+
+#[test]
+fn test_clamp_nan_bounds() {
+    assert_vm_error!(
+        "pub fn main() { let nan = 0.0 / 0.0; 5.0.clamp(nan, 1.0) }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "clamp bounds must not be NaN");
+        }
+    );
+}
+
+#[test]
+fn test_min_mixed_numbers() {
+    assert_vm_error!(
+        "pub fn main() { 1.min(2.0) }",
+        BadArgument { error, arg: 1 } => {
+            match error.into_kind() {
+                VmErrorKind::Expected { expected, actual } => {
+                    assert_eq!(expected.to_string(), "integer");
+                    assert_eq!(actual.to_string(), "float");
+                }
+                kind => panic!("unexpected error {:?}", kind),
+            }
+        }
+    );
+}
@@ -0,0 +1,70 @@
+use rune_tests::*;
+
+#[test]
+fn test_numeric_conversions() {
+    assert_eq! {
+        rune!((f64, f64, i64, i64, i64) => pub fn main() {
+            (
+                int::to_float(2),
+                3.to_float(),
+                float::to_int(2.9),
+                (-2.9).to_int(),
+                float::to_int(-9223372036854775808.0),
+            )
+        }),
+        (2.0, 3.0, 2, -2, i64::MIN),
+    };
+
+    assert_eq! {
+        rune!((char, char, i64, i64) => pub fn main() {
+            (65.to_char(), int::to_char(0x1f600), 'A'.to_int(), char::to_int('ä'))
+        }),
+        ('A', '😀', 65, 228),
+    };
+
+    assert!(rune!(bool => pub fn main() { 65.to_char() == 'A' }));
+}
+
+#[test]
+fn test_float_to_int_errors() {
+    assert_vm_error!(
+        "pub fn main() { (0.0 / 0.0).to_int() }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "cannot convert NaN to an integer");
+        }
+    );
+
+    assert_vm_error!(
+        "pub fn main() { float::to_int(1.0 / 0.0) }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "`inf` is out of range for an integer");
+        }
+    );
+
+    assert_vm_error!(
+        "pub fn main() { 9223372036854775808.0.to_int() }",
+        Panic { reason } => {
+            assert_eq!(
+                reason.to_string(),
+                "`9223372036854776000` is out of range for an integer"
+            );
+        }
+    );
+}
+
+#[test]
+fn test_int_to_char_errors() {
+    assert_vm_error!(
+        "pub fn main() { 0xd800.to_char() }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "`55296` is not a valid unicode scalar value");
+        }
+    );
+
+    assert_vm_error!(
+        "pub fn main() { int::to_char(-1) }",
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "`-1` is not a valid unicode scalar value");
+        }
+    );
+}
@@ -0,0 +1,58 @@
+use rune_tests::*;
+
+#[test]
+fn test_object_insertion_order() {
+    let out: (Vec<String>, Vec<i64>) = rune! {
+        (Vec<String>, Vec<i64>) =>
+        pub fn main() {
+            let object = #{ c: 1, a: 2 };
+            object.b = 3;
+            object.insert("d", 4);
+
+            // NB: re-inserting an existing key keeps its position.
+            object.a = 5;
+
+            let keys = [];
+            let values = [];
+
+            for (key, value) in object {
+                keys.push(key);
+                values.push(value);
+            }
+
+            (keys, values)
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            vec![
+                String::from("c"),
+                String::from("a"),
+                String::from("b"),
+                String::from("d")
+            ],
+            vec![1, 5, 3, 4]
+        )
+    );
+}
+
+#[test]
+fn test_object_keys_values_order() {
+    let out: (Vec<String>, Vec<i64>) = rune! {
+        (Vec<String>, Vec<i64>) =>
+        pub fn main() {
+            let object = #{ z: 1, y: 2, x: 3 };
+            (object.keys().collect_vec(), object.values().collect_vec())
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            vec![String::from("z"), String::from("y"), String::from("x")],
+            vec![1, 2, 3]
+        )
+    );
+}
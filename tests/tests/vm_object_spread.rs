@@ -0,0 +1,59 @@
+use rune_tests::*;
+use runestick::VmErrorKind;
+
+#[test]
+fn test_object_spread() {
+    assert_eq! {
+        rune!((i64, i64, i64, usize, i64) => pub fn main() {
+            let base = #{a: 1, b: 2};
+            let derived = #{..base, b: 20, c: 30};
+            base.a = 10;
+            (derived.a, derived.b, derived.c, derived.len(), base.b)
+        }),
+        (1, 20, 30, 3, 2),
+    };
+}
+
+#[test]
+fn test_object_spread_only() {
+    assert_eq! {
+        rune!((usize, usize) => pub fn main() {
+            let base = #{a: 1, b: 2};
+            let copy = #{..base};
+            copy.c = 3;
+            (base.len(), copy.len())
+        }),
+        (2, 3),
+    };
+}
+
+#[test]
+fn test_object_spread_not_object() {
+    assert_vm_error!(
+        "pub fn main() { #{..[1, 2], a: 1} }",
+        VmErrorKind::Expected { expected, actual } => {
+            assert_eq!(expected.to_string(), "Object");
+            assert_eq!(actual.to_string(), "Vec");
+        }
+    );
+}
+
+#[test]
+fn test_object_spread_not_first() {
+    assert_compile_error! {
+        "pub fn main() { let base = #{}; #{a: 1, ..base} }",
+        span, ObjectSpreadNotFirst => {
+            assert_eq!(span, Span::new(40, 46));
+        }
+    };
+}
+
+#[test]
+fn test_object_spread_named() {
+    assert_compile_error! {
+        "struct Foo { a } pub fn main() { let base = #{}; Foo {..base, a: 1} }",
+        span, UnsupportedObjectSpread => {
+            assert_eq!(span, Span::new(54, 60));
+        }
+    };
+}
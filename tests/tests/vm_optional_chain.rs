@@ -0,0 +1,80 @@
+use rune_tests::*;
+
+#[test]
+fn test_optional_chain_present() {
+    assert_eq! {
+        rune! { (String, i64) =>
+            pub fn main() {
+                let user = #{address: Some(#{city: Some("Stockholm")})};
+                (user.address?.city?, user.address?.city?.len())
+            }
+        },
+        (String::from("Stockholm"), 9),
+    };
+}
+
+#[test]
+fn test_optional_chain_absent() {
+    assert_eq! {
+        rune! { (Option<i64>, Option<i64>, ()) =>
+            pub fn main() {
+                let nowhere = #{address: None};
+                let nameless = #{address: Some(#{city: None})};
+                let unit = ();
+
+                (
+                    nowhere.address?.city?.len(),
+                    nameless.address?.city?.len(),
+                    unit?.city,
+                )
+            }
+        },
+        (None, None, ()),
+    };
+}
+
+#[test]
+fn test_optional_chain_cleans_temporaries() {
+    assert_eq! {
+        rune! { (i64, i64, Option<i64>, i64) =>
+            pub fn main() {
+                let present = Some((|n| n + 1,));
+                let absent = None;
+                (1, present?.0(2), absent?.0(2), 4)
+            }
+        },
+        (1, 3, None, 4),
+    };
+}
+
+#[test]
+fn test_optional_chain_error_returns() {
+    assert_eq! {
+        rune! { Result<i64, String> =>
+            fn lookup(ok) {
+                if ok { Ok(#{value: 42}) } else { Err("missing") }
+            }
+
+            pub fn main() {
+                let value = lookup(true)?.value;
+                Ok(value + lookup(false)?.value)
+            }
+        },
+        Err(String::from("missing")),
+    };
+}
+
+#[test]
+fn test_optional_chain_wrong_type() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let value = 42;
+            value?.field
+        }
+        "#,
+        UnsupportedObjectSlotIndexGet { target } => {
+            assert_eq!(target.to_string(), "integer");
+        }
+    );
+}
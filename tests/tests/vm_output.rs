@@ -0,0 +1,94 @@
+use rune_tests::*;
+use runestick::{Context, Vm, VmErrorKind};
+use std::io;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Broken;
+
+impl io::Write for Broken {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "sink is broken"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn vm(source: &str) -> runestick::Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let unit = Arc::new(compile_source(&context, source).expect("compile").0);
+    Ok(Vm::new(runtime, unit))
+}
+
+#[test]
+fn test_output_sink() -> runestick::Result<()> {
+    let mut vm = vm(r#"
+    fn inner() {
+        print("from a generator");
+        yield 1;
+    }
+
+    pub fn main() {
+        println("Hello");
+        dbg([1, 2]);
+        inner().next();
+    }
+    "#)?;
+
+    let buffer = Buffer::default();
+    vm.set_output(buffer.clone());
+    vm.execute(&["main"], ())?.complete()?;
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert!(output.starts_with("Hello\n["));
+    assert!(output.ends_with("] [1, 2] = [1, 2]\nfrom a generator"));
+    Ok(())
+}
+
+#[test]
+fn test_output_sink_error() -> runestick::Result<()> {
+    let mut vm = vm(r#"pub fn main() { println("Hello") }"#)?;
+    vm.set_output(Broken);
+
+    let error = vm.execute(&["main"], ())?.complete().unwrap_err();
+
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::Panic { reason } => assert_eq!(reason.to_string(), "sink is broken"),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_output_sink_closure() -> runestick::Result<()> {
+    let mut vm = vm(r#"
+    pub fn main() {
+        println("direct");
+        [1, 2].iter().map(|x| { println("closure"); x }).collect_vec()
+    }
+    "#)?;
+
+    let buffer = Buffer::default();
+    vm.set_output(buffer.clone());
+    vm.execute(&["main"], ())?.complete()?;
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "direct\nclosure\nclosure\n");
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use rune_tests::*;
+
+#[test]
+fn test_parse_int() {
+    assert_eq! {
+        rune!((i64, i64, i64) => pub fn main() {
+            (int::parse("42")?, int::parse("-7")?, int::parse_radix("ff", 16)?)
+        }),
+        (42, -7, 255),
+    };
+}
+
+#[test]
+fn test_parse_float() {
+    assert_eq! {
+        rune!(f64 => pub fn main() {
+            float::parse("2.5")?
+        }),
+        2.5,
+    };
+}
+
+#[test]
+fn test_parse_malformed() {
+    assert_eq! {
+        rune!((bool, bool, bool, bool, bool, bool) => pub fn main() {
+            (
+                int::parse("").is_err(),
+                int::parse(" 42").is_err(),
+                int::parse("nope").is_err(),
+                int::parse("9223372036854775808").is_err(),
+                int::parse_radix("fg", 16).is_err(),
+                float::parse("1.0.0").is_err(),
+            )
+        }),
+        (true, true, true, true, true, true),
+    };
+}
+
+#[test]
+fn test_parse_bad_radix() {
+    assert_vm_error!(
+        r#"pub fn main() { int::parse_radix("10", 37) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "radix `37` is not in the range 2..=36");
+        }
+    );
+}
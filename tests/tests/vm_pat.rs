@@ -47,3 +47,47 @@ fn test_match_binding() {
         true,
     };
 }
+
+#[test]
+fn test_const_array_length() {
+    assert_eq! {
+        rune! { i64 =>
+            const N = 3;
+
+            pub fn main() {
+                let [a, b, c]: [_; N] = [1, 2, 3];
+                let [d, ..]: [_; N - 1] = [4, 5];
+                a + b + c + d
+            }
+        },
+        10,
+    };
+}
+
+#[test]
+fn test_const_array_length_mismatch() {
+    assert_compile_error! {
+        r#"const N = 3; pub fn main() { let [a, b]: [_; N] = [1, 2]; }"#,
+        span, ArrayPatternLengthMismatch { expected, actual } => {
+            assert_eq!(span, Span::new(33, 39));
+            assert_eq!(expected, 3);
+            assert_eq!(actual, 2);
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { let [a, b, c, ..]: [_; 2] = [1, 2]; }"#,
+        span, ArrayPatternLengthMismatch { expected, actual } => {
+            assert_eq!(span, Span::new(20, 33));
+            assert_eq!(expected, 2);
+            assert_eq!(actual, 3);
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { let [a]: [_; "one"] = [1]; }"#,
+        span, BadArrayLength => {
+            assert_eq!(span, Span::new(29, 34));
+        }
+    };
+}
@@ -0,0 +1,79 @@
+use rune_tests::*;
+use runestick::{Any, Module};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A value which keeps track of how many instances of it are alive.
+#[derive(Any)]
+struct Tracked {
+    live: &'static AtomicUsize,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.live.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn make_module(live: &'static AtomicUsize) -> Module {
+    let mut module = Module::new();
+    module.ty::<Tracked>().unwrap();
+
+    module
+        .function(&["tracked"], move || {
+            live.fetch_add(1, Ordering::SeqCst);
+            Tracked { live }
+        })
+        .unwrap();
+
+    module
+        .function(&["live"], move || live.load(Ordering::SeqCst) as i64)
+        .unwrap();
+
+    module
+}
+
+#[test]
+fn test_reclaim_dropped_containers() {
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+    let max_live = rune_n! {
+        make_module(&LIVE),
+        (),
+        i64 => pub fn main() {
+            let max = 0;
+
+            for n in 0..1000 {
+                let vec = [tracked(), [tracked()]];
+                let _object = #{vec, tuple: (tracked(), n)};
+
+                if live() > max {
+                    max = live();
+                }
+            }
+
+            max
+        }
+    };
+
+    assert_eq!(max_live, 3);
+    assert_eq!(LIVE.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_reclaim_broken_cycle() {
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+    let output = rune_n! {
+        make_module(&LIVE),
+        (),
+        i64 => pub fn main() {
+            let object = #{tracked: tracked()};
+            object.me = object;
+            object.clear();
+            live()
+        }
+    };
+
+    assert_eq!(output, 0);
+    assert_eq!(LIVE.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,92 @@
+use rune_tests::*;
+
+#[test]
+fn test_rest_args() {
+    assert_eq! {
+        rune! { (i64, i64) =>
+            fn count(level, ..rest) {
+                rest.len()
+            }
+
+            pub fn main() {
+                (count("info"), count("info", 1, 2, 3))
+            }
+        },
+        (0, 3),
+    };
+
+    assert_eq! {
+        rune! { Vec<i64> =>
+            fn collect(a, ..rest) {
+                rest.push(a);
+                rest
+            }
+
+            pub fn main() {
+                collect(1, 2, 3, 4)
+            }
+        },
+        vec![2, 3, 4, 1],
+    };
+
+    assert_eq! {
+        rune! { (i64, i64) =>
+            fn sum(first = 10, ..rest) {
+                let sum = first;
+
+                for n in rest {
+                    sum += n;
+                }
+
+                sum
+            }
+
+            pub fn main() {
+                (sum(), sum(1, 2, 3))
+            }
+        },
+        (10, 6),
+    };
+}
+
+#[test]
+fn test_rest_args_fn_ptr() {
+    let function = rune! { Function =>
+        fn count(a, ..rest) { rest.len() }
+
+        pub fn main() { count }
+    };
+
+    assert_eq!(function.call::<_, i64>((1i64,)).unwrap(), 0);
+    assert_eq!(
+        function.call::<_, i64>((1i64, 2i64, 3i64, 4i64)).unwrap(),
+        3
+    );
+    assert!(function.call::<_, i64>(()).is_err());
+}
+
+#[test]
+fn test_rest_args_errors() {
+    assert_compile_error! {
+        r#"fn foo(..rest, a) {} pub fn main() {}"#,
+        span, FnArgRestNotLast => {
+            assert_eq!(span, Span::new(7, 13));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn foo(a, b, ..rest) {} pub fn main() { foo(1) }"#,
+        span, UnsupportedArgumentCount { expected, actual, .. } => {
+            assert_eq!(span, Span::new(40, 46));
+            assert_eq!(expected, 2);
+            assert_eq!(actual, 1);
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { let f = |..rest| rest; f() }"#,
+        span, UnsupportedFnArgRest => {
+            assert_eq!(span, Span::new(25, 31));
+        }
+    };
+}
@@ -0,0 +1,43 @@
+use rune_tests::*;
+use runestick::{Context, FromValue, Vm};
+use std::sync::Arc;
+
+#[test]
+fn test_reuse_vm() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let source = r#"
+    fn fib(n) { if n < 2 { n } else { fib(n - 1) + fib(n - 2) } }
+    pub fn main(n) { let values = [fib(n), n]; values[0] }
+    "#;
+    let unit = Arc::new(compile_source(&context, source).expect("compile").0);
+
+    let mut vm = Vm::new(runtime, unit);
+
+    for n in 0..1000 {
+        let output = i64::from_value(vm.call(&["main"], (n % 10,))?)?;
+        assert_eq!(output, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34][n as usize % 10]);
+        vm.clear();
+
+        assert!(vm.stack().is_empty());
+        assert!(vm.call_frames().is_empty());
+        assert_eq!(vm.ip(), 0);
+    }
+
+    // An execution which is abandoned halfway through leaves its state
+    // behind, which is freed when the virtual machine is cleared.
+    let mut execution = vm.execute(&["main"], (20i64,))?;
+
+    for _ in 0..100 {
+        assert!(execution.step()?.is_none());
+    }
+
+    drop(execution);
+    assert!(!vm.call_frames().is_empty());
+    vm.clear();
+    assert!(vm.stack().is_empty());
+    assert!(vm.call_frames().is_empty());
+
+    assert_eq!(i64::from_value(vm.call(&["main"], (10i64,))?)?, 55);
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use rune_tests::*;
+use runestick::{Any, Context, FromValue, Module, Vm, VmErrorKind, VmSnapshot};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+struct Point { x, y }
+
+fn sum(points) {
+    let total = 0;
+    let n = 0;
+
+    while n < points.len() {
+        total += points[n].x * points[n].y;
+        n += 1;
+    }
+
+    total
+}
+
+pub fn main() {
+    let points = [];
+    let n = 0;
+
+    while n < 10 {
+        points.push(Point { x: n, y: n + 1 });
+        n += 1;
+    }
+
+    let label = "sum";
+    let sum = sum(points);
+    (label, sum, Some(sum / 2))
+}
+"#;
+
+#[derive(Any)]
+struct External;
+
+#[test]
+fn test_snapshot_and_restore() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let unit = Arc::new(compile_source(&context, SOURCE).expect("compile").0);
+
+    let mut vm = Vm::new(runtime.clone(), unit.clone());
+    let expected = <(String, i64, Option<i64>)>::from_value(vm.call(&["main"], ())?)?;
+    assert_eq!(expected, (String::from("sum"), 330, Some(165)));
+
+    // Pause the script at various points, serialize its state into bytes and
+    // resume it in a fresh virtual machine.
+    for steps in [1, 10, 50, 100, 200].iter().copied() {
+        let mut vm = Vm::new(runtime.clone(), unit.clone());
+        let mut execution = vm.execute(&["main"], ())?;
+
+        for _ in 0..steps {
+            assert!(execution.step()?.is_none());
+        }
+
+        let bytes = serde_json::to_vec(&execution.vm().snapshot()?)?;
+        drop(execution);
+
+        let snapshot: VmSnapshot = serde_json::from_slice(&bytes)?;
+        let vm = Vm::restore(runtime.clone(), unit.clone(), snapshot)?;
+        let output = <(String, i64, Option<i64>)>::from_value(vm.complete()?)?;
+        assert_eq!(output, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_deterministic() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let unit = Arc::new(compile_source(&context, SOURCE).expect("compile").0);
+
+    // Snapshot the script after every instruction.
+    let trace = || -> runestick::Result<Vec<Vec<u8>>> {
+        let mut vm = Vm::new(runtime.clone(), unit.clone());
+        let mut execution = vm.execute(&["main"], ())?;
+        let mut trace = Vec::new();
+
+        while execution.step()?.is_none() {
+            trace.push(serde_json::to_vec(&execution.vm().snapshot()?)?);
+        }
+
+        Ok(trace)
+    };
+
+    let first = trace()?;
+    // NB: make sure values in the second run are allocated elsewhere.
+    let _padding = (0..100).map(|n| vec![n; n]).collect::<Vec<_>>();
+    let second = trace()?;
+
+    assert!(first.len() > 100);
+    assert!(first == second, "snapshots of identical executions differ");
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_external() -> runestick::Result<()> {
+    let mut module = Module::new();
+    module.function(&["external"], || External)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let runtime = Arc::new(context.runtime());
+    let source = r#"pub fn main() { let value = external(); 42 }"#;
+    let unit = Arc::new(compile_source(&context, source).expect("compile").0);
+
+    let mut vm = Vm::new(runtime, unit);
+    let mut execution = vm.execute(&["main"], ())?;
+    assert!(execution.step()?.is_none());
+
+    let error = execution.vm().snapshot().unwrap_err();
+
+    match error.into_kind() {
+        VmErrorKind::SnapshotNotSupported { actual } => {
+            assert_eq!(actual.to_string(), "External");
+        }
+        actual => panic!("unexpected error `{:?}`", actual),
+    }
+
+    Ok(())
+}
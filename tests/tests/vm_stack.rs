@@ -0,0 +1,39 @@
+use rune_tests::*;
+use runestick::{Context, FromValue, Vm, VmErrorKind};
+use std::sync::Arc;
+
+#[test]
+fn test_inspect_stack() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let source = r#"
+    pub fn main() {
+        let a = 42;
+        let b = "hello";
+        a + b.len()
+    }
+    "#;
+    let unit = Arc::new(compile_source(&context, source).expect("compile").0);
+
+    let mut vm = Vm::new(runtime, unit);
+    let mut execution = vm.execute(&["main"], ())?;
+
+    // Run the script until both local variables have been assigned.
+    while execution.vm().slot(1).is_err() {
+        assert!(execution.step()?.is_none());
+    }
+
+    let vm = execution.vm();
+    assert_eq!(i64::from_value(vm.slot(0)?.clone())?, 42);
+    assert_eq!(String::from_value(vm.slot(1)?.clone())?, "hello");
+    assert!(vm
+        .stack()
+        .iter()
+        .any(|value| matches!(value, runestick::Value::Integer(42))));
+
+    let error = vm.slot(2).unwrap_err();
+    assert!(matches!(error.kind(), VmErrorKind::StackError { .. }));
+
+    assert_eq!(i64::from_value(execution.complete()?)?, 47);
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use rune_tests::*;
+
+#[test]
+fn test_string_chars() {
+    assert_eq! {
+        rune!(Vec<char> => pub fn main() {
+            let out = [];
+
+            for c in "aé🦀b".chars() {
+                out.push(c);
+            }
+
+            out
+        }),
+        vec!['a', 'é', '🦀', 'b'],
+    };
+
+    assert_eq! {
+        rune!(Vec<char> => pub fn main() {
+            "aé🦀b".chars().rev().collect_vec()
+        }),
+        vec!['b', '🦀', 'é', 'a'],
+    };
+}
+
+#[test]
+fn test_string_char_at() {
+    // `char_at` uses character indexes, while `byte_len` and `get` use bytes.
+    assert_eq! {
+        rune!((Option<char>, Option<char>, Option<char>, Option<char>) => pub fn main() {
+            let s = "aé🦀b";
+            (s.char_at(1), s.char_at(2), s.char_at(3), s.char_at(4))
+        }),
+        (Some('é'), Some('🦀'), Some('b'), None),
+    };
+
+    assert_eq! {
+        rune!((i64, Option<String>, Option<String>) => pub fn main() {
+            let s = "aé🦀b";
+            (s.byte_len(), s.get(1..3), s.get(1..2))
+        }),
+        (8, Some(String::from("é")), None),
+    };
+}
+
+#[test]
+fn test_string_len() {
+    assert_eq! {
+        rune!((usize, usize) => pub fn main() {
+            let s = "€";
+            (s.len(), s.byte_len())
+        }),
+        (1, 3),
+    };
+
+    assert_eq! {
+        rune!((usize, usize) => pub fn main() {
+            let s = String::from_str("aé🦀b");
+            (s.len(), s.byte_len())
+        }),
+        (4, 8),
+    };
+}
@@ -0,0 +1,51 @@
+use rune_tests::*;
+use runestick::Inst;
+
+#[test]
+fn test_string_concat_display() {
+    assert_eq! {
+        rune!(String => pub fn main() {
+            let count = 42;
+            "answer: " + count + ", " + [1, 2] + '!'
+        }),
+        "answer: 42, [1, 2]!",
+    };
+}
+
+#[test]
+fn test_string_concat_single_instruction() {
+    let context = runestick::Context::with_default_modules().unwrap();
+    let (unit, _) = compile_source(
+        &context,
+        r#"pub fn main() { let count = 42; "count: " + count + "!" }"#,
+    )
+    .unwrap();
+
+    let concats = unit
+        .iter_instructions()
+        .filter_map(|inst| match inst {
+            Inst::StringConcat { len, size_hint } => Some((len, size_hint)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(concats, vec![(3, 8)]);
+
+    assert!(!unit
+        .iter_instructions()
+        .any(|inst| matches!(inst, Inst::Op { .. })));
+}
+
+#[test]
+fn test_string_add_display() {
+    // NB: the same rules apply whether or not the chain starts with a literal.
+    assert_eq! {
+        rune!(String => pub fn main() {
+            let s = "answer: ";
+            let t = s + 42 + ", " + [1, 2];
+            t += '!';
+            t
+        }),
+        "answer: 42, [1, 2]!",
+    };
+}
@@ -0,0 +1,117 @@
+use rune_tests::*;
+use runestick::{FromValue, Vm};
+use std::sync::Arc;
+
+/// Run the given function to completion one step at a time, returning its
+/// output and the largest number of call frames observed.
+fn run_stepped(source: &str, n: i64) -> runestick::Result<(i64, usize)> {
+    let context = runestick::Context::with_default_modules()?;
+    let unit = compile_source(&context, source)
+        .expect("source should compile")
+        .0;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+
+    let mut execution = vm.execute(&["main"], (n,))?;
+    let mut max_frames = 0;
+
+    let output = loop {
+        if let Some(output) = execution.step()? {
+            break output;
+        }
+
+        max_frames = max_frames.max(execution.vm().call_frames().len());
+    };
+
+    Ok((i64::from_value(output)?, max_frames))
+}
+
+#[test]
+fn test_tail_call_countdown() {
+    let output = rune! { i64 =>
+        fn countdown(n, acc) {
+            if n == 0 {
+                return acc;
+            }
+
+            countdown(n - 1, acc + 1)
+        }
+
+        pub fn main() {
+            countdown(1000000, 0)
+        }
+    };
+
+    assert_eq!(output, 1000000);
+}
+
+#[test]
+fn test_tail_call_reuses_frame() -> runestick::Result<()> {
+    let (output, max_frames) = run_stepped(
+        r#"
+        fn countdown(n) {
+            if n == 0 { 0 } else { countdown(n - 1) }
+        }
+
+        pub fn main(n) { countdown(n) }
+        "#,
+        1000,
+    )?;
+
+    assert_eq!(output, 0);
+    assert!(max_frames <= 1, "frames grew to {}", max_frames);
+    Ok(())
+}
+
+#[test]
+fn test_tail_call_mutual_recursion() -> runestick::Result<()> {
+    let (output, max_frames) = run_stepped(
+        r#"
+        fn is_even(n) { match n { 0 => 1, n => is_odd(n - 1) } }
+        fn is_odd(n) { match n { 0 => 0, n => is_even(n - 1) } }
+
+        pub fn main(n) { is_even(n) }
+        "#,
+        1001,
+    )?;
+
+    assert_eq!(output, 0);
+    assert!(max_frames <= 1, "frames grew to {}", max_frames);
+    Ok(())
+}
+
+#[test]
+fn test_tail_call_function_pointer() -> runestick::Result<()> {
+    let (output, max_frames) = run_stepped(
+        r#"
+        fn countdown(f, n) {
+            if n == 0 { 0 } else { f(f, n - 1) }
+        }
+
+        pub fn main(n) {
+            let base = 1;
+            let closure = |f, n| countdown(f, n) + base;
+            countdown(countdown, n) + closure(countdown, 1)
+        }
+        "#,
+        1000,
+    )?;
+
+    assert_eq!(output, 1);
+    assert!(max_frames <= 2, "frames grew to {}", max_frames);
+    Ok(())
+}
+
+#[test]
+fn test_non_tail_call() -> runestick::Result<()> {
+    let (output, max_frames) = run_stepped(
+        r#"
+        fn sum(n) { if n == 0 { 0 } else { n + sum(n - 1) } }
+        pub fn main(n) { sum(n) }
+        "#,
+        100,
+    )?;
+
+    assert_eq!(output, 5050);
+    assert!(max_frames >= 100, "frames only grew to {}", max_frames);
+    Ok(())
+}
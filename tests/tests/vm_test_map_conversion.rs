@@ -0,0 +1,61 @@
+use rune_tests::*;
+use runestick::VmErrorKind;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[test]
+fn test_hash_map_roundtrip() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let mut input = HashMap::new();
+    input.insert(String::from("a"), 1i64);
+    input.insert(String::from("b"), 2i64);
+
+    let output: HashMap<String, i64> = run(
+        &context,
+        r#"
+        pub fn main(map) {
+            map.c = map.a + map.b;
+            map.a = 10;
+            map
+        }
+        "#,
+        &["main"],
+        (input,),
+    )?;
+
+    let mut expected = HashMap::new();
+    expected.insert(String::from("a"), 10i64);
+    expected.insert(String::from("b"), 2i64);
+    expected.insert(String::from("c"), 3i64);
+    assert_eq!(output, expected);
+    Ok(())
+}
+
+#[test]
+fn test_hash_map_not_object() {
+    assert_vm_error!(
+        HashMap<String, i64> => r#"pub fn main() { [1, 2, 3] }"#,
+        VmErrorKind::Expected { expected, actual } => {
+            assert_eq!(expected.to_string(), "Object");
+            assert_eq!(actual.to_string(), "Vec");
+        }
+    );
+}
+
+#[test]
+fn test_hash_map_bad_value() {
+    assert_vm_error!(
+        HashMap<String, i64> => r#"pub fn main() { #{a: 1, b: "two"} }"#,
+        VmErrorKind::BadObjectValue { error, key } => {
+            assert_eq!(key, "b");
+
+            match error.into_kind() {
+                VmErrorKind::Expected { expected, .. } => {
+                    assert_eq!(expected.to_string(), "integer");
+                }
+                actual => panic!("unexpected error `{:?}`", actual),
+            }
+        }
+    );
+}
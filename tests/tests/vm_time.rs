@@ -0,0 +1,97 @@
+#![cfg(feature = "tokio")]
+
+use rune_tests::*;
+use runestick::{Clock, Context, FromValue, MockClock, SystemClock, Vm};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn vm(clock: &MockClock, source: &str) -> runestick::Result<Vm> {
+    let mut context = Context::with_default_modules()?;
+    context.set_clock(Box::new(clock.clone()));
+
+    let unit = compile_source(&context, source).expect("compile").0;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[tokio::test]
+async fn test_mock_clock_sleep() -> runestick::Result<()> {
+    let clock = MockClock::new();
+    clock.advance(Duration::from_secs(100));
+
+    let mut vm = vm(
+        &clock,
+        r#"
+        use std::time::Duration;
+
+        pub async fn main() {
+            let start = std::time::now();
+            std::time::sleep(Duration::from_secs(3600)).await;
+            (start.as_secs(), (std::time::now() - start).as_secs())
+        }
+        "#,
+    )?;
+
+    let (output, ()) = tokio::join!(vm.async_call(&["main"], ()), async {
+        clock.advance(Duration::from_secs(1800));
+        tokio::task::yield_now().await;
+        clock.advance(Duration::from_secs(1800));
+    });
+
+    assert_eq!(<(u64, u64)>::from_value(output?)?, (100, 3600));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_clock_zero_sleep() -> runestick::Result<()> {
+    let clock = MockClock::new();
+
+    let mut vm = vm(
+        &clock,
+        r#"
+        pub async fn main() {
+            std::time::sleep(std::time::Duration::from_millis(0)).await;
+            std::time::now().as_millis()
+        }
+        "#,
+    )?;
+
+    let output = vm.async_call(&["main"], ()).await?;
+    assert_eq!(u64::from_value(output)?, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_system_clock_sleep() {
+    let clock = SystemClock;
+    let start = Instant::now();
+
+    let sleep = |millis| {
+        let sleep = clock.sleep(Duration::from_millis(millis));
+
+        async move {
+            sleep.await;
+            start.elapsed()
+        }
+    };
+
+    let (a, b, c) = tokio::join!(sleep(30), sleep(10), sleep(20));
+
+    assert!(a >= Duration::from_millis(30));
+    assert!(b >= Duration::from_millis(10));
+    assert!(c >= Duration::from_millis(20));
+}
+
+#[test]
+fn test_duration() {
+    assert_eq! {
+        rune! { (u64, u64, bool) =>
+            use std::time::Duration;
+
+            pub fn main() {
+                let d = Duration::from_secs(2) + Duration::from_millis(500);
+                (d.as_millis(), (d - Duration::from_secs(1)).as_secs(), d == Duration::from_secs_f64(2.5))
+            }
+        },
+        (2500, 1, true)
+    };
+}
@@ -0,0 +1,110 @@
+use rune_tests::*;
+use runestick::{Context, FromValue, Inst, InstOp, Vm};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_trace_instructions() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let source = r#"
+    pub fn main() {
+        let n = 0;
+
+        while n < 10 {
+            n = n + 1;
+        }
+
+        n
+    }
+    "#;
+    let unit = Arc::new(compile_source(&context, source).expect("compile").0);
+
+    // Count the instructions by stepping through the execution one
+    // instruction at a time.
+    let mut expected = 0;
+    let mut vm = Vm::new(runtime.clone(), unit.clone());
+    let mut execution = vm.execute(&["main"], ())?;
+
+    loop {
+        expected += 1;
+
+        if execution.step()?.is_some() {
+            break;
+        }
+    }
+
+    let trace = Arc::new(Mutex::new(Vec::new()));
+    let mut vm = Vm::new(runtime, unit.clone());
+
+    {
+        let trace = trace.clone();
+        vm.set_trace(move |ip, span| trace.lock().unwrap().push((ip, span)));
+    }
+
+    let output = vm.call(&["main"], ())?;
+    assert_eq!(i64::from_value(output)?, 10);
+
+    let trace = trace.lock().unwrap();
+    assert_eq!(trace.len(), expected);
+
+    let add = unit
+        .iter_instructions()
+        .position(|inst| {
+            matches!(
+                inst,
+                Inst::Op {
+                    op: InstOp::Add,
+                    ..
+                }
+            )
+        })
+        .expect("add instruction");
+
+    let adds = trace
+        .iter()
+        .filter(|(ip, _)| *ip == add)
+        .collect::<Vec<_>>();
+    assert_eq!(adds.len(), 10);
+    assert!(adds
+        .iter()
+        .all(|(_, span)| matches!(span, Some(span) if &source[span.range()] == "n + 1")));
+
+    // NB: the trace is opt-in.
+    vm.remove_trace();
+    vm.call(&["main"], ())?;
+    assert_eq!(trace.len(), expected);
+    Ok(())
+}
+
+#[test]
+fn test_trace_closure() -> runestick::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+    let source = r#"
+    pub fn main() {
+        [1, 2, 3].iter().map(|x| x * 2).collect_vec()
+    }
+    "#;
+    let unit = Arc::new(compile_source(&context, source).expect("compile").0);
+
+    let trace = Arc::new(Mutex::new(Vec::new()));
+    let mut vm = Vm::new(runtime, unit);
+
+    {
+        let trace = trace.clone();
+        vm.set_trace(move |ip, span| trace.lock().unwrap().push((ip, span)));
+    }
+
+    let output = vm.call(&["main"], ())?;
+    assert_eq!(Vec::<i64>::from_value(output)?, vec![2, 4, 6]);
+
+    // NB: the closure is called by the native `map` implementation, which
+    // runs it on a separate virtual machine.
+    let trace = trace.lock().unwrap();
+    let muls = trace
+        .iter()
+        .filter(|(_, span)| matches!(span, Some(span) if &source[span.range()] == "x * 2"))
+        .count();
+    assert_eq!(muls, 3);
+    Ok(())
+}
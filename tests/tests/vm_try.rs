@@ -48,3 +48,72 @@ fn test_unwrap() {
         Err(3),
     };
 }
+
+#[test]
+fn test_try_ok_propagates() {
+    assert_eq! {
+        rune! { Result<i64, String> =>
+            fn parse(n) {
+                Ok(n * 2)
+            }
+
+            pub fn main() {
+                let a = parse(1)?;
+                let b = parse(a)?;
+                Ok(a + b)
+            }
+        },
+        Ok(6),
+    };
+}
+
+#[test]
+fn test_try_err_short_circuits() {
+    assert_eq! {
+        rune! { (Result<i64, String>, Vec<i64>) =>
+            fn run(log) {
+                log.push(1);
+                Err("first")?;
+                log.push(2);
+                Ok(0)
+            }
+
+            pub fn main() {
+                let log = [];
+                let result = run(log);
+                (result, log)
+            }
+        },
+        (Err(String::from("first")), vec![1]),
+    };
+}
+
+#[test]
+fn test_try_caller_without_result() {
+    // NB: the error is returned as a plain value, which the caller is free to
+    // use however it wants.
+    assert_eq! {
+        rune! { bool =>
+            fn helper() {
+                Err("boom")?;
+                1
+            }
+
+            pub fn main() {
+                let value = helper();
+                value is Result && value.is_err()
+            }
+        },
+        true,
+    };
+}
+
+#[test]
+fn test_try_unsupported_operand() {
+    assert_vm_error!(
+        "pub fn main() { 1? }",
+        UnsupportedIsValueOperand { actual } => {
+            assert_eq!(actual.to_string(), "integer");
+        }
+    );
+}
@@ -14,3 +14,32 @@ fn test_mutate_tuples() {
         "Now You Don't !",
     };
 }
+
+#[test]
+fn test_tuple_from_value() {
+    assert_eq! {
+        rune! { (i64,) => pub fn main() { (42,) } },
+        (42,)
+    };
+
+    assert_eq! {
+        rune! { (i64, String, bool) => pub fn main() { (1, "two", true) } },
+        (1, String::from("two"), true)
+    };
+
+    assert_eq! {
+        rune! { (i64, String) => pub fn main() { [1, "two"] } },
+        (1, String::from("two"))
+    };
+}
+
+#[test]
+fn test_tuple_from_value_length_mismatch() {
+    assert_vm_error!(
+        (i64, i64, i64) => r#"pub fn main() { (1, 2) }"#,
+        ExpectedTupleLength { actual, expected } => {
+            assert_eq!(actual, 2);
+            assert_eq!(expected, 3);
+        }
+    );
+}
@@ -36,3 +36,37 @@ fn test_wildcard_precedence() {
         pub fn main() { Foo is a::Foo }
     });
 }
+
+#[test]
+fn test_wildcard_and_alias_calls() {
+    assert_eq! {
+        rune! { i64 =>
+            mod a { pub fn foo() { 1 } pub fn bar() { 2 } }
+            use a::*;
+            use a::bar as baz;
+            pub fn main() { foo() + baz() }
+        },
+        3,
+    };
+}
+
+#[test]
+fn test_wildcard_ambiguity_only_when_used() {
+    assert_eq! {
+        rune! { i64 =>
+            mod a { pub fn foo() { 1 } pub fn bar() { 3 } }
+            mod b { pub fn foo() { 2 } }
+            use a::*;
+            use b::*;
+            pub fn main() { bar() }
+        },
+        3,
+    };
+
+    assert_compile_error! {
+        r#"mod a { pub fn foo() { 1 } } mod b { pub fn foo() { 2 } } use a::*; use b::*; pub fn main() { foo() }"#,
+        span, QueryError { error: AmbiguousItem { .. } } => {
+            assert_eq!(span, Span::new(94, 97));
+        }
+    };
+}